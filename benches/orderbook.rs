@@ -116,8 +116,8 @@ fn bench_order_cancel(c: &mut Criterion) {
     fn generate_unique_ids() -> [usize; COUNT] {
         let mut ids = [0; COUNT];
         let start = 0;
-        for i in 0..COUNT {
-            ids[i] = (start + i * STEP) % N;
+        for (i, id) in ids.iter_mut().enumerate() {
+            *id = (start + i * STEP) % N;
         }
         ids
     }
@@ -133,8 +133,7 @@ fn bench_order_cancel(c: &mut Criterion) {
 
             // Cancel a batch of orders per iteration deterministically
             for id in unique_ids {
-                let result = book.cancel_order(OrderId(id as u64)).unwrap();
-                black_box(result);
+                book.cancel_order(OrderId(id as u64)).unwrap();
             }
 
             black_box(&book);
@@ -149,8 +148,7 @@ fn bench_order_cancel(c: &mut Criterion) {
             let mut book = initial_book.clone();
 
             for id in unique_ids {
-                let result = book.cancel_order(OrderId(id as u64)).unwrap();
-                black_box(result);
+                book.cancel_order(OrderId(id as u64)).unwrap();
             }
 
             black_box(&book);
@@ -168,8 +166,12 @@ fn bench_stress(c: &mut Criterion) {
     const NUM_LIMIT_ORDERS: usize = 1000;
     let limit_orders: Vec<(Side, Price, OrderId)> = (0..NUM_LIMIT_ORDERS)
         .map(|i| {
-            let side = if i % 2 == 0 { Side::Bid } else { Side::Ask };
-            let price = 95 + (i as Price % 10); // prices from 95 to 104
+            // Bids from 95 to 99, asks from 100 to 104, so the book never crosses
+            let (side, price) = if i % 2 == 0 {
+                (Side::Bid, 95 + (i as Price / 2 % 5))
+            } else {
+                (Side::Ask, 100 + (i as Price / 2 % 5))
+            };
             let order_id = OrderId(i as u64);
             (side, price, order_id)
         })
@@ -201,7 +203,7 @@ fn bench_stress(c: &mut Criterion) {
 
             // Cancel subset of orders deterministically
             for &order_id in &cancel_orders {
-                black_box(book.cancel_order(order_id).unwrap())
+                book.cancel_order(order_id).unwrap();
             }
 
             // Execute all market orders
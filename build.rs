@@ -0,0 +1,11 @@
+fn main() {
+    // Generates the `grpc` module's messages and service from the proto definitions,
+    // with a vendored protoc so no system install is needed
+    #[cfg(feature = "grpc")]
+    {
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+        // Safe, the build script is single threaded
+        unsafe { std::env::set_var("PROTOC", protoc) };
+        tonic_build::compile_protos("proto/bulkbook.proto").expect("compiling protos");
+    }
+}
@@ -0,0 +1,61 @@
+/* C interface to bulk-book, built with
+ *   cargo rustc --release --features cdylib --crate-type cdylib
+ *
+ * A handle must not be used after bulkbook_free or from two threads at once.
+ * Sides are 0 for a bid and 1 for an ask.
+ */
+#ifndef BULKBOOK_H
+#define BULKBOOK_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct BulkBook BulkBook;
+
+typedef enum BulkBookStatus {
+    BULKBOOK_OK = 0,
+    BULKBOOK_INVALID_ARGUMENT = 1, /* A null pointer, unknown side, or fill index out of range */
+    BULKBOOK_ORDER_ID_ALREADY_EXISTS = 2,
+    BULKBOOK_ORDER_ID_NOT_FOUND = 3,
+    BULKBOOK_ZERO_QUANTITY = 4,
+    BULKBOOK_TRADING_HALTED = 5,
+    BULKBOOK_REJECTED = 6, /* Any other rule the order broke */
+    BULKBOOK_INTERNAL = 7, /* The book's invariants are broken */
+} BulkBookStatus;
+
+typedef struct BulkBookFill {
+    int64_t price;
+    uint64_t quantity;
+    uint64_t maker_order_id;
+    uint64_t trade_id;
+    uint64_t timestamp;
+    uint64_t maker_remaining_quantity;
+    bool maker_fully_filled;
+} BulkBookFill;
+
+BulkBook *bulkbook_new(void);
+void bulkbook_free(BulkBook *book);
+
+/* resting and filled may be null */
+BulkBookStatus bulkbook_limit(BulkBook *book, uint8_t side, uint64_t order_id, int64_t price,
+                              uint64_t quantity, uint64_t *resting);
+BulkBookStatus bulkbook_market(BulkBook *book, uint8_t side, uint64_t quantity, uint64_t *filled);
+BulkBookStatus bulkbook_cancel(BulkBook *book, uint64_t order_id);
+
+/* Fills from the last bulkbook_limit or bulkbook_market call */
+size_t bulkbook_fill_count(const BulkBook *book);
+BulkBookStatus bulkbook_fill(const BulkBook *book, size_t index, BulkBookFill *fill);
+
+/* False if the side is empty or an argument is invalid */
+bool bulkbook_best(const BulkBook *book, uint8_t side, int64_t *price, uint64_t *quantity);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
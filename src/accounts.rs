@@ -0,0 +1,130 @@
+use hashbrown::HashMap;
+
+use crate::{
+    orderbook::OrderBook,
+    types::{AuctionFill, Fill, OwnerId, Price, Quantity, Side},
+};
+
+// One owner's position and cash, in the same units as price * quantity
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Account {
+    pub position: i128,   // Net quantity bought, negative when short
+    pub balance: i128,    // Deposits plus sales less purchases, after fees
+    pub fees: i128,       // Fees paid, negative after earning more in rebates
+    pub volume: Quantity, // Traded quantity on either side
+}
+
+impl Account {
+    // The position once `quantity` more trades on `side`, for max position checks
+    pub fn position_after(&self, side: Side, quantity: Quantity) -> i128 {
+        match side {
+            Side::Bid => self.position + quantity as i128,
+            Side::Ask => self.position - quantity as i128,
+        }
+    }
+
+    // Whether trading `quantity` on `side` only shrinks the position, without flipping
+    // it, as a reduce-only order must
+    pub fn reduces(&self, side: Side, quantity: Quantity) -> bool {
+        let after = self.position_after(side, quantity);
+        after.abs() < self.position.abs() && after.signum() != -self.position.signum()
+    }
+
+    fn trade(&mut self, side: Side, price: Price, quantity: Quantity, fee: i128) {
+        let notional = price as i128 * quantity as i128;
+        self.position = self.position_after(side, quantity);
+        self.balance += match side {
+            Side::Bid => -notional,
+            Side::Ask => notional,
+        } - fee;
+        self.fees += fee;
+        self.volume = self.volume.saturating_add(quantity);
+    }
+}
+
+// Accounts of every owner that has traded or deposited, kept while set on a book.
+// Only orders with an owner are tracked.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Accounts(pub(crate) HashMap<OwnerId, Account>);
+
+impl Accounts {
+    // An owner's account, all zero if it has none yet
+    pub fn get(&self, owner: OwnerId) -> Account {
+        self.0.get(&owner).copied().unwrap_or_default()
+    }
+
+    // Adds to an owner's balance, or takes from it when negative
+    pub fn deposit(&mut self, owner: OwnerId, amount: i128) {
+        self.0.entry(owner).or_default().balance += amount;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (OwnerId, &Account)> {
+        self.0.iter().map(|(owner, account)| (*owner, account))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Settles a continuous fill between its maker and taker, with any fees on it
+    pub(crate) fn record(
+        &mut self,
+        taker_side: Side,
+        fill: &Fill,
+        maker_owner: Option<OwnerId>,
+        taker_owner: Option<OwnerId>,
+    ) {
+        let (maker_fee, taker_fee) = fill
+            .fees
+            .map_or((0, 0), |fees| (fees.maker_fee, fees.taker_fee));
+        let trades = [
+            (maker_owner, taker_side.opposite(), maker_fee),
+            (taker_owner, taker_side, taker_fee),
+        ];
+        for (owner, side, fee) in trades {
+            if let Some(owner) = owner {
+                self.0
+                    .entry(owner)
+                    .or_default()
+                    .trade(side, fill.price, fill.quantity, fee);
+            }
+        }
+    }
+
+    // Settles an auction fill between its buyer and seller, with any fees on it
+    pub(crate) fn record_auction(
+        &mut self,
+        fill: &AuctionFill,
+        buy_owner: Option<OwnerId>,
+        sell_owner: Option<OwnerId>,
+    ) {
+        let (buy_fee, sell_fee) = fill
+            .fees
+            .map_or((0, 0), |fees| (fees.buy_fee, fees.sell_fee));
+        let trades = [
+            (buy_owner, Side::Bid, buy_fee),
+            (sell_owner, Side::Ask, sell_fee),
+        ];
+        for (owner, side, fee) in trades {
+            if let Some(owner) = owner {
+                self.0
+                    .entry(owner)
+                    .or_default()
+                    .trade(side, fill.price, fill.quantity, fee);
+            }
+        }
+    }
+}
+
+impl OrderBook {
+    // An owner's account, None unless the book tracks accounts
+    pub fn account(&self, owner: OwnerId) -> Option<Account> {
+        self.accounts.as_ref().map(|accounts| accounts.get(owner))
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    orderbook::{OrderBook, PriceLevel},
+    types::{Price, Quantity},
+};
+
+// Resting quantity and price * quantity summed over the top `levels` of one side
+fn side_aggregate<'a>(
+    levels: impl Iterator<Item = (Price, &'a PriceLevel)>,
+    count: usize,
+) -> (Quantity, f64) {
+    levels
+        .take(count)
+        .fold((0, 0.0), |(quantity, notional), (price, level)| {
+            (
+                quantity + level.total_quantity,
+                notional + price as f64 * level.total_quantity as f64,
+            )
+        })
+}
+
+impl OrderBook {
+    // (bid volume - ask volume) / (bid volume + ask volume) over the top `levels` of each
+    // side, from -1.0 (all asks) to 1.0 (all bids). None if both are empty.
+    pub fn imbalance(&self, levels: usize) -> Option<f64> {
+        let (bid_quantity, _) = side_aggregate(self.bids(), levels);
+        let (ask_quantity, _) = side_aggregate(self.asks(), levels);
+        let total = bid_quantity + ask_quantity;
+
+        (total > 0).then(|| (bid_quantity as f64 - ask_quantity as f64) / total as f64)
+    }
+
+    // Mid price weighted by the size on each side over the top `levels`: each side's
+    // volume weighted price, leaning toward the side with less size behind it.
+    // With one level this is the usual micro-price. None unless both sides have liquidity.
+    pub fn weighted_mid_price(&self, levels: usize) -> Option<f64> {
+        let (bid_quantity, bid_notional) = side_aggregate(self.bids(), levels);
+        let (ask_quantity, ask_notional) = side_aggregate(self.asks(), levels);
+        if bid_quantity == 0 || ask_quantity == 0 {
+            return None;
+        }
+
+        let (bid_quantity, ask_quantity) = (bid_quantity as f64, ask_quantity as f64);
+        let bid_price = bid_notional / bid_quantity;
+        let ask_price = ask_notional / ask_quantity;
+        Some((bid_price * ask_quantity + ask_price * bid_quantity) / (bid_quantity + ask_quantity))
+    }
+}
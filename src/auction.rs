@@ -0,0 +1,239 @@
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use crate::{
+    error::{AuctionError, InternalError},
+    events::BookEvent,
+    orderbook::OrderBook,
+    trading_state::TradingState,
+    types::{AuctionFill, OrderId, Price, Quantity, Side, UncrossResult},
+};
+
+// The price an auction would uncross at if it ended now, published while orders arrive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndicativePrice {
+    pub price: Price,
+    pub matched_quantity: Quantity,
+    pub imbalance_quantity: Quantity, // Volume left unmatched at that price
+    pub imbalance_side: Option<Side>, // Side with the unmatched volume, None if balanced
+}
+
+// Where a crossed book would uncross, with the volume on each side at that price
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Equilibrium {
+    pub price: Price,
+    pub buy_quantity: Quantity,  // Bids priced at or above `price`
+    pub sell_quantity: Quantity, // Asks priced at or below `price`
+}
+
+impl Equilibrium {
+    pub fn matched_quantity(&self) -> Quantity {
+        self.buy_quantity.min(self.sell_quantity)
+    }
+
+    fn surplus(&self) -> Quantity {
+        self.buy_quantity.abs_diff(self.sell_quantity)
+    }
+
+    fn indicative_price(&self) -> IndicativePrice {
+        IndicativePrice {
+            price: self.price,
+            matched_quantity: self.matched_quantity(),
+            imbalance_quantity: self.surplus(),
+            imbalance_side: match self.buy_quantity.cmp(&self.sell_quantity) {
+                std::cmp::Ordering::Greater => Some(Side::Bid),
+                std::cmp::Ordering::Less => Some(Side::Ask),
+                std::cmp::Ordering::Equal => None,
+            },
+        }
+    }
+}
+
+impl OrderBook {
+    // Where the auction would uncross right now, None outside an auction or while the
+    // book isn't crossed. Kept up to date after every change to the book.
+    pub fn indicative_price(&self) -> Option<IndicativePrice> {
+        self.cached_indicative_price
+    }
+
+    pub(crate) fn refresh_indicative_price(&mut self) {
+        self.cached_indicative_price = if self.trading_state == TradingState::Auction {
+            self.equilibrium()
+                .map(|equilibrium| equilibrium.indicative_price())
+        } else {
+            None
+        };
+    }
+
+    // Finds the uncross price of a crossed book among the resting prices between the
+    // best ask and the best bid, hidden orders included. The price executing the most
+    // volume wins, then the one leaving the least unmatched surplus, then the one closest
+    // to the last trade price, and finally the lowest. None unless the book is crossed.
+    pub(crate) fn equilibrium(&self) -> Option<Equilibrium> {
+        let (best_bid, _, _) = self.auction_head(Side::Bid).ok()??;
+        let (best_ask, _, _) = self.auction_head(Side::Ask).ok()??;
+        if best_bid < best_ask {
+            return None;
+        }
+
+        // Both sides in ascending price order over the crossed range
+        let bids = self.auction_volumes(Side::Bid, best_ask..=best_bid);
+        let asks = self.auction_volumes(Side::Ask, best_ask..=best_bid);
+
+        // Every bid in range is willing to pay the lowest candidate price
+        let mut buy_quantity: Quantity = bids.iter().map(|&(_, quantity)| quantity).sum();
+        let mut sell_quantity: Quantity = 0;
+        let (mut next_bid, mut next_ask) = (bids.iter().peekable(), asks.iter().peekable());
+        let mut candidates = Vec::with_capacity(bids.len() + asks.len());
+
+        loop {
+            let price = match (next_bid.peek(), next_ask.peek()) {
+                (Some(&&(bid, _)), Some(&&(ask, _))) => bid.min(ask),
+                (Some(&&(price, _)), None) | (None, Some(&&(price, _))) => price,
+                (None, None) => break,
+            };
+            if let Some(&(_, quantity)) = next_ask.next_if(|&&(ask, _)| ask == price) {
+                sell_quantity += quantity;
+            }
+            candidates.push(Equilibrium {
+                price,
+                buy_quantity,
+                sell_quantity,
+            });
+            // Bids at this price can't pay any higher candidate
+            if let Some(&(_, quantity)) = next_bid.next_if(|&&(bid, _)| bid == price) {
+                buy_quantity -= quantity;
+            }
+        }
+
+        let last_trade_price = self.last_trade_price;
+        candidates.into_iter().min_by_key(|candidate| {
+            (
+                std::cmp::Reverse(candidate.matched_quantity()),
+                candidate.surplus(),
+                last_trade_price.map(|last| last.abs_diff(candidate.price)),
+                candidate.price,
+            )
+        })
+    }
+
+    // Executes every order that can trade at the equilibrium price in one step, as at the
+    // end of an opening, closing, or resume auction. Bids and asks are paired off in price
+    // then time priority and all trade at that price, hidden orders after the visible
+    // ones at the same price as when matching. Whatever can't trade stays resting,
+    // so the book is no longer crossed afterwards. The trading state is left unchanged.
+    pub fn uncross(&mut self) -> Result<UncrossResult, AuctionError> {
+        if self.trading_state == TradingState::Halted {
+            return Err(AuctionError::TradingHalted);
+        }
+        let Some(equilibrium) = self.equilibrium() else {
+            return Err(AuctionError::NotCrossed);
+        };
+
+        let price = equilibrium.price;
+        let timestamp = self.now();
+        let mut remaining = equilibrium.matched_quantity();
+
+        // Each side gives up the whole matched quantity, check both can before trading
+        for side in [Side::Bid, Side::Ask] {
+            if self.walk_resting(side, remaining, None, None, None)? < remaining {
+                return Err(InternalError::VolumeMismatch.into());
+            }
+        }
+        let mut fills = Vec::new();
+
+        while remaining > 0 {
+            let (
+                Some((buy_price, buy_order_id, buy_quantity)),
+                Some((sell_price, sell_order_id, sell_quantity)),
+            ) = (self.auction_head(Side::Bid)?, self.auction_head(Side::Ask)?)
+            else {
+                return Err(InternalError::VolumeMismatch.into());
+            };
+
+            let mut fill = AuctionFill {
+                price,
+                quantity: remaining.min(buy_quantity).min(sell_quantity),
+                buy_order_id,
+                buy_price,
+                sell_order_id,
+                sell_price,
+                trade_id: self.next_trade_id,
+                timestamp,
+                fees: None,
+            };
+            self.next_trade_id.0 += 1;
+            remaining -= fill.quantity;
+            let owner =
+                |order_id: OrderId| self.index_map.get(&order_id).and_then(|entry| entry.owner);
+            let (buy_owner, sell_owner) = (owner(fill.buy_order_id), owner(fill.sell_order_id));
+            if let Some(schedule) = &self.fee_schedule {
+                fill.fees = Some(
+                    self.owner_volumes
+                        .charge_auction(schedule, &fill, buy_owner, sell_owner),
+                );
+            }
+            if let Some(accounts) = &mut self.accounts {
+                accounts.record_auction(&fill, buy_owner, sell_owner);
+            }
+            self.events
+                .emit(BookEvent::AuctionTrade { fill: fill.clone() });
+
+            for order_id in [fill.buy_order_id, fill.sell_order_id] {
+                self.take_resting_quantity(order_id, fill.quantity)
+                    .map_err(|error| error.into_internal())?;
+            }
+            fills.push(fill);
+        }
+
+        self.last_trade_price = Some(price);
+        let sequence = self.after_mutation();
+        Ok(UncrossResult {
+            price,
+            quantity: equilibrium.matched_quantity(),
+            fills,
+            sequence,
+        })
+    }
+
+    // Next in line on one side as (price, order Id, quantity), the head of the best level
+    // with hidden orders only going first at a better price
+    fn auction_head(
+        &self,
+        side: Side,
+    ) -> Result<Option<(Price, OrderId, Quantity)>, InternalError> {
+        let best = |hidden: bool| {
+            let levels = self.levels(side, hidden);
+            match side {
+                Side::Bid => levels.last(),
+                Side::Ask => levels.first(),
+            }
+        };
+        let (price, level) = match (best(false), best(true)) {
+            (Some(visible), Some(hidden)) => match side {
+                Side::Bid if hidden.0 > visible.0 => hidden,
+                Side::Ask if hidden.0 < visible.0 => hidden,
+                _ => visible,
+            },
+            (Some(level), None) | (None, Some(level)) => level,
+            (None, None) => return Ok(None),
+        };
+        let node = self
+            .orders
+            .get(level.head)
+            .ok_or(InternalError::DanglingLevelHead { side, price })?;
+        Ok(Some((price, node.order_id, node.quantity)))
+    }
+
+    // Resting quantity at each price in the range on one side, visible and hidden orders
+    // together, lowest price first
+    fn auction_volumes(&self, side: Side, prices: RangeInclusive<Price>) -> Vec<(Price, Quantity)> {
+        let mut volumes = BTreeMap::new();
+        for hidden in [false, true] {
+            for (price, level) in self.levels(side, hidden).range(prices.clone()) {
+                *volumes.entry(price).or_insert(0) += level.total_quantity;
+            }
+        }
+        volumes.into_iter().collect()
+    }
+}
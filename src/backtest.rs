@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use crate::{
+    clock::ManualClock,
+    command::Command,
+    error::{CancelOrderError, LimitOrderError, MarketOrderError},
+    events::BookEvent,
+    orderbook::OrderBook,
+    types::{Fill, OrderId, Price, Quantity, Side, Timestamp},
+};
+
+// Order Ids from here up belong to the strategy, historical Ids must stay below it
+pub const STRATEGY_ORDER_ID_BASE: u64 = 1 << 63;
+
+pub fn is_strategy_order(order_id: OrderId) -> bool {
+    order_id.0 >= STRATEGY_ORDER_ID_BASE
+}
+
+// One message of historical market data, as the command that reproduces it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoricalEvent {
+    pub timestamp: Timestamp,
+    pub command: Command,
+}
+
+// A trade one of the strategy's orders took part in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrategyFill {
+    pub timestamp: Timestamp,
+    pub order_id: OrderId, // The strategy's order, for aggressive fills there may be none
+    pub side: Side,        // The strategy's side
+    pub price: Price,
+    pub quantity: Quantity,
+    pub aggressive: bool, // The strategy took liquidity rather than resting
+}
+
+// What a strategy can see and do from inside a callback
+pub struct BacktestContext<'a> {
+    book: &'a mut OrderBook,
+    fills: &'a mut Vec<StrategyFill>,
+    next_order_id: &'a mut u64,
+    now: Timestamp,
+}
+
+impl BacktestContext<'_> {
+    pub fn book(&self) -> &OrderBook {
+        self.book
+    }
+
+    pub fn now(&self) -> Timestamp {
+        self.now
+    }
+
+    // Places a limit order, trading against the book first like any other. Anything
+    // left rests behind the historical orders already at its price.
+    pub fn limit(
+        &mut self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+    ) -> Result<OrderId, LimitOrderError> {
+        let order_id = OrderId(*self.next_order_id);
+        let result = self
+            .book
+            .execute_limit_order(side, order_id, price, quantity)?;
+        *self.next_order_id += 1;
+        self.record_aggressive(side, Some(order_id), &result.fills);
+        Ok(order_id)
+    }
+
+    // Returns the quantity filled
+    pub fn market(&mut self, side: Side, quantity: Quantity) -> Result<Quantity, MarketOrderError> {
+        let result = self.book.execute_market_order(side, quantity)?;
+        self.record_aggressive(side, None, &result.fills);
+        Ok(result.filled_quantity)
+    }
+
+    pub fn cancel(&mut self, order_id: OrderId) -> Result<(), CancelOrderError> {
+        if !is_strategy_order(order_id) {
+            return Err(CancelOrderError::OrderIdNotFound);
+        }
+        self.book.cancel_order(order_id)
+    }
+
+    fn record_aggressive(&mut self, side: Side, order_id: Option<OrderId>, fills: &[Fill]) {
+        self.fills.extend(fills.iter().map(|fill| StrategyFill {
+            timestamp: fill.timestamp,
+            order_id: order_id.unwrap_or_default(),
+            side,
+            price: fill.price,
+            quantity: fill.quantity,
+            aggressive: true,
+        }));
+    }
+}
+
+// A user strategy, called back as history plays out. Both callbacks default to doing
+// nothing. Any closure taking the context and the event is a strategy too.
+pub trait Strategy {
+    // After each historical event has been applied to the book
+    fn on_event(&mut self, _context: &mut BacktestContext<'_>, _event: &HistoricalEvent) {}
+
+    // For each fill of the strategy's orders, after the `on_event` that led to it
+    fn on_fill(&mut self, _context: &mut BacktestContext<'_>, _fill: &StrategyFill) {}
+}
+
+impl<F: FnMut(&mut BacktestContext<'_>, &HistoricalEvent)> Strategy for F {
+    fn on_event(&mut self, context: &mut BacktestContext<'_>, event: &HistoricalEvent) {
+        self(context, event)
+    }
+}
+
+// Totals and fills so far
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BacktestReport {
+    pub events: usize,
+    pub rejected_events: usize, // Historical commands the book turned down, see `Backtest`
+    pub fills: Vec<StrategyFill>,
+}
+
+impl BacktestReport {
+    // Net quantity bought
+    pub fn position(&self) -> i128 {
+        self.fills
+            .iter()
+            .map(|fill| match fill.side {
+                Side::Bid => fill.quantity as i128,
+                Side::Ask => -(fill.quantity as i128),
+            })
+            .sum()
+    }
+
+    // Net cash received, negative after buying
+    pub fn cash(&self) -> i128 {
+        self.fills
+            .iter()
+            .map(|fill| {
+                let notional = fill.price as i128 * fill.quantity as i128;
+                match fill.side {
+                    Side::Bid => -notional,
+                    Side::Ask => notional,
+                }
+            })
+            .sum()
+    }
+}
+
+// Rebuilds a book from historical events while a strategy trades against it. The
+// strategy's orders go into the same book, so the queue model is the book's own:
+// a resting strategy order fills only once the historical orders ahead of it at its
+// price have traded or cancelled, and historical aggressors that reach it fill it
+// instead of the liquidity behind. The strategy's trades take liquidity history went
+// on to use, so historical commands can then find their order gone or already
+// filled. Those are counted in `rejected_events` and otherwise skipped.
+//
+// Fills are stamped with the historical timestamps through the book's clock.
+#[derive(Debug)]
+pub struct Backtest<S: Strategy> {
+    book: OrderBook,
+    clock: Arc<ManualClock>,
+    strategy: S,
+    next_order_id: u64,
+    report: BacktestReport,
+}
+
+impl<S: Strategy> Backtest<S> {
+    pub fn new(strategy: S) -> Self {
+        let clock = Arc::new(ManualClock::default());
+        Self {
+            book: OrderBook::with_clock(clock.clone()),
+            clock,
+            strategy,
+            next_order_id: STRATEGY_ORDER_ID_BASE,
+            report: BacktestReport::default(),
+        }
+    }
+
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    pub fn report(&self) -> &BacktestReport {
+        &self.report
+    }
+
+    pub fn run(&mut self, events: impl IntoIterator<Item = HistoricalEvent>) -> &BacktestReport {
+        for event in events {
+            self.step(&event);
+        }
+        &self.report
+    }
+
+    // Applies one historical event, then gives the strategy its turn
+    pub fn step(&mut self, event: &HistoricalEvent) {
+        self.clock.set(event.timestamp);
+        self.report.events += 1;
+        let delivered = self.report.fills.len();
+
+        match self.book.apply(event.command.clone()) {
+            Ok(events) => {
+                for book_event in &events {
+                    if let BookEvent::Trade { taker_side, fill } = book_event
+                        && is_strategy_order(fill.maker_order_id)
+                    {
+                        self.report.fills.push(StrategyFill {
+                            timestamp: fill.timestamp,
+                            order_id: fill.maker_order_id,
+                            side: taker_side.opposite(),
+                            price: fill.price,
+                            quantity: fill.quantity,
+                            aggressive: false,
+                        });
+                    }
+                }
+            }
+            Err(_) => self.report.rejected_events += 1,
+        }
+
+        let Self {
+            book,
+            strategy,
+            next_order_id,
+            report,
+            ..
+        } = self;
+        let mut context = BacktestContext {
+            book,
+            fills: &mut report.fills,
+            next_order_id,
+            now: event.timestamp,
+        };
+        strategy.on_event(&mut context, event);
+
+        // Fills can lead to more orders and so more fills, each is delivered once
+        let mut next = delivered;
+        while let Some(&fill) = context.fills.get(next) {
+            strategy.on_fill(&mut context, &fill);
+            next += 1;
+        }
+    }
+}
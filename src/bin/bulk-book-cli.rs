@@ -0,0 +1,26 @@
+// Drives a book from stdin one command per line, see `help`. `quit` or end of
+// input exits.
+use std::io::{self, BufRead, Write};
+
+use bulk_book::repl::Repl;
+
+fn main() -> io::Result<()> {
+    let mut repl = Repl::new();
+    let mut stdout = io::stdout();
+    let mut lines = io::stdin().lock().lines();
+
+    loop {
+        write!(stdout, "> ")?;
+        stdout.flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            return Ok(());
+        };
+        if matches!(line.trim(), "quit" | "exit") {
+            return Ok(());
+        }
+        let output = repl.execute(&line);
+        if !output.is_empty() {
+            writeln!(stdout, "{output}")?;
+        }
+    }
+}
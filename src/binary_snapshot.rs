@@ -0,0 +1,484 @@
+use std::cmp::Reverse;
+
+use slab::Slab;
+
+use crate::{
+    accounts::{Account, Accounts},
+    book_side::{BookSide, PriceArray},
+    codec::{ByteReader, ByteWriter},
+    error::DecodeError,
+    fees::{FeeRate, FeeSchedule, FeeTier},
+    instrument::Instrument,
+    journal::{read_command, write_command},
+    order::TimeInForce,
+    order_ids::{DuplicateOrderIdPolicy, OrderIdMode},
+    orderbook::{IndexMapEntry, OrderBook, OrderNode, PriceLevel},
+    pegged_orders::{PegType, PeggedOrder},
+    stop_orders::StopOrder,
+    trading_state::{HaltPolicy, TradingState},
+    types::{ClientOrderId, OrderId, OwnerId, Quantity, Side, TradeId},
+};
+
+const MAGIC: &[u8; 4] = b"BBOK";
+// Bumped whenever the layout changes, snapshots of any other version are rejected
+const VERSION: u8 = 1;
+
+impl OrderBook {
+    // Encodes the full book state: every level's orders in FIFO order, hidden levels
+    // after the visible ones, each side's layout with any price array's range, plus stops, pegs, GTD deadlines,
+    // DAY orders, owners, timestamps, client order Ids, the instrument, the price
+    // setting, the trading state with any queued commands, the fee schedule, owners'
+    // accounts, the order Id mode and duplicate policy, and the order Id, trade Id, and
+    // sequence number counters. Owners' rolling volumes for fee tiers start over from the
+    // restored book.
+    // Listeners and recorded events are not part of the snapshot.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = ByteWriter::default();
+        writer.bytes.extend_from_slice(MAGIC);
+        writer.u8(VERSION);
+
+        writer.u64(self.next_trade_id.0);
+        writer.option_price(self.last_trade_price);
+        writer.u64(self.command_sequence);
+        writer.u64(self.event_sequence());
+
+        match &self.instrument {
+            Some(instrument) => {
+                writer.u8(1);
+                writer.i64(instrument.tick_size());
+                writer.u64(instrument.lot_size());
+                writer.i64(instrument.min_price());
+                writer.i64(instrument.max_price());
+                writer.u64(instrument.max_order_size());
+                writer.u64(instrument.min_order_size());
+                writer.u32(instrument.price_decimals());
+            }
+            None => writer.u8(0),
+        }
+        writer.u8(self.reject_non_positive_prices as u8);
+        writer.u8(match self.trading_state {
+            TradingState::Open => 0,
+            TradingState::Halted => 1,
+            TradingState::CancelOnly => 2,
+            TradingState::Auction => 3,
+        });
+        writer.u8((self.halt_policy == HaltPolicy::Queue) as u8);
+        writer.len(self.queued_commands.len());
+        for command in &self.queued_commands {
+            write_command(&mut writer, command);
+        }
+        match &self.fee_schedule {
+            Some(schedule) => {
+                writer.u8(1);
+                write_fee_rate(&mut writer, schedule.maker);
+                write_fee_rate(&mut writer, schedule.taker);
+                writer.u64(schedule.volume_window);
+                writer.len(schedule.tiers.len());
+                for tier in &schedule.tiers {
+                    writer.u64(tier.min_volume);
+                    write_fee_rate(&mut writer, tier.maker);
+                    write_fee_rate(&mut writer, tier.taker);
+                }
+            }
+            None => writer.u8(0),
+        }
+
+        let levels = [&self.bids, &self.asks, &self.hidden_bids, &self.hidden_asks];
+        for levels in levels {
+            // Array backed sides come back with the same price range
+            match levels {
+                BookSide::Tree(_) => writer.u8(0),
+                BookSide::Array(array) => {
+                    let (min_price, max_price, tick_size) = array.bounds();
+                    writer.u8(1);
+                    writer.i64(min_price);
+                    writer.i64(max_price);
+                    writer.i64(tick_size);
+                }
+            }
+            writer.len(levels.len());
+            for (price, level) in levels.iter() {
+                writer.i64(price);
+                writer.len(level.order_count);
+
+                let mut next = Some(level.head);
+                while let Some(index) = next {
+                    let node = &self.orders[index];
+                    writer.u64(node.order_id.0);
+                    writer.u64(node.quantity);
+                    let entry = &self.index_map[&node.order_id];
+                    writer.option_u64(entry.expires_at);
+                    writer.option_u64(entry.owner.map(|owner| owner.0));
+                    writer.u64(entry.timestamp);
+                    writer.option_u64(node.client_order_id.map(|id| id.0));
+                    next = node.next;
+                }
+            }
+        }
+
+        for stops in [&self.buy_stops, &self.sell_stops] {
+            writer.len(stops.len());
+            for (stop_price, order_ids) in stops {
+                writer.i64(*stop_price);
+                writer.len(order_ids.len());
+                for order_id in order_ids {
+                    let stop = &self.stop_orders[order_id];
+                    writer.u64(order_id.0);
+                    writer.i64(stop.limit_price);
+                    writer.u64(stop.quantity);
+                }
+            }
+        }
+
+        writer.len(self.pegged_orders.len());
+        for (order_id, peg) in &self.pegged_orders {
+            writer.u64(order_id.0);
+            writer.side(peg.side);
+            writer.u8(match peg.peg_type {
+                PegType::Primary => 0,
+                PegType::Market => 1,
+                PegType::Midpoint => 2,
+            });
+            writer.i64(peg.offset);
+        }
+        writer.option_price(self.peg_references.0);
+        writer.option_price(self.peg_references.1);
+
+        // In the order they were placed, leaving out any that stopped resting since
+        let day_orders: Vec<_> = self
+            .day_orders
+            .iter()
+            .filter(|order_id| {
+                self.index_map
+                    .get(*order_id)
+                    .is_some_and(|entry| entry.time_in_force == TimeInForce::Day)
+            })
+            .collect();
+        writer.len(day_orders.len());
+        for order_id in day_orders {
+            writer.u64(order_id.0);
+        }
+
+        match &self.accounts {
+            Some(accounts) => {
+                writer.u8(1);
+                // By owner, so equal books encode to equal bytes
+                let mut owners: Vec<_> = accounts.iter().collect();
+                owners.sort_unstable_by_key(|(owner, _)| *owner);
+                writer.len(owners.len());
+                for (owner, account) in owners {
+                    writer.u64(owner.0);
+                    writer.i128(account.position);
+                    writer.i128(account.balance);
+                    writer.i128(account.fees);
+                    writer.u64(account.volume);
+                }
+            }
+            None => writer.u8(0),
+        }
+
+        writer.u8((self.order_id_mode == OrderIdMode::Assigned) as u8);
+        writer.u64(self.next_order_id.0);
+        writer.u8(match self.duplicate_order_id_policy {
+            DuplicateOrderIdPolicy::Reject => 0,
+            DuplicateOrderIdPolicy::CancelReplace => 1,
+            DuplicateOrderIdPolicy::Amend => 2,
+        });
+
+        writer.bytes
+    }
+
+    // Rebuilds a book from `to_bytes` output. Orders keep their queue positions, but are
+    // laid out afresh in the slab as by `compact`, so indices handed out before the
+    // snapshot don't carry over.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = ByteReader::new(bytes);
+        if &reader.take::<4>()? != MAGIC {
+            return Err(DecodeError::BadMagic);
+        }
+        let version = reader.u8()?;
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+
+        let mut book = Self::new();
+        book.next_trade_id = TradeId(reader.u64()?);
+        book.last_trade_price = reader.option_price()?;
+        book.command_sequence = reader.u64()?;
+        book.events.set_sequence(reader.u64()?);
+
+        if reader.flag()? {
+            let tick_size = reader.i64()?;
+            let lot_size = reader.u64()?;
+            let (min_price, max_price) = (reader.i64()?, reader.i64()?);
+            let max_order_size = reader.u64()?;
+            let min_order_size = reader.u64()?;
+            let price_decimals = reader.u32()?;
+            let instrument = Instrument::builder()
+                .tick_size(tick_size)
+                .lot_size(lot_size)
+                .price_range(min_price, max_price)
+                .order_size_range(min_order_size, max_order_size)
+                .price_decimals(price_decimals)
+                .build()
+                .map_err(|_| DecodeError::Corrupt)?;
+            book.instrument = Some(instrument);
+        }
+        book.reject_non_positive_prices = reader.flag()?;
+        book.trading_state = match reader.u8()? {
+            0 => TradingState::Open,
+            1 => TradingState::Halted,
+            2 => TradingState::CancelOnly,
+            3 => TradingState::Auction,
+            _ => return Err(DecodeError::Corrupt),
+        };
+        book.halt_policy = if reader.flag()? {
+            HaltPolicy::Queue
+        } else {
+            HaltPolicy::Reject
+        };
+        for _ in 0..reader.len()? {
+            book.queued_commands.push(read_command(&mut reader)?);
+        }
+        if reader.flag()? {
+            let mut schedule = FeeSchedule {
+                maker: read_fee_rate(&mut reader)?,
+                taker: read_fee_rate(&mut reader)?,
+                volume_window: reader.u64()?,
+                ..Default::default()
+            };
+            for _ in 0..reader.len()? {
+                schedule.tiers.push(FeeTier {
+                    min_volume: reader.u64()?,
+                    maker: read_fee_rate(&mut reader)?,
+                    taker: read_fee_rate(&mut reader)?,
+                });
+            }
+            book.fee_schedule = Some(schedule);
+        }
+
+        // Numbered in the order they're read, each level's orders next to each other
+        let mut nodes: Vec<(usize, OrderNode)> = Vec::new();
+        let sides = [
+            (Side::Bid, false),
+            (Side::Ask, false),
+            (Side::Bid, true),
+            (Side::Ask, true),
+        ];
+        for (side, hidden) in sides {
+            if reader.flag()? {
+                let (min_price, max_price) = (reader.i64()?, reader.i64()?);
+                let array = PriceArray::new(min_price, max_price, reader.i64()?)
+                    .ok_or(DecodeError::Corrupt)?;
+                *book.levels_mut(side, hidden) = BookSide::Array(array);
+            }
+            for _ in 0..reader.len()? {
+                let price = reader.i64()?;
+                if !book.levels(side, hidden).accepts(price) {
+                    return Err(DecodeError::Corrupt);
+                }
+                let order_count = reader.len()?;
+                if order_count == 0 {
+                    return Err(DecodeError::Corrupt);
+                }
+
+                let first = nodes.len();
+                let mut total_quantity: Quantity = 0;
+                for position in 0..order_count {
+                    let index = nodes.len();
+                    let order_id = OrderId(reader.u64()?);
+                    let quantity = reader.u64()?;
+                    let expires_at = reader.option_u64()?;
+                    let owner = reader.option_u64()?.map(OwnerId);
+                    let timestamp = reader.u64()?;
+                    let client_order_id = reader.option_u64()?.map(ClientOrderId);
+
+                    let duplicate = book.index_map.contains_key(&order_id);
+                    if duplicate || quantity == 0 {
+                        return Err(DecodeError::Corrupt);
+                    }
+
+                    // Link behind the previous order at this level
+                    let mut previous = None;
+                    if position > 0
+                        && let Some((previous_index, previous_node)) = nodes.last_mut()
+                    {
+                        previous_node.next = Some(index);
+                        previous = Some(*previous_index);
+                    }
+                    nodes.push((
+                        index,
+                        OrderNode {
+                            quantity,
+                            order_id,
+                            previous,
+                            next: None,
+                            client_order_id,
+                        },
+                    ));
+
+                    book.index_map.insert(
+                        order_id,
+                        IndexMapEntry {
+                            order_index: index,
+                            price,
+                            side,
+                            expires_at,
+                            time_in_force: expires_at
+                                .map_or(TimeInForce::GoodTillCancel, TimeInForce::GoodTillDate),
+                            owner,
+                            timestamp,
+                            hidden,
+                        },
+                    );
+                    if let Some(expires_at) = expires_at {
+                        book.expiry_queue.push(Reverse((expires_at, order_id)));
+                    }
+                    if let Some(owner) = owner {
+                        book.owner_orders.insert(owner, order_id);
+                    }
+                    book.queue_ranks.push(side, hidden, price, order_id);
+                    total_quantity = total_quantity
+                        .checked_add(quantity)
+                        .ok_or(DecodeError::Corrupt)?;
+                }
+
+                let level = PriceLevel {
+                    head: nodes[first].0,
+                    tail: nodes[nodes.len() - 1].0,
+                    order_count,
+                    total_quantity,
+                };
+                if book.levels_mut(side, hidden).insert(price, level).is_some() {
+                    return Err(DecodeError::Corrupt);
+                }
+
+                let totals = book.side_totals_mut(side, hidden);
+                totals.quantity = totals
+                    .quantity
+                    .checked_add(total_quantity)
+                    .ok_or(DecodeError::Corrupt)?;
+                totals.order_count += order_count;
+            }
+        }
+        book.orders = Slab::from_iter(nodes);
+
+        for side in [Side::Bid, Side::Ask] {
+            for _ in 0..reader.len()? {
+                let stop_price = reader.i64()?;
+                let mut order_ids = Vec::new();
+                for _ in 0..reader.len()? {
+                    let order_id = OrderId(reader.u64()?);
+                    let stop = StopOrder {
+                        side,
+                        stop_price,
+                        limit_price: reader.i64()?,
+                        quantity: reader.u64()?,
+                    };
+                    if book.order_id_exists(order_id) {
+                        return Err(DecodeError::Corrupt);
+                    }
+                    book.stop_orders.insert(order_id, stop);
+                    order_ids.push(order_id);
+                }
+
+                let stops = match side {
+                    Side::Bid => &mut book.buy_stops,
+                    Side::Ask => &mut book.sell_stops,
+                };
+                if stops.insert(stop_price, order_ids).is_some() {
+                    return Err(DecodeError::Corrupt);
+                }
+            }
+        }
+
+        for _ in 0..reader.len()? {
+            let order_id = OrderId(reader.u64()?);
+            let side = reader.side()?;
+            let peg_type = match reader.u8()? {
+                0 => PegType::Primary,
+                1 => PegType::Market,
+                2 => PegType::Midpoint,
+                _ => return Err(DecodeError::Corrupt),
+            };
+            let offset = reader.i64()?;
+            if !book.index_map.contains_key(&order_id) {
+                return Err(DecodeError::Corrupt);
+            }
+            book.pegged_orders.insert(
+                order_id,
+                PeggedOrder {
+                    side,
+                    peg_type,
+                    offset,
+                },
+            );
+        }
+        book.peg_references = (reader.option_price()?, reader.option_price()?);
+
+        for _ in 0..reader.len()? {
+            let order_id = OrderId(reader.u64()?);
+            let Some(entry) = book.index_map.get_mut(&order_id) else {
+                return Err(DecodeError::Corrupt);
+            };
+            if entry.time_in_force != TimeInForce::GoodTillCancel {
+                return Err(DecodeError::Corrupt);
+            }
+            entry.time_in_force = TimeInForce::Day;
+            book.day_orders.push(order_id);
+        }
+        if reader.flag()? {
+            let mut accounts = Accounts::default();
+            for _ in 0..reader.len()? {
+                let owner = OwnerId(reader.u64()?);
+                let account = Account {
+                    position: reader.i128()?,
+                    balance: reader.i128()?,
+                    fees: reader.i128()?,
+                    volume: reader.u64()?,
+                };
+                if accounts.0.insert(owner, account).is_some() {
+                    return Err(DecodeError::Corrupt);
+                }
+            }
+            book.accounts = Some(accounts);
+        }
+        book.order_id_mode = if reader.flag()? {
+            OrderIdMode::Assigned
+        } else {
+            OrderIdMode::Caller
+        };
+        book.next_order_id = OrderId(reader.u64()?);
+        book.duplicate_order_id_policy = match reader.u8()? {
+            0 => DuplicateOrderIdPolicy::Reject,
+            1 => DuplicateOrderIdPolicy::CancelReplace,
+            2 => DuplicateOrderIdPolicy::Amend,
+            _ => return Err(DecodeError::Corrupt),
+        };
+
+        if !reader.is_empty() {
+            return Err(DecodeError::Corrupt);
+        }
+
+        book.refresh_top_of_book();
+        book.refresh_indicative_price();
+        Ok(book)
+    }
+}
+
+fn write_fee_rate(writer: &mut ByteWriter, rate: FeeRate) {
+    let (kind, value) = match rate {
+        FeeRate::Bps(bps) => (0, bps),
+        FeeRate::Fixed(amount) => (1, amount),
+    };
+    writer.u8(kind);
+    writer.i64(value);
+}
+
+fn read_fee_rate(reader: &mut ByteReader) -> Result<FeeRate, DecodeError> {
+    match reader.u8()? {
+        0 => Ok(FeeRate::Bps(reader.i64()?)),
+        1 => Ok(FeeRate::Fixed(reader.i64()?)),
+        _ => Err(DecodeError::Corrupt),
+    }
+}
@@ -0,0 +1,349 @@
+use std::{
+    collections::{BTreeMap, btree_map},
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{
+    error::InstrumentError,
+    instrument::Instrument,
+    orderbook::{OrderBook, PriceLevel},
+    types::Price,
+};
+
+// Largest price array a book will allocate per side. Each slot takes 40 bytes whether
+// or not a level rests there, so a full array is about 40 MiB a side.
+pub const MAX_ARRAY_LEVELS: usize = 1 << 20;
+
+// The price levels of one side of the book, keyed by price in ascending order.
+// Backed by a BTreeMap by default, or by a price-indexed array for instruments with
+// a known tick range, trading memory for O(1) level access.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BookSide {
+    Tree(BTreeMap<Price, PriceLevel>),
+    Array(PriceArray),
+}
+
+impl Default for BookSide {
+    fn default() -> Self {
+        BookSide::Tree(BTreeMap::new())
+    }
+}
+
+// Sides are equal when they hold the same levels, however they're stored
+impl PartialEq for BookSide {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for BookSide {}
+
+impl BookSide {
+    pub fn len(&self) -> usize {
+        match self {
+            BookSide::Tree(levels) => levels.len(),
+            BookSide::Array(array) => array.len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Whether a level could be stored at this price
+    pub fn accepts(&self, price: Price) -> bool {
+        match self {
+            BookSide::Tree(_) => true,
+            BookSide::Array(array) => array.slot(price).is_some(),
+        }
+    }
+
+    pub fn contains_key(&self, price: &Price) -> bool {
+        self.get(price).is_some()
+    }
+
+    pub fn get(&self, price: &Price) -> Option<&PriceLevel> {
+        match self {
+            BookSide::Tree(levels) => levels.get(price),
+            BookSide::Array(array) => array.levels[array.slot(*price)?].as_ref(),
+        }
+    }
+
+    pub fn get_mut(&mut self, price: &Price) -> Option<&mut PriceLevel> {
+        match self {
+            BookSide::Tree(levels) => levels.get_mut(price),
+            BookSide::Array(array) => {
+                let slot = array.slot(*price)?;
+                array.levels[slot].as_mut()
+            }
+        }
+    }
+
+    // Panics if an array backed side can't hold the price, check `accepts` first
+    pub fn insert(&mut self, price: Price, level: PriceLevel) -> Option<PriceLevel> {
+        match self {
+            BookSide::Tree(levels) => levels.insert(price, level),
+            BookSide::Array(array) => {
+                let slot = array
+                    .slot(price)
+                    .expect("price outside the book side's array range");
+                let previous = array.levels[slot].replace(level);
+                if previous.is_none() {
+                    array.occupied[slot / 64] |= 1 << (slot % 64);
+                    array.len += 1;
+                }
+                previous
+            }
+        }
+    }
+
+    pub fn remove(&mut self, price: &Price) -> Option<PriceLevel> {
+        match self {
+            BookSide::Tree(levels) => levels.remove(price),
+            BookSide::Array(array) => {
+                let slot = array.slot(*price)?;
+                let removed = array.levels[slot].take()?;
+                array.occupied[slot / 64] &= !(1 << (slot % 64));
+                array.len -= 1;
+                Some(removed)
+            }
+        }
+    }
+
+    // Every level in ascending price order, for updating levels in place
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut PriceLevel> {
+        let (tree, array) = match self {
+            BookSide::Tree(levels) => (Some(levels.values_mut()), None),
+            BookSide::Array(array) => (None, Some(array.levels.iter_mut().flatten())),
+        };
+        tree.into_iter()
+            .flatten()
+            .chain(array.into_iter().flatten())
+    }
+
+    // Lowest priced level
+    pub fn first(&self) -> Option<(Price, &PriceLevel)> {
+        self.iter().next()
+    }
+
+    // Highest priced level
+    pub fn last(&self) -> Option<(Price, &PriceLevel)> {
+        self.iter().next_back()
+    }
+
+    pub fn first_mut(&mut self) -> Option<(Price, &mut PriceLevel)> {
+        match self {
+            BookSide::Tree(levels) => levels
+                .iter_mut()
+                .next()
+                .map(|(price, level)| (*price, level)),
+            BookSide::Array(array) => {
+                let slot = array.next_occupied(0, array.levels.len())?;
+                let price = array.price(slot);
+                Some((price, array.levels[slot].as_mut()?))
+            }
+        }
+    }
+
+    pub fn last_mut(&mut self) -> Option<(Price, &mut PriceLevel)> {
+        match self {
+            BookSide::Tree(levels) => levels
+                .iter_mut()
+                .next_back()
+                .map(|(price, level)| (*price, level)),
+            BookSide::Array(array) => {
+                let slot = array.previous_occupied(0, array.levels.len())?;
+                let price = array.price(slot);
+                Some((price, array.levels[slot].as_mut()?))
+            }
+        }
+    }
+
+    // Every level in ascending price order
+    pub fn iter(&self) -> Levels<'_> {
+        self.range(..)
+    }
+
+    pub fn range(&self, range: impl RangeBounds<Price>) -> Levels<'_> {
+        match self {
+            BookSide::Tree(levels) => Levels::Tree(
+                levels.range((range.start_bound().cloned(), range.end_bound().cloned())),
+            ),
+            BookSide::Array(array) => {
+                let (front, back) = array.slot_range(range);
+                Levels::Array { array, front, back }
+            }
+        }
+    }
+}
+
+// Levels stored by slot, `(price - min_price) / tick_size`, with a bitset of occupied
+// slots so the best level is found a word at a time
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriceArray {
+    min_price: Price,
+    tick_size: Price,
+    levels: Vec<Option<PriceLevel>>,
+    occupied: Vec<u64>,
+    len: usize,
+}
+
+impl PriceArray {
+    // Covers every tick from `min_price` to `max_price` inclusive
+    pub fn new(min_price: Price, max_price: Price, tick_size: Price) -> Option<Self> {
+        if tick_size <= 0 || max_price < min_price {
+            return None;
+        }
+        let slots = (max_price as i128 - min_price as i128) / tick_size as i128 + 1;
+        if slots > MAX_ARRAY_LEVELS as i128 {
+            return None;
+        }
+
+        let slots = slots as usize;
+        Some(Self {
+            min_price,
+            tick_size,
+            levels: vec![None; slots],
+            occupied: vec![0; slots.div_ceil(64)],
+            len: 0,
+        })
+    }
+
+    // The lowest and highest prices covered and the tick between them, as passed to `new`
+    pub fn bounds(&self) -> (Price, Price, Price) {
+        (
+            self.min_price,
+            self.price(self.levels.len() - 1),
+            self.tick_size,
+        )
+    }
+
+    fn slot(&self, price: Price) -> Option<usize> {
+        let offset = price.checked_sub(self.min_price)?;
+        if offset < 0 || offset % self.tick_size != 0 {
+            return None;
+        }
+        let slot = (offset / self.tick_size) as usize;
+        (slot < self.levels.len()).then_some(slot)
+    }
+
+    fn price(&self, slot: usize) -> Price {
+        self.min_price + slot as Price * self.tick_size
+    }
+
+    // Slots covering a price range, as a half open `front..back`
+    fn slot_range(&self, range: impl RangeBounds<Price>) -> (usize, usize) {
+        let slots = self.levels.len() as i128;
+        let offset = |price: Price| price as i128 - self.min_price as i128;
+        let tick = self.tick_size as i128;
+
+        let front = match range.start_bound() {
+            Bound::Included(&price) => {
+                offset(price).div_euclid(tick) + (offset(price).rem_euclid(tick) != 0) as i128
+            }
+            Bound::Excluded(&price) => offset(price).div_euclid(tick) + 1,
+            Bound::Unbounded => 0,
+        };
+        let back = match range.end_bound() {
+            Bound::Included(&price) => offset(price).div_euclid(tick) + 1,
+            Bound::Excluded(&price) => {
+                offset(price).div_euclid(tick) + (offset(price).rem_euclid(tick) != 0) as i128
+            }
+            Bound::Unbounded => slots,
+        };
+
+        let clamp = |slot: i128| slot.clamp(0, slots) as usize;
+        (clamp(front), clamp(back).max(clamp(front)))
+    }
+
+    // First occupied slot in `front..back`
+    fn next_occupied(&self, front: usize, back: usize) -> Option<usize> {
+        let mut slot = front;
+        while slot < back {
+            let word = self.occupied[slot / 64] >> (slot % 64);
+            if word != 0 {
+                let found = slot + word.trailing_zeros() as usize;
+                return (found < back).then_some(found);
+            }
+            slot = (slot / 64 + 1) * 64;
+        }
+        None
+    }
+
+    // Last occupied slot in `front..back`
+    fn previous_occupied(&self, front: usize, back: usize) -> Option<usize> {
+        let mut end = back;
+        while end > front {
+            let last = end - 1;
+            let word = self.occupied[last / 64] << (63 - last % 64);
+            if word != 0 {
+                let found = last - word.leading_zeros() as usize;
+                return (found >= front).then_some(found);
+            }
+            end = last / 64 * 64;
+        }
+        None
+    }
+}
+
+pub enum Levels<'a> {
+    Tree(btree_map::Range<'a, Price, PriceLevel>),
+    Array {
+        array: &'a PriceArray,
+        front: usize,
+        back: usize,
+    },
+}
+
+impl<'a> Iterator for Levels<'a> {
+    type Item = (Price, &'a PriceLevel);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Levels::Tree(range) => range.next().map(|(price, level)| (*price, level)),
+            Levels::Array { array, front, back } => {
+                let slot = array.next_occupied(*front, *back)?;
+                *front = slot + 1;
+                Some((array.price(slot), array.levels[slot].as_ref()?))
+            }
+        }
+    }
+}
+
+impl DoubleEndedIterator for Levels<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            Levels::Tree(range) => range.next_back().map(|(price, level)| (*price, level)),
+            Levels::Array { array, front, back } => {
+                let slot = array.previous_occupied(*front, *back)?;
+                *back = slot;
+                Some((array.price(slot), array.levels[slot].as_ref()?))
+            }
+        }
+    }
+}
+
+impl OrderBook {
+    // A book whose sides are price-indexed arrays spanning the instrument's price limits.
+    // Fails if the range covers more than MAX_ARRAY_LEVELS ticks, or if the minimum price
+    // is off the tick grid, since the array's slots start from it.
+    pub fn with_array_sides(instrument: Instrument) -> Result<Self, InstrumentError> {
+        if instrument.min_price().rem_euclid(instrument.tick_size()) != 0 {
+            return Err(InstrumentError::PriceNotOnTick);
+        }
+        let array = PriceArray::new(
+            instrument.min_price(),
+            instrument.max_price(),
+            instrument.tick_size(),
+        )
+        .ok_or(InstrumentError::PriceRangeTooWide)?;
+
+        Ok(Self {
+            bids: BookSide::Array(array.clone()),
+            asks: BookSide::Array(array),
+            ..Self::with_instrument(instrument)
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::VecDeque;
+
+use crate::{
+    events::BookEvent,
+    types::{Fill, Price, Quantity, Timestamp},
+};
+
+// One OHLCV bar covering [start, start + interval)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Candle {
+    pub start: Timestamp,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+    pub close: Price,
+    pub volume: Quantity,
+    pub trade_count: usize,
+}
+
+impl Candle {
+    fn new(start: Timestamp, price: Price, quantity: Quantity) -> Self {
+        Self {
+            start,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: quantity,
+            trade_count: 1,
+        }
+    }
+
+    fn add(&mut self, price: Price, quantity: Quantity) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume += quantity;
+        self.trade_count += 1;
+    }
+}
+
+// Builds OHLCV bars of a fixed interval from timestamped fills, keeping the most
+// recent `history` completed bars. Bars are aligned to multiples of the interval,
+// and intervals without any trades produce no bar.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CandleAggregator {
+    interval: Timestamp,
+    history: usize,
+    current: Option<Candle>,
+    completed: VecDeque<Candle>,
+}
+
+impl CandleAggregator {
+    // Panics if `interval` is zero
+    pub fn new(interval: Timestamp, history: usize) -> Self {
+        assert!(interval > 0, "candle interval must be non-zero");
+        Self {
+            interval,
+            history,
+            current: None,
+            completed: VecDeque::with_capacity(history),
+        }
+    }
+
+    pub fn interval(&self) -> Timestamp {
+        self.interval
+    }
+
+    // Adds a fill to its bar, returning the previous bar if this fill closed it.
+    // Fills older than the open bar are folded into it rather than reopening history.
+    pub fn record(&mut self, fill: &Fill) -> Option<Candle> {
+        self.record_trade(fill.timestamp, fill.price, fill.quantity)
+    }
+
+    fn record_trade(
+        &mut self,
+        timestamp: Timestamp,
+        price: Price,
+        quantity: Quantity,
+    ) -> Option<Candle> {
+        let start = timestamp - timestamp % self.interval;
+        match &mut self.current {
+            Some(candle) if start <= candle.start => {
+                candle.add(price, quantity);
+                None
+            }
+            current => {
+                let closed = current.replace(Candle::new(start, price, quantity));
+                if let Some(candle) = closed {
+                    if self.completed.len() == self.history {
+                        self.completed.pop_front();
+                    }
+                    if self.history > 0 {
+                        self.completed.push_back(candle);
+                    }
+                }
+                closed
+            }
+        }
+    }
+
+    // Records the fill carried by a trade or auction trade event, ignoring every other event
+    pub fn record_event(&mut self, event: &BookEvent) -> Option<Candle> {
+        match event {
+            BookEvent::Trade { fill, .. } => self.record(fill),
+            BookEvent::AuctionTrade { fill } => {
+                self.record_trade(fill.timestamp, fill.price, fill.quantity)
+            }
+            _ => None,
+        }
+    }
+
+    // The bar still open for trades, if any fill has been recorded
+    pub fn current(&self) -> Option<&Candle> {
+        self.current.as_ref()
+    }
+
+    // Completed bars, oldest first
+    pub fn completed(&self) -> impl DoubleEndedIterator<Item = &Candle> + ExactSizeIterator {
+        self.completed.iter()
+    }
+
+    // Completed bars followed by the open one, oldest first
+    pub fn candles(&self) -> impl DoubleEndedIterator<Item = &Candle> {
+        self.completed.iter().chain(self.current.as_ref())
+    }
+}
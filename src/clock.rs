@@ -0,0 +1,85 @@
+use std::{
+    fmt::Debug,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    orderbook::OrderBook,
+    types::{OrderId, Timestamp},
+};
+
+// Source of the timestamps stamped on resting orders and fills. Clocks are shared
+// between a book and its clones, so they must be `Send + Sync`.
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> Timestamp;
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> Timestamp {
+        (**self).now()
+    }
+}
+
+// Wall clock time, in nanoseconds since the Unix epoch
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as Timestamp)
+    }
+}
+
+// Only moves when told to, for tests and replaying historical data.
+// Share it with the book through an `Arc` to keep control of it.
+#[derive(Debug, Default)]
+pub struct ManualClock(AtomicU64);
+
+impl ManualClock {
+    pub fn new(now: Timestamp) -> Self {
+        Self(AtomicU64::new(now))
+    }
+
+    pub fn set(&self, now: Timestamp) {
+        self.0.store(now, Ordering::Relaxed);
+    }
+
+    pub fn advance(&self, elapsed: Timestamp) {
+        self.0.fetch_add(elapsed, Ordering::Relaxed);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Timestamp {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl OrderBook {
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
+        let mut book = Self::new();
+        book.set_clock(clock);
+        book
+    }
+
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.clock = Some(Arc::new(clock));
+    }
+
+    // Current time from the book's clock. Books without a clock stamp everything 0,
+    // which keeps replays byte-identical.
+    pub fn now(&self) -> Timestamp {
+        self.clock.as_ref().map_or(0, |clock| clock.now())
+    }
+
+    // Expires every GTD order whose deadline has passed by the book's clock
+    pub fn expire_due_orders(&mut self) -> Vec<OrderId> {
+        self.expire_orders(self.now())
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{
+    error::{AmendOrderError, CancelOrderError, CommandError, LimitOrderError, MarketOrderError},
+    events::BookEvent,
+    orderbook::OrderBook,
+    trading_state::HaltPolicy,
+    types::{LimitOrderResult, MarketOrderResult, OrderId, Price, Quantity, Side},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Command {
+    Limit {
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+    },
+    Market {
+        side: Side,
+        quantity: Quantity,
+    },
+    Cancel {
+        order_id: OrderId,
+    },
+    Amend {
+        order_id: OrderId,
+        quantity: Quantity,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CommandResult {
+    Limit(Result<LimitOrderResult, LimitOrderError>),
+    Market(Result<MarketOrderResult, MarketOrderError>),
+    Cancel(Result<(), CancelOrderError>),
+    Amend(Result<(), AmendOrderError>),
+    Queued, // Held until the book reopens, see `HaltPolicy::Queue`
+}
+
+impl CommandResult {
+    pub fn into_result(self) -> Result<(), CommandError> {
+        match self {
+            Self::Limit(Err(error)) => Err(CommandError::Limit(error)),
+            Self::Market(Err(error)) => Err(CommandError::Market(error)),
+            Self::Cancel(Err(error)) => Err(CommandError::Cancel(error)),
+            Self::Amend(Err(error)) => Err(CommandError::Amend(error)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl OrderBook {
+    // Applies one command and returns the events it produced, in order. This is the same
+    // path `apply_batch`, `replay`, and the journal use, so gateways and fuzzers see
+    // exactly what they would. Events are returned whether or not the book is recording
+    // them, and stay recorded as well if it is. A command queued by `HaltPolicy::Queue`
+    // produces no events until the book reopens.
+    pub fn apply(&mut self, command: Command) -> Result<Vec<BookEvent>, CommandError> {
+        let capture = self.events.begin_capture();
+        let result = self.apply_command(&command).into_result();
+        let events = self.events.end_capture(capture);
+        result.map(|()| events)
+    }
+
+    // Applies each command in sequence, a failed command doesn't stop the rest of the batch
+    pub fn apply_batch(&mut self, commands: &[Command]) -> Vec<CommandResult> {
+        self.replay(commands.iter().cloned())
+    }
+
+    // Applies a recorded command stream, ie. the commands from `read_journal`, like
+    // `apply_batch`. Matching never depends on hash map iteration order or randomness,
+    // so the same stream into the same starting book gives byte-identical `to_bytes` on
+    // any machine. Resting orders and fills are stamped from the book's clock though,
+    // so that only holds for a book without one or with a clock that reads the same.
+    pub fn replay(&mut self, commands: impl Iterator<Item = Command>) -> Vec<CommandResult> {
+        commands
+            .map(|command| self.apply_command(&command))
+            .collect()
+    }
+
+    pub(crate) fn apply_command(&mut self, command: &Command) -> CommandResult {
+        if self.halt_policy == HaltPolicy::Queue && !self.accepts_command(command) {
+            self.queued_commands.push(command.clone());
+            return CommandResult::Queued;
+        }
+        self.dispatch_command(command)
+    }
+
+    // Applies a command now, never queueing it
+    pub(crate) fn dispatch_command(&mut self, command: &Command) -> CommandResult {
+        match *command {
+            Command::Limit {
+                side,
+                order_id,
+                price,
+                quantity,
+            } => CommandResult::Limit(self.execute_limit_order(side, order_id, price, quantity)),
+            Command::Market { side, quantity } => {
+                CommandResult::Market(self.execute_market_order(side, quantity))
+            }
+            Command::Cancel { order_id } => CommandResult::Cancel(self.cancel_order(order_id)),
+            Command::Amend { order_id, quantity } => {
+                CommandResult::Amend(self.amend_quantity(order_id, quantity))
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{
+    error::CsvError,
+    orderbook::OrderBook,
+    types::{Level3, Level3Snapshot, OrderId, Price, Quantity},
+};
+
+const HEADER: &str = "side,id,price,qty,priority";
+
+impl OrderBook {
+    // Dumps every resting order as `side,id,price,qty,priority`, where priority is the
+    // order's queue position within its price level
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(HEADER);
+        csv.push('\n');
+
+        let snapshot = self.level3_snapshot();
+        for (side, levels) in [("bid", &snapshot.bids), ("ask", &snapshot.asks)] {
+            for level in levels {
+                for (priority, (order_id, quantity)) in level.orders.iter().enumerate() {
+                    let _ = writeln!(
+                        csv,
+                        "{side},{},{},{quantity},{priority}",
+                        order_id.0, level.price
+                    );
+                }
+            }
+        }
+        csv
+    }
+
+    // Loads a book from `to_csv` output. Rows may come in any order, each level is
+    // rebuilt in priority order. The header row is optional.
+    pub fn from_csv(csv: &str) -> Result<Self, CsvError> {
+        type Levels = BTreeMap<Price, BTreeMap<usize, (OrderId, Quantity)>>;
+        let mut bids = Levels::new();
+        let mut asks = Levels::new();
+
+        for (index, line) in csv.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || (index == 0 && line == HEADER) {
+                continue;
+            }
+
+            let invalid = || CsvError::InvalidRow(line_number);
+            let fields: Vec<_> = line.split(',').map(str::trim).collect();
+            let [side, id, price, quantity, priority] = fields[..] else {
+                return Err(invalid());
+            };
+            let levels = match side {
+                "bid" => &mut bids,
+                "ask" => &mut asks,
+                _ => return Err(invalid()),
+            };
+            let order = (
+                OrderId(id.parse().map_err(|_| invalid())?),
+                quantity.parse().map_err(|_| invalid())?,
+            );
+            let price: Price = price.parse().map_err(|_| invalid())?;
+            let priority: usize = priority.parse().map_err(|_| invalid())?;
+
+            let level = levels.entry(price).or_default();
+            if level.insert(priority, order).is_some() {
+                return Err(CsvError::DuplicatePriority(line_number));
+            }
+        }
+
+        let to_level3 = |(price, orders): (Price, BTreeMap<usize, (OrderId, Quantity)>)| Level3 {
+            price,
+            orders: orders.into_values().collect(),
+        };
+        let snapshot = Level3Snapshot {
+            bids: bids.into_iter().rev().map(to_level3).collect(),
+            asks: asks.into_iter().map(to_level3).collect(),
+            sequence: 0,
+        };
+        Self::from_level3_snapshot(&snapshot).map_err(CsvError::Book)
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::VecDeque;
+
+use hashbrown::HashMap;
+
+use crate::{
+    error::{CancelOrderError, DarkOrderError},
+    orderbook::OrderBook,
+    trading_state::TradingState,
+    types::{OrderId, Price, Quantity, Side, Timestamp, TradeId},
+};
+
+// An order waiting in the dark pool. It never has a price of its own, it trades at
+// whatever the lit midpoint is when it crosses, as long as that's within its limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DarkOrder {
+    pub side: Side,
+    pub order_id: OrderId,
+    pub quantity: Quantity,
+    pub limit_price: Option<Price>, // Worst midpoint it trades at, any midpoint without one
+}
+
+// A trade between two dark orders, priced at the lit midpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DarkFill {
+    pub buy_order_id: OrderId,
+    pub sell_order_id: OrderId,
+    pub quantity: Quantity,
+    pub reference_bid: Price, // The lit best bid and ask the midpoint was taken from
+    pub reference_ask: Price,
+    pub trade_id: TradeId, // Numbered by the pool, apart from the lit book's trades
+    pub timestamp: Timestamp,
+}
+
+impl DarkFill {
+    // Half a tick off the price grid when the lit spread is odd, as `mid_price` is
+    pub fn price(&self) -> f64 {
+        (self.reference_bid as f64 + self.reference_ask as f64) / 2.0
+    }
+}
+
+// A midpoint matching facility run beside a lit book. Its orders are never shown
+// anywhere and never touch the lit book: buyers and sellers cross each other at the
+// lit midpoint, in time priority on each side, skipping orders whose limit the
+// midpoint is beyond. The lit book is only read for its best bid and ask.
+//
+// Nothing crosses while the lit book is one-sided, locked, crossed, or not matching,
+// since there's no midpoint to trust. Orders cross on arrival, and `cross` should be
+// called again whenever the lit book's best prices move, to match what the new
+// midpoint brings within limits.
+#[derive(Debug, Clone)]
+pub struct DarkPool {
+    orders: HashMap<OrderId, DarkOrder>,
+    bids: VecDeque<OrderId>, // Oldest first
+    asks: VecDeque<OrderId>,
+    next_trade_id: TradeId,
+}
+
+impl Default for DarkPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DarkPool {
+    pub fn new() -> Self {
+        Self {
+            orders: Default::default(),
+            bids: Default::default(),
+            asks: Default::default(),
+            next_trade_id: TradeId(1),
+        }
+    }
+
+    pub fn get_order(&self, order_id: OrderId) -> Option<&DarkOrder> {
+        self.orders.get(&order_id)
+    }
+
+    // Quantity waiting on one side
+    pub fn volume(&self, side: Side) -> Quantity {
+        self.queue(side)
+            .iter()
+            .filter_map(|order_id| self.orders.get(order_id))
+            .map(|order| order.quantity)
+            .sum()
+    }
+
+    pub fn order_count(&self, side: Side) -> usize {
+        self.queue(side).len()
+    }
+
+    // Queues the order behind every other on its side, then crosses against `book`'s
+    // midpoint. Returns the fills, of this order or any other now within its limit.
+    pub fn submit(
+        &mut self,
+        book: &OrderBook,
+        order: DarkOrder,
+    ) -> Result<Vec<DarkFill>, DarkOrderError> {
+        if order.quantity == 0 {
+            return Err(DarkOrderError::ZeroQuantity);
+        }
+        if self.orders.contains_key(&order.order_id) {
+            return Err(DarkOrderError::OrderIdAlreadyExists);
+        }
+
+        self.orders.insert(order.order_id, order);
+        match order.side {
+            Side::Bid => self.bids.push_back(order.order_id),
+            Side::Ask => self.asks.push_back(order.order_id),
+        }
+        Ok(self.cross(book))
+    }
+
+    // Returns the cancelled order with whatever quantity it had left
+    pub fn cancel(&mut self, order_id: OrderId) -> Result<DarkOrder, CancelOrderError> {
+        let order = self
+            .orders
+            .remove(&order_id)
+            .ok_or(CancelOrderError::OrderIdNotFound)?;
+        let queue = match order.side {
+            Side::Bid => &mut self.bids,
+            Side::Ask => &mut self.asks,
+        };
+        queue.retain(|&queued| queued != order_id);
+        Ok(order)
+    }
+
+    // Matches every buyer and seller the lit book's current midpoint is within the
+    // limits of, oldest first on each side
+    pub fn cross(&mut self, book: &OrderBook) -> Vec<DarkFill> {
+        let mut fills = Vec::new();
+        let (Some((bid, _)), Some((ask, _))) = (book.best_bid(), book.best_ask()) else {
+            return fills;
+        };
+        if bid >= ask || book.trading_state() != TradingState::Open {
+            return fills;
+        }
+
+        // Compared doubled, so an odd spread's half-tick midpoint stays exact
+        let midpoint = bid as i128 + ask as i128;
+        let within_limit = |order: &DarkOrder| match (order.side, order.limit_price) {
+            (_, None) => true,
+            (Side::Bid, Some(limit)) => 2 * limit as i128 >= midpoint,
+            (Side::Ask, Some(limit)) => 2 * limit as i128 <= midpoint,
+        };
+        let eligible = |queue: &VecDeque<OrderId>| -> Vec<OrderId> {
+            queue
+                .iter()
+                .copied()
+                .filter(|order_id| self.orders.get(order_id).is_some_and(within_limit))
+                .collect()
+        };
+        let (buyers, sellers) = (eligible(&self.bids), eligible(&self.asks));
+
+        let timestamp = book.now();
+        let (mut buyer, mut seller) = (0, 0);
+        while let (Some(&buy_order_id), Some(&sell_order_id)) =
+            (buyers.get(buyer), sellers.get(seller))
+        {
+            let quantity = self.orders[&buy_order_id]
+                .quantity
+                .min(self.orders[&sell_order_id].quantity);
+            fills.push(DarkFill {
+                buy_order_id,
+                sell_order_id,
+                quantity,
+                reference_bid: bid,
+                reference_ask: ask,
+                trade_id: self.next_trade_id,
+                timestamp,
+            });
+            self.next_trade_id.0 += 1;
+
+            if self.take(buy_order_id, quantity) {
+                buyer += 1;
+            }
+            if self.take(sell_order_id, quantity) {
+                seller += 1;
+            }
+        }
+
+        if !fills.is_empty() {
+            let orders = &self.orders;
+            self.bids.retain(|order_id| orders.contains_key(order_id));
+            self.asks.retain(|order_id| orders.contains_key(order_id));
+        }
+        fills
+    }
+
+    // Takes traded quantity off an order, returning whether it's now fully filled
+    fn take(&mut self, order_id: OrderId, quantity: Quantity) -> bool {
+        let Some(order) = self.orders.get_mut(&order_id) else {
+            return true;
+        };
+        order.quantity -= quantity;
+        if order.quantity > 0 {
+            return false;
+        }
+        self.orders.remove(&order_id);
+        true
+    }
+
+    fn queue(&self, side: Side) -> &VecDeque<OrderId> {
+        match side {
+            Side::Bid => &self.bids,
+            Side::Ask => &self.asks,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use rust_decimal::Decimal;
+
+use crate::{
+    error::{InstrumentError, LimitOrderError},
+    instrument::Instrument,
+    orderbook::OrderBook,
+    types::{LimitOrderResult, OrderId, Price, Quantity, Side},
+};
+
+// Scales a decimal price up by 10^decimals into integer price units, refusing anything
+// that would lose precision or overflow
+fn scale_price(price: Decimal, decimals: u32) -> Result<Price, InstrumentError> {
+    let scale = 10i128
+        .checked_pow(decimals)
+        .and_then(|scale| Decimal::try_from_i128_with_scale(scale, 0).ok())
+        .ok_or(InstrumentError::PriceOutOfRange)?;
+    let scaled = price
+        .checked_mul(scale)
+        .ok_or(InstrumentError::PriceOutOfRange)?;
+    if !scaled.fract().is_zero() {
+        return Err(InstrumentError::PriceTooPrecise);
+    }
+    Price::try_from(scaled).map_err(|_| InstrumentError::PriceOutOfRange)
+}
+
+impl Instrument {
+    // Converts a human readable price like 101.25 into the book's integer units, so with
+    // 2 price decimals it becomes 10125
+    pub fn price_from_decimal(&self, price: Decimal) -> Result<Price, InstrumentError> {
+        scale_price(price, self.price_decimals())
+    }
+
+    // The inverse of `price_from_decimal`. Panics if the instrument has more than 28
+    // price decimals, the most a Decimal can hold.
+    pub fn price_to_decimal(&self, price: Price) -> Decimal {
+        Decimal::new(price, self.price_decimals())
+    }
+}
+
+impl OrderBook {
+    // Decimal price conversions use the instrument's price decimals, or whole units
+    // for a book without an instrument
+    pub fn price_from_decimal(&self, price: Decimal) -> Result<Price, InstrumentError> {
+        scale_price(price, self.price_decimals())
+    }
+
+    pub fn price_to_decimal(&self, price: Price) -> Decimal {
+        Decimal::new(price, self.price_decimals())
+    }
+
+    // Places a limit order at a decimal price, otherwise exactly like `execute_limit_order`
+    pub fn execute_decimal_limit_order(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Decimal,
+        quantity: Quantity,
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        let price = self.price_from_decimal(price)?;
+        self.execute_limit_order(side, order_id, price, quantity)
+    }
+
+    fn price_decimals(&self) -> u32 {
+        self.instrument
+            .as_ref()
+            .map_or(0, |instrument| instrument.price_decimals())
+    }
+}
@@ -0,0 +1,186 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use crate::{
+    events::BookEvent,
+    orderbook::OrderBook,
+    types::{Depth, DepthLevel, Price, Quantity, Side},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthDelta {
+    Inserted {
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+    },
+    Updated {
+        side: Side,
+        price: Price,
+        quantity: Quantity, // New aggregate quantity at the level
+    },
+    Deleted {
+        side: Side,
+        price: Price,
+    },
+}
+
+// Turns recorded book events into the minimal set of L2 level changes, for incremental
+// feeds. Keeps its own copy of the last published aggregate per level, so levels that
+// come and go, or return to the same size, within one batch produce no delta.
+#[derive(Debug, Clone, Default)]
+pub struct DepthDeltaTracker {
+    bids: BTreeMap<Price, Quantity>,
+    asks: BTreeMap<Price, Quantity>,
+    touched: Vec<(Side, Price)>,
+}
+
+impl DepthDeltaTracker {
+    // Seeds the published state from the book's current levels
+    pub fn new(book: &OrderBook) -> Self {
+        Self {
+            bids: book
+                .bids()
+                .map(|(price, level)| (price, level.total_quantity))
+                .collect(),
+            asks: book
+                .asks()
+                .map(|(price, level)| (price, level.total_quantity))
+                .collect(),
+            touched: Vec::new(),
+        }
+    }
+
+    // Compares every level the events touched against the book and returns the changes,
+    // in the order the levels were first touched.
+    pub fn update(&mut self, book: &OrderBook, events: &[BookEvent]) -> Vec<DepthDelta> {
+        let mut deltas = Vec::new();
+        self.update_into(book, events, &mut deltas);
+        deltas
+    }
+
+    pub fn update_into(
+        &mut self,
+        book: &OrderBook,
+        events: &[BookEvent],
+        deltas: &mut Vec<DepthDelta>,
+    ) {
+        self.touched.clear();
+        for event in events {
+            let touched = match *event {
+                BookEvent::OrderAdded { side, price, .. }
+                | BookEvent::OrderCancelled { side, price, .. }
+                | BookEvent::OrderReduced { side, price, .. }
+                | BookEvent::LevelAdded { side, price }
+                | BookEvent::LevelRemoved { side, price } => [Some((side, price)), None],
+                BookEvent::Trade {
+                    taker_side,
+                    ref fill,
+                } => [Some((taker_side.opposite(), fill.price)), None],
+                // Both orders rested, each at its own level rather than the uncross price
+                BookEvent::AuctionTrade { ref fill } => [
+                    Some((Side::Bid, fill.buy_price)),
+                    Some((Side::Ask, fill.sell_price)),
+                ],
+            };
+            for touched in touched.into_iter().flatten() {
+                if !self.touched.contains(&touched) {
+                    self.touched.push(touched);
+                }
+            }
+        }
+
+        for &(side, price) in &self.touched {
+            let (published, levels) = match side {
+                Side::Bid => (&mut self.bids, &book.bids),
+                Side::Ask => (&mut self.asks, &book.asks),
+            };
+            let current = levels.get(&price).map(|level| level.total_quantity);
+
+            match (published.get(&price).copied(), current) {
+                (None, Some(quantity)) => {
+                    published.insert(price, quantity);
+                    deltas.push(DepthDelta::Inserted {
+                        side,
+                        price,
+                        quantity,
+                    });
+                }
+                (Some(previous), Some(quantity)) if previous != quantity => {
+                    published.insert(price, quantity);
+                    deltas.push(DepthDelta::Updated {
+                        side,
+                        price,
+                        quantity,
+                    });
+                }
+                (Some(_), None) => {
+                    published.remove(&price);
+                    deltas.push(DepthDelta::Deleted { side, price });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+// The minimal level changes turning one depth snapshot into another, for reconciling
+// against an external venue's snapshots or conflating a feed. Bids come before asks,
+// each best price first. Levels are compared by aggregate quantity alone, and levels
+// crossing the cut-off of snapshots limited to a number of levels show as inserted
+// or deleted.
+pub fn diff_depth(before: &Depth, after: &Depth) -> Vec<DepthDelta> {
+    let mut deltas = Vec::new();
+    diff_side(Side::Bid, &before.bids, &after.bids, &mut deltas);
+    diff_side(Side::Ask, &before.asks, &after.asks, &mut deltas);
+    deltas
+}
+
+// Walks both sides' levels together, as each is sorted best price first
+fn diff_side(
+    side: Side,
+    before: &[DepthLevel],
+    after: &[DepthLevel],
+    deltas: &mut Vec<DepthDelta>,
+) {
+    let (mut before, mut after) = (before.iter().peekable(), after.iter().peekable());
+    loop {
+        let next = match (before.peek(), after.peek()) {
+            (None, None) => break,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(&&(old, ..)), Some(&&(new, ..))) => match side {
+                Side::Bid => new.cmp(&old),
+                Side::Ask => old.cmp(&new),
+            },
+        };
+
+        match next {
+            Ordering::Less => {
+                if let Some(&(price, ..)) = before.next() {
+                    deltas.push(DepthDelta::Deleted { side, price });
+                }
+            }
+            Ordering::Greater => {
+                if let Some(&(price, quantity, _)) = after.next() {
+                    deltas.push(DepthDelta::Inserted {
+                        side,
+                        price,
+                        quantity,
+                    });
+                }
+            }
+            Ordering::Equal => {
+                if let (Some(&(_, previous, _)), Some(&(price, quantity, _))) =
+                    (before.next(), after.next())
+                    && previous != quantity
+                {
+                    deltas.push(DepthDelta::Updated {
+                        side,
+                        price,
+                        quantity,
+                    });
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    iter, panic,
+    thread::{self, JoinHandle},
+};
+
+use crate::{
+    command::{Command, CommandResult},
+    error::EngineError,
+    events::BookEvent,
+    orderbook::OrderBook,
+    spsc::{self, Consumer, Producer},
+};
+
+// What applying one command did, in the order the matching thread applied them
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    pub command: Command,
+    pub result: CommandResult,
+    pub events: Vec<BookEvent>, // Every event the command produced
+}
+
+// The one producer's handle onto the command queue
+#[derive(Debug)]
+pub struct CommandSender(Producer<Command>);
+
+impl CommandSender {
+    // Waits for room in the queue if it's full
+    pub fn submit(&mut self, command: Command) -> Result<(), EngineError> {
+        self.0.push(command).map_err(EngineError::Stopped)
+    }
+
+    pub fn try_submit(&mut self, command: Command) -> Result<(), EngineError> {
+        self.0
+            .try_push(command)
+            .map_err(|command| match self.0.is_closed() {
+                true => EngineError::Stopped(command),
+                false => EngineError::Full(command),
+            })
+    }
+}
+
+// The reading end of the response ring, along with the matching thread it drains
+#[derive(Debug)]
+pub struct ResponseReceiver {
+    responses: Consumer<Response>,
+    thread: JoinHandle<()>,
+}
+
+impl ResponseReceiver {
+    // Waits for the next response, None once the matching thread has exited
+    pub fn recv(&mut self) -> Option<Response> {
+        self.responses.pop()
+    }
+
+    pub fn try_recv(&mut self) -> Option<Response> {
+        self.responses.try_pop()
+    }
+
+    // Waits for the matching thread to apply everything already queued, returning the
+    // responses nobody had read yet. Blocks until the `CommandSender` is dropped. A
+    // panic on the matching thread is re-raised here.
+    pub fn join(self) -> Vec<Response> {
+        let Self {
+            mut responses,
+            thread,
+        } = self;
+
+        let remaining = iter::from_fn(|| responses.pop()).collect();
+        if let Err(payload) = thread.join() {
+            panic::resume_unwind(payload);
+        }
+        remaining
+    }
+}
+
+// A book owned by a dedicated matching thread, the single writer. Commands come in on a
+// bounded lock-free single producer, single consumer ring and the thread answers each
+// with a `Response` on a second such ring. Nobody reading responses stalls the thread
+// once the response ring fills, which pushes back on the producer in turn. Either end
+// waits by spinning briefly, then yielding, then napping, rather than on a lock.
+#[derive(Debug)]
+pub struct MatchingEngine {
+    sender: CommandSender,
+    receiver: ResponseReceiver,
+}
+
+impl MatchingEngine {
+    pub fn spawn(capacity: usize) -> Self {
+        Self::spawn_with(capacity, OrderBook::new())
+    }
+
+    // Moves an existing book onto the matching thread. `capacity` bounds both the
+    // command and response rings, and is at least one.
+    pub fn spawn_with(capacity: usize, mut book: OrderBook) -> Self {
+        let (sender, mut commands) = spsc::ring(capacity);
+        let (mut respond, responses) = spsc::ring(capacity);
+
+        let thread = thread::spawn(move || {
+            // Runs until the sender is gone, or nobody is left to read responses
+            while let Some(command) = commands.pop() {
+                if respond.push(book.respond(command)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            sender: CommandSender(sender),
+            receiver: ResponseReceiver { responses, thread },
+        }
+    }
+
+    // Separates the two ends, e.g. to submit from one thread and read responses on
+    // another
+    pub fn split(self) -> (CommandSender, ResponseReceiver) {
+        (self.sender, self.receiver)
+    }
+
+    pub fn submit(&mut self, command: Command) -> Result<(), EngineError> {
+        self.sender.submit(command)
+    }
+
+    pub fn try_submit(&mut self, command: Command) -> Result<(), EngineError> {
+        self.sender.try_submit(command)
+    }
+
+    // Waits for the next response, None once the matching thread has exited
+    pub fn recv(&mut self) -> Option<Response> {
+        self.receiver.recv()
+    }
+
+    pub fn try_recv(&mut self) -> Option<Response> {
+        self.receiver.try_recv()
+    }
+
+    // Stops taking commands and waits for the matching thread to apply everything
+    // already queued, returning the responses nobody had read yet. A panic on the
+    // matching thread is re-raised here.
+    pub fn shutdown(self) -> Vec<Response> {
+        let (sender, receiver) = self.split();
+        drop(sender);
+        receiver.join()
+    }
+}
+
+impl OrderBook {
+    pub(crate) fn respond(&mut self, command: Command) -> Response {
+        let capture = self.events.begin_capture();
+        let result = self.apply_command(&command);
+        let events = self.events.end_capture(capture);
+        Response {
+            command,
+            result,
+            events,
+        }
+    }
+}
@@ -1,16 +1,305 @@
+use crate::{
+    command::Command,
+    types::{OrderId, Price, Quantity, Side},
+};
+
+// Which of the book's invariants turned out to be broken, so corruption can be traced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InternalError {
+    DanglingLevelHead { side: Side, price: Price }, // A level's head isn't a live order
+    DanglingLevelTail { side: Side, price: Price }, // A level's tail isn't a live order
+    MissingNode { index: usize },                   // An indexed order's slab slot is empty
+    MissingLevel { side: Side, price: Price },      // An indexed order's price level is gone
+    MissingIndexEntry { order_id: OrderId },        // A resting order isn't in the Id lookup
+    StopQueueMismatch { order_id: OrderId },        // Stop orders and their price queues disagree
+    VolumeMismatch,                                 // Totals disagree with the orders behind them
+    UnexpectedRejection,                            // A step that was checked up front failed
+}
+
+// One inconsistency found by `OrderBook::validate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantViolation {
+    EmptyLevel {
+        side: Side,
+        price: Price,
+    },
+    MissingNode {
+        side: Side,
+        price: Price,
+        index: usize,
+    }, // A level links to a freed slab slot
+    BrokenLink {
+        side: Side,
+        price: Price,
+        index: usize,
+    }, // A node's previous doesn't point back
+    LevelTailMismatch {
+        side: Side,
+        price: Price,
+    }, // The walk from head ends elsewhere
+    OrderCountMismatch {
+        side: Side,
+        price: Price,
+        recorded: usize,
+        actual: usize,
+    },
+    LevelQuantityMismatch {
+        side: Side,
+        price: Price,
+        recorded: Quantity,
+        actual: Quantity,
+    },
+    SideTotalsMismatch {
+        side: Side,
+    },
+    ZeroQuantityOrder {
+        order_id: OrderId,
+    },
+    IndexMismatch {
+        order_id: OrderId,
+    }, // The Id lookup disagrees with where the order rests
+    UnindexedOrder {
+        order_id: OrderId,
+    }, // A resting order missing from the Id lookup
+    UnlinkedOrder {
+        index: usize,
+    }, // A slab node no price level reaches
+    OwnerIndexMismatch {
+        order_id: OrderId,
+    },
+    PegMismatch {
+        order_id: OrderId,
+    }, // A pegged order that isn't resting on its side
+    StopQueueMismatch {
+        order_id: OrderId,
+    }, // Stop orders and their price queues disagree
+    QueueRankMismatch {
+        order_id: OrderId,
+    }, // An order's arrival slot doesn't give its place in its level's list
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum CancelOrderError {
     OrderIdNotFound,
-    InternalError,
+    ZeroQuantity,  // Only from `execute_resting_order`
+    TradingHalted, // The book's trading state doesn't allow it
+    Internal(InternalError),
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum MarketOrderError {
-    InternalError,
+    OrderIdAlreadyExists, // Only when resting the remainder under a new Id
+    TradingHalted,
+    Instrument(InstrumentError),
+    Internal(InternalError),
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum LimitOrderError {
     OrderIdAlreadyExists,
-    InternalError,
+    ZeroQuantity,
+    NonPositivePrice, // Only when the book rejects non-positive prices
+    TradingHalted,
+    InsufficientLiquidity,
+    PostOnlyWouldCross,
+    PegReferenceUnavailable,
+    QuantityOverflow, // Resting it would overflow the side's total volume
+    Instrument(InstrumentError),
+    Internal(InternalError),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum StopOrderError {
+    OrderIdAlreadyExists,
+    ZeroQuantity,
+    NonPositivePrice, // Only when the book rejects non-positive prices
+    TradingHalted,
+    Instrument(InstrumentError),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AmendOrderError {
+    OrderIdNotFound,
+    ZeroQuantity,
+    QuantityOverflow, // The increase would overflow the side's total volume
+    TradingHalted,
+    Instrument(InstrumentError),
+    Internal(InternalError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DarkOrderError {
+    OrderIdAlreadyExists, // Among the dark pool's own orders
+    ZeroQuantity,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AuctionError {
+    NotCrossed, // No bid is at or above the best ask, so nothing can trade
+    TradingHalted,
+    Internal(InternalError),
+}
+
+// Why `apply` rejected a command, carrying the error of the operation it maps to
+#[derive(Debug, PartialEq, Eq)]
+pub enum CommandError {
+    Limit(LimitOrderError),
+    Market(MarketOrderError),
+    Cancel(CancelOrderError),
+    Amend(AmendOrderError),
+}
+
+// Why a `MatchingEngine` didn't take a command, handing the command back
+#[derive(Debug, PartialEq, Eq)]
+pub enum EngineError {
+    Full(Command),    // The command queue is at capacity, only from `try_submit`
+    Stopped(Command), // The matching thread has exited
+}
+
+// Why an order broke the book's instrument rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstrumentError {
+    PriceNotOnTick,
+    PriceOutOfRange,
+    PriceTooPrecise, // A decimal price with more places than the instrument's price decimals
+    QuantityNotOnLot,
+    QuantityTooSmall,
+    QuantityTooLarge,
+    PriceRangeTooWide, // Too many ticks between the price limits for an array backed book
+    NonPositiveTickSize,
+    ZeroLotSize,
+    InvertedPriceRange,     // The minimum price is above the maximum
+    InvertedOrderSizeRange, // The minimum order size is above the maximum
+}
+
+impl From<InternalError> for CancelOrderError {
+    fn from(error: InternalError) -> Self {
+        Self::Internal(error)
+    }
+}
+
+impl From<InternalError> for MarketOrderError {
+    fn from(error: InternalError) -> Self {
+        Self::Internal(error)
+    }
+}
+
+impl From<InternalError> for LimitOrderError {
+    fn from(error: InternalError) -> Self {
+        Self::Internal(error)
+    }
+}
+
+impl From<InternalError> for AmendOrderError {
+    fn from(error: InternalError) -> Self {
+        Self::Internal(error)
+    }
+}
+
+impl From<InternalError> for AuctionError {
+    fn from(error: InternalError) -> Self {
+        Self::Internal(error)
+    }
+}
+
+impl CancelOrderError {
+    // The broken invariant behind this error, for callers that had already ruled out
+    // every other way it could fail
+    pub(crate) fn into_internal(self) -> InternalError {
+        match self {
+            Self::Internal(error) => error,
+            _ => InternalError::UnexpectedRejection,
+        }
+    }
+}
+
+impl MarketOrderError {
+    // The broken invariant behind this error, for callers that had already ruled out
+    // every other way it could fail
+    pub(crate) fn into_internal(self) -> InternalError {
+        match self {
+            Self::Internal(error) => error,
+            _ => InternalError::UnexpectedRejection,
+        }
+    }
+}
+
+impl LimitOrderError {
+    // The broken invariant behind this error, for callers that had already ruled out
+    // every other way it could fail
+    pub(crate) fn into_internal(self) -> InternalError {
+        match self {
+            Self::Internal(error) => error,
+            _ => InternalError::UnexpectedRejection,
+        }
+    }
+}
+
+impl From<InstrumentError> for MarketOrderError {
+    fn from(error: InstrumentError) -> Self {
+        Self::Instrument(error)
+    }
+}
+
+impl From<InstrumentError> for LimitOrderError {
+    fn from(error: InstrumentError) -> Self {
+        Self::Instrument(error)
+    }
+}
+
+impl From<InstrumentError> for StopOrderError {
+    fn from(error: InstrumentError) -> Self {
+        Self::Instrument(error)
+    }
+}
+
+impl From<InstrumentError> for AmendOrderError {
+    fn from(error: InstrumentError) -> Self {
+        Self::Instrument(error)
+    }
+}
+
+// Why bytes couldn't be decoded back into a book or record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    BadMagic,
+    UnsupportedVersion(u8),
+    Truncated, // Ran out of bytes part way through
+    Corrupt,   // Well formed, but describes an impossible book
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    ValueOutOfRange, // A price or quantity doesn't fit the wire format's field
+}
+
+#[cfg(feature = "itch")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItchBookError {
+    Decode(DecodeError),
+    UnknownOrderReference(u64),
+    DuplicateOrderReference(u64),
+    Internal(InternalError),
+}
+
+#[cfg(feature = "itch")]
+impl From<DecodeError> for ItchBookError {
+    fn from(error: DecodeError) -> Self {
+        Self::Decode(error)
+    }
+}
+
+// Why a FIX message couldn't be translated, identified by the offending tag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixError {
+    MissingField(u32),
+    InvalidValue(u32),
+    UnsupportedMessageType(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CsvError {
+    InvalidRow(usize), // 1-based line number
+    DuplicatePriority(usize),
+    Book(LimitOrderError),
 }
@@ -0,0 +1,211 @@
+use std::fmt;
+
+use crate::{
+    listener::OrderBookListener,
+    types::{AuctionFill, ClientOrderId, Fill, OrderId, Price, Quantity, Sequence, Side},
+};
+
+// One canonical record of every change to the resting book
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BookEvent {
+    OrderAdded {
+        order_id: OrderId,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        client_order_id: Option<ClientOrderId>,
+    },
+    OrderCancelled {
+        order_id: OrderId,
+        side: Side,
+        price: Price,
+        quantity: Quantity, // Remaining quantity at the time of the cancel
+        client_order_id: Option<ClientOrderId>,
+    },
+    OrderReduced {
+        order_id: OrderId,
+        side: Side,
+        price: Price,
+        new_quantity: Quantity,
+        client_order_id: Option<ClientOrderId>,
+    },
+    Trade {
+        taker_side: Side,
+        fill: Fill,
+    },
+    AuctionTrade {
+        fill: AuctionFill,
+    },
+    LevelAdded {
+        side: Side,
+        price: Price,
+    },
+    LevelRemoved {
+        side: Side,
+        price: Price,
+    },
+}
+
+impl BookEvent {
+    // Forwards the event to the matching listener callback
+    pub fn dispatch(&self, listener: &mut dyn OrderBookListener) {
+        match *self {
+            BookEvent::OrderAdded {
+                order_id,
+                side,
+                price,
+                quantity,
+                ..
+            } => listener.on_order_added(order_id, side, price, quantity),
+            BookEvent::OrderCancelled {
+                order_id,
+                side,
+                price,
+                quantity,
+                ..
+            } => listener.on_order_cancelled(order_id, side, price, quantity),
+            BookEvent::OrderReduced {
+                order_id,
+                side,
+                price,
+                new_quantity,
+                ..
+            } => listener.on_order_reduced(order_id, side, price, new_quantity),
+            BookEvent::Trade {
+                taker_side,
+                ref fill,
+            } => listener.on_trade(taker_side, fill),
+            BookEvent::AuctionTrade { ref fill } => listener.on_auction_trade(fill),
+            BookEvent::LevelAdded { side, price } => listener.on_level_added(side, price),
+            BookEvent::LevelRemoved { side, price } => listener.on_level_removed(side, price),
+        }
+    }
+}
+
+// An event alongside its place in the book's event stream, gaps mean missed events
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SequencedEvent {
+    pub sequence: Sequence,
+    pub event: BookEvent,
+}
+
+// Where the book's events go: an optional listener, and an optional buffer for callers
+// that collect the events of each operation. Cloning a book doesn't clone its listener,
+// so a copy can't double-report into the original's integration.
+// Every event is numbered, whether or not anything is listening.
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventSink {
+    #[cfg_attr(feature = "serde", serde(skip))] // Listeners are attached again after restoring
+    listener: Option<Box<dyn OrderBookListener>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    recorded: Option<Vec<SequencedEvent>>,
+    sequence: Sequence, // Last sequence number given to an event
+}
+
+impl EventSink {
+    pub(crate) fn emit(&mut self, event: BookEvent) {
+        self.sequence += 1;
+        if let Some(listener) = self.listener.as_deref_mut() {
+            event.dispatch(listener);
+        }
+        if let Some(recorded) = &mut self.recorded {
+            recorded.push(SequencedEvent {
+                sequence: self.sequence,
+                event,
+            });
+        }
+    }
+
+    // Only the listener hears about top-of-book changes, they're derived from the
+    // recorded events rather than being changes of their own
+    pub(crate) fn top_of_book_changed(
+        &mut self,
+        best_bid: Option<(Price, Quantity)>,
+        best_ask: Option<(Price, Quantity)>,
+    ) {
+        if let Some(listener) = self.listener.as_deref_mut() {
+            listener.on_top_of_book_changed(best_bid, best_ask);
+        }
+    }
+
+    pub(crate) fn set_listener(&mut self, listener: impl OrderBookListener + 'static) {
+        self.listener = Some(Box::new(listener));
+    }
+
+    pub(crate) fn set_recording(&mut self, enabled: bool) {
+        self.recorded = enabled.then(|| self.recorded.take().unwrap_or_default());
+    }
+
+    pub(crate) fn drain_into(&mut self, buffer: &mut Vec<BookEvent>) {
+        if let Some(recorded) = &mut self.recorded {
+            buffer.extend(recorded.drain(..).map(|recorded| recorded.event));
+        }
+    }
+
+    pub(crate) fn drain_sequenced_into(&mut self, buffer: &mut Vec<SequencedEvent>) {
+        if let Some(recorded) = &mut self.recorded {
+            buffer.append(recorded);
+        }
+    }
+
+    // Records from here on, returning where the new events start and whether events
+    // were already being recorded
+    pub(crate) fn begin_capture(&mut self) -> (usize, bool) {
+        let recording = self.is_recording();
+        (self.recorded.get_or_insert_default().len(), recording)
+    }
+
+    // Copies out the events since `begin_capture`, leaving them recorded only if
+    // recording was already on
+    pub(crate) fn end_capture(&mut self, (start, recording): (usize, bool)) -> Vec<BookEvent> {
+        let events = self
+            .recorded
+            .iter()
+            .flat_map(|recorded| recorded.get(start..).unwrap_or_default())
+            .map(|recorded| recorded.event.clone())
+            .collect();
+        if !recording {
+            self.recorded = None;
+        }
+        events
+    }
+
+    pub(crate) fn set_sequence(&mut self, sequence: Sequence) {
+        self.sequence = sequence;
+    }
+
+    pub fn sequence(&self) -> Sequence {
+        self.sequence
+    }
+
+    pub fn has_listener(&self) -> bool {
+        self.listener.is_some()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorded.is_some()
+    }
+}
+
+impl Clone for EventSink {
+    fn clone(&self) -> Self {
+        Self {
+            listener: None,
+            recorded: self.recorded.as_ref().map(|_| Vec::new()),
+            sequence: self.sequence,
+        }
+    }
+}
+
+impl fmt::Debug for EventSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventSink")
+            .field("has_listener", &self.has_listener())
+            .field("recorded", &self.recorded)
+            .field("sequence", &self.sequence)
+            .finish()
+    }
+}
@@ -0,0 +1,243 @@
+use hashbrown::HashMap;
+
+use crate::{
+    orderbook::OrderBook,
+    trade_stats::RollingTradeStats,
+    types::{AuctionFill, Fill, OwnerId, Price, Quantity, Side, Timestamp},
+};
+
+// How a fee is charged on a fill. Negative values pay a rebate instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeeRate {
+    Bps(i64),   // Basis points of the fill's notional, rounded toward zero
+    Fixed(i64), // A flat amount per fill, regardless of its size
+}
+
+impl Default for FeeRate {
+    fn default() -> Self {
+        Self::Bps(0)
+    }
+}
+
+impl FeeRate {
+    pub fn fee(&self, notional: i128) -> i128 {
+        match *self {
+            Self::Bps(bps) => notional * bps as i128 / 10_000,
+            Self::Fixed(amount) => amount as i128,
+        }
+    }
+}
+
+// Rates for owners who traded at least `min_volume` over the schedule's volume window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeTier {
+    pub min_volume: Quantity,
+    pub maker: FeeRate,
+    pub taker: FeeRate,
+}
+
+// Maker and taker rates applied to every fill once set on a book. Owners whose rolling
+// traded volume reaches a tier get that tier's rates instead, orders without an owner
+// always pay the base rates. Auction fills count towards the volume too, with both sides
+// paying the maker rate since neither took liquidity.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeSchedule {
+    pub maker: FeeRate,
+    pub taker: FeeRate,
+    pub tiers: Vec<FeeTier>,
+    pub volume_window: Timestamp, // Trailing time the tier volume is summed over, no tiers if zero
+}
+
+// Fees on a single fill, in the same units as price * quantity. Each side's net notional
+// is what it pays when buying, or receives when selling, once its own fee is included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FillFees {
+    pub maker_fee: i128,
+    pub taker_fee: i128,
+    pub maker_net_notional: i128,
+    pub taker_net_notional: i128,
+}
+
+// Fees on a single auction fill, laid out as `FillFees` but by buyer and seller
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuctionFillFees {
+    pub buy_fee: i128,
+    pub sell_fee: i128,
+    pub buy_net_notional: i128,
+    pub sell_net_notional: i128,
+}
+
+impl FeeSchedule {
+    // Maker and taker rates for an owner with this rolling volume, from the highest tier
+    // it reaches
+    pub fn rates(&self, volume: Quantity) -> (FeeRate, FeeRate) {
+        self.tiers
+            .iter()
+            .filter(|tier| tier.min_volume <= volume)
+            .max_by_key(|tier| tier.min_volume)
+            .map_or((self.maker, self.taker), |tier| (tier.maker, tier.taker))
+    }
+
+    // Fees on a fill given the rolling volume each side traded before it
+    pub fn fees(
+        &self,
+        taker_side: Side,
+        price: Price,
+        quantity: Quantity,
+        maker_volume: Quantity,
+        taker_volume: Quantity,
+    ) -> FillFees {
+        let notional = price as i128 * quantity as i128;
+        let maker_fee = self.rates(maker_volume).0.fee(notional);
+        let taker_fee = self.rates(taker_volume).1.fee(notional);
+        let net = |side: Side, fee: i128| match side {
+            Side::Bid => notional + fee,
+            Side::Ask => notional - fee,
+        };
+
+        FillFees {
+            maker_fee,
+            taker_fee,
+            maker_net_notional: net(taker_side.opposite(), maker_fee),
+            taker_net_notional: net(taker_side, taker_fee),
+        }
+    }
+
+    // Fees on an auction fill given the rolling volume each side traded before it, both
+    // at the maker rate
+    pub fn auction_fees(
+        &self,
+        price: Price,
+        quantity: Quantity,
+        buy_volume: Quantity,
+        sell_volume: Quantity,
+    ) -> AuctionFillFees {
+        let notional = price as i128 * quantity as i128;
+        let buy_fee = self.rates(buy_volume).0.fee(notional);
+        let sell_fee = self.rates(sell_volume).0.fee(notional);
+
+        AuctionFillFees {
+            buy_fee,
+            sell_fee,
+            buy_net_notional: notional + buy_fee,
+            sell_net_notional: notional - sell_fee,
+        }
+    }
+
+    fn tracks_volume(&self) -> bool {
+        !self.tiers.is_empty() && self.volume_window > 0
+    }
+}
+
+// Rolling traded volume per owner, kept while the fee schedule has tiers
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnerVolumes(HashMap<OwnerId, RollingTradeStats>);
+
+impl OwnerVolumes {
+    // Prices a fill from each owner's volume before it, then adds the fill to both
+    pub(crate) fn charge(
+        &mut self,
+        schedule: &FeeSchedule,
+        taker_side: Side,
+        fill: &Fill,
+        maker_owner: Option<OwnerId>,
+        taker_owner: Option<OwnerId>,
+    ) -> FillFees {
+        if !schedule.tracks_volume() {
+            return schedule.fees(taker_side, fill.price, fill.quantity, 0, 0);
+        }
+
+        let (maker_volume, taker_volume) = self.trade(
+            schedule,
+            [maker_owner, taker_owner],
+            fill.timestamp,
+            fill.price,
+            fill.quantity,
+        );
+        schedule.fees(
+            taker_side,
+            fill.price,
+            fill.quantity,
+            maker_volume,
+            taker_volume,
+        )
+    }
+
+    // Same as `charge` for an auction fill between a buyer and a seller
+    pub(crate) fn charge_auction(
+        &mut self,
+        schedule: &FeeSchedule,
+        fill: &AuctionFill,
+        buy_owner: Option<OwnerId>,
+        sell_owner: Option<OwnerId>,
+    ) -> AuctionFillFees {
+        if !schedule.tracks_volume() {
+            return schedule.auction_fees(fill.price, fill.quantity, 0, 0);
+        }
+
+        let (buy_volume, sell_volume) = self.trade(
+            schedule,
+            [buy_owner, sell_owner],
+            fill.timestamp,
+            fill.price,
+            fill.quantity,
+        );
+        schedule.auction_fees(fill.price, fill.quantity, buy_volume, sell_volume)
+    }
+
+    // Both owners' volume before a trade, then adds the trade to each of them
+    fn trade(
+        &mut self,
+        schedule: &FeeSchedule,
+        owners: [Option<OwnerId>; 2],
+        timestamp: Timestamp,
+        price: Price,
+        quantity: Quantity,
+    ) -> (Quantity, Quantity) {
+        let volumes = (
+            self.advance(schedule, owners[0], timestamp),
+            self.advance(schedule, owners[1], timestamp),
+        );
+        for owner in owners.into_iter().flatten() {
+            if let Some(stats) = self.0.get_mut(&owner) {
+                stats.record_trade(timestamp, price, quantity);
+            }
+        }
+        volumes
+    }
+
+    // An owner's volume over the window ending at `now`, 0 without an owner
+    fn advance(
+        &mut self,
+        schedule: &FeeSchedule,
+        owner: Option<OwnerId>,
+        now: Timestamp,
+    ) -> Quantity {
+        let Some(owner) = owner else {
+            return 0;
+        };
+        let stats = self
+            .0
+            .entry(owner)
+            .or_insert_with(|| RollingTradeStats::new(schedule.volume_window));
+        stats.advance_to(now);
+        stats.volume()
+    }
+}
+
+impl OrderBook {
+    // An owner's traded volume over the fee schedule's window, as of its most recent fill.
+    // Only tracked while the schedule has tiers.
+    pub fn owner_volume(&self, owner: OwnerId) -> Quantity {
+        self.owner_volumes
+            .0
+            .get(&owner)
+            .map_or(0, RollingTradeStats::volume)
+    }
+}
@@ -0,0 +1,232 @@
+// C ABI for embedding the book, declared in `include/bulkbook.h`. Build the shared
+// library with `cargo rustc --release --features cdylib --crate-type cdylib`.
+//
+// Every function takes a handle from `bulkbook_new`, which must not be used after
+// `bulkbook_free` or from two threads at once. Null handles and out-pointers are
+// rejected with `InvalidArgument` rather than dereferenced.
+#![allow(clippy::missing_safety_doc)]
+
+use crate::{
+    error::{CancelOrderError, LimitOrderError, MarketOrderError},
+    orderbook::OrderBook,
+    types::{Fill, OrderId, Side},
+};
+
+// An opaque book plus the fills of its last limit or market order
+pub struct BulkBook {
+    book: OrderBook,
+    fills: Vec<Fill>,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkBookStatus {
+    Ok = 0,
+    InvalidArgument = 1, // A null pointer, unknown side, or fill index out of range
+    OrderIdAlreadyExists = 2,
+    OrderIdNotFound = 3,
+    ZeroQuantity = 4,
+    TradingHalted = 5,
+    Rejected = 6, // Any other rule the order broke
+    Internal = 7, // The book's invariants are broken
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BulkBookFill {
+    pub price: i64,
+    pub quantity: u64,
+    pub maker_order_id: u64,
+    pub trade_id: u64,
+    pub timestamp: u64,
+    pub maker_remaining_quantity: u64,
+    pub maker_fully_filled: bool,
+}
+
+impl From<&Fill> for BulkBookFill {
+    fn from(fill: &Fill) -> Self {
+        Self {
+            price: fill.price,
+            quantity: fill.quantity,
+            maker_order_id: fill.maker_order_id.0,
+            trade_id: fill.trade_id.0,
+            timestamp: fill.timestamp,
+            maker_remaining_quantity: fill.maker_remaining_quantity,
+            maker_fully_filled: fill.maker_fully_filled,
+        }
+    }
+}
+
+impl From<LimitOrderError> for BulkBookStatus {
+    fn from(error: LimitOrderError) -> Self {
+        match error {
+            LimitOrderError::OrderIdAlreadyExists => Self::OrderIdAlreadyExists,
+            LimitOrderError::ZeroQuantity => Self::ZeroQuantity,
+            LimitOrderError::TradingHalted => Self::TradingHalted,
+            LimitOrderError::Internal(_) => Self::Internal,
+            _ => Self::Rejected,
+        }
+    }
+}
+
+impl From<MarketOrderError> for BulkBookStatus {
+    fn from(error: MarketOrderError) -> Self {
+        match error {
+            MarketOrderError::OrderIdAlreadyExists => Self::OrderIdAlreadyExists,
+            MarketOrderError::TradingHalted => Self::TradingHalted,
+            MarketOrderError::Instrument(_) => Self::Rejected,
+            MarketOrderError::Internal(_) => Self::Internal,
+        }
+    }
+}
+
+impl From<CancelOrderError> for BulkBookStatus {
+    fn from(error: CancelOrderError) -> Self {
+        match error {
+            CancelOrderError::OrderIdNotFound => Self::OrderIdNotFound,
+            CancelOrderError::ZeroQuantity => Self::ZeroQuantity,
+            CancelOrderError::TradingHalted => Self::TradingHalted,
+            CancelOrderError::Internal(_) => Self::Internal,
+        }
+    }
+}
+
+// 0 is a bid, 1 an ask
+fn parse_side(side: u8) -> Option<Side> {
+    match side {
+        0 => Some(Side::Bid),
+        1 => Some(Side::Ask),
+        _ => None,
+    }
+}
+
+fn status<T>(result: Result<T, impl Into<BulkBookStatus>>) -> BulkBookStatus {
+    match result {
+        Ok(_) => BulkBookStatus::Ok,
+        Err(error) => error.into(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn bulkbook_new() -> *mut BulkBook {
+    Box::into_raw(Box::new(BulkBook {
+        book: OrderBook::new(),
+        fills: Vec::new(),
+    }))
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bulkbook_free(book: *mut BulkBook) {
+    if !book.is_null() {
+        drop(unsafe { Box::from_raw(book) });
+    }
+}
+
+// `resting` receives the quantity left resting on the book, and may be null
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bulkbook_limit(
+    book: *mut BulkBook,
+    side: u8,
+    order_id: u64,
+    price: i64,
+    quantity: u64,
+    resting: *mut u64,
+) -> BulkBookStatus {
+    let (Some(handle), Some(side)) = (unsafe { book.as_mut() }, parse_side(side)) else {
+        return BulkBookStatus::InvalidArgument;
+    };
+    handle.fills.clear();
+    let fills = &mut handle.fills;
+    let result =
+        handle
+            .book
+            .execute_limit_order_with(side, OrderId(order_id), price, quantity, |fill| {
+                fills.push(fill)
+            });
+    if let Ok(result) = &result
+        && let Some(resting) = unsafe { resting.as_mut() }
+    {
+        *resting = result.resting_quantity;
+    }
+    status(result)
+}
+
+// `filled` receives the quantity traded, and may be null
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bulkbook_market(
+    book: *mut BulkBook,
+    side: u8,
+    quantity: u64,
+    filled: *mut u64,
+) -> BulkBookStatus {
+    let (Some(handle), Some(side)) = (unsafe { book.as_mut() }, parse_side(side)) else {
+        return BulkBookStatus::InvalidArgument;
+    };
+    handle.fills.clear();
+    let result = handle
+        .book
+        .execute_market_order_into(side, quantity, &mut handle.fills);
+    if let Ok(remaining) = result
+        && let Some(filled) = unsafe { filled.as_mut() }
+    {
+        *filled = quantity - remaining;
+    }
+    status(result)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bulkbook_cancel(book: *mut BulkBook, order_id: u64) -> BulkBookStatus {
+    let Some(handle) = (unsafe { book.as_mut() }) else {
+        return BulkBookStatus::InvalidArgument;
+    };
+    status(handle.book.cancel_order(OrderId(order_id)))
+}
+
+// Fills from the last `bulkbook_limit` or `bulkbook_market` call, 0 for a null handle
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bulkbook_fill_count(book: *const BulkBook) -> usize {
+    unsafe { book.as_ref() }.map_or(0, |handle| handle.fills.len())
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bulkbook_fill(
+    book: *const BulkBook,
+    index: usize,
+    fill: *mut BulkBookFill,
+) -> BulkBookStatus {
+    let (Some(handle), Some(out)) = (unsafe { book.as_ref() }, unsafe { fill.as_mut() }) else {
+        return BulkBookStatus::InvalidArgument;
+    };
+    let Some(found) = handle.fills.get(index) else {
+        return BulkBookStatus::InvalidArgument;
+    };
+    *out = found.into();
+    BulkBookStatus::Ok
+}
+
+// Writes the best price and its quantity on `side`, returning false if the side is
+// empty or an argument is invalid
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bulkbook_best(
+    book: *const BulkBook,
+    side: u8,
+    price: *mut i64,
+    quantity: *mut u64,
+) -> bool {
+    let (Some(handle), Some(side)) = (unsafe { book.as_ref() }, parse_side(side)) else {
+        return false;
+    };
+    let best = match side {
+        Side::Bid => handle.book.best_bid(),
+        Side::Ask => handle.book.best_ask(),
+    };
+    let (Some((best_price, best_quantity)), Some(price), Some(quantity)) =
+        (best, unsafe { price.as_mut() }, unsafe {
+            quantity.as_mut()
+        })
+    else {
+        return false;
+    };
+    (*price, *quantity) = (best_price, best_quantity);
+    true
+}
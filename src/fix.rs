@@ -0,0 +1,493 @@
+use hashbrown::HashMap;
+
+use crate::{
+    command::{Command, CommandResult},
+    error::FixError,
+    orderbook::OrderBook,
+    types::{Fill, OrderId, Price, Quantity, Side},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixOrderType {
+    Market,
+    Limit,
+}
+
+// The order entry messages the adapter understands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixMessage {
+    NewOrderSingle {
+        cl_ord_id: String,
+        side: Side,
+        order_type: FixOrderType,
+        price: Option<Price>,
+        order_qty: Quantity,
+    },
+    OrderCancelRequest {
+        cl_ord_id: String,
+        orig_cl_ord_id: String,
+    },
+    OrderCancelReplaceRequest {
+        cl_ord_id: String,
+        orig_cl_ord_id: String,
+        price: Option<Price>,
+        order_qty: Quantity, // New total quantity, including anything already filled
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecType {
+    New,
+    Trade,
+    Canceled,
+    Replaced,
+    Rejected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrdStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    Replaced,
+    Rejected,
+}
+
+// Mirrors the fields of a FIX ExecutionReport (35=8). Rejected cancels and replaces
+// are reported here too, a gateway sends those as an OrderCancelReject (35=9).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionReport {
+    pub order_id: Option<OrderId>,
+    pub cl_ord_id: String,
+    pub orig_cl_ord_id: Option<String>,
+    pub exec_type: ExecType,
+    pub ord_status: OrdStatus,
+    pub side: Option<Side>,
+    pub last_px: Option<Price>,
+    pub last_qty: Quantity,
+    pub leaves_qty: Quantity,
+    pub cum_qty: Quantity,
+    pub text: Option<String>, // Reject reason
+}
+
+impl FixMessage {
+    // Parses the application fields of a tag=value message, delimited by SOH or '|'.
+    // Framing fields (BodyLength, CheckSum, ...) are left to the session layer.
+    // Prices are scaled by 10^price_decimals into the book's integer prices.
+    pub fn parse(raw: &str, price_decimals: u32) -> Result<Self, FixError> {
+        let fields: HashMap<u32, &str> = raw
+            .split(['\x01', '|'])
+            .filter_map(|field| {
+                let (tag, value) = field.split_once('=')?;
+                Some((tag.parse().ok()?, value))
+            })
+            .collect();
+
+        let field = |tag: u32| fields.get(&tag).copied().ok_or(FixError::MissingField(tag));
+        let quantity = |tag: u32| {
+            field(tag)?
+                .parse::<Quantity>()
+                .map_err(|_| FixError::InvalidValue(tag))
+        };
+        let price = |tag: u32| match fields.get(&tag) {
+            Some(value) => parse_price(value, price_decimals)
+                .map(Some)
+                .ok_or(FixError::InvalidValue(tag)),
+            None => Ok(None),
+        };
+
+        match field(35)? {
+            "D" => {
+                let side = match field(54)? {
+                    "1" => Side::Bid,
+                    "2" => Side::Ask,
+                    _ => return Err(FixError::InvalidValue(54)),
+                };
+                let order_type = match field(40)? {
+                    "1" => FixOrderType::Market,
+                    "2" => FixOrderType::Limit,
+                    _ => return Err(FixError::InvalidValue(40)),
+                };
+                // Only Day and GTC rest the way a plain limit order does
+                if !matches!(fields.get(&59), None | Some(&"0") | Some(&"1")) {
+                    return Err(FixError::InvalidValue(59));
+                }
+                let price = price(44)?;
+                if order_type == FixOrderType::Limit && price.is_none() {
+                    return Err(FixError::MissingField(44));
+                }
+
+                Ok(FixMessage::NewOrderSingle {
+                    cl_ord_id: field(11)?.to_string(),
+                    side,
+                    order_type,
+                    price,
+                    order_qty: quantity(38)?,
+                })
+            }
+            "F" => Ok(FixMessage::OrderCancelRequest {
+                cl_ord_id: field(11)?.to_string(),
+                orig_cl_ord_id: field(41)?.to_string(),
+            }),
+            "G" => Ok(FixMessage::OrderCancelReplaceRequest {
+                cl_ord_id: field(11)?.to_string(),
+                orig_cl_ord_id: field(41)?.to_string(),
+                price: price(44)?,
+                order_qty: quantity(38)?,
+            }),
+            other => Err(FixError::UnsupportedMessageType(other.to_string())),
+        }
+    }
+}
+
+// Scales a decimal string by 10^decimals, rejecting any precision beyond that
+fn parse_price(value: &str, decimals: u32) -> Option<Price> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let negative = whole.starts_with('-');
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let scale = 10i64.checked_pow(decimals)?;
+    let whole = whole.parse::<Price>().ok()?.checked_mul(scale)?;
+    let fraction = match fraction {
+        "" => 0,
+        digits => digits.parse::<Price>().ok()? * 10i64.pow(decimals - digits.len() as u32),
+    };
+    if negative {
+        whole.checked_sub(fraction)
+    } else {
+        whole.checked_add(fraction)
+    }
+}
+
+// Client side view of a live order, to fill in cumulative quantities
+#[derive(Debug, Clone)]
+struct FixOrder {
+    cl_ord_id: String,
+    side: Side,
+    order_qty: Quantity,
+    cum_qty: Quantity,
+}
+
+// Sits between a FIX gateway and the book: translates order entry messages into
+// commands, and their results into execution reports for every order involved,
+// including resting orders filled by the incoming one.
+#[derive(Debug, Clone, Default)]
+pub struct FixAdapter {
+    orders: HashMap<OrderId, FixOrder>,
+    cl_ord_ids: HashMap<String, OrderId>, // Latest ClOrdID of each live order
+    next_order_id: u64,
+}
+
+impl FixAdapter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle(&mut self, book: &mut OrderBook, message: &FixMessage) -> Vec<ExecutionReport> {
+        let mut reports = Vec::new();
+        match message {
+            FixMessage::NewOrderSingle {
+                cl_ord_id,
+                side,
+                order_type,
+                price,
+                order_qty,
+            } => {
+                if self.cl_ord_ids.contains_key(cl_ord_id) {
+                    reports.push(reject(cl_ord_id, None, "Duplicate ClOrdID"));
+                    return reports;
+                }
+                let order_id = self.assign_order_id();
+                let command = match (order_type, price) {
+                    (FixOrderType::Limit, Some(price)) => Command::Limit {
+                        side: *side,
+                        order_id,
+                        price: *price,
+                        quantity: *order_qty,
+                    },
+                    _ => Command::Market {
+                        side: *side,
+                        quantity: *order_qty,
+                    },
+                };
+                let order = FixOrder {
+                    cl_ord_id: cl_ord_id.clone(),
+                    side: *side,
+                    order_qty: *order_qty,
+                    cum_qty: 0,
+                };
+                self.submit(book, order_id, order, &command, None, &mut reports);
+            }
+            FixMessage::OrderCancelRequest {
+                cl_ord_id,
+                orig_cl_ord_id,
+            } => {
+                let Some((order_id, order)) = self.live_order(orig_cl_ord_id) else {
+                    reports.push(reject(cl_ord_id, Some(orig_cl_ord_id), "Unknown order"));
+                    return reports;
+                };
+                match book.dispatch_command(&Command::Cancel { order_id }) {
+                    CommandResult::Cancel(Ok(())) => {
+                        self.forget(order_id);
+                        reports.push(ExecutionReport {
+                            order_id: Some(order_id),
+                            cl_ord_id: cl_ord_id.clone(),
+                            orig_cl_ord_id: Some(orig_cl_ord_id.clone()),
+                            exec_type: ExecType::Canceled,
+                            ord_status: OrdStatus::Canceled,
+                            side: Some(order.side),
+                            last_px: None,
+                            last_qty: 0,
+                            leaves_qty: 0,
+                            cum_qty: order.cum_qty,
+                            text: None,
+                        });
+                    }
+                    result => reports.push(reject(
+                        cl_ord_id,
+                        Some(orig_cl_ord_id),
+                        &format!("{result:?}"),
+                    )),
+                }
+            }
+            FixMessage::OrderCancelReplaceRequest {
+                cl_ord_id,
+                orig_cl_ord_id,
+                price,
+                order_qty,
+            } => {
+                let Some((order_id, order)) = self.live_order(orig_cl_ord_id) else {
+                    reports.push(reject(cl_ord_id, Some(orig_cl_ord_id), "Unknown order"));
+                    return reports;
+                };
+                let Some(resting) = book.get_order(order_id) else {
+                    reports.push(reject(cl_ord_id, Some(orig_cl_ord_id), "Order not resting"));
+                    return reports;
+                };
+                let leaves_qty = order_qty.saturating_sub(order.cum_qty);
+                if leaves_qty == 0 {
+                    reports.push(reject(
+                        cl_ord_id,
+                        Some(orig_cl_ord_id),
+                        "Quantity at or below filled quantity",
+                    ));
+                    return reports;
+                }
+
+                let replaced = FixOrder {
+                    cl_ord_id: cl_ord_id.clone(),
+                    order_qty: *order_qty,
+                    ..order
+                };
+
+                // A quantity change keeps the order, a price change replaces it
+                if price.is_none_or(|price| price == resting.price) {
+                    match book.dispatch_command(&Command::Amend {
+                        order_id,
+                        quantity: leaves_qty,
+                    }) {
+                        CommandResult::Amend(Ok(())) => {
+                            self.forget(order_id);
+                            reports.push(ExecutionReport {
+                                order_id: Some(order_id),
+                                cl_ord_id: cl_ord_id.clone(),
+                                orig_cl_ord_id: Some(orig_cl_ord_id.clone()),
+                                exec_type: ExecType::Replaced,
+                                ord_status: OrdStatus::Replaced,
+                                side: Some(replaced.side),
+                                last_px: None,
+                                last_qty: 0,
+                                leaves_qty,
+                                cum_qty: replaced.cum_qty,
+                                text: None,
+                            });
+                            self.track(order_id, replaced);
+                        }
+                        result => reports.push(reject(
+                            cl_ord_id,
+                            Some(orig_cl_ord_id),
+                            &format!("{result:?}"),
+                        )),
+                    }
+                    return reports;
+                }
+
+                if book.dispatch_command(&Command::Cancel { order_id })
+                    != CommandResult::Cancel(Ok(()))
+                {
+                    reports.push(reject(cl_ord_id, Some(orig_cl_ord_id), "Cancel failed"));
+                    return reports;
+                }
+                self.forget(order_id);
+
+                let new_order_id = self.assign_order_id();
+                let command = Command::Limit {
+                    side: replaced.side,
+                    order_id: new_order_id,
+                    price: price.unwrap_or(resting.price),
+                    quantity: leaves_qty,
+                };
+                self.submit(
+                    book,
+                    new_order_id,
+                    replaced,
+                    &command,
+                    Some(orig_cl_ord_id),
+                    &mut reports,
+                );
+            }
+        }
+        reports
+    }
+
+    fn assign_order_id(&mut self) -> OrderId {
+        self.next_order_id += 1;
+        OrderId(self.next_order_id)
+    }
+
+    fn live_order(&self, cl_ord_id: &str) -> Option<(OrderId, FixOrder)> {
+        let order_id = *self.cl_ord_ids.get(cl_ord_id)?;
+        Some((order_id, self.orders.get(&order_id)?.clone()))
+    }
+
+    fn track(&mut self, order_id: OrderId, order: FixOrder) {
+        self.cl_ord_ids.insert(order.cl_ord_id.clone(), order_id);
+        self.orders.insert(order_id, order);
+    }
+
+    fn forget(&mut self, order_id: OrderId) {
+        if let Some(order) = self.orders.remove(&order_id) {
+            self.cl_ord_ids.remove(&order.cl_ord_id);
+        }
+    }
+
+    // Applies a new or replacement order, reporting the acknowledgement, every fill on
+    // both sides, and the cancellation of any unfilled market order remainder.
+    // Commands are never queued by a halted book here, they're rejected so the
+    // client hears back straight away.
+    fn submit(
+        &mut self,
+        book: &mut OrderBook,
+        order_id: OrderId,
+        mut order: FixOrder,
+        command: &Command,
+        orig_cl_ord_id: Option<&String>,
+        reports: &mut Vec<ExecutionReport>,
+    ) {
+        let (fills, resting) = match book.dispatch_command(command) {
+            CommandResult::Limit(Ok(result)) => (result.fills, result.resting_quantity > 0),
+            CommandResult::Market(Ok(result)) => (result.fills, false),
+            result => {
+                reports.push(reject(
+                    &order.cl_ord_id,
+                    orig_cl_ord_id,
+                    &format!("{result:?}"),
+                ));
+                return;
+            }
+        };
+
+        let (exec_type, ord_status) = match orig_cl_ord_id {
+            Some(_) => (ExecType::Replaced, OrdStatus::Replaced),
+            None => (ExecType::New, OrdStatus::New),
+        };
+        reports.push(order.report(order_id, orig_cl_ord_id, exec_type, ord_status, None));
+
+        for fill in &fills {
+            order.cum_qty += fill.quantity;
+            let status = order.fill_status();
+            reports.push(order.report(order_id, None, ExecType::Trade, status, Some(fill)));
+            self.report_maker_fill(fill, reports);
+        }
+
+        if resting {
+            self.track(order_id, order);
+        } else if order.cum_qty < order.order_qty {
+            // Market orders don't rest, whatever didn't fill is cancelled
+            let mut report = order.report(
+                order_id,
+                None,
+                ExecType::Canceled,
+                OrdStatus::Canceled,
+                None,
+            );
+            report.leaves_qty = 0;
+            reports.push(report);
+        }
+    }
+
+    fn report_maker_fill(&mut self, fill: &Fill, reports: &mut Vec<ExecutionReport>) {
+        let Some(maker) = self.orders.get_mut(&fill.maker_order_id) else {
+            return; // Entered outside the adapter
+        };
+        maker.cum_qty += fill.quantity;
+        // The book knows whether the fill used up the resting order
+        let status = match fill.maker_fully_filled {
+            true => OrdStatus::Filled,
+            false => OrdStatus::PartiallyFilled,
+        };
+        reports.push(maker.report(
+            fill.maker_order_id,
+            None,
+            ExecType::Trade,
+            status,
+            Some(fill),
+        ));
+        if status == OrdStatus::Filled {
+            self.forget(fill.maker_order_id);
+        }
+    }
+}
+
+impl FixOrder {
+    fn fill_status(&self) -> OrdStatus {
+        if self.cum_qty >= self.order_qty {
+            OrdStatus::Filled
+        } else {
+            OrdStatus::PartiallyFilled
+        }
+    }
+
+    fn report(
+        &self,
+        order_id: OrderId,
+        orig_cl_ord_id: Option<&String>,
+        exec_type: ExecType,
+        ord_status: OrdStatus,
+        fill: Option<&Fill>,
+    ) -> ExecutionReport {
+        ExecutionReport {
+            order_id: Some(order_id),
+            cl_ord_id: self.cl_ord_id.clone(),
+            orig_cl_ord_id: orig_cl_ord_id.cloned(),
+            exec_type,
+            ord_status,
+            side: Some(self.side),
+            last_px: fill.map(|fill| fill.price),
+            last_qty: fill.map_or(0, |fill| fill.quantity),
+            leaves_qty: self.order_qty.saturating_sub(self.cum_qty),
+            cum_qty: self.cum_qty,
+            text: None,
+        }
+    }
+}
+
+fn reject(cl_ord_id: &str, orig_cl_ord_id: Option<&String>, reason: &str) -> ExecutionReport {
+    ExecutionReport {
+        order_id: None,
+        cl_ord_id: cl_ord_id.to_string(),
+        orig_cl_ord_id: orig_cl_ord_id.cloned(),
+        exec_type: ExecType::Rejected,
+        ord_status: OrdStatus::Rejected,
+        side: None,
+        last_px: None,
+        last_qty: 0,
+        leaves_qty: 0,
+        cum_qty: 0,
+        text: Some(reason.to_string()),
+    }
+}
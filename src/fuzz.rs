@@ -0,0 +1,64 @@
+use crate::{
+    command::{Command, CommandResult},
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+// Bytes consumed per decoded command
+pub const FUZZ_COMMAND_LEN: usize = 4;
+
+// Most commands decoded from one input, so every fuzz iteration stays fast
+pub const MAX_FUZZ_COMMANDS: usize = 1024;
+
+// Decodes one 4 byte chunk:
+// - byte 0: the low two bits pick Limit, Market, Cancel, or Amend, bit 2 the side
+// - byte 1: the order Id, kept to 256 values so cancels and amends find their order
+// - byte 2: the price, 84..=115 around 100 so orders cross often
+// - byte 3: the quantity, 0..=63, where zero exercises the rejection paths
+pub fn decode_fuzz_command(chunk: [u8; FUZZ_COMMAND_LEN]) -> Command {
+    let [kind, order_id, price, quantity] = chunk;
+    let side = if kind & 0b100 == 0 {
+        Side::Bid
+    } else {
+        Side::Ask
+    };
+    let order_id = OrderId(order_id as u64);
+    let price = 84 + (price % 32) as i64;
+    let quantity = (quantity % 64) as u64;
+
+    match kind & 0b11 {
+        0 => Command::Limit {
+            side,
+            order_id,
+            price,
+            quantity,
+        },
+        1 => Command::Market { side, quantity },
+        2 => Command::Cancel { order_id },
+        _ => Command::Amend { order_id, quantity },
+    }
+}
+
+// Every command an input decodes to, ignoring a trailing partial chunk
+pub fn decode_fuzz_input(bytes: &[u8]) -> impl Iterator<Item = Command> + '_ {
+    bytes
+        .chunks_exact(FUZZ_COMMAND_LEN)
+        .take(MAX_FUZZ_COMMANDS)
+        .filter_map(|chunk| chunk.try_into().ok())
+        .map(decode_fuzz_command)
+}
+
+impl OrderBook {
+    // Applies arbitrary bytes as a bounded command sequence, so a fuzz target is just
+    //
+    //     fuzz_target!(|data: &[u8]| {
+    //         let mut book = OrderBook::new();
+    //         book.apply_fuzz_input(data);
+    //         assert_eq!(book.validate(), Ok(()));
+    //     });
+    //
+    // Any input is valid, rejected commands are returned like any other result.
+    pub fn apply_fuzz_input(&mut self, bytes: &[u8]) -> Vec<CommandResult> {
+        self.replay(decode_fuzz_input(bytes))
+    }
+}
@@ -0,0 +1,273 @@
+// tonic fixes `Status` as every handler's error type, large as it is
+#![allow(clippy::result_large_err)]
+
+use std::{pin::Pin, thread};
+
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio_stream::{Stream, StreamExt, wrappers::BroadcastStream};
+use tonic::{Request, Response, Status};
+
+use crate::{
+    command,
+    events::BookEvent,
+    orderbook::OrderBook,
+    types::{Depth, DepthLevel, OrderId, Side},
+};
+
+// Messages and service generated from `proto/bulkbook.proto`
+pub mod proto {
+    tonic::include_proto!("bulkbook.v1");
+}
+
+use proto::order_book_service_server::{OrderBookService, OrderBookServiceServer};
+
+enum BookRequest {
+    Command(
+        command::Command,
+        oneshot::Sender<Result<Vec<proto::Event>, Status>>,
+    ),
+    Depth(usize, oneshot::Sender<Depth>),
+}
+
+// The `OrderBookService` RPCs over a book on its own matching thread. Events are
+// broadcast from that thread, so subscribers see them in exactly the order the book
+// produced them. Clones share the same book, and the thread exits once every clone
+// is dropped. Serve it with
+//
+//     Server::builder().add_service(service.into_server()).serve(addr).await
+#[derive(Debug, Clone)]
+pub struct GrpcBookService {
+    requests: mpsc::Sender<BookRequest>,
+    events: broadcast::Sender<proto::Event>,
+}
+
+impl GrpcBookService {
+    pub fn spawn(capacity: usize) -> Self {
+        Self::spawn_with(capacity, OrderBook::new())
+    }
+
+    // Moves an existing book onto the matching thread. At most `capacity` requests
+    // wait for the thread at once, and each subscriber may fall up to `capacity` events
+    // behind before it's cut off.
+    pub fn spawn_with(capacity: usize, mut book: OrderBook) -> Self {
+        let (requests, mut incoming) = mpsc::channel(capacity);
+        let (events, _) = broadcast::channel(capacity);
+
+        let broadcast = events.clone();
+        thread::spawn(move || {
+            while let Some(request) = incoming.blocking_recv() {
+                match request {
+                    BookRequest::Command(command, reply) => {
+                        let response = book.respond(command);
+                        // Events are numbered consecutively, ending at the book's sequence
+                        let first = book.event_sequence() + 1 - response.events.len() as u64;
+                        let events: Vec<_> = (first..)
+                            .zip(&response.events)
+                            .map(|(sequence, event)| to_proto_event(sequence, event))
+                            .collect();
+                        for event in &events {
+                            // Nobody subscribed is fine
+                            let _ = broadcast.send(*event);
+                        }
+                        let result = response.result.into_result().map(|()| events);
+                        let _ = reply
+                            .send(result.map_err(|error| {
+                                Status::failed_precondition(format!("{error:?}"))
+                            }));
+                    }
+                    BookRequest::Depth(levels, reply) => {
+                        let _ = reply.send(book.depth(levels));
+                    }
+                }
+            }
+        });
+
+        Self { requests, events }
+    }
+
+    pub fn into_server(self) -> OrderBookServiceServer<Self> {
+        OrderBookServiceServer::new(self)
+    }
+
+    async fn request<T>(
+        &self,
+        request: impl FnOnce(oneshot::Sender<T>) -> BookRequest,
+    ) -> Result<T, Status> {
+        let (reply, response) = oneshot::channel();
+        let stopped = || Status::unavailable("the matching thread has stopped");
+        self.requests
+            .send(request(reply))
+            .await
+            .map_err(|_| stopped())?;
+        response.await.map_err(|_| stopped())
+    }
+}
+
+#[tonic::async_trait]
+impl OrderBookService for GrpcBookService {
+    async fn submit(
+        &self,
+        request: Request<proto::Command>,
+    ) -> Result<Response<proto::CommandReply>, Status> {
+        let command = from_proto_command(request.into_inner())?;
+        let events = self
+            .request(|reply| BookRequest::Command(command, reply))
+            .await??;
+        Ok(Response::new(proto::CommandReply { events }))
+    }
+
+    async fn get_depth(
+        &self,
+        request: Request<proto::DepthRequest>,
+    ) -> Result<Response<proto::DepthReply>, Status> {
+        let levels = request.into_inner().levels as usize;
+        let depth = self
+            .request(|reply| BookRequest::Depth(levels, reply))
+            .await?;
+        let to_levels = |levels: Vec<DepthLevel>| {
+            levels
+                .into_iter()
+                .map(|(price, quantity, order_count)| proto::Level {
+                    price,
+                    quantity,
+                    order_count: order_count as u64,
+                })
+                .collect()
+        };
+        Ok(Response::new(proto::DepthReply {
+            bids: to_levels(depth.bids),
+            asks: to_levels(depth.asks),
+        }))
+    }
+
+    type SubscribeStream = Pin<Box<dyn Stream<Item = Result<proto::Event, Status>> + Send>>;
+
+    async fn subscribe(
+        &self,
+        _request: Request<proto::SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let events = BroadcastStream::new(self.events.subscribe()).map(|event| {
+            event.map_err(|error| Status::data_loss(format!("subscriber fell behind: {error}")))
+        });
+        Ok(Response::new(Box::pin(events)))
+    }
+}
+
+fn from_proto_side(side: i32) -> Result<Side, Status> {
+    match proto::Side::try_from(side) {
+        Ok(proto::Side::Bid) => Ok(Side::Bid),
+        Ok(proto::Side::Ask) => Ok(Side::Ask),
+        Err(_) => Err(Status::invalid_argument(format!("unknown side {side}"))),
+    }
+}
+
+fn to_proto_side(side: Side) -> i32 {
+    match side {
+        Side::Bid => proto::Side::Bid,
+        Side::Ask => proto::Side::Ask,
+    }
+    .into()
+}
+
+fn from_proto_command(command: proto::Command) -> Result<command::Command, Status> {
+    use proto::command::Kind;
+
+    Ok(
+        match command
+            .kind
+            .ok_or_else(|| Status::invalid_argument("empty command"))?
+        {
+            Kind::Limit(limit) => command::Command::Limit {
+                side: from_proto_side(limit.side)?,
+                order_id: OrderId(limit.order_id),
+                price: limit.price,
+                quantity: limit.quantity,
+            },
+            Kind::Market(market) => command::Command::Market {
+                side: from_proto_side(market.side)?,
+                quantity: market.quantity,
+            },
+            Kind::Cancel(cancel) => command::Command::Cancel {
+                order_id: OrderId(cancel.order_id),
+            },
+            Kind::Amend(amend) => command::Command::Amend {
+                order_id: OrderId(amend.order_id),
+                quantity: amend.quantity,
+            },
+        },
+    )
+}
+
+fn to_proto_event(sequence: u64, event: &BookEvent) -> proto::Event {
+    use proto::event::Kind;
+
+    let kind = match *event {
+        BookEvent::OrderAdded {
+            order_id,
+            side,
+            price,
+            quantity,
+            ..
+        } => Kind::OrderAdded(proto::OrderAdded {
+            order_id: order_id.0,
+            side: to_proto_side(side),
+            price,
+            quantity,
+        }),
+        BookEvent::OrderCancelled {
+            order_id,
+            side,
+            price,
+            quantity,
+            ..
+        } => Kind::OrderCancelled(proto::OrderCancelled {
+            order_id: order_id.0,
+            side: to_proto_side(side),
+            price,
+            quantity,
+        }),
+        BookEvent::OrderReduced {
+            order_id,
+            side,
+            price,
+            new_quantity,
+            ..
+        } => Kind::OrderReduced(proto::OrderReduced {
+            order_id: order_id.0,
+            side: to_proto_side(side),
+            price,
+            new_quantity,
+        }),
+        BookEvent::Trade {
+            taker_side,
+            ref fill,
+        } => Kind::Trade(proto::Trade {
+            taker_side: to_proto_side(taker_side),
+            price: fill.price,
+            quantity: fill.quantity,
+            maker_order_id: fill.maker_order_id.0,
+            trade_id: fill.trade_id.0,
+            timestamp: fill.timestamp,
+        }),
+        BookEvent::AuctionTrade { ref fill } => Kind::AuctionTrade(proto::AuctionTrade {
+            price: fill.price,
+            quantity: fill.quantity,
+            buy_order_id: fill.buy_order_id.0,
+            sell_order_id: fill.sell_order_id.0,
+            trade_id: fill.trade_id.0,
+            timestamp: fill.timestamp,
+        }),
+        BookEvent::LevelAdded { side, price } => Kind::LevelAdded(proto::LevelChanged {
+            side: to_proto_side(side),
+            price,
+        }),
+        BookEvent::LevelRemoved { side, price } => Kind::LevelRemoved(proto::LevelChanged {
+            side: to_proto_side(side),
+            price,
+        }),
+    };
+    proto::Event {
+        sequence,
+        kind: Some(kind),
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    error::LimitOrderError,
+    order_ids::{Arrival, OrderIdClaim},
+    orderbook::OrderBook,
+    self_trade::SelfTradePrevention,
+    types::{Fill, LimitOrderResult, OrderId, OwnerId, Price, Quantity, Side},
+};
+
+// Hidden orders rest in levels of their own, kept out of depth, the best bid and ask,
+// level 3 and shared snapshots, and every book event besides the trades they take
+// part in, so no market data built from the book reveals them. They still match like
+// any other resting order, ahead of worse prices but behind every visible order at
+// their own price, whatever the time they arrived.
+//
+// They need the book to be matching to be placed and sit out peg references. Those
+// resting when an auction starts count towards its uncross price and trade in it like
+// visible orders, queued behind them at the same price.
+impl OrderBook {
+    // Same as `execute_limit_order`, resting any remainder hidden. Place one with other
+    // attributes through `Order::builder(..).hidden()` and `submit_order`.
+    pub fn execute_hidden_order(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        let arrival = Arrival {
+            hidden: true,
+            ..Default::default()
+        };
+        let order_id = match self.claim_order_id(order_id, side, price, quantity, arrival)? {
+            OrderIdClaim::New(order_id) => order_id,
+            OrderIdClaim::Amended(result) => return Ok(result),
+        };
+        if self.order_id_exists(order_id) {
+            return Err(LimitOrderError::OrderIdAlreadyExists);
+        }
+        self.check_limit_order(price, quantity)?;
+        if !self.accepts_orders() {
+            return Err(LimitOrderError::TradingHalted);
+        }
+
+        let mut fills = Vec::new();
+        let result =
+            self.place_hidden_order_with(side, order_id, price, quantity, None, |fill| {
+                fills.push(fill)
+            })?;
+        let sequence = self.after_mutation();
+        Ok(LimitOrderResult {
+            fills,
+            sequence,
+            ..result
+        })
+    }
+
+    // Total hidden quantity resting on one side, for the book's operator alone
+    pub fn hidden_volume(&self, side: Side) -> Quantity {
+        match side {
+            Side::Bid => self.hidden_bid_totals.quantity,
+            Side::Ask => self.hidden_ask_totals.quantity,
+        }
+    }
+
+    pub fn hidden_order_count(&self, side: Side) -> usize {
+        match side {
+            Side::Bid => self.hidden_bid_totals.order_count,
+            Side::Ask => self.hidden_ask_totals.order_count,
+        }
+    }
+
+    // Matches like `place_limit_order_with`, then rests the remainder in the hidden
+    // levels. The Id, price, and trading state are the caller's to check.
+    pub(crate) fn place_hidden_order_with(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+        owner: Option<(OwnerId, SelfTradePrevention)>,
+        on_fill: impl FnMut(Fill),
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        if !self.matches_orders() {
+            return Err(LimitOrderError::TradingHalted);
+        }
+        self.check_rest(side, price, quantity, true)?;
+
+        let quantity = self
+            .match_order(side, quantity, Some(price), owner, on_fill)
+            .map_err(|error| error.into_internal())?;
+        if quantity == 0 {
+            return Ok(LimitOrderResult {
+                order_id,
+                ..Default::default()
+            });
+        }
+
+        let (index, queue_position) = self.rest_order(side, order_id, price, quantity, true)?;
+        if let Some((owner, _)) = owner {
+            self.track_owner(order_id, owner);
+        }
+        Ok(LimitOrderResult {
+            order_id,
+            fills: Vec::new(),
+            resting_quantity: quantity,
+            order_index: Some(index),
+            // Counting the visible orders ahead, it's at the tail so this is quick
+            queue_position: self.queue_position(order_id).or(Some(queue_position)),
+            sequence: 0, // Assigned once the order is accepted
+        })
+    }
+}
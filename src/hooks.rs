@@ -0,0 +1,104 @@
+use std::fmt;
+
+use crate::{
+    orderbook::OrderBook,
+    types::{Fill, OwnerId, Price, Quantity, Side},
+};
+
+// An incoming order about to match against the book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncomingOrder {
+    pub side: Side,
+    pub quantity: Quantity,
+    pub limit_price: Option<Price>, // None for market orders
+    pub owner: Option<OwnerId>,
+}
+
+// One trade from matching, with the owners on either side of it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchedTrade {
+    pub taker_side: Side,
+    pub fill: Fill,
+    pub maker_owner: Option<OwnerId>,
+    pub taker_owner: Option<OwnerId>,
+}
+
+// Callbacks around continuous matching, for surveillance, logging, or owner-level
+// checks layered on top of the book. Both default to doing nothing.
+//
+// `before_match` sees the book as the incoming order found it, once the order has
+// passed validation. `after_trade` fires for each trade once the operation that made
+// it has settled, so the book is never seen half way through a match. Auction uncrosses
+// and `execute_resting_order` don't match incoming orders and don't call either. Like
+// listeners, hooks must be `Send`.
+pub trait MatchHook: Send {
+    fn before_match(&mut self, _book: &OrderBook, _order: &IncomingOrder) {}
+
+    fn after_trade(&mut self, _book: &OrderBook, _trade: &MatchedTrade) {}
+}
+
+// The book's hook, with the trades waiting for the current operation to settle. Like
+// listeners, the hook isn't cloned along with the book.
+#[derive(Default)]
+pub struct MatchHooks {
+    hook: Option<Box<dyn MatchHook>>,
+    pending: Vec<MatchedTrade>,
+}
+
+impl MatchHooks {
+    pub fn is_set(&self) -> bool {
+        self.hook.is_some()
+    }
+
+    pub(crate) fn queue_trade(&mut self, trade: MatchedTrade) {
+        self.pending.push(trade);
+    }
+}
+
+impl Clone for MatchHooks {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for MatchHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MatchHooks")
+            .field("is_set", &self.is_set())
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+impl OrderBook {
+    // Replaces any hook set before
+    pub fn set_match_hook(&mut self, hook: impl MatchHook + 'static) {
+        self.match_hooks.hook = Some(Box::new(hook));
+    }
+
+    pub fn clear_match_hook(&mut self) {
+        self.match_hooks = MatchHooks::default();
+    }
+
+    // The hook is lent out for the call, so it can read the book it's attached to
+    pub(crate) fn run_before_match(&mut self, order: IncomingOrder) {
+        if let Some(mut hook) = self.match_hooks.hook.take() {
+            hook.before_match(self, &order);
+            self.match_hooks.hook = Some(hook);
+        }
+    }
+
+    pub(crate) fn run_after_trades(&mut self) {
+        if self.match_hooks.pending.is_empty() {
+            return;
+        }
+        let Some(mut hook) = self.match_hooks.hook.take() else {
+            return;
+        };
+        let trades = std::mem::take(&mut self.match_hooks.pending);
+        for trade in &trades {
+            hook.after_trade(self, trade);
+        }
+        self.match_hooks.hook = Some(hook);
+    }
+}
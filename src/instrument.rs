@@ -0,0 +1,205 @@
+use crate::{
+    error::InstrumentError,
+    orderbook::OrderBook,
+    types::{Price, Quantity, Side},
+};
+
+// Trading rules for the instrument a book lists. Prices must be a multiple of the
+// tick size within [min_price, max_price], quantities a multiple of the lot size
+// within [min_order_size, max_order_size]. Every price is an integer count of
+// 10^-price_decimals, which only matters when converting decimal prices. Built with
+// `Instrument::builder`, which rejects rules no order could meet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Instrument {
+    tick_size: Price,
+    lot_size: Quantity,
+    min_price: Price,
+    max_price: Price,
+    min_order_size: Quantity,
+    max_order_size: Quantity,
+    price_decimals: u32, // e.g. 2 when a price of 10125 means 101.25
+}
+
+impl Default for Instrument {
+    fn default() -> Self {
+        Self {
+            tick_size: 1,
+            lot_size: 1,
+            min_price: Price::MIN,
+            max_price: Price::MAX,
+            min_order_size: 0,
+            max_order_size: Quantity::MAX,
+            price_decimals: 0,
+        }
+    }
+}
+
+// Starts from the default instrument, which accepts any price and quantity
+#[derive(Debug, Clone, Default)]
+pub struct InstrumentBuilder(Instrument);
+
+impl InstrumentBuilder {
+    pub fn tick_size(mut self, tick_size: Price) -> Self {
+        self.0.tick_size = tick_size;
+        self
+    }
+
+    pub fn lot_size(mut self, lot_size: Quantity) -> Self {
+        self.0.lot_size = lot_size;
+        self
+    }
+
+    pub fn price_range(mut self, min_price: Price, max_price: Price) -> Self {
+        self.0.min_price = min_price;
+        self.0.max_price = max_price;
+        self
+    }
+
+    pub fn order_size_range(mut self, min_order_size: Quantity, max_order_size: Quantity) -> Self {
+        self.0.min_order_size = min_order_size;
+        self.0.max_order_size = max_order_size;
+        self
+    }
+
+    pub fn price_decimals(mut self, price_decimals: u32) -> Self {
+        self.0.price_decimals = price_decimals;
+        self
+    }
+
+    pub fn build(self) -> Result<Instrument, InstrumentError> {
+        let instrument = self.0;
+        if instrument.tick_size <= 0 {
+            return Err(InstrumentError::NonPositiveTickSize);
+        }
+        if instrument.lot_size == 0 {
+            return Err(InstrumentError::ZeroLotSize);
+        }
+        if instrument.min_price > instrument.max_price {
+            return Err(InstrumentError::InvertedPriceRange);
+        }
+        if instrument.min_order_size > instrument.max_order_size {
+            return Err(InstrumentError::InvertedOrderSizeRange);
+        }
+        Ok(instrument)
+    }
+}
+
+// Goes through the builder, so a snapshot can't bring in rules it would reject
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Instrument {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Fields {
+            tick_size: Price,
+            lot_size: Quantity,
+            min_price: Price,
+            max_price: Price,
+            min_order_size: Quantity,
+            max_order_size: Quantity,
+            price_decimals: u32,
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+        Instrument::builder()
+            .tick_size(fields.tick_size)
+            .lot_size(fields.lot_size)
+            .price_range(fields.min_price, fields.max_price)
+            .order_size_range(fields.min_order_size, fields.max_order_size)
+            .price_decimals(fields.price_decimals)
+            .build()
+            .map_err(|error| serde::de::Error::custom(format!("invalid instrument: {error:?}")))
+    }
+}
+
+impl Instrument {
+    pub fn builder() -> InstrumentBuilder {
+        InstrumentBuilder::default()
+    }
+
+    pub fn tick_size(&self) -> Price {
+        self.tick_size
+    }
+
+    pub fn lot_size(&self) -> Quantity {
+        self.lot_size
+    }
+
+    pub fn min_price(&self) -> Price {
+        self.min_price
+    }
+
+    pub fn max_price(&self) -> Price {
+        self.max_price
+    }
+
+    pub fn min_order_size(&self) -> Quantity {
+        self.min_order_size
+    }
+
+    pub fn max_order_size(&self) -> Quantity {
+        self.max_order_size
+    }
+
+    pub fn price_decimals(&self) -> u32 {
+        self.price_decimals
+    }
+
+    pub fn check_price(&self, price: Price) -> Result<(), InstrumentError> {
+        if price < self.min_price || price > self.max_price {
+            return Err(InstrumentError::PriceOutOfRange);
+        }
+        if price.rem_euclid(self.tick_size) != 0 {
+            return Err(InstrumentError::PriceNotOnTick);
+        }
+        Ok(())
+    }
+
+    pub fn check_quantity(&self, quantity: Quantity) -> Result<(), InstrumentError> {
+        if quantity < self.min_order_size {
+            return Err(InstrumentError::QuantityTooSmall);
+        }
+        if quantity > self.max_order_size {
+            return Err(InstrumentError::QuantityTooLarge);
+        }
+        if !quantity.is_multiple_of(self.lot_size) {
+            return Err(InstrumentError::QuantityNotOnLot);
+        }
+        Ok(())
+    }
+
+    // Rounds a price onto the tick grid, away from the opposite side
+    pub fn round_passive(&self, side: Side, price: Price) -> Price {
+        let below = price - price.rem_euclid(self.tick_size);
+        match side {
+            Side::Bid => below,
+            Side::Ask if below == price => price,
+            Side::Ask => below + self.tick_size,
+        }
+    }
+}
+
+impl OrderBook {
+    pub fn with_instrument(instrument: Instrument) -> Self {
+        Self {
+            instrument: Some(instrument),
+            ..Self::new()
+        }
+    }
+
+    // Checks an incoming order against the instrument, if the book has one.
+    // Market orders have no price to check.
+    pub(crate) fn check_order(
+        &self,
+        price: Option<Price>,
+        quantity: Quantity,
+    ) -> Result<(), InstrumentError> {
+        let Some(instrument) = &self.instrument else {
+            return Ok(());
+        };
+        if let Some(price) = price {
+            instrument.check_price(price)?;
+        }
+        instrument.check_quantity(quantity)
+    }
+}
@@ -0,0 +1,178 @@
+use hashbrown::HashMap;
+
+use crate::{
+    error::EncodeError,
+    events::BookEvent,
+    orderbook::OrderBook,
+    types::{OrderId, Price, Quantity, Side, TradeId},
+};
+
+// Fixed message sizes, following the ITCH 5.0 layouts
+pub const ADD_ORDER_LEN: usize = 36;
+pub const ORDER_EXECUTED_LEN: usize = 31;
+pub const ORDER_CANCEL_LEN: usize = 23;
+pub const ORDER_DELETE_LEN: usize = 19;
+
+// Converts book events into ITCH-style binary messages: Add Order ('A'), Order Executed
+// ('E'), Order Cancel ('X', a partial cancel) and Order Delete ('D'). Fields are big-endian,
+// prices are written as-is so they're in whatever units the book uses. Level events have
+// no ITCH equivalent and are skipped.
+#[derive(Debug, Clone)]
+pub struct ItchEncoder {
+    pub stock_locate: u16,
+    pub stock: [u8; 8],                    // Space padded symbol
+    tracking_number: u16,                  // Incremented per message, wrapping
+    remaining: HashMap<OrderId, Quantity>, // Needed to turn reductions into cancelled shares
+}
+
+impl ItchEncoder {
+    // Seeds quantities from orders already resting, so later reductions can be encoded
+    pub fn new(book: &OrderBook, stock_locate: u16, stock: &str) -> Self {
+        let mut symbol = [b' '; 8];
+        for (slot, byte) in symbol.iter_mut().zip(stock.bytes()) {
+            *slot = byte;
+        }
+
+        let remaining = book
+            .index_map
+            .iter()
+            .filter_map(|(order_id, entry)| {
+                let node = book.orders.get(entry.order_index)?;
+                Some((*order_id, node.quantity))
+            })
+            .collect();
+
+        Self {
+            stock_locate,
+            stock: symbol,
+            tracking_number: 0,
+            remaining,
+        }
+    }
+
+    // Appends the messages for each event to `out`. Timestamps are nanoseconds since
+    // midnight, only the low 48 bits are sent.
+    pub fn encode(
+        &mut self,
+        events: &[BookEvent],
+        timestamp: u64,
+        out: &mut Vec<u8>,
+    ) -> Result<(), EncodeError> {
+        for event in events {
+            self.encode_event(event, timestamp, out)?;
+        }
+        Ok(())
+    }
+
+    fn encode_event(
+        &mut self,
+        event: &BookEvent,
+        timestamp: u64,
+        out: &mut Vec<u8>,
+    ) -> Result<(), EncodeError> {
+        match *event {
+            BookEvent::OrderAdded {
+                order_id,
+                side,
+                price,
+                quantity,
+                ..
+            } => {
+                let shares = shares(quantity)?;
+                let price = price_field(price)?;
+                self.header(b'A', timestamp, order_id, out);
+                out.push(match side {
+                    Side::Bid => b'B',
+                    Side::Ask => b'S',
+                });
+                out.extend_from_slice(&shares.to_be_bytes());
+                out.extend_from_slice(&self.stock);
+                out.extend_from_slice(&price.to_be_bytes());
+                self.remaining.insert(order_id, quantity);
+            }
+            BookEvent::Trade { ref fill, .. } => {
+                self.executed(
+                    fill.maker_order_id,
+                    fill.quantity,
+                    fill.trade_id,
+                    timestamp,
+                    out,
+                )?;
+            }
+            // Both orders were resting, so each gets an execution under the same match number
+            BookEvent::AuctionTrade { ref fill } => {
+                self.executed(
+                    fill.buy_order_id,
+                    fill.quantity,
+                    fill.trade_id,
+                    timestamp,
+                    out,
+                )?;
+                self.executed(
+                    fill.sell_order_id,
+                    fill.quantity,
+                    fill.trade_id,
+                    timestamp,
+                    out,
+                )?;
+            }
+            BookEvent::OrderReduced {
+                order_id,
+                new_quantity,
+                ..
+            } => {
+                let previous = self.remaining.insert(order_id, new_quantity);
+                let cancelled = shares(previous.unwrap_or(new_quantity) - new_quantity)?;
+                self.header(b'X', timestamp, order_id, out);
+                out.extend_from_slice(&cancelled.to_be_bytes());
+            }
+            BookEvent::OrderCancelled { order_id, .. } => {
+                self.remaining.remove(&order_id);
+                self.header(b'D', timestamp, order_id, out);
+            }
+            BookEvent::LevelAdded { .. } | BookEvent::LevelRemoved { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn executed(
+        &mut self,
+        order_id: OrderId,
+        quantity: Quantity,
+        trade_id: TradeId,
+        timestamp: u64,
+        out: &mut Vec<u8>,
+    ) -> Result<(), EncodeError> {
+        let shares = shares(quantity)?;
+        self.header(b'E', timestamp, order_id, out);
+        out.extend_from_slice(&shares.to_be_bytes());
+        out.extend_from_slice(&trade_id.0.to_be_bytes());
+
+        // An execution for the whole remainder removes the order, as in ITCH
+        if let Some(remaining) = self.remaining.get_mut(&order_id) {
+            *remaining = remaining.saturating_sub(quantity);
+            if *remaining == 0 {
+                self.remaining.remove(&order_id);
+            }
+        }
+        Ok(())
+    }
+
+    // Message type, stock locate, tracking number, timestamp, and order reference
+    fn header(&mut self, message_type: u8, timestamp: u64, order_id: OrderId, out: &mut Vec<u8>) {
+        out.push(message_type);
+        out.extend_from_slice(&self.stock_locate.to_be_bytes());
+        out.extend_from_slice(&self.tracking_number.to_be_bytes());
+        out.extend_from_slice(&timestamp.to_be_bytes()[2..]);
+        out.extend_from_slice(&order_id.0.to_be_bytes());
+        self.tracking_number = self.tracking_number.wrapping_add(1);
+    }
+}
+
+fn shares(quantity: Quantity) -> Result<u32, EncodeError> {
+    u32::try_from(quantity).map_err(|_| EncodeError::ValueOutOfRange)
+}
+
+fn price_field(price: Price) -> Result<u32, EncodeError> {
+    u32::try_from(price).map_err(|_| EncodeError::ValueOutOfRange)
+}
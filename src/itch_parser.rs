@@ -0,0 +1,297 @@
+use crate::{
+    codec::ByteReader,
+    error::{DecodeError, ItchBookError},
+    orderbook::OrderBook,
+    types::{OrderId, Price, Quantity, Side},
+};
+
+// The Nasdaq TotalView-ITCH 5.0 messages that change the order book. Prices keep the
+// feed's four implied decimal places.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItchMessage {
+    AddOrder {
+        stock_locate: u16,
+        timestamp: u64,
+        order_ref: u64,
+        side: Side,
+        shares: u32,
+        stock: [u8; 8],
+        price: u32,
+    },
+    OrderExecuted {
+        stock_locate: u16,
+        timestamp: u64,
+        order_ref: u64,
+        executed_shares: u32,
+        match_number: u64,
+    },
+    OrderExecutedWithPrice {
+        stock_locate: u16,
+        timestamp: u64,
+        order_ref: u64,
+        executed_shares: u32,
+        match_number: u64,
+        execution_price: u32,
+    },
+    OrderCancel {
+        stock_locate: u16,
+        timestamp: u64,
+        order_ref: u64,
+        cancelled_shares: u32,
+    },
+    OrderDelete {
+        stock_locate: u16,
+        timestamp: u64,
+        order_ref: u64,
+    },
+    OrderReplace {
+        stock_locate: u16,
+        timestamp: u64,
+        original_order_ref: u64,
+        new_order_ref: u64,
+        shares: u32,
+        price: u32,
+    },
+    Other(u8), // Any other message type, identified by its type byte
+}
+
+impl ItchMessage {
+    // Parses a single message, without its length prefix
+    pub fn parse(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = ByteReader::new(bytes);
+        let message_type = reader.u8()?;
+        if !matches!(message_type, b'A' | b'F' | b'E' | b'C' | b'X' | b'D' | b'U') {
+            return Ok(ItchMessage::Other(message_type));
+        }
+
+        let stock_locate = u16::from_be_bytes(reader.take()?);
+        let _tracking_number: [u8; 2] = reader.take()?;
+        let timestamp_bytes: [u8; 6] = reader.take()?;
+        let mut timestamp = [0; 8];
+        timestamp[2..].copy_from_slice(&timestamp_bytes);
+        let timestamp = u64::from_be_bytes(timestamp);
+
+        let u32_be = |reader: &mut ByteReader| Ok(u32::from_be_bytes(reader.take()?));
+        let u64_be = |reader: &mut ByteReader| Ok(u64::from_be_bytes(reader.take()?));
+
+        let message = match message_type {
+            // 'F' is an attributed add, the trailing MPID is ignored
+            b'A' | b'F' => ItchMessage::AddOrder {
+                stock_locate,
+                timestamp,
+                order_ref: u64_be(&mut reader)?,
+                side: match reader.u8()? {
+                    b'B' => Side::Bid,
+                    b'S' => Side::Ask,
+                    _ => return Err(DecodeError::Corrupt),
+                },
+                shares: u32_be(&mut reader)?,
+                stock: reader.take()?,
+                price: u32_be(&mut reader)?,
+            },
+            b'E' => ItchMessage::OrderExecuted {
+                stock_locate,
+                timestamp,
+                order_ref: u64_be(&mut reader)?,
+                executed_shares: u32_be(&mut reader)?,
+                match_number: u64_be(&mut reader)?,
+            },
+            b'C' => ItchMessage::OrderExecutedWithPrice {
+                stock_locate,
+                timestamp,
+                order_ref: u64_be(&mut reader)?,
+                executed_shares: u32_be(&mut reader)?,
+                match_number: u64_be(&mut reader)?,
+                execution_price: {
+                    let _printable = reader.u8()?;
+                    u32_be(&mut reader)?
+                },
+            },
+            b'X' => ItchMessage::OrderCancel {
+                stock_locate,
+                timestamp,
+                order_ref: u64_be(&mut reader)?,
+                cancelled_shares: u32_be(&mut reader)?,
+            },
+            b'D' => ItchMessage::OrderDelete {
+                stock_locate,
+                timestamp,
+                order_ref: u64_be(&mut reader)?,
+            },
+            _ => ItchMessage::OrderReplace {
+                stock_locate,
+                timestamp,
+                original_order_ref: u64_be(&mut reader)?,
+                new_order_ref: u64_be(&mut reader)?,
+                shares: u32_be(&mut reader)?,
+                price: u32_be(&mut reader)?,
+            },
+        };
+        Ok(message)
+    }
+
+    pub fn stock_locate(&self) -> Option<u16> {
+        match *self {
+            ItchMessage::AddOrder { stock_locate, .. }
+            | ItchMessage::OrderExecuted { stock_locate, .. }
+            | ItchMessage::OrderExecutedWithPrice { stock_locate, .. }
+            | ItchMessage::OrderCancel { stock_locate, .. }
+            | ItchMessage::OrderDelete { stock_locate, .. }
+            | ItchMessage::OrderReplace { stock_locate, .. } => Some(stock_locate),
+            ItchMessage::Other(_) => None,
+        }
+    }
+}
+
+// Splits a stream of messages each prefixed by a big-endian u16 length, the framing
+// used by Nasdaq's historical ITCH files
+pub struct ItchFrames<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ItchFrames<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<'a> Iterator for ItchFrames<'a> {
+    type Item = Result<&'a [u8], DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let Some((len, rest)) = self.bytes.split_first_chunk::<2>() else {
+            self.bytes = &[];
+            return Some(Err(DecodeError::Truncated));
+        };
+        let len = u16::from_be_bytes(*len) as usize;
+        let Some(message) = rest.get(..len) else {
+            self.bytes = &[];
+            return Some(Err(DecodeError::Truncated));
+        };
+        self.bytes = &rest[len..];
+        Some(Ok(message))
+    }
+}
+
+// Maintains the book for one stock from an ITCH feed. Adds rest without matching,
+// since the exchange reports every execution itself.
+#[derive(Debug, Clone, Default)]
+pub struct ItchBookBuilder {
+    pub stock_locate: u16,
+    pub book: OrderBook,
+}
+
+impl ItchBookBuilder {
+    pub fn new(stock_locate: u16) -> Self {
+        Self {
+            stock_locate,
+            book: OrderBook::new(),
+        }
+    }
+
+    // Parses and applies every framed message in `bytes`, stopping at the first error
+    pub fn apply_stream(&mut self, bytes: &[u8]) -> Result<(), ItchBookError> {
+        for frame in ItchFrames::new(bytes) {
+            let message = ItchMessage::parse(frame?)?;
+            self.apply(&message)?;
+        }
+        Ok(())
+    }
+
+    // Messages for other stocks, and non book messages, are ignored
+    pub fn apply(&mut self, message: &ItchMessage) -> Result<(), ItchBookError> {
+        if message.stock_locate() != Some(self.stock_locate) {
+            return Ok(());
+        }
+
+        match *message {
+            ItchMessage::AddOrder {
+                order_ref,
+                side,
+                shares,
+                price,
+                ..
+            } => self.add(side, order_ref, price as Price, shares as Quantity)?,
+            ItchMessage::OrderExecuted {
+                order_ref,
+                executed_shares,
+                ..
+            } => {
+                self.book
+                    .execute_resting_order(OrderId(order_ref), executed_shares as Quantity, None)
+                    .map_err(|_| ItchBookError::UnknownOrderReference(order_ref))?;
+            }
+            ItchMessage::OrderExecutedWithPrice {
+                order_ref,
+                executed_shares,
+                execution_price,
+                ..
+            } => {
+                self.book
+                    .execute_resting_order(
+                        OrderId(order_ref),
+                        executed_shares as Quantity,
+                        Some(execution_price as Price),
+                    )
+                    .map_err(|_| ItchBookError::UnknownOrderReference(order_ref))?;
+            }
+            ItchMessage::OrderCancel {
+                order_ref,
+                cancelled_shares,
+                ..
+            } => {
+                self.book
+                    .cancel_quantity(OrderId(order_ref), cancelled_shares as Quantity)
+                    .map_err(|_| ItchBookError::UnknownOrderReference(order_ref))?;
+            }
+            ItchMessage::OrderDelete { order_ref, .. } => {
+                self.book
+                    .cancel_order(OrderId(order_ref))
+                    .map_err(|_| ItchBookError::UnknownOrderReference(order_ref))?;
+            }
+            // The replacement keeps the original's side but loses its priority
+            ItchMessage::OrderReplace {
+                original_order_ref,
+                new_order_ref,
+                shares,
+                price,
+                ..
+            } => {
+                let Some(original) = self.book.get_order(OrderId(original_order_ref)) else {
+                    return Err(ItchBookError::UnknownOrderReference(original_order_ref));
+                };
+                self.book
+                    .cancel_order(OrderId(original_order_ref))
+                    .map_err(|_| ItchBookError::UnknownOrderReference(original_order_ref))?;
+                self.add(
+                    original.side,
+                    new_order_ref,
+                    price as Price,
+                    shares as Quantity,
+                )?;
+            }
+            ItchMessage::Other(_) => {}
+        }
+        Ok(())
+    }
+
+    fn add(
+        &mut self,
+        side: Side,
+        order_ref: u64,
+        price: Price,
+        quantity: Quantity,
+    ) -> Result<(), ItchBookError> {
+        if self.book.order_id_exists(OrderId(order_ref)) {
+            return Err(ItchBookError::DuplicateOrderReference(order_ref));
+        }
+        self.book
+            .rest_order(side, OrderId(order_ref), price, quantity, false)
+            .map_err(|error| ItchBookError::Internal(error.into_internal()))?;
+        self.book.after_mutation();
+        Ok(())
+    }
+}
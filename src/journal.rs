@@ -0,0 +1,229 @@
+use std::io::{self, Write};
+
+use crate::{
+    codec::{ByteReader, ByteWriter},
+    command::{Command, CommandResult},
+    error::DecodeError,
+    orderbook::OrderBook,
+    types::OrderId,
+};
+
+// A command as it was written to the journal, numbered from 1 in the order applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalRecord {
+    pub sequence: u64,
+    pub command: Command,
+}
+
+// Write-ahead journaling: every command is appended to the writer, and flushed,
+// before it touches the book. Replaying the journal into the book it started from
+// reproduces the same state, rejected commands included.
+pub struct JournaledBook<W: Write> {
+    pub book: OrderBook,
+    writer: W,
+    next_sequence: u64,
+}
+
+impl<W: Write> JournaledBook<W> {
+    pub fn new(book: OrderBook, writer: W) -> Self {
+        Self::with_sequence(book, writer, 1)
+    }
+
+    // Continues an existing journal, ie. after recovering the book from it
+    pub fn with_sequence(book: OrderBook, writer: W, next_sequence: u64) -> Self {
+        Self {
+            book,
+            writer,
+            next_sequence,
+        }
+    }
+
+    // Nothing is applied if the record can't be written
+    pub fn apply(&mut self, command: &Command) -> io::Result<CommandResult> {
+        let record = JournalRecord {
+            sequence: self.next_sequence,
+            command: command.clone(),
+        };
+        self.writer.write_all(&record.to_bytes())?;
+        self.writer.flush()?;
+        self.next_sequence += 1;
+
+        Ok(self.book.apply_command(command))
+    }
+
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+
+    // Binary snapshot of the book tagged with the last applied sequence number,
+    // so recovery knows where in the journal to pick up
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut writer = ByteWriter::default();
+        writer.u64(self.next_sequence - 1);
+        writer.bytes.extend_from_slice(&self.book.to_bytes());
+        writer.bytes
+    }
+
+    pub fn into_parts(self) -> (OrderBook, W) {
+        (self.book, self.writer)
+    }
+}
+
+impl JournalRecord {
+    // Framed as payload length, payload, then a checksum of the payload
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut payload = ByteWriter::default();
+        payload.u64(self.sequence);
+        write_command(&mut payload, &self.command);
+
+        let mut record = ByteWriter::default();
+        record.len(payload.bytes.len());
+        record.bytes.extend_from_slice(&payload.bytes);
+        record.u32(checksum(&payload.bytes));
+        record.bytes
+    }
+
+    // Decodes the record at the front of `bytes`, returning it with the bytes it used
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+        let mut reader = ByteReader::new(bytes);
+        let len = reader.len()?;
+        let Some(payload) = reader.bytes.get(..len) else {
+            return Err(DecodeError::Truncated);
+        };
+        reader.bytes = &reader.bytes[len..];
+        if reader.u32()? != checksum(payload) {
+            return Err(DecodeError::Corrupt);
+        }
+
+        let mut payload = ByteReader::new(payload);
+        let sequence = payload.u64()?;
+        let command = read_command(&mut payload)?;
+        if !payload.is_empty() {
+            return Err(DecodeError::Corrupt);
+        }
+
+        let used = bytes.len() - reader.bytes.len();
+        Ok((Self { sequence, command }, used))
+    }
+}
+
+// Commands are tagged with a leading byte, also used for the commands queued in a
+// binary snapshot
+pub(crate) fn write_command(writer: &mut ByteWriter, command: &Command) {
+    match *command {
+        Command::Limit {
+            side,
+            order_id,
+            price,
+            quantity,
+        } => {
+            writer.u8(0);
+            writer.side(side);
+            writer.u64(order_id.0);
+            writer.i64(price);
+            writer.u64(quantity);
+        }
+        Command::Market { side, quantity } => {
+            writer.u8(1);
+            writer.side(side);
+            writer.u64(quantity);
+        }
+        Command::Cancel { order_id } => {
+            writer.u8(2);
+            writer.u64(order_id.0);
+        }
+        Command::Amend { order_id, quantity } => {
+            writer.u8(3);
+            writer.u64(order_id.0);
+            writer.u64(quantity);
+        }
+    }
+}
+
+pub(crate) fn read_command(reader: &mut ByteReader) -> Result<Command, DecodeError> {
+    Ok(match reader.u8()? {
+        0 => Command::Limit {
+            side: reader.side()?,
+            order_id: OrderId(reader.u64()?),
+            price: reader.i64()?,
+            quantity: reader.u64()?,
+        },
+        1 => Command::Market {
+            side: reader.side()?,
+            quantity: reader.u64()?,
+        },
+        2 => Command::Cancel {
+            order_id: OrderId(reader.u64()?),
+        },
+        3 => Command::Amend {
+            order_id: OrderId(reader.u64()?),
+            quantity: reader.u64()?,
+        },
+        _ => return Err(DecodeError::Corrupt),
+    })
+}
+
+#[derive(Debug)]
+pub struct Recovery {
+    pub book: OrderBook,
+    pub last_sequence: u64, // Last command applied, 0 if none ever were
+    pub valid_len: usize,   // Journal bytes read cleanly, truncate the file here before appending
+    pub tail_error: Option<DecodeError>, // Why the rest of the journal was discarded, if any was
+}
+
+// Restores a book from a `JournaledBook::snapshot` and replays the journal records
+// after it. Records the snapshot already covers are skipped. A torn, corrupt, or
+// out of sequence record ends the replay rather than failing it, since a crash
+// mid-write only ever damages the tail.
+pub fn recover(snapshot: &[u8], journal: &[u8]) -> Result<Recovery, DecodeError> {
+    let mut reader = ByteReader::new(snapshot);
+    let mut last_sequence = reader.u64()?;
+    let mut book = OrderBook::from_bytes(reader.bytes)?;
+
+    let mut valid_len = 0;
+    let mut tail_error = None;
+    while valid_len < journal.len() {
+        let (record, used) = match JournalRecord::from_bytes(&journal[valid_len..]) {
+            Ok(decoded) => decoded,
+            Err(error) => {
+                tail_error = Some(error);
+                break;
+            }
+        };
+
+        if record.sequence > last_sequence {
+            if record.sequence != last_sequence + 1 {
+                tail_error = Some(DecodeError::Corrupt);
+                break;
+            }
+            book.apply_command(&record.command);
+            last_sequence = record.sequence;
+        }
+        valid_len += used;
+    }
+
+    Ok(Recovery {
+        book,
+        last_sequence,
+        valid_len,
+        tail_error,
+    })
+}
+
+// Decodes a whole journal, failing on the first bad record
+pub fn read_journal(mut bytes: &[u8]) -> Result<Vec<JournalRecord>, DecodeError> {
+    let mut records = Vec::new();
+    while !bytes.is_empty() {
+        let (record, used) = JournalRecord::from_bytes(bytes)?;
+        records.push(record);
+        bytes = &bytes[used..];
+    }
+    Ok(records)
+}
+
+// FNV-1a, enough to catch torn or garbled writes
+fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    })
+}
@@ -0,0 +1,107 @@
+use std::time::{Duration, Instant};
+
+use hdrhistogram::Histogram;
+
+use crate::orderbook::OrderBook;
+
+// Longest latency tracked, anything slower is recorded as this
+const MAX_TRACKED: Duration = Duration::from_secs(60);
+
+// Significant figures kept per value, 3 keeps every percentile within 0.1%
+const SIGNIFICANT_FIGURES: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    LimitInsert, // Limit orders, including any matching before they rest
+    Cancel,
+    MarketMatch,
+}
+
+// Percentiles of one operation's latencies, in nanoseconds
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyReport {
+    pub count: u64,
+    pub min: u64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub p999: u64,
+    pub max: u64,
+}
+
+// Wall time of every accepted limit order, cancel, and market order, from the call to
+// the book settling, in HDR histograms. Rejections aren't recorded. Recording costs
+// two clock reads and a histogram increment per operation.
+#[derive(Debug, Clone)]
+pub struct LatencyHistograms {
+    limit_insert: Histogram<u64>,
+    cancel: Histogram<u64>,
+    market_match: Histogram<u64>,
+}
+
+impl Default for LatencyHistograms {
+    fn default() -> Self {
+        let histogram = || {
+            Histogram::new_with_bounds(1, MAX_TRACKED.as_nanos() as u64, SIGNIFICANT_FIGURES)
+                .expect("valid histogram bounds")
+        };
+        Self {
+            limit_insert: histogram(),
+            cancel: histogram(),
+            market_match: histogram(),
+        }
+    }
+}
+
+impl LatencyHistograms {
+    pub fn histogram(&self, operation: Operation) -> &Histogram<u64> {
+        match operation {
+            Operation::LimitInsert => &self.limit_insert,
+            Operation::Cancel => &self.cancel,
+            Operation::MarketMatch => &self.market_match,
+        }
+    }
+
+    pub fn report(&self, operation: Operation) -> LatencyReport {
+        let histogram = self.histogram(operation);
+        if histogram.is_empty() {
+            return LatencyReport::default();
+        }
+        LatencyReport {
+            count: histogram.len(),
+            min: histogram.min(),
+            p50: histogram.value_at_quantile(0.5),
+            p90: histogram.value_at_quantile(0.9),
+            p99: histogram.value_at_quantile(0.99),
+            p999: histogram.value_at_quantile(0.999),
+            max: histogram.max(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.limit_insert.reset();
+        self.cancel.reset();
+        self.market_match.reset();
+    }
+
+    pub(crate) fn record(&mut self, operation: Operation, started: Instant) {
+        let elapsed = started.elapsed().as_nanos().min(u64::MAX as u128) as u64;
+        let histogram = match operation {
+            Operation::LimitInsert => &mut self.limit_insert,
+            Operation::Cancel => &mut self.cancel,
+            Operation::MarketMatch => &mut self.market_match,
+        };
+        histogram.saturating_record(elapsed.max(1));
+    }
+}
+
+impl OrderBook {
+    pub fn latencies(&self) -> &LatencyHistograms {
+        &self.latencies
+    }
+
+    // Starts every histogram afresh, eg. after warming up
+    pub fn reset_latencies(&mut self) {
+        self.latencies.reset();
+    }
+}
@@ -1,4 +1,66 @@
-mod error;
+pub mod accounts;
+pub mod analytics;
+pub mod auction;
+pub mod backtest;
+pub mod binary_snapshot;
+pub mod book_side;
+#[cfg(feature = "candles")]
+pub mod candles;
+pub mod clock;
+mod codec;
+pub mod command;
+pub mod csv;
+pub mod dark_pool;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+pub mod depth_delta;
+pub mod engine;
+pub mod error;
+pub mod events;
+pub mod fees;
+#[cfg(feature = "cdylib")]
+pub mod ffi;
+pub mod fix;
+pub mod fuzz;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod hidden_orders;
+pub mod hooks;
+pub mod instrument;
+pub mod itch_encoder;
+#[cfg(feature = "itch")]
+pub mod itch_parser;
+pub mod journal;
+#[cfg(feature = "latency")]
+pub mod latency;
+pub mod listener;
+#[cfg(feature = "websocket")]
+pub mod market_data_server;
+pub mod mass_cancel;
+pub mod order;
+pub mod order_ids;
 pub mod orderbook;
+pub mod pegged_orders;
+#[cfg(feature = "publish")]
+pub mod publish;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod queue_ranks;
+pub mod repl;
+pub mod sbe;
+pub mod self_trade;
+#[cfg(feature = "tokio")]
+pub mod service;
+pub mod shared_snapshot;
+pub mod sim;
+mod spsc;
+pub mod stop_orders;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod tests;
+pub mod trade_stats;
+pub mod trading_state;
 pub mod types;
+pub mod validate;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
@@ -0,0 +1,55 @@
+use crate::types::{AuctionFill, Fill, OrderId, Price, Quantity, Side};
+
+// Push notifications for every change to the resting book. All callbacks default
+// to doing nothing, so implementors only override what they care about. Listeners
+// must be `Send` so the book can move to another thread along with its listener.
+pub trait OrderBookListener: Send {
+    // An order (or the unfilled remainder of one) started resting
+    fn on_order_added(
+        &mut self,
+        _order_id: OrderId,
+        _side: Side,
+        _price: Price,
+        _quantity: Quantity,
+    ) {
+    }
+
+    // A resting order was removed without trading, quantity is what was left
+    fn on_order_cancelled(
+        &mut self,
+        _order_id: OrderId,
+        _side: Side,
+        _price: Price,
+        _quantity: Quantity,
+    ) {
+    }
+
+    // A resting order's quantity was reduced in place, keeping its priority
+    fn on_order_reduced(
+        &mut self,
+        _order_id: OrderId,
+        _side: Side,
+        _price: Price,
+        _new_quantity: Quantity,
+    ) {
+    }
+
+    // An incoming order on `taker_side` traded against a resting order
+    fn on_trade(&mut self, _taker_side: Side, _fill: &Fill) {}
+
+    // Two resting orders matched when an auction uncrossed
+    fn on_auction_trade(&mut self, _fill: &AuctionFill) {}
+
+    fn on_level_added(&mut self, _side: Side, _price: Price) {}
+
+    fn on_level_removed(&mut self, _side: Side, _price: Price) {}
+
+    // The best bid or ask price or size changed, fired once per operation after the
+    // rest of its callbacks. Consumers that only track the BBO can implement just this.
+    fn on_top_of_book_changed(
+        &mut self,
+        _best_bid: Option<(Price, Quantity)>,
+        _best_ask: Option<(Price, Quantity)>,
+    ) {
+    }
+}
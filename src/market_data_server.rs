@@ -0,0 +1,188 @@
+use std::{
+    io,
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver},
+    },
+    thread,
+};
+
+use tungstenite::{Message, WebSocket};
+
+use crate::{
+    command::Command,
+    depth_delta::{DepthDelta, DepthDeltaTracker},
+    error::CommandError,
+    events::BookEvent,
+    orderbook::OrderBook,
+    types::{Sequence, Side},
+};
+
+// A minimal market data feed: a live book whose trades and L2 level changes are
+// pushed to every connected WebSocket client as JSON text messages,
+//
+//     {"type":"snapshot","bids":[[price,qty],...],"asks":[...],"sequence":n}
+//     {"type":"trade","sequence":n,"taker_side":"bid","price":p,"quantity":q,"trade_id":id}
+//     {"type":"delta","sequence":n,"side":"ask","price":p,"quantity":q}
+//
+// Each client gets a full snapshot when it's admitted, then every message after it.
+// Sequences are per-message and gapless, so a client that sees a gap has missed
+// something and should reconnect. A delta's quantity is the level's new aggregate,
+// zero once the level is gone.
+//
+// Clients are accepted and handshaken on a background thread, but messages are
+// written from the thread applying commands, so a client that stops reading will
+// eventually stall the book. Clients whose connection fails are dropped.
+#[derive(Debug)]
+pub struct MarketDataServer {
+    book: OrderBook,
+    tracker: DepthDeltaTracker,
+    sequence: Sequence, // Last message sequence sent
+    subscribers: Vec<WebSocket<TcpStream>>,
+    pending: Receiver<WebSocket<TcpStream>>, // Handshaken, waiting for their snapshot
+    local_addr: SocketAddr,
+    stopping: Arc<AtomicBool>,
+}
+
+impl MarketDataServer {
+    // Listens on `addr` (port 0 picks a free port, see `local_addr`) and publishes
+    // the given book
+    pub fn bind(book: OrderBook, addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let stopping = Arc::new(AtomicBool::new(false));
+        let (admit, pending) = mpsc::channel();
+
+        let stop = stopping.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::Acquire) {
+                    break;
+                }
+                // A failed handshake only loses that client
+                let Ok(socket) = stream
+                    .map_err(drop)
+                    .and_then(|stream| tungstenite::accept(stream).map_err(drop))
+                else {
+                    continue;
+                };
+                if admit.send(socket).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            tracker: DepthDeltaTracker::new(&book),
+            book,
+            sequence: 0,
+            subscribers: Vec::new(),
+            pending,
+            local_addr,
+            stopping,
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+
+    pub fn sequence(&self) -> Sequence {
+        self.sequence
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    // Sends a snapshot to every client that connected since the last call and starts
+    // publishing to them, returning how many joined. `apply` does this first too.
+    pub fn admit_subscribers(&mut self) -> usize {
+        let mut admitted = 0;
+        while let Ok(mut socket) = self.pending.try_recv() {
+            // The snapshot carries the last sequence sent, the next message follows it
+            let depth = self.book.depth_json(usize::MAX, self.sequence);
+            let snapshot = format!(r#"{{"type":"snapshot",{}"#, &depth[1..]);
+            if socket.send(Message::text(snapshot)).is_ok() {
+                self.subscribers.push(socket);
+                admitted += 1;
+            }
+        }
+        admitted
+    }
+
+    // Applies a command to the book and publishes its trades, then the level changes
+    // it left behind
+    pub fn apply(&mut self, command: Command) -> Result<Vec<BookEvent>, CommandError> {
+        self.admit_subscribers();
+        let events = self.book.apply(command)?;
+
+        let mut messages = Vec::new();
+        for event in &events {
+            if let BookEvent::Trade {
+                taker_side,
+                ref fill,
+            } = *event
+            {
+                self.sequence += 1;
+                messages.push(format!(
+                    r#"{{"type":"trade","sequence":{},"taker_side":"{}","price":{},"quantity":{},"trade_id":{}}}"#,
+                    self.sequence,
+                    side_name(taker_side),
+                    fill.price,
+                    fill.quantity,
+                    fill.trade_id.0
+                ));
+            }
+        }
+        for delta in self.tracker.update(&self.book, &events) {
+            let (side, price, quantity) = match delta {
+                DepthDelta::Inserted {
+                    side,
+                    price,
+                    quantity,
+                }
+                | DepthDelta::Updated {
+                    side,
+                    price,
+                    quantity,
+                } => (side, price, quantity),
+                DepthDelta::Deleted { side, price } => (side, price, 0),
+            };
+            self.sequence += 1;
+            messages.push(format!(
+                r#"{{"type":"delta","sequence":{},"side":"{}","price":{price},"quantity":{quantity}}}"#,
+                self.sequence,
+                side_name(side)
+            ));
+        }
+
+        self.subscribers.retain_mut(|socket| {
+            messages
+                .iter()
+                .all(|message| socket.send(Message::text(message.as_str())).is_ok())
+        });
+        Ok(events)
+    }
+}
+
+impl Drop for MarketDataServer {
+    // Wakes the accept thread with a throwaway connection so it sees it should stop
+    fn drop(&mut self) {
+        self.stopping.store(true, Ordering::Release);
+        let _ = TcpStream::connect(self.local_addr);
+    }
+}
+
+fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::Bid => "bid",
+        Side::Ask => "ask",
+    }
+}
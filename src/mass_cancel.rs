@@ -0,0 +1,158 @@
+use std::ops::RangeBounds;
+
+use crate::{
+    book_side::BookSide,
+    events::BookEvent,
+    orderbook::OrderBook,
+    types::{OrderDetails, Price, Side},
+};
+
+impl OrderBook {
+    // Cancels every order resting on one side within a price range, in a single pass over
+    // the affected levels. Returns the cancelled orders best price first, in time priority
+    // within each level, with any hidden orders after the visible ones. Cancels nothing
+    // while the book is halted.
+    pub fn cancel_range(
+        &mut self,
+        side: Side,
+        prices: impl RangeBounds<Price>,
+    ) -> Vec<OrderDetails> {
+        if !self.accepts_cancels() {
+            return Vec::new();
+        }
+        let levels = |levels: &BookSide| -> Vec<Price> {
+            let mut prices: Vec<_> = levels
+                .range((prices.start_bound(), prices.end_bound()))
+                .map(|(price, _)| price)
+                .collect();
+            if side == Side::Bid {
+                prices.reverse();
+            }
+            prices
+        };
+        let (visible, hidden) = match side {
+            Side::Bid => (levels(&self.bids), levels(&self.hidden_bids)),
+            Side::Ask => (levels(&self.asks), levels(&self.hidden_asks)),
+        };
+
+        let mut cancelled = Vec::new();
+        for price in visible {
+            self.remove_level(side, price, false, &mut cancelled);
+        }
+        for price in hidden {
+            self.remove_level(side, price, true, &mut cancelled);
+        }
+        self.after_mutation();
+
+        cancelled
+    }
+
+    // Empties the book, cancelling every resting order bids first, best price first, and
+    // hidden orders last.
+    // Untriggered stops and pegs are discarded too, while the trade id sequence, instrument,
+    // and listener are kept so the book can be reused. Returns the cancelled resting orders,
+    // none while the book is halted.
+    pub fn clear(&mut self) -> Vec<OrderDetails> {
+        if !self.accepts_cancels() {
+            return Vec::new();
+        }
+        let mut cancelled = Vec::with_capacity(self.index_map.len());
+
+        let bids: Vec<_> = self.bids().map(|(price, _)| price).collect();
+        for price in bids {
+            self.remove_level(Side::Bid, price, false, &mut cancelled);
+        }
+        let asks: Vec<_> = self.asks().map(|(price, _)| price).collect();
+        for price in asks {
+            self.remove_level(Side::Ask, price, false, &mut cancelled);
+        }
+        let hidden_bids: Vec<_> = self
+            .hidden_bids
+            .iter()
+            .rev()
+            .map(|(price, _)| price)
+            .collect();
+        for price in hidden_bids {
+            self.remove_level(Side::Bid, price, true, &mut cancelled);
+        }
+        let hidden_asks: Vec<_> = self.hidden_asks.iter().map(|(price, _)| price).collect();
+        for price in hidden_asks {
+            self.remove_level(Side::Ask, price, true, &mut cancelled);
+        }
+
+        // Nothing is left to expire, trigger, or re-price
+        self.orders.clear();
+        self.expiry_queue.clear();
+        self.day_orders.clear();
+        self.stop_orders.clear();
+        self.buy_stops.clear();
+        self.sell_stops.clear();
+        self.pegged_orders.clear();
+        self.peg_references = (None, None);
+        self.after_mutation();
+
+        cancelled
+    }
+
+    // Drops a whole price level along with every order queued at it,
+    // appending their details to `cancelled`. Hidden levels go without any events.
+    pub(crate) fn remove_level(
+        &mut self,
+        side: Side,
+        price: Price,
+        hidden: bool,
+        cancelled: &mut Vec<OrderDetails>,
+    ) {
+        let level = match (side, hidden) {
+            (Side::Bid, false) => self.bids.remove(&price),
+            (Side::Ask, false) => self.asks.remove(&price),
+            (Side::Bid, true) => self.hidden_bids.remove(&price),
+            (Side::Ask, true) => self.hidden_asks.remove(&price),
+        };
+        let Some(level) = level else {
+            return;
+        };
+        self.queue_ranks.remove_level(side, hidden, price);
+
+        let mut next = Some(level.head);
+        let mut queue_position = 0;
+        while let Some(index) = next
+            && let Some(node) = self.orders.try_remove(index)
+        {
+            self.page_versions.touch(index);
+            let entry = self.index_map.remove(&node.order_id);
+            if let Some(owner) = entry.as_ref().and_then(|entry| entry.owner) {
+                self.owner_orders.remove(owner, node.order_id);
+            }
+            self.pegged_orders.remove(&node.order_id);
+
+            if !hidden {
+                self.events.emit(BookEvent::OrderCancelled {
+                    order_id: node.order_id,
+                    side,
+                    price,
+                    quantity: node.quantity,
+                    client_order_id: node.client_order_id,
+                });
+            }
+            cancelled.push(OrderDetails {
+                order_id: node.order_id,
+                side,
+                price,
+                quantity: node.quantity,
+                queue_position, // As it stood before the level was removed
+                timestamp: entry.map_or(0, |entry| entry.timestamp),
+                client_order_id: node.client_order_id,
+            });
+            queue_position += 1;
+            next = node.next;
+        }
+
+        let totals = self.side_totals_mut(side, hidden);
+        totals.quantity -= level.total_quantity;
+        totals.order_count -= level.order_count;
+        if !hidden {
+            self.events.emit(BookEvent::LevelRemoved { side, price });
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    error::{InternalError, LimitOrderError},
+    order_ids::{Arrival, OrderIdClaim},
+    orderbook::OrderBook,
+    self_trade::SelfTradePrevention,
+    types::{ClientOrderId, LimitOrderResult, OrderId, OwnerId, Price, Quantity, Side, Timestamp},
+};
+
+// How long an order stays working once it reaches the book
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeInForce {
+    #[default]
+    GoodTillCancel,
+    Day,                     // Rests until cancelled or `cancel_day_orders` ends the session
+    ImmediateOrCancel,       // Matches what it can on arrival, the rest is cancelled
+    FillOrKill,              // Fills in full on arrival or is rejected
+    GoodTillDate(Timestamp), // Rests until cancelled or `expire_orders` passes this time
+}
+
+// A limit order with every attribute the book understands, submitted with `submit_order`.
+// Build one with `Order::builder`, so new attributes can be added without breaking callers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Order {
+    pub side: Side,
+    pub order_id: OrderId,
+    pub price: Price,
+    pub quantity: Quantity,
+    pub time_in_force: TimeInForce,
+    pub post_only: bool,        // Rejected if it would take liquidity
+    pub min_quantity: Quantity, // Rejected unless at least this much fills on arrival
+    pub owner: Option<(OwnerId, SelfTradePrevention)>,
+    pub hidden: bool, // Rests out of market data, see `hidden_orders`
+    pub client_order_id: Option<ClientOrderId>, // Echoed back while the order rests
+}
+
+impl Order {
+    pub fn builder(
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+    ) -> OrderBuilder {
+        OrderBuilder(Order {
+            side,
+            order_id,
+            price,
+            quantity,
+            time_in_force: TimeInForce::GoodTillCancel,
+            post_only: false,
+            min_quantity: 0,
+            owner: None,
+            hidden: false,
+            client_order_id: None,
+        })
+    }
+}
+
+// Starts from a plain good-till-cancel limit order
+#[derive(Debug, Clone)]
+pub struct OrderBuilder(Order);
+
+impl OrderBuilder {
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.0.time_in_force = time_in_force;
+        self
+    }
+
+    pub fn post_only(mut self) -> Self {
+        self.0.post_only = true;
+        self
+    }
+
+    pub fn min_quantity(mut self, min_quantity: Quantity) -> Self {
+        self.0.min_quantity = min_quantity;
+        self
+    }
+
+    pub fn owner(mut self, owner: OwnerId, policy: SelfTradePrevention) -> Self {
+        self.0.owner = Some((owner, policy));
+        self
+    }
+
+    pub fn hidden(mut self) -> Self {
+        self.0.hidden = true;
+        self
+    }
+
+    pub fn client_order_id(mut self, client_order_id: ClientOrderId) -> Self {
+        self.0.client_order_id = Some(client_order_id);
+        self
+    }
+
+    pub fn build(self) -> Order {
+        self.0
+    }
+}
+
+impl OrderBook {
+    // Places an order with all of its attributes applied together, e.g. an owned
+    // good-till-date order with a minimum quantity. The positional `execute_*_order`
+    // methods remain as shortcuts for the common cases.
+    pub fn submit_order(&mut self, order: &Order) -> Result<LimitOrderResult, LimitOrderError> {
+        let Order {
+            side,
+            order_id,
+            price,
+            quantity,
+            time_in_force,
+            post_only,
+            min_quantity,
+            owner,
+            hidden,
+            client_order_id,
+        } = *order;
+
+        let immediate = matches!(
+            time_in_force,
+            TimeInForce::ImmediateOrCancel | TimeInForce::FillOrKill
+        );
+        let required = match time_in_force {
+            TimeInForce::FillOrKill => quantity,
+            _ => min_quantity.min(quantity),
+        };
+        let arrival = Arrival {
+            immediate,
+            post_only,
+            hidden,
+            required,
+            owner,
+        };
+        let order_id = match self.claim_order_id(order_id, side, price, quantity, arrival)? {
+            OrderIdClaim::New(order_id) => order_id,
+            OrderIdClaim::Amended(result) => return Ok(result),
+        };
+        if self.order_id_exists(order_id) {
+            return Err(LimitOrderError::OrderIdAlreadyExists);
+        }
+        self.check_limit_order(price, quantity)?;
+        if !self.accepts_orders() {
+            return Err(LimitOrderError::TradingHalted);
+        }
+
+        // Orders that only live on arrival need the book to be matching
+        if immediate && !self.matches_orders() {
+            return Err(LimitOrderError::TradingHalted);
+        }
+        if post_only && self.would_cross(side, price) {
+            return Err(LimitOrderError::PostOnlyWouldCross);
+        }
+        // Trades self-trade prevention would cancel instead don't count
+        if required > 0
+            && self.walk_resting(side.opposite(), quantity, Some(price), owner, None)? < required
+        {
+            return Err(LimitOrderError::InsufficientLiquidity);
+        }
+
+        let mut fills = Vec::new();
+        self.incoming_client_order_id = client_order_id;
+        let placed = if immediate {
+            self.match_order(side, quantity, Some(price), owner, |fill| fills.push(fill))
+                .map(|_| LimitOrderResult {
+                    order_id,
+                    ..Default::default()
+                })
+                .map_err(|error| error.into_internal().into())
+        } else if hidden {
+            self.place_hidden_order_with(side, order_id, price, quantity, owner, |fill| {
+                fills.push(fill)
+            })
+        } else {
+            self.place_limit_order_with(side, order_id, price, quantity, owner, |fill| {
+                fills.push(fill)
+            })
+        };
+        // Only taken if the order rests
+        self.incoming_client_order_id = None;
+        let result = placed?;
+
+        // Only a resting remainder needs its time in force kept
+        if result.resting_quantity > 0 {
+            match time_in_force {
+                TimeInForce::GoodTillDate(expires_at) => self.track_expiry(order_id, expires_at)?,
+                TimeInForce::Day => self.track_day_order(order_id)?,
+                _ => {}
+            }
+        }
+
+        let sequence = self.after_mutation();
+        Ok(LimitOrderResult {
+            fills,
+            sequence,
+            ..result
+        })
+    }
+
+    // Ends the session for DAY orders, cancelling every one still resting in the order
+    // they were placed, whatever the trading state. Returns the cancelled Ids.
+    pub fn cancel_day_orders(&mut self) -> Vec<OrderId> {
+        let mut cancelled = Vec::new();
+        for order_id in std::mem::take(&mut self.day_orders) {
+            // Tracked lazily, so skip orders that were already filled, cancelled, or
+            // replaced by a new order reusing the same Id
+            let still_resting = self
+                .index_map
+                .get(&order_id)
+                .is_some_and(|entry| entry.time_in_force == TimeInForce::Day);
+            if still_resting && self.withdraw_order(order_id).is_ok() {
+                cancelled.push(order_id);
+            }
+        }
+        cancelled
+    }
+
+    pub(crate) fn track_day_order(&mut self, order_id: OrderId) -> Result<(), LimitOrderError> {
+        let Some(entry) = self.index_map.get_mut(&order_id) else {
+            return Err(InternalError::MissingIndexEntry { order_id }.into());
+        };
+        self.page_versions.touch(entry.order_index);
+        entry.time_in_force = TimeInForce::Day;
+        self.day_orders.push(order_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    error::{AmendOrderError, InternalError, LimitOrderError},
+    orderbook::OrderBook,
+    self_trade::SelfTradePrevention,
+    types::{LimitOrderResult, OrderId, OwnerId, Price, Quantity, Side},
+};
+
+// Where new orders get their Ids from
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OrderIdMode {
+    #[default]
+    Caller, // Each order brings its own Id, see `DuplicateOrderIdPolicy` for Ids in use
+    // The book numbers limit orders itself, ignoring the Id they're given, and reports
+    // the Id in `LimitOrderResult::order_id`. Stop orders and market remainders still
+    // rest under the caller's Id.
+    Assigned,
+}
+
+// What a limit order arriving under an Id that's already in use does, when callers
+// bring their own Ids. Only limit orders follow it, stop orders and market remainders
+// are always rejected.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DuplicateOrderIdPolicy {
+    #[default]
+    Reject,
+    // Cancels the order using the Id, resting or a stop, then places the new one under
+    // it. The old order is only pulled once the new one is known to be accepted, so a
+    // rejected replacement leaves it in place.
+    CancelReplace,
+    // Changes the resting order's quantity to the new order's, keeping its queue
+    // position when decreasing and everything else about it. A new order at another
+    // price or on the other side, one that can't rest, or one duplicating a stop
+    // cancel-replaces instead.
+    Amend,
+}
+
+// What a new order needs from the book on arrival, checked before a cancel-replace
+// pulls the order it replaces
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Arrival {
+    pub immediate: bool, // Never rests, so the book has to be matching
+    pub post_only: bool,
+    pub hidden: bool,
+    pub required: Quantity, // Has to fill on arrival, as for fill-or-kill or a minimum quantity
+    pub owner: Option<(OwnerId, SelfTradePrevention)>,
+}
+
+// The Id a new order goes ahead under, or the result of it amending the order it duplicated
+pub(crate) enum OrderIdClaim {
+    New(OrderId),
+    Amended(LimitOrderResult),
+}
+
+impl OrderBook {
+    // The Id a new order is placed under. Assigned Ids count up from `next_order_id`,
+    // skipping any still in use, so they can't clash with Ids the caller chose or with
+    // orders restored from a snapshot. Ids of orders that are then rejected aren't reused.
+    pub(crate) fn new_order_id(&mut self, requested: OrderId) -> OrderId {
+        if self.order_id_mode == OrderIdMode::Caller {
+            return requested;
+        }
+        loop {
+            let order_id = self.next_order_id;
+            self.next_order_id.0 += 1;
+            if !self.order_id_exists(order_id) {
+                return order_id;
+            }
+        }
+    }
+
+    // Resolves the Id for a new limit order, applying the duplicate policy to an Id in
+    // use. Duplicates the policy rejects come back as `New`, for placement to reject.
+    pub(crate) fn claim_order_id(
+        &mut self,
+        requested: OrderId,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        arrival: Arrival,
+    ) -> Result<OrderIdClaim, LimitOrderError> {
+        let order_id = self.new_order_id(requested);
+        if self.duplicate_order_id_policy == DuplicateOrderIdPolicy::Reject
+            || !self.order_id_exists(order_id)
+        {
+            return Ok(OrderIdClaim::New(order_id));
+        }
+        self.check_limit_order(price, quantity)?;
+        if !self.accepts_orders() {
+            return Err(LimitOrderError::TradingHalted);
+        }
+
+        let amends = self.duplicate_order_id_policy == DuplicateOrderIdPolicy::Amend
+            && !arrival.immediate
+            && self
+                .index_map
+                .get(&order_id)
+                .is_some_and(|entry| entry.side == side && entry.price == price);
+        if amends {
+            self.amend_quantity(order_id, quantity)
+                .map_err(amend_rejection)?;
+            let entry = self
+                .index_map
+                .get(&order_id)
+                .ok_or(InternalError::MissingIndexEntry { order_id })?;
+            return Ok(OrderIdClaim::Amended(LimitOrderResult {
+                order_id,
+                fills: Vec::new(),
+                resting_quantity: quantity,
+                order_index: Some(entry.order_index),
+                queue_position: self.queue_position(order_id),
+                sequence: self.command_sequence(),
+            }));
+        }
+
+        self.check_replacement(order_id, side, price, quantity, arrival)?;
+        // Pulled quietly, the new order's placement settles the book for both
+        if self.index_map.contains_key(&order_id) {
+            self.remove_resting_order(order_id)
+                .map_err(|error| error.into_internal())?;
+            self.pegged_orders.remove(&order_id);
+        } else {
+            self.cancel_stop_order(order_id)
+                .map_err(|error| error.into_internal())?;
+        }
+        Ok(OrderIdClaim::New(order_id))
+    }
+
+    // Every way the replacement could still be turned away once the order it replaces is
+    // gone, judged as if it were, so that order is only pulled for one that goes ahead
+    fn check_replacement(
+        &self,
+        replaced: OrderId,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        arrival: Arrival,
+    ) -> Result<(), LimitOrderError> {
+        if arrival.immediate && !self.matches_orders() {
+            return Err(LimitOrderError::TradingHalted);
+        }
+        let opposite = side.opposite();
+        if arrival.post_only
+            && self.walk_resting(opposite, 1, Some(price), None, Some(replaced))? > 0
+        {
+            return Err(LimitOrderError::PostOnlyWouldCross);
+        }
+        if arrival.required > 0
+            && self.walk_resting(
+                opposite,
+                quantity,
+                Some(price),
+                arrival.owner,
+                Some(replaced),
+            )? < arrival.required
+        {
+            return Err(LimitOrderError::InsufficientLiquidity);
+        }
+        // Whatever rests is at most the full quantity
+        if !arrival.immediate
+            && self
+                .side_totals(side, arrival.hidden)
+                .quantity
+                .checked_add(quantity)
+                .is_none()
+        {
+            return Err(LimitOrderError::QuantityOverflow);
+        }
+        Ok(())
+    }
+}
+
+// The order was validated as a new limit order first, so only the increase can fail
+fn amend_rejection(error: AmendOrderError) -> LimitOrderError {
+    match error {
+        AmendOrderError::QuantityOverflow => LimitOrderError::QuantityOverflow,
+        AmendOrderError::Instrument(error) => error.into(),
+        AmendOrderError::Internal(error) => error.into(),
+        _ => InternalError::UnexpectedRejection.into(),
+    }
+}
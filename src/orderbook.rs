@@ -1,36 +1,141 @@
-use std::collections::BTreeMap;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap},
+    ops::Bound::{Excluded, Unbounded},
+    sync::Arc,
+};
 
 use hashbrown::HashMap;
 use slab::Slab;
 
 use crate::{
-    error::{CancelOrderError, LimitOrderError, MarketOrderError},
-    types::{Fill, OrderId, Price, Quantity, Side},
+    accounts::Accounts,
+    auction::IndicativePrice,
+    book_side::BookSide,
+    clock::Clock,
+    command::Command,
+    error::{
+        AmendOrderError, CancelOrderError, InstrumentError, InternalError, LimitOrderError,
+        MarketOrderError,
+    },
+    events::{BookEvent, EventSink, SequencedEvent},
+    fees::{FeeSchedule, OwnerVolumes},
+    hooks::{IncomingOrder, MatchHooks, MatchedTrade},
+    instrument::Instrument,
+    listener::OrderBookListener,
+    order::{Order, TimeInForce},
+    order_ids::{Arrival, DuplicateOrderIdPolicy, OrderIdClaim, OrderIdMode},
+    pegged_orders::PeggedOrder,
+    queue_ranks::QueueRanks,
+    self_trade::{OwnerIndex, SelfTradePrevention},
+    shared_snapshot::PageVersions,
+    stop_orders::StopOrder,
+    trading_state::{HaltPolicy, TradingState},
+    types::{
+        ClientOrderId, Depth, DepthLevel, Fill, Level3, Level3Snapshot, LimitOrderResult,
+        MarketOrderResult, MarketRemainder, OpenOrder, OrderDetails, OrderId, OwnerId, Price,
+        Quantity, Sequence, Side, SideTotals, Timestamp, TradeId, VolumeAhead,
+    },
 };
 
+#[cfg(feature = "latency")]
+use std::time::Instant;
+
+#[cfg(feature = "latency")]
+use crate::latency::{LatencyHistograms, Operation};
+#[cfg(feature = "publish")]
+use crate::publish::DepthPublisher;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrderNode {
     pub quantity: Quantity,
     pub order_id: OrderId,
     pub previous: Option<usize>,
     pub next: Option<usize>,
+    pub client_order_id: Option<ClientOrderId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PriceLevel {
     pub head: usize,
     pub tail: usize,
     pub order_count: usize,
+    pub total_quantity: Quantity, // Sum of every order's remaining quantity at this level
+}
+
+// Walks the orders at a single price level head-to-tail, ie. in time priority
+pub struct LevelOrders<'a> {
+    orders: &'a Slab<OrderNode>,
+    next: Option<usize>,
 }
 
-type BookSideType = BTreeMap<Price, PriceLevel>;
+impl Iterator for LevelOrders<'_> {
+    type Item = (OrderId, Quantity);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.orders.get(self.next?)?;
+        self.next = node.next;
+        Some((node.order_id, node.quantity))
+    }
+}
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrderBook {
-    pub bids: BookSideType,
-    pub asks: BookSideType,
+    pub bids: BookSide,
+    pub asks: BookSide,
     pub orders: Slab<OrderNode>, // General Storage for order nodes
     pub index_map: HashMap<OrderId, IndexMapEntry>, // Reverse lookup Order Id, for fast cancels
+    pub next_trade_id: TradeId,  // Assigned to the next fill, then incremented
+    pub order_id_mode: OrderIdMode,
+    pub duplicate_order_id_policy: DuplicateOrderIdPolicy, // For Ids in use in `OrderIdMode::Caller`
+    pub next_order_id: OrderId, // Tried for the next order in `OrderIdMode::Assigned`
+    pub expiry_queue: BinaryHeap<Reverse<(Timestamp, OrderId)>>, // Earliest GTD deadline first
+    pub day_orders: Vec<OrderId>, // DAY orders in the order they were placed, pruned lazily
+    pub last_trade_price: Option<Price>,
+    pub stop_orders: HashMap<OrderId, StopOrder>, // Untriggered stops, kept off the book
+    pub buy_stops: BTreeMap<Price, Vec<OrderId>>, // Keyed by stop price, FIFO per price
+    pub sell_stops: BTreeMap<Price, Vec<OrderId>>, // Keyed by stop price, FIFO per price
+    pub pegged_orders: BTreeMap<OrderId, PeggedOrder>, // Resting orders that track a reference price
+    pub peg_references: (Option<Price>, Option<Price>), // Best bid & ask the pegs were last priced from
+    pub cached_best_bid: Option<(Price, Quantity)>, // Top-of-book, refreshed after every mutation
+    pub cached_best_ask: Option<(Price, Quantity)>,
+    pub cached_indicative_price: Option<IndicativePrice>, // Refreshed after every mutation in an auction
+    pub command_sequence: Sequence, // Last sequence number given to an accepted command
+    pub bid_totals: SideTotals,     // Resting quantity & order count, kept in step with the levels
+    pub ask_totals: SideTotals,
+    pub hidden_bids: BookSide, // Resting orders kept out of market data, see `hidden_orders`
+    pub hidden_asks: BookSide,
+    pub hidden_bid_totals: SideTotals,
+    pub hidden_ask_totals: SideTotals,
+    pub owner_orders: OwnerIndex, // Resting orders per owner, for mass cancels
+    pub queue_ranks: QueueRanks,  // Arrival slots per level, for queue positions
+    #[cfg_attr(feature = "serde", serde(skip))] // Restored books start over with new stamps
+    pub page_versions: PageVersions, // When each page of `orders` last changed, for snapshots
+    pub events: EventSink,        // Listener & recorded events for every change to the resting book
+    pub instrument: Option<Instrument>, // Trading rules every incoming order is checked against
+    pub reject_non_positive_prices: bool, // For markets where zero or negative prices are a bug
+    pub fee_schedule: Option<FeeSchedule>, // Annotates every fill with maker and taker fees
+    pub owner_volumes: OwnerVolumes, // Rolling traded volume per owner, for fee tiers
+    pub accounts: Option<Accounts>, // Positions and balances per owner, updated from fills
+    pub trading_state: TradingState,
+    pub halt_policy: HaltPolicy,
+    pub queued_commands: Vec<Command>, // Held by `HaltPolicy::Queue` until the book reopens
+    #[cfg_attr(feature = "serde", serde(skip))] // Hooks are attached again after restoring
+    pub match_hooks: MatchHooks,
+    // Given to the next order to rest, set only while `submit_order` or an amend places one
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) incoming_client_order_id: Option<ClientOrderId>,
+    #[cfg_attr(feature = "serde", serde(skip))] // Clocks are attached again after restoring
+    pub clock: Option<Arc<dyn Clock>>, // Timestamps resting orders and fills, 0 without one
+    #[cfg(feature = "publish")]
+    #[cfg_attr(feature = "serde", serde(skip))] // Readers subscribe again after restoring
+    pub depth_publisher: Option<DepthPublisher>, // Lock-free views for market data readers
+    #[cfg(feature = "latency")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub latencies: LatencyHistograms,
 }
 
 impl Default for OrderBook {
@@ -39,11 +144,17 @@ impl Default for OrderBook {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IndexMapEntry {
     pub order_index: usize,
     pub price: Price,
     pub side: Side,
+    pub expires_at: Option<Timestamp>,
+    pub time_in_force: TimeInForce, // Only ever GoodTillCancel, Day, or GoodTillDate once resting
+    pub owner: Option<OwnerId>,     // Checked by self-trade prevention
+    pub timestamp: Timestamp,       // When the order started resting at its current priority
+    pub hidden: bool,               // Rests in the hidden levels rather than the visible ones
 }
 
 impl OrderBook {
@@ -53,35 +164,853 @@ impl OrderBook {
             asks: Default::default(),
             orders: Default::default(),
             index_map: Default::default(),
+            next_trade_id: TradeId(1),
+            order_id_mode: OrderIdMode::Caller,
+            duplicate_order_id_policy: DuplicateOrderIdPolicy::Reject,
+            next_order_id: OrderId(1),
+            expiry_queue: Default::default(),
+            day_orders: Vec::new(),
+            last_trade_price: None,
+            stop_orders: Default::default(),
+            buy_stops: Default::default(),
+            sell_stops: Default::default(),
+            pegged_orders: Default::default(),
+            peg_references: (None, None),
+            cached_best_bid: None,
+            cached_best_ask: None,
+            cached_indicative_price: None,
+            command_sequence: 0,
+            bid_totals: Default::default(),
+            ask_totals: Default::default(),
+            hidden_bids: Default::default(),
+            hidden_asks: Default::default(),
+            hidden_bid_totals: Default::default(),
+            hidden_ask_totals: Default::default(),
+            owner_orders: Default::default(),
+            queue_ranks: Default::default(),
+            page_versions: Default::default(),
+            events: Default::default(),
+            instrument: None,
+            reject_non_positive_prices: false,
+            fee_schedule: None,
+            owner_volumes: Default::default(),
+            accounts: None,
+            trading_state: TradingState::Open,
+            halt_policy: HaltPolicy::Reject,
+            queued_commands: Vec::new(),
+            match_hooks: Default::default(),
+            incoming_client_order_id: None,
+            clock: None,
+            #[cfg(feature = "publish")]
+            depth_publisher: None,
+            #[cfg(feature = "latency")]
+            latencies: Default::default(),
+        }
+    }
+
+    // Pre-sizes order storage and the Id lookup so the first `orders` inserts never
+    // regrow or rehash. Price levels live in a BTreeMap, which has nothing to pre-size.
+    pub fn with_capacity(orders: usize) -> Self {
+        Self {
+            orders: Slab::with_capacity(orders),
+            index_map: HashMap::with_capacity(orders),
+            ..Self::new()
+        }
+    }
+
+    // Makes room for at least `additional` more resting orders
+    pub fn reserve(&mut self, additional: usize) {
+        self.orders.reserve(additional);
+        self.index_map.reserve(additional);
+    }
+
+    pub fn with_listener(listener: impl OrderBookListener + 'static) -> Self {
+        let mut book = Self::new();
+        book.set_listener(listener);
+        book
+    }
+
+    pub fn set_listener(&mut self, listener: impl OrderBookListener + 'static) {
+        self.events.set_listener(listener);
+    }
+
+    // While enabled, every BookEvent is buffered until taken by the caller
+    pub fn record_events(&mut self, enabled: bool) {
+        self.events.set_recording(enabled);
+    }
+
+    // Events recorded since the last call, in the order they happened
+    pub fn take_events(&mut self) -> Vec<BookEvent> {
+        let mut events = Vec::new();
+        self.events.drain_into(&mut events);
+        events
+    }
+
+    // Same as `take_events`, with each event's sequence number
+    pub fn take_sequenced_events(&mut self) -> Vec<SequencedEvent> {
+        let mut events = Vec::new();
+        self.events.drain_sequenced_into(&mut events);
+        events
+    }
+
+    // Appends recorded events to a caller-provided buffer, so it can be reused
+    pub fn drain_events_into(&mut self, buffer: &mut Vec<BookEvent>) {
+        self.events.drain_into(buffer);
+    }
+
+    pub fn best_bid(&self) -> Option<(Price, Quantity)> {
+        self.cached_best_bid
+    }
+
+    pub fn best_ask(&self) -> Option<(Price, Quantity)> {
+        self.cached_best_ask
+    }
+
+    // None unless both sides of the book have liquidity
+    pub fn spread(&self) -> Option<Price> {
+        let ((bid, _), (ask, _)) = (self.best_bid()?, self.best_ask()?);
+        ask.checked_sub(bid)
+    }
+
+    // Not rounded to a whole price, so an odd spread gives a half-price midpoint
+    pub fn mid_price(&self) -> Option<f64> {
+        let ((bid, _), (ask, _)) = (self.best_bid()?, self.best_ask()?);
+        Some((bid as f64 + ask as f64) / 2.0)
+    }
+
+    // Dumps every resting order, grouped by level in priority order and FIFO within a level
+    pub fn level3_snapshot(&self) -> Level3Snapshot {
+        let orders_at = |side: Side, price: Price| Level3 {
+            price,
+            orders: self.orders_at(side, price).collect(),
+        };
+
+        Level3Snapshot {
+            bids: self
+                .bids()
+                .map(|(price, _)| orders_at(Side::Bid, price))
+                .collect(),
+            asks: self
+                .asks()
+                .map(|(price, _)| orders_at(Side::Ask, price))
+                .collect(),
+            sequence: self.event_sequence(),
+        }
+    }
+
+    // Rebuilds a book with the exact priority queues of a level 3 snapshot
+    pub fn from_level3_snapshot(snapshot: &Level3Snapshot) -> Result<Self, LimitOrderError> {
+        let mut book = Self::new();
+
+        for (side, levels) in [(Side::Bid, &snapshot.bids), (Side::Ask, &snapshot.asks)] {
+            for level in levels {
+                for &(order_id, quantity) in &level.orders {
+                    book.place_limit_order(side, order_id, level.price, quantity)?;
+                }
+            }
+        }
+
+        // Carry on numbering events from where the snapshot left off
+        book.after_mutation();
+        book.events.set_sequence(snapshot.sequence);
+        Ok(book)
+    }
+
+    // Number of orders ahead of a resting order at its price level, which for a hidden
+    // order includes every visible one at its price. Counted from the order's arrival slot
+    // in `queue_ranks`, so it costs O(log n) in the level's depth rather than a walk.
+    pub fn queue_position(&self, order_id: OrderId) -> Option<usize> {
+        let entry = self.index_map.get(&order_id)?;
+        let visible_ahead = match entry.hidden {
+            true => self
+                .level(entry.side, entry.price, false)
+                .map_or(0, |level| level.order_count),
+            false => 0,
+        };
+        let ahead = self
+            .queue_ranks
+            .position(entry.side, entry.hidden, entry.price, order_id)?;
+        Some(visible_ahead + ahead)
+    }
+
+    // Quantity that has to trade before a resting order can fill, hidden orders
+    // included. Walks towards the nearer end of the level, as quantities ahead change with
+    // every partial fill and reduction.
+    pub fn volume_ahead(&self, order_id: OrderId) -> Option<VolumeAhead> {
+        let entry = self.index_map.get(&order_id)?;
+        let node = self.orders.get(entry.order_index)?;
+        let level = self.level(entry.side, entry.price, entry.hidden)?;
+        let better = |hidden: bool| -> Quantity {
+            let levels = self.levels(entry.side, hidden);
+            match entry.side {
+                Side::Bid => levels
+                    .range((Excluded(entry.price), Unbounded))
+                    .map(|(_, level)| level.total_quantity)
+                    .sum(),
+                Side::Ask => levels
+                    .range(..entry.price)
+                    .map(|(_, level)| level.total_quantity)
+                    .sum(),
+            }
+        };
+        let better_prices = better(false) + better(true);
+        let visible_ahead = match entry.hidden {
+            true => self
+                .level(entry.side, entry.price, false)
+                .map_or(0, |level| level.total_quantity),
+            false => 0,
+        };
+
+        let (mut ahead, mut behind) = (node.previous, node.next);
+        let (mut ahead_quantity, mut behind_quantity) = (0, 0);
+        let at_price = loop {
+            let Some(ahead_index) = ahead else {
+                break ahead_quantity;
+            };
+            let Some(behind_index) = behind else {
+                break level.total_quantity - node.quantity - behind_quantity;
+            };
+            let (ahead_node, behind_node) = (
+                self.orders.get(ahead_index)?,
+                self.orders.get(behind_index)?,
+            );
+            ahead_quantity += ahead_node.quantity;
+            behind_quantity += behind_node.quantity;
+            ahead = ahead_node.previous;
+            behind = behind_node.next;
+        };
+
+        Some(VolumeAhead {
+            at_price: visible_ahead + at_price,
+            better_prices,
+        })
+    }
+
+    // Read-only view of a resting order, None if it isn't on the book
+    pub fn get_order(&self, order_id: OrderId) -> Option<OrderDetails> {
+        let entry = self.index_map.get(&order_id)?;
+        let node = self.orders.get(entry.order_index)?;
+
+        Some(OrderDetails {
+            order_id,
+            side: entry.side,
+            price: entry.price,
+            quantity: node.quantity,
+            queue_position: self.queue_position(order_id)?,
+            timestamp: entry.timestamp,
+            client_order_id: node.client_order_id,
+        })
+    }
+
+    // Bid levels in priority order, highest price first
+    pub fn bids(&self) -> impl DoubleEndedIterator<Item = (Price, &PriceLevel)> {
+        self.bids.iter().rev()
+    }
+
+    // Ask levels in priority order, lowest price first
+    pub fn asks(&self) -> impl DoubleEndedIterator<Item = (Price, &PriceLevel)> {
+        self.asks.iter()
+    }
+
+    // Orders resting at a price level in FIFO order, empty if the level doesn't exist
+    pub fn orders_at(&self, side: Side, price: Price) -> LevelOrders<'_> {
+        let level = match side {
+            Side::Bid => self.bids.get(&price),
+            Side::Ask => self.asks.get(&price),
+        };
+
+        LevelOrders {
+            orders: &self.orders,
+            next: level.map(|level| level.head),
+        }
+    }
+
+    // Every resting order across the book, for reconciling against an order management
+    // system: bids then asks, best price first and in time priority within a level, with
+    // hidden orders after the visible ones on their side. A hidden order's queue position
+    // counts the visible orders at its price, as with `queue_position`.
+    pub fn open_orders(&self) -> impl Iterator<Item = OpenOrder> + '_ {
+        let side_orders = move |side: Side, hidden: bool| {
+            let book = match (side, hidden) {
+                (Side::Bid, false) => &self.bids,
+                (Side::Ask, false) => &self.asks,
+                (Side::Bid, true) => &self.hidden_bids,
+                (Side::Ask, true) => &self.hidden_asks,
+            };
+            // Best price first, which is the top of the bids but the bottom of the asks
+            let (ascending, descending) = match side {
+                Side::Bid => (None, Some(book.iter().rev())),
+                Side::Ask => (Some(book.iter()), None),
+            };
+            let levels = ascending.into_iter().flatten();
+            levels
+                .chain(descending.into_iter().flatten())
+                .flat_map(move |(price, level)| {
+                    let visible_ahead = match hidden {
+                        true => self
+                            .level(side, price, false)
+                            .map_or(0, |level| level.order_count),
+                        false => 0,
+                    };
+                    let orders = LevelOrders {
+                        orders: &self.orders,
+                        next: Some(level.head),
+                    };
+                    orders
+                        .enumerate()
+                        .map(move |(position, (order_id, quantity))| {
+                            (order_id, side, price, quantity, visible_ahead + position)
+                        })
+                })
+        };
+
+        side_orders(Side::Bid, false)
+            .chain(side_orders(Side::Bid, true))
+            .chain(side_orders(Side::Ask, false))
+            .chain(side_orders(Side::Ask, true))
+    }
+
+    // Snapshot of the top `levels` price levels on each side, best price first
+    pub fn depth(&self, levels: usize) -> Depth {
+        let summarize = |(price, level): (Price, &PriceLevel)| -> DepthLevel {
+            (price, level.total_quantity, level.order_count)
+        };
+
+        Depth {
+            bids: self.bids().take(levels).map(summarize).collect(),
+            asks: self.asks().take(levels).map(summarize).collect(),
+        }
+    }
+
+    // Top `levels` of depth in the `{"bids": [[price, qty], ...], "asks": [...], "sequence": n}`
+    // shape most crypto venues publish. The sequence number is the caller's, ie. the
+    // journal sequence or a feed counter, so consumers can line it up with updates.
+    pub fn depth_json(&self, levels: usize, sequence: u64) -> String {
+        let side = |levels: &[DepthLevel]| {
+            let entries: Vec<_> = levels
+                .iter()
+                .map(|(price, quantity, _)| format!("[{price},{quantity}]"))
+                .collect();
+            entries.join(",")
+        };
+
+        let depth = self.depth(levels);
+        format!(
+            r#"{{"bids":[{}],"asks":[{}],"sequence":{sequence}}}"#,
+            side(&depth.bids),
+            side(&depth.asks)
+        )
+    }
+
+    // Keeps derived state in sync, called at the end of every public mutation.
+    // Returns the sequence number given to the accepted mutation.
+    pub(crate) fn after_mutation(&mut self) -> Sequence {
+        self.trigger_elected_stops();
+        self.reprice_pegged_orders();
+        self.refresh_top_of_book();
+        self.refresh_indicative_price();
+        #[cfg(feature = "debug-invariants")]
+        self.assert_invariants();
+
+        self.command_sequence += 1;
+        #[cfg(feature = "publish")]
+        self.publish_view();
+        self.run_after_trades();
+        self.command_sequence
+    }
+
+    // Sequence number of the last accepted command
+    pub fn command_sequence(&self) -> Sequence {
+        self.command_sequence
+    }
+
+    // Sequence number of the last emitted event
+    pub fn event_sequence(&self) -> Sequence {
+        self.events.sequence()
+    }
+
+    pub(crate) fn refresh_top_of_book(&mut self) {
+        let best_bid =
+            Self::next_bid(&self.bids).map(|(price, level)| (price, level.total_quantity));
+        let best_ask =
+            Self::next_ask(&self.asks).map(|(price, level)| (price, level.total_quantity));
+
+        if (best_bid, best_ask) != (self.cached_best_bid, self.cached_best_ask) {
+            self.cached_best_bid = best_bid;
+            self.cached_best_ask = best_ask;
+            self.events.top_of_book_changed(best_bid, best_ask);
+        }
+    }
+
+    pub(crate) fn levels(&self, side: Side, hidden: bool) -> &BookSide {
+        match (side, hidden) {
+            (Side::Bid, false) => &self.bids,
+            (Side::Ask, false) => &self.asks,
+            (Side::Bid, true) => &self.hidden_bids,
+            (Side::Ask, true) => &self.hidden_asks,
+        }
+    }
+
+    pub(crate) fn levels_mut(&mut self, side: Side, hidden: bool) -> &mut BookSide {
+        match (side, hidden) {
+            (Side::Bid, false) => &mut self.bids,
+            (Side::Ask, false) => &mut self.asks,
+            (Side::Bid, true) => &mut self.hidden_bids,
+            (Side::Ask, true) => &mut self.hidden_asks,
+        }
+    }
+
+    fn level(&self, side: Side, price: Price, hidden: bool) -> Option<&PriceLevel> {
+        self.levels(side, hidden).get(&price)
+    }
+
+    fn level_mut(&mut self, side: Side, price: Price, hidden: bool) -> Option<&mut PriceLevel> {
+        match (side, hidden) {
+            (Side::Bid, false) => self.bids.get_mut(&price),
+            (Side::Ask, false) => self.asks.get_mut(&price),
+            (Side::Bid, true) => self.hidden_bids.get_mut(&price),
+            (Side::Ask, true) => self.hidden_asks.get_mut(&price),
+        }
+    }
+
+    // Checks a resting order's node and price level are in place, before anything is
+    // changed, so reducing or removing it can't fail part way through
+    fn check_resting(&self, entry: &IndexMapEntry) -> Result<(), InternalError> {
+        if !self.orders.contains(entry.order_index) {
+            return Err(InternalError::MissingNode {
+                index: entry.order_index,
+            });
+        }
+        if self.level(entry.side, entry.price, entry.hidden).is_none() {
+            return Err(InternalError::MissingLevel {
+                side: entry.side,
+                price: entry.price,
+            });
+        }
+        Ok(())
+    }
+
+    // Every way resting `quantity` at `price` could fail. Orders that match first check
+    // this up front for their full quantity, so they never trade and then fail to rest.
+    pub(crate) fn check_rest(
+        &self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        hidden: bool,
+    ) -> Result<(), LimitOrderError> {
+        // Every level total is bounded by its side's total, so this covers both
+        if self
+            .side_totals(side, hidden)
+            .quantity
+            .checked_add(quantity)
+            .is_none()
+        {
+            return Err(LimitOrderError::QuantityOverflow);
+        }
+        // Array backed sides only hold prices on their tick grid
+        if !self.levels(side, hidden).accepts(price) {
+            return Err(InstrumentError::PriceOutOfRange.into());
         }
+        self.check_level_tail(side, price, hidden)?;
+        Ok(())
+    }
+
+    // A new order joins the back of an existing level, which needs the level's tail
+    pub(crate) fn check_level_tail(
+        &self,
+        side: Side,
+        price: Price,
+        hidden: bool,
+    ) -> Result<(), InternalError> {
+        match self.level(side, price, hidden) {
+            Some(level) if !self.orders.contains(level.tail) => {
+                Err(InternalError::DanglingLevelTail { side, price })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Checks that pulling a resting order and placing it again with `quantity` at the
+    // back of its level can't fail half way, leaving the order pulled but not re-placed
+    fn check_requeue(
+        &self,
+        entry: &IndexMapEntry,
+        previous: Option<usize>,
+        quantity: Quantity,
+    ) -> Result<(), InternalError> {
+        let (side, price) = (entry.side, entry.price);
+        if let Some(level) = self.level(side, price, entry.hidden)
+            && level.order_count > 1
+        {
+            // Pulling the order leaves the one ahead of it as the tail
+            let tail = if level.tail == entry.order_index {
+                previous
+            } else {
+                Some(level.tail)
+            };
+            if !tail.is_some_and(|tail| self.orders.contains(tail)) {
+                return Err(InternalError::DanglingLevelTail { side, price });
+            }
+        }
+        if self.matches_orders() {
+            self.walk_resting(side.opposite(), quantity, Some(price), None, None)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn side_totals_mut(&mut self, side: Side, hidden: bool) -> &mut SideTotals {
+        match (side, hidden) {
+            (Side::Bid, false) => &mut self.bid_totals,
+            (Side::Ask, false) => &mut self.ask_totals,
+            (Side::Bid, true) => &mut self.hidden_bid_totals,
+            (Side::Ask, true) => &mut self.hidden_ask_totals,
+        }
+    }
+
+    pub(crate) fn side_totals(&self, side: Side, hidden: bool) -> SideTotals {
+        match (side, hidden) {
+            (Side::Bid, false) => self.bid_totals,
+            (Side::Ask, false) => self.ask_totals,
+            (Side::Bid, true) => self.hidden_bid_totals,
+            (Side::Ask, true) => self.hidden_ask_totals,
+        }
+    }
+
+    // Total visible quantity resting on one side of the book
+    pub fn side_volume(&self, side: Side) -> Quantity {
+        match side {
+            Side::Bid => self.bid_totals.quantity,
+            Side::Ask => self.ask_totals.quantity,
+        }
+    }
+
+    // Number of visible orders resting on one side of the book
+    pub fn side_order_count(&self, side: Side) -> usize {
+        match side {
+            Side::Bid => self.bid_totals.order_count,
+            Side::Ask => self.ask_totals.order_count,
+        }
+    }
+
+    // Rebuilds order storage without the free slots left behind by cancels and fills,
+    // releasing their memory. Each level is copied head to tail so queue priority is
+    // unchanged, only slab indices are remapped.
+    pub fn compact(&mut self) {
+        let old_orders = std::mem::take(&mut self.orders);
+        let mut orders = Slab::with_capacity(old_orders.len());
+
+        let levels = (self.bids.values_mut().chain(self.asks.values_mut()))
+            .chain(self.hidden_bids.values_mut())
+            .chain(self.hidden_asks.values_mut());
+        for level in levels {
+            let mut previous = None;
+            let mut next = Some(level.head);
+
+            while let Some(old_index) = next
+                && let Some(node) = old_orders.get(old_index)
+            {
+                let index = orders.insert(OrderNode {
+                    previous,
+                    next: None,
+                    ..node.clone()
+                });
+
+                // Link the copy behind the previously copied order
+                match previous.and_then(|previous| orders.get_mut(previous)) {
+                    Some(previous) => previous.next = Some(index),
+                    None => level.head = index,
+                }
+                if let Some(entry) = self.index_map.get_mut(&node.order_id) {
+                    entry.order_index = index;
+                }
+
+                previous = Some(index);
+                next = node.next;
+            }
+
+            if let Some(tail) = previous {
+                level.tail = tail;
+            }
+        }
+
+        self.orders = orders;
+        self.page_versions.touch_all();
+        self.index_map.shrink_to_fit();
+        self.stop_orders.shrink_to_fit();
+        #[cfg(feature = "debug-invariants")]
+        self.assert_invariants();
+    }
+
+    // Ids are unique across resting orders and untriggered stops
+    pub(crate) fn order_id_exists(&self, order_id: OrderId) -> bool {
+        self.index_map.contains_key(&order_id) || self.stop_orders.contains_key(&order_id)
     }
 
     pub fn cancel_order(&mut self, order_id: OrderId) -> Result<(), CancelOrderError> {
+        #[cfg(feature = "latency")]
+        let started = Instant::now();
+        if !self.accepts_cancels() {
+            return Err(CancelOrderError::TradingHalted);
+        }
+        self.withdraw_order(order_id)?;
+        #[cfg(feature = "latency")]
+        self.latencies.record(Operation::Cancel, started);
+        Ok(())
+    }
+
+    // Cancels whatever the trading state, for housekeeping such as expiring orders
+    pub(crate) fn withdraw_order(&mut self, order_id: OrderId) -> Result<(), CancelOrderError> {
+        // Untriggered stops aren't on the book
+        if self.index_map.contains_key(&order_id) {
+            self.remove_resting_order(order_id)?;
+            self.pegged_orders.remove(&order_id);
+        } else {
+            self.cancel_stop_order(order_id)?;
+        }
+        self.after_mutation();
+        Ok(())
+    }
+
+    // Reduces a resting order by `quantity` without touching its queue position,
+    // fully cancelling it once nothing remains. Returns the remaining quantity.
+    pub fn cancel_quantity(
+        &mut self,
+        order_id: OrderId,
+        quantity: Quantity,
+    ) -> Result<Quantity, CancelOrderError> {
+        if !self.accepts_cancels() {
+            return Err(CancelOrderError::TradingHalted);
+        }
+        let Some(entry) = self.index_map.get(&order_id).cloned() else {
+            return Err(CancelOrderError::OrderIdNotFound);
+        };
+        self.check_resting(&entry)?;
+        self.page_versions.touch(entry.order_index);
+        let Some(node) = self.orders.get_mut(entry.order_index) else {
+            return Err(InternalError::MissingNode {
+                index: entry.order_index,
+            }
+            .into());
+        };
+
+        if quantity < node.quantity {
+            node.quantity -= quantity;
+            let (remaining, client_order_id) = (node.quantity, node.client_order_id);
+
+            let Some(level) = self.level_mut(entry.side, entry.price, entry.hidden) else {
+                return Err(InternalError::MissingLevel {
+                    side: entry.side,
+                    price: entry.price,
+                }
+                .into());
+            };
+            level.total_quantity -= quantity;
+            self.side_totals_mut(entry.side, entry.hidden).quantity -= quantity;
+
+            if !entry.hidden {
+                self.events.emit(BookEvent::OrderReduced {
+                    order_id,
+                    side: entry.side,
+                    price: entry.price,
+                    new_quantity: remaining,
+                    client_order_id,
+                });
+            }
+
+            self.after_mutation();
+            return Ok(remaining);
+        }
+
+        self.cancel_order(order_id)?;
+        Ok(0)
+    }
+
+    // Trades a specific resting order, as when following executions reported by an
+    // external feed rather than matching here. `price` defaults to the order's own price.
+    // Like any other trade, it needs the book to be matching.
+    pub fn execute_resting_order(
+        &mut self,
+        order_id: OrderId,
+        quantity: Quantity,
+        price: Option<Price>,
+    ) -> Result<Fill, CancelOrderError> {
+        if !self.matches_orders() {
+            return Err(CancelOrderError::TradingHalted);
+        }
+        if quantity == 0 {
+            return Err(CancelOrderError::ZeroQuantity);
+        }
+        let Some(entry) = self.index_map.get(&order_id).cloned() else {
+            return Err(CancelOrderError::OrderIdNotFound);
+        };
+        self.check_resting(&entry)?;
+        let Some(node) = self.orders.get(entry.order_index) else {
+            return Err(InternalError::MissingNode {
+                index: entry.order_index,
+            }
+            .into());
+        };
+
+        let quantity = quantity.min(node.quantity);
+
+        let price = price.unwrap_or(entry.price);
+        let taker_side = entry.side.opposite();
+        let mut fill = Fill {
+            price,
+            quantity,
+            maker_order_id: order_id,
+            maker_client_order_id: node.client_order_id,
+            maker_remaining_quantity: node.quantity - quantity,
+            maker_fully_filled: quantity == node.quantity,
+            trade_id: self.next_trade_id,
+            timestamp: self.now(),
+            fees: None,
+        };
+        if let Some(schedule) = &self.fee_schedule {
+            fill.fees =
+                Some(
+                    self.owner_volumes
+                        .charge(schedule, taker_side, &fill, entry.owner, None),
+                );
+        }
+        if let Some(accounts) = &mut self.accounts {
+            accounts.record(taker_side, &fill, entry.owner, None);
+        }
+        self.next_trade_id.0 += 1;
+        self.last_trade_price = Some(fill.price);
+        self.events.emit(BookEvent::Trade {
+            taker_side,
+            fill: fill.clone(),
+        });
+
+        self.take_resting_quantity(order_id, quantity)?;
+
+        self.after_mutation();
+        Ok(fill)
+    }
+
+    // Takes traded quantity off a resting order, after its trade event was emitted.
+    // Fully executed orders leave the book, as they would when matched here.
+    pub(crate) fn take_resting_quantity(
+        &mut self,
+        order_id: OrderId,
+        quantity: Quantity,
+    ) -> Result<(), CancelOrderError> {
+        let Some(entry) = self.index_map.get(&order_id).cloned() else {
+            return Err(CancelOrderError::OrderIdNotFound);
+        };
+        self.check_resting(&entry)?;
+        self.page_versions.touch(entry.order_index);
+        let Some(node) = self.orders.get_mut(entry.order_index) else {
+            return Err(InternalError::MissingNode {
+                index: entry.order_index,
+            }
+            .into());
+        };
+
+        if quantity < node.quantity {
+            node.quantity -= quantity;
+            let Some(level) = self.level_mut(entry.side, entry.price, entry.hidden) else {
+                return Err(InternalError::MissingLevel {
+                    side: entry.side,
+                    price: entry.price,
+                }
+                .into());
+            };
+            level.total_quantity -= quantity;
+            self.side_totals_mut(entry.side, entry.hidden).quantity -= quantity;
+            return Ok(());
+        }
+
+        let (_, _, level_removed) = self.unlink_resting_order(order_id)?;
+        self.pegged_orders.remove(&order_id);
+        if level_removed && !entry.hidden {
+            self.events.emit(BookEvent::LevelRemoved {
+                side: entry.side,
+                price: entry.price,
+            });
+        }
+        Ok(())
+    }
+
+    // Unlinks a resting order from its price level and frees its node
+    pub(crate) fn remove_resting_order(
+        &mut self,
+        order_id: OrderId,
+    ) -> Result<(), CancelOrderError> {
+        let (entry, node, level_removed) = self.unlink_resting_order(order_id)?;
+        if entry.hidden {
+            return Ok(());
+        }
+
+        self.events.emit(BookEvent::OrderCancelled {
+            order_id,
+            side: entry.side,
+            price: entry.price,
+            quantity: node.quantity,
+            client_order_id: node.client_order_id,
+        });
+        if level_removed {
+            self.events.emit(BookEvent::LevelRemoved {
+                side: entry.side,
+                price: entry.price,
+            });
+        }
+
+        Ok(())
+    }
+
+    // Removes an order from the book without emitting any events. Returns its index entry,
+    // its node as it was left, and whether its price level was removed with it.
+    fn unlink_resting_order(
+        &mut self,
+        order_id: OrderId,
+    ) -> Result<(IndexMapEntry, OrderNode, bool), CancelOrderError> {
         // Lookup if order exists
-        let Some(entry) = self.index_map.remove(&order_id) else {
+        let Some(entry) = self.index_map.get(&order_id).cloned() else {
             return Err(CancelOrderError::OrderIdNotFound);
         };
-        let price_level_map = match entry.side {
-            Side::Bid => &mut self.bids,
-            Side::Ask => &mut self.asks,
+        self.check_resting(&entry)?;
+        self.index_map.remove(&order_id);
+        if let Some(owner) = entry.owner {
+            self.owner_orders.remove(owner, order_id);
+        }
+        self.queue_ranks
+            .remove(entry.side, entry.hidden, entry.price, order_id);
+        let price_level_map = match (entry.side, entry.hidden) {
+            (Side::Bid, false) => &mut self.bids,
+            (Side::Ask, false) => &mut self.asks,
+            (Side::Bid, true) => &mut self.hidden_bids,
+            (Side::Ask, true) => &mut self.hidden_asks,
         };
 
         // Find the price level
         let Some(price_level) = price_level_map.get_mut(&entry.price) else {
-            return Err(CancelOrderError::InternalError);
+            return Err(InternalError::MissingLevel {
+                side: entry.side,
+                price: entry.price,
+            }
+            .into());
         };
         let node_index = entry.order_index;
 
         // Store some local data to get around borrow checker
-        let Some((prev_index, next_index)) = self
+        let Some((prev_index, next_index, quantity)) = self
             .orders
             .get(node_index)
-            .map(|node| (node.previous, node.next))
+            .map(|node| (node.previous, node.next, node.quantity))
         else {
-            return Err(CancelOrderError::InternalError);
+            return Err(InternalError::MissingNode { index: node_index }.into());
         };
 
         // Update node indices
+        for index in [Some(node_index), prev_index, next_index]
+            .into_iter()
+            .flatten()
+        {
+            self.page_versions.touch(index);
+        }
         if let Some(prev_node) = prev_index.and_then(|prev| self.orders.get_mut(prev)) {
             prev_node.next = next_index;
         } else {
@@ -96,177 +1025,907 @@ impl OrderBook {
 
         // Update meta-level things
         price_level.order_count -= 1;
+        price_level.total_quantity = price_level.total_quantity.saturating_sub(quantity);
 
         // Cleanup removed levels & order
-        if price_level.order_count == 0 {
+        let level_removed = price_level.order_count == 0;
+        if level_removed {
             price_level_map.remove(&entry.price);
         }
 
-        self.orders.remove(node_index);
+        let node = self.orders.remove(node_index);
 
+        let totals = self.side_totals_mut(entry.side, entry.hidden);
+        totals.quantity = totals.quantity.saturating_sub(quantity);
+        totals.order_count -= 1;
+
+        Ok((entry, node, level_removed))
+    }
+
+    // Changes a resting order's quantity. Decreasing keeps the order's queue position,
+    // increasing moves it to the back of its price level.
+    pub fn amend_quantity(
+        &mut self,
+        order_id: OrderId,
+        new_quantity: Quantity,
+    ) -> Result<(), AmendOrderError> {
+        if !self.accepts_orders() {
+            return Err(AmendOrderError::TradingHalted);
+        }
+        if new_quantity == 0 {
+            return Err(AmendOrderError::ZeroQuantity);
+        }
+        self.check_order(None, new_quantity)?;
+
+        let Some(entry) = self.index_map.get(&order_id).cloned() else {
+            return Err(AmendOrderError::OrderIdNotFound);
+        };
+        self.check_resting(&entry)?;
+        self.page_versions.touch(entry.order_index);
+        let Some(node) = self.orders.get_mut(entry.order_index) else {
+            return Err(InternalError::MissingNode {
+                index: entry.order_index,
+            }
+            .into());
+        };
+        let (current_quantity, previous, client_order_id) =
+            (node.quantity, node.previous, node.client_order_id);
+
+        if new_quantity <= current_quantity {
+            let reduction = node.quantity - new_quantity;
+            node.quantity = new_quantity;
+
+            let Some(level) = self.level_mut(entry.side, entry.price, entry.hidden) else {
+                return Err(InternalError::MissingLevel {
+                    side: entry.side,
+                    price: entry.price,
+                }
+                .into());
+            };
+            level.total_quantity -= reduction;
+            self.side_totals_mut(entry.side, entry.hidden).quantity -= reduction;
+
+            if !entry.hidden {
+                self.events.emit(BookEvent::OrderReduced {
+                    order_id,
+                    side: entry.side,
+                    price: entry.price,
+                    new_quantity,
+                    client_order_id,
+                });
+            }
+
+            self.after_mutation();
+            return Ok(());
+        }
+
+        // Check before pulling the order, so a rejected increase leaves it in place
+        if self
+            .side_totals(entry.side, entry.hidden)
+            .quantity
+            .checked_add(new_quantity - current_quantity)
+            .is_none()
+        {
+            return Err(AmendOrderError::QuantityOverflow);
+        }
+        self.check_requeue(&entry, previous, new_quantity)?;
+
+        // Loses priority, re-queue at the tail of the same level
+        self.remove_resting_order(order_id)
+            .map_err(|error| error.into_internal())?;
+        self.incoming_client_order_id = client_order_id;
+        let rested = if entry.hidden {
+            // The book can't cross a hidden order at its own price, so it only needs to rest
+            self.rest_order(entry.side, order_id, entry.price, new_quantity, true)
+                .map(drop)
+        } else {
+            self.place_limit_order(entry.side, order_id, entry.price, new_quantity)
+                .map(drop)
+        };
+        self.incoming_client_order_id = None;
+        rested.map_err(|error| error.into_internal())?;
+
+        // Carry over any deadline, time in force, and owner from the original order
+        let Some(new_entry) = self.index_map.get_mut(&order_id) else {
+            return Err(InternalError::MissingIndexEntry { order_id }.into());
+        };
+        self.page_versions.touch(new_entry.order_index);
+        new_entry.expires_at = entry.expires_at;
+        new_entry.time_in_force = entry.time_in_force;
+        if let Some(owner) = entry.owner {
+            self.track_owner(order_id, owner);
+        }
+
+        self.after_mutation();
         Ok(())
     }
 
-    fn next_bid(bids: &BookSideType) -> Option<(Price, PriceLevel)> {
-        bids.last_key_value().map(|(k, v)| (*k, v.clone()))
+    fn next_bid(bids: &BookSide) -> Option<(Price, &PriceLevel)> {
+        bids.last()
     }
 
-    fn next_ask(asks: &BookSideType) -> Option<(Price, PriceLevel)> {
-        asks.first_key_value().map(|(k, v)| (*k, v.clone()))
+    fn next_ask(asks: &BookSide) -> Option<(Price, &PriceLevel)> {
+        asks.first()
     }
 
-    fn next_bid_mut(bids: &mut BookSideType) -> Option<&mut PriceLevel> {
-        bids.values_mut().last()
+    fn next_bid_mut(bids: &mut BookSide) -> Option<(Price, &mut PriceLevel)> {
+        bids.last_mut()
     }
 
-    fn next_ask_mut(asks: &mut BookSideType) -> Option<&mut PriceLevel> {
-        asks.values_mut().next()
+    fn next_ask_mut(asks: &mut BookSide) -> Option<(Price, &mut PriceLevel)> {
+        asks.first_mut()
     }
 
+    // Any quantity the book couldn't fill is reported back as `remaining`
     pub fn execute_market_order(
         &mut self,
         side: Side,
-        mut quantity: Quantity,
-    ) -> Result<Vec<Fill>, MarketOrderError> {
-        struct MarketOrderHelper<'a> {
-            book: &'a mut BookSideType,
-            next_fn: fn(&BookSideType) -> Option<(Price, PriceLevel)>,
-            next_mut_fn: fn(&mut BookSideType) -> Option<&mut PriceLevel>,
-        }
-
-        let MarketOrderHelper {
-            book,
-            next_fn,
-            next_mut_fn,
-        } = match side {
-            Side::Bid => {
-                let book = &mut self.asks;
-                MarketOrderHelper {
-                    book,
-                    next_fn: Self::next_ask,
-                    next_mut_fn: Self::next_ask_mut,
-                }
+        quantity: Quantity,
+    ) -> Result<MarketOrderResult, MarketOrderError> {
+        self.execute_market_order_with_remainder(side, quantity, MarketRemainder::Return)
+    }
+
+    // Same as `execute_market_order`, but appends fills to a caller owned buffer so it
+    // can be reused between orders. Returns the quantity left unfilled.
+    pub fn execute_market_order_into(
+        &mut self,
+        side: Side,
+        quantity: Quantity,
+        fills: &mut Vec<Fill>,
+    ) -> Result<Quantity, MarketOrderError> {
+        self.execute_market_order_with(side, quantity, |fill| fills.push(fill))
+    }
+
+    // Same as `execute_market_order`, but hands each fill to `on_fill` as it happens
+    // instead of collecting them. Returns the quantity left unfilled.
+    pub fn execute_market_order_with(
+        &mut self,
+        side: Side,
+        quantity: Quantity,
+        on_fill: impl FnMut(Fill),
+    ) -> Result<Quantity, MarketOrderError> {
+        #[cfg(feature = "latency")]
+        let started = Instant::now();
+        self.check_order(None, quantity)?;
+
+        let remaining = self.match_order(side, quantity, None, None, on_fill)?;
+        self.after_mutation();
+        #[cfg(feature = "latency")]
+        self.latencies.record(Operation::MarketMatch, started);
+        Ok(remaining)
+    }
+
+    // Market order with price protection: stops matching rather than trade at a level
+    // worse than `worst_price`, reporting what's left as `remaining`.
+    pub fn execute_market_order_with_limit(
+        &mut self,
+        side: Side,
+        quantity: Quantity,
+        worst_price: Price,
+    ) -> Result<MarketOrderResult, MarketOrderError> {
+        self.check_order(None, quantity)?;
+
+        let mut fills = Vec::new();
+        let remaining = self.match_order(side, quantity, Some(worst_price), None, |fill| {
+            fills.push(fill)
+        })?;
+        let sequence = self.after_mutation();
+        Ok(MarketOrderResult {
+            remaining,
+            sequence,
+            ..MarketOrderResult::from_fills(fills)
+        })
+    }
+
+    // Market order whose unfilled quantity is handled as `remainder` says. Resting it
+    // needs a trade to price it from, without one the remainder is returned instead.
+    pub fn execute_market_order_with_remainder(
+        &mut self,
+        side: Side,
+        quantity: Quantity,
+        remainder: MarketRemainder,
+    ) -> Result<MarketOrderResult, MarketOrderError> {
+        #[cfg(feature = "latency")]
+        let started = Instant::now();
+        if let MarketRemainder::RestAtLastTrade(order_id) = remainder
+            && self.order_id_exists(order_id)
+        {
+            return Err(MarketOrderError::OrderIdAlreadyExists);
+        }
+        self.check_order(None, quantity)?;
+
+        let mut fills = Vec::new();
+        let unfilled = self.match_order(side, quantity, None, None, |fill| fills.push(fill))?;
+
+        let mut result = MarketOrderResult::from_fills(fills);
+        match (remainder, self.last_trade_price) {
+            _ if unfilled == 0 => {}
+            (MarketRemainder::Discard, _) => {}
+            (MarketRemainder::RestAtLastTrade(order_id), Some(price)) => {
+                let rested = self
+                    .place_limit_order(side, order_id, price, unfilled)
+                    .map_err(|error| error.into_internal())?;
+                result.resting_quantity = rested.resting_quantity;
             }
-            Side::Ask => {
-                let book = &mut self.bids;
-                MarketOrderHelper {
-                    book,
-                    next_fn: Self::next_bid,
-                    next_mut_fn: Self::next_bid_mut,
-                }
+            (MarketRemainder::Return | MarketRemainder::RestAtLastTrade(_), _) => {
+                result.remaining = unfilled;
             }
-        };
+        }
 
-        let mut fills = Vec::new();
+        result.sequence = self.after_mutation();
+        #[cfg(feature = "latency")]
+        self.latencies.record(Operation::MarketMatch, started);
+        Ok(result)
+    }
+
+    // Matches an incoming order against the opposite side of the book in price-time priority.
+    // Stops once quantity is exhausted, the book is depleted, or the next level is worse
+    // than the limit price (if any). Resting orders from the same owner are never traded,
+    // the policy decides which side gets cancelled instead. Returns the unfilled quantity.
+    pub(crate) fn match_order(
+        &mut self,
+        side: Side,
+        mut quantity: Quantity,
+        limit_price: Option<Price>,
+        owner: Option<(OwnerId, SelfTradePrevention)>,
+        mut on_fill: impl FnMut(Fill),
+    ) -> Result<Quantity, MarketOrderError> {
+        if !self.matches_orders() {
+            return Err(MarketOrderError::TradingHalted);
+        }
+        self.walk_resting(side.opposite(), quantity, limit_price, owner, None)?;
+        if self.match_hooks.is_set() {
+            self.run_before_match(IncomingOrder {
+                side,
+                quantity,
+                limit_price,
+                owner: owner.map(|(owner, _)| owner),
+            });
+        }
+
+        // Every fill from one incoming order shares a timestamp
+        let now = self.now();
+
+        struct MarketOrderHelper<'a> {
+            book: &'a mut BookSide,
+            totals: &'a mut SideTotals,
+            next_mut_fn: fn(&mut BookSide) -> Option<(Price, &mut PriceLevel)>,
+        }
 
         while quantity > 0 {
-            let Some((price, mut top_level)) = next_fn(book) else {
+            // Hidden orders only go first at a better price, at the same price they wait
+            // behind every visible order
+            let hidden = match side {
+                Side::Bid => Self::next_ask(&self.hidden_asks).is_some_and(|(hidden, _)| {
+                    Self::next_ask(&self.asks).is_none_or(|(visible, _)| hidden < visible)
+                }),
+                Side::Ask => Self::next_bid(&self.hidden_bids).is_some_and(|(hidden, _)| {
+                    Self::next_bid(&self.bids).is_none_or(|(visible, _)| hidden > visible)
+                }),
+            };
+            let MarketOrderHelper {
+                book,
+                totals,
+                next_mut_fn,
+            } = match (side, hidden) {
+                (Side::Bid, false) => MarketOrderHelper {
+                    book: &mut self.asks,
+                    totals: &mut self.ask_totals,
+                    next_mut_fn: Self::next_ask_mut,
+                },
+                (Side::Ask, false) => MarketOrderHelper {
+                    book: &mut self.bids,
+                    totals: &mut self.bid_totals,
+                    next_mut_fn: Self::next_bid_mut,
+                },
+                (Side::Bid, true) => MarketOrderHelper {
+                    book: &mut self.hidden_asks,
+                    totals: &mut self.hidden_ask_totals,
+                    next_mut_fn: Self::next_ask_mut,
+                },
+                (Side::Ask, true) => MarketOrderHelper {
+                    book: &mut self.hidden_bids,
+                    totals: &mut self.hidden_bid_totals,
+                    next_mut_fn: Self::next_bid_mut,
+                },
+            };
+
+            // Work on the best level in place, the side is only touched again once it empties
+            let Some((price, level)) = next_mut_fn(book) else {
                 break; // No more levels left in book
             };
 
-            while let Some(node) = self.orders.get(top_level.head).cloned() {
-                // This order will be fully consumed
-                if quantity >= node.quantity {
-                    fills.push(Fill {
+            // Stop once the best opposing price no longer crosses the limit
+            if let Some(limit) = limit_price {
+                let crosses = match side {
+                    Side::Bid => price <= limit,
+                    Side::Ask => price >= limit,
+                };
+                if !crosses {
+                    break;
+                }
+            }
+
+            while quantity > 0 {
+                self.page_versions.touch(level.head);
+                let Some(node) = self.orders.get_mut(level.head) else {
+                    return Err(InternalError::DanglingLevelHead {
+                        side: side.opposite(),
                         price,
-                        quantity: node.quantity,
-                    });
-                    quantity -= node.quantity;
-
-                    // Remove the resting order from id lookup
-                    self.index_map.remove(&node.order_id);
-
-                    // Remove the node from memory
-                    self.orders.remove(top_level.head);
-
-                    // Remove the resting order from the price level
-                    if let Some(next) = node.next {
-                        // We need to update the pointer to the "next" order
-                        let Some(top_level_ref) = next_mut_fn(book) else {
-                            return Err(MarketOrderError::InternalError);
-                        };
-                        if let Some(next_order) = self.orders.get_mut(next) {
-                            next_order.previous = None;
-                        }
-                        top_level.head = next;
-                        top_level.order_count -= 1;
-
-                        // Sync the local and stored values.
-                        *top_level_ref = top_level.clone();
-                    } else {
-                        // No orders remain, just delete this level entirely
-                        book.remove(&price);
-                        break;
                     }
+                    .into());
+                };
+
+                let self_trade = owner.filter(|(owner, _)| {
+                    self.index_map
+                        .get(&node.order_id)
+                        .is_some_and(|entry| entry.owner == Some(*owner))
+                });
+
+                // Quantity taken off the resting order, by a trade or by self-trade prevention
+                let traded = if let Some((_, policy)) = self_trade {
+                    let reduction = match policy {
+                        SelfTradePrevention::CancelNewest => 0,
+                        SelfTradePrevention::CancelOldest => node.quantity,
+                        SelfTradePrevention::CancelBoth => node.quantity,
+                        SelfTradePrevention::DecrementAndCancel => quantity.min(node.quantity),
+                    };
+                    quantity = match policy {
+                        SelfTradePrevention::CancelOldest => quantity,
+                        SelfTradePrevention::DecrementAndCancel => quantity - reduction,
+                        _ => 0,
+                    };
+
+                    // Hidden orders leave no trace besides their trades
+                    if !hidden && reduction == node.quantity {
+                        self.events.emit(BookEvent::OrderCancelled {
+                            order_id: node.order_id,
+                            side: side.opposite(),
+                            price,
+                            quantity: reduction,
+                            client_order_id: node.client_order_id,
+                        });
+                    } else if !hidden && reduction > 0 {
+                        self.events.emit(BookEvent::OrderReduced {
+                            order_id: node.order_id,
+                            side: side.opposite(),
+                            price,
+                            new_quantity: node.quantity - reduction,
+                            client_order_id: node.client_order_id,
+                        });
+                    }
+                    reduction
                 } else {
-                    // This resting order will be partially consumed
-                    let Some(top_node_ref) = self.orders.get_mut(top_level.head) else {
-                        return Err(MarketOrderError::InternalError);
+                    let traded = quantity.min(node.quantity);
+                    let mut fill = Fill {
+                        price,
+                        quantity: traded,
+                        maker_order_id: node.order_id,
+                        maker_client_order_id: node.client_order_id,
+                        maker_remaining_quantity: node.quantity - traded,
+                        maker_fully_filled: traded == node.quantity,
+                        trade_id: self.next_trade_id,
+                        timestamp: now,
+                        fees: None,
+                    };
+                    let taker_owner = owner.map(|(owner, _)| owner);
+                    let tracks_owners = self.fee_schedule.is_some()
+                        || self.accounts.is_some()
+                        || self.match_hooks.is_set();
+                    let maker_owner = if tracks_owners {
+                        self.index_map
+                            .get(&node.order_id)
+                            .and_then(|entry| entry.owner)
+                    } else {
+                        None
                     };
+                    if let Some(schedule) = &self.fee_schedule {
+                        fill.fees = Some(self.owner_volumes.charge(
+                            schedule,
+                            side,
+                            &fill,
+                            maker_owner,
+                            taker_owner,
+                        ));
+                    }
+                    if let Some(accounts) = &mut self.accounts {
+                        accounts.record(side, &fill, maker_owner, taker_owner);
+                    }
+                    if self.match_hooks.is_set() {
+                        self.match_hooks.queue_trade(MatchedTrade {
+                            taker_side: side,
+                            fill: fill.clone(),
+                            maker_owner,
+                            taker_owner,
+                        });
+                    }
+                    self.events.emit(BookEvent::Trade {
+                        taker_side: side,
+                        fill: fill.clone(),
+                    });
+                    on_fill(fill);
+                    self.next_trade_id.0 += 1;
+                    self.last_trade_price = Some(price);
+                    quantity -= traded;
+                    traded
+                };
+                level.total_quantity = level.total_quantity.saturating_sub(traded);
+                totals.quantity = totals.quantity.saturating_sub(traded);
+
+                // This resting order was only partially consumed
+                if traded < node.quantity {
+                    node.quantity -= traded;
+                    break;
+                }
+
+                // This order was fully consumed, remove it from id lookup and memory
+                let (order_id, next) = (node.order_id, node.next);
+                if let Some(entry) = self.index_map.remove(&order_id)
+                    && let Some(owner) = entry.owner
+                {
+                    self.owner_orders.remove(owner, order_id);
+                }
+                self.queue_ranks
+                    .remove(side.opposite(), hidden, price, order_id);
+                self.pegged_orders.remove(&order_id);
+                self.orders.remove(level.head);
+                level.order_count -= 1;
+                totals.order_count -= 1;
 
-                    // Push remaining quantity
-                    fills.push(Fill { price, quantity });
-                    top_node_ref.quantity -= quantity;
-                    quantity = 0;
+                // Advance the level's head to the next order in the queue
+                let Some(next) = next else {
                     break;
+                };
+                self.page_versions.touch(next);
+                if let Some(next_order) = self.orders.get_mut(next) {
+                    next_order.previous = None;
+                }
+                level.head = next;
+            }
+
+            // No orders remain, delete this level entirely
+            if level.order_count == 0 {
+                book.remove(&price);
+                if !hidden {
+                    self.events.emit(BookEvent::LevelRemoved {
+                        side: side.opposite(),
+                        price,
+                    });
                 }
             }
         }
 
-        Ok(fills)
+        Ok(quantity)
     }
 
-    pub fn execute_limit_order(
+    // Visible and hidden levels of each side, bids then asks, in the order matching
+    // reaches them: best price first, the visible level ahead of the hidden one
+    pub(crate) fn matching_levels(
+        &self,
+    ) -> (
+        impl Iterator<Item = (Price, &PriceLevel)>,
+        impl Iterator<Item = (Price, &PriceLevel)>,
+    ) {
+        (
+            merge_levels(
+                self.bids(),
+                self.hidden_bids.iter().rev(),
+                |hidden, visible| hidden > visible,
+            ),
+            merge_levels(self.asks(), self.hidden_asks.iter(), |hidden, visible| {
+                hidden < visible
+            }),
+        )
+    }
+
+    // Walks the resting orders on `side` that taking `quantity` from it would consume,
+    // best price first, without touching them. Mirrors `match_order`, including
+    // self-trade prevention, so a broken level in the way is reported before the first
+    // fill instead of part way through. Returns the quantity that would trade, which
+    // self-trade prevention can leave short of what the side holds. A `replaced` order
+    // is walked past as if it were already gone.
+    pub(crate) fn walk_resting(
+        &self,
+        side: Side,
+        mut quantity: Quantity,
+        limit_price: Option<Price>,
+        owner: Option<(OwnerId, SelfTradePrevention)>,
+        replaced: Option<OrderId>,
+    ) -> Result<Quantity, InternalError> {
+        let (mut bids, mut asks) = self.matching_levels();
+        let levels: &mut dyn Iterator<Item = (Price, &PriceLevel)> = match side {
+            Side::Bid => &mut bids,
+            Side::Ask => &mut asks,
+        };
+
+        let mut traded: Quantity = 0;
+        for (price, level) in levels {
+            let crosses = match (side, limit_price) {
+                (_, None) => true,
+                (Side::Bid, Some(limit)) => price >= limit,
+                (Side::Ask, Some(limit)) => price <= limit,
+            };
+            if quantity == 0 || !crosses {
+                break;
+            }
+
+            let (mut next, mut walked) = (Some(level.head), 0);
+            while quantity > 0 {
+                let Some(node) = next.and_then(|index| self.orders.get(index)) else {
+                    // Running out is fine once every order on the level was walked, otherwise
+                    // the level's head is left behind on a freed node
+                    if next.is_none() && walked == level.order_count {
+                        break;
+                    }
+                    return Err(InternalError::DanglingLevelHead { side, price });
+                };
+                next = node.next;
+                walked += 1;
+                if replaced == Some(node.order_id) {
+                    continue;
+                }
+
+                let self_trade = owner.filter(|(owner, _)| {
+                    self.index_map
+                        .get(&node.order_id)
+                        .is_some_and(|entry| entry.owner == Some(*owner))
+                });
+                match self_trade {
+                    None => {
+                        let fill = quantity.min(node.quantity);
+                        traded += fill;
+                        quantity -= fill;
+                    }
+                    Some((_, SelfTradePrevention::CancelOldest)) => {}
+                    Some((_, SelfTradePrevention::DecrementAndCancel)) => {
+                        quantity -= quantity.min(node.quantity);
+                    }
+                    Some(_) => quantity = 0,
+                }
+            }
+        }
+
+        Ok(traded)
+    }
+
+    pub fn execute_fok_order(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        let arrival = Arrival {
+            immediate: true,
+            required: quantity,
+            ..Default::default()
+        };
+        let order_id = match self.claim_order_id(order_id, side, price, quantity, arrival)? {
+            OrderIdClaim::New(order_id) => order_id,
+            OrderIdClaim::Amended(result) => return Ok(result),
+        };
+        if self.order_id_exists(order_id) {
+            return Err(LimitOrderError::OrderIdAlreadyExists);
+        }
+        self.check_limit_order(price, quantity)?;
+        if !self.matches_orders() {
+            return Err(LimitOrderError::TradingHalted);
+        }
+
+        // Either the whole quantity fills now, or nothing happens
+        if self.walk_resting(side.opposite(), quantity, Some(price), None, None)? < quantity {
+            return Err(LimitOrderError::InsufficientLiquidity);
+        }
+
+        let mut fills = Vec::new();
+        self.match_order(side, quantity, Some(price), None, |fill| fills.push(fill))
+            .map_err(|error| error.into_internal())?;
+        let sequence = self.after_mutation();
+
+        Ok(LimitOrderResult {
+            order_id,
+            fills,
+            sequence,
+            ..Default::default()
+        })
+    }
+
+    // Limit order which only executes if at least `min_quantity` can be filled on arrival,
+    // any unfilled remainder then rests as usual.
+    pub fn execute_min_qty_order(
         &mut self,
         side: Side,
         order_id: OrderId,
         price: Price,
         quantity: Quantity,
+        min_quantity: Quantity,
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        let order = Order::builder(side, order_id, price, quantity)
+            .min_quantity(min_quantity)
+            .build();
+        self.submit_order(&order)
+    }
+
+    // Rejects orders with nothing to trade, or at a price the book refuses,
+    // before checking them against the instrument
+    pub(crate) fn check_limit_order(
+        &self,
+        price: Price,
+        quantity: Quantity,
     ) -> Result<(), LimitOrderError> {
-        if self.index_map.get(&order_id).is_some() {
+        if quantity == 0 {
+            return Err(LimitOrderError::ZeroQuantity);
+        }
+        if self.reject_non_positive_prices && price <= 0 {
+            return Err(LimitOrderError::NonPositivePrice);
+        }
+        Ok(self.check_order(Some(price), quantity)?)
+    }
+
+    // Checks whether an incoming order at this price would take liquidity, hidden or not
+    pub(crate) fn would_cross(&self, side: Side, price: Price) -> bool {
+        match side {
+            Side::Bid => [&self.asks, &self.hidden_asks]
+                .into_iter()
+                .any(|asks| Self::next_ask(asks).is_some_and(|(ask, _)| ask <= price)),
+            Side::Ask => [&self.bids, &self.hidden_bids]
+                .into_iter()
+                .any(|bids| Self::next_bid(bids).is_some_and(|(bid, _)| bid >= price)),
+        }
+    }
+
+    pub fn execute_post_only_order(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        // Makers only, reject anything that would match on arrival
+        let order = Order::builder(side, order_id, price, quantity)
+            .post_only()
+            .build();
+        self.submit_order(&order)
+    }
+
+    pub fn execute_gtd_order(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+        expires_at: Timestamp,
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        let result = self.execute_limit_order(side, order_id, price, quantity)?;
+
+        // Only a resting remainder needs to be tracked for expiry
+        if result.resting_quantity > 0 {
+            self.track_expiry(result.order_id, expires_at)?;
+        }
+
+        Ok(result)
+    }
+
+    pub(crate) fn track_expiry(
+        &mut self,
+        order_id: OrderId,
+        expires_at: Timestamp,
+    ) -> Result<(), LimitOrderError> {
+        let Some(entry) = self.index_map.get_mut(&order_id) else {
+            return Err(InternalError::MissingIndexEntry { order_id }.into());
+        };
+        self.page_versions.touch(entry.order_index);
+        entry.expires_at = Some(expires_at);
+        entry.time_in_force = TimeInForce::GoodTillDate(expires_at);
+        self.expiry_queue.push(Reverse((expires_at, order_id)));
+        Ok(())
+    }
+
+    // Cancels every resting order whose deadline is at or before `now`,
+    // returning the Ids of the expired orders in deadline order.
+    pub fn expire_orders(&mut self, now: Timestamp) -> Vec<OrderId> {
+        let mut expired = Vec::new();
+
+        while let Some(&Reverse((expires_at, order_id))) = self.expiry_queue.peek() {
+            if expires_at > now {
+                break;
+            }
+            self.expiry_queue.pop();
+
+            // Entries are removed lazily, so skip orders that were already filled,
+            // cancelled, or replaced by a new order reusing the same Id
+            let still_resting = self
+                .index_map
+                .get(&order_id)
+                .is_some_and(|entry| entry.expires_at == Some(expires_at));
+            if still_resting && self.withdraw_order(order_id).is_ok() {
+                expired.push(order_id);
+            }
+        }
+
+        expired
+    }
+
+    pub fn execute_limit_order(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        #[cfg(feature = "latency")]
+        let started = Instant::now();
+        let order_id =
+            match self.claim_order_id(order_id, side, price, quantity, Arrival::default())? {
+                OrderIdClaim::New(order_id) => order_id,
+                OrderIdClaim::Amended(result) => return Ok(result),
+            };
+        let result = self.place_limit_order(side, order_id, price, quantity)?;
+        let sequence = self.after_mutation();
+        #[cfg(feature = "latency")]
+        self.latencies.record(Operation::LimitInsert, started);
+        Ok(LimitOrderResult { sequence, ..result })
+    }
+
+    // Same as `execute_limit_order`, but hands each fill to `on_fill` as it happens,
+    // the returned result's `fills` is left empty.
+    pub fn execute_limit_order_with(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+        on_fill: impl FnMut(Fill),
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        #[cfg(feature = "latency")]
+        let started = Instant::now();
+        let order_id =
+            match self.claim_order_id(order_id, side, price, quantity, Arrival::default())? {
+                OrderIdClaim::New(order_id) => order_id,
+                OrderIdClaim::Amended(result) => return Ok(result),
+            };
+        let result = self.place_limit_order_with(side, order_id, price, quantity, None, on_fill)?;
+        let sequence = self.after_mutation();
+        #[cfg(feature = "latency")]
+        self.latencies.record(Operation::LimitInsert, started);
+        Ok(LimitOrderResult { sequence, ..result })
+    }
+
+    // Matches and rests a limit order without re-pricing pegged orders afterwards
+    pub(crate) fn place_limit_order(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        let mut fills = Vec::new();
+        let result =
+            self.place_limit_order_with(side, order_id, price, quantity, None, |fill| {
+                fills.push(fill)
+            })?;
+        Ok(LimitOrderResult { fills, ..result })
+    }
+
+    // Any resting remainder is tagged with the owner, if given
+    pub(crate) fn place_limit_order_with(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+        owner: Option<(OwnerId, SelfTradePrevention)>,
+        on_fill: impl FnMut(Fill),
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        if self.order_id_exists(order_id) {
             return Err(LimitOrderError::OrderIdAlreadyExists);
         }
+        self.check_limit_order(price, quantity)?;
+        if !self.accepts_orders() {
+            return Err(LimitOrderError::TradingHalted);
+        }
+        self.check_rest(side, price, quantity, false)?;
 
-        let book = match side {
-            Side::Bid => &mut self.bids,
-            Side::Ask => &mut self.asks,
+        // Take any liquidity crossing the limit price first, unless the book is in an
+        // auction where everything rests until it uncrosses
+        let quantity = if self.matches_orders() {
+            self.match_order(side, quantity, Some(price), owner, on_fill)
+                .map_err(|error| error.into_internal())?
+        } else {
+            quantity
+        };
+
+        // Fully filled, nothing left to rest
+        if quantity == 0 {
+            return Ok(LimitOrderResult {
+                order_id,
+                ..Default::default()
+            });
+        }
+
+        let (index, queue_position) = self.rest_order(side, order_id, price, quantity, false)?;
+        if let Some((owner, _)) = owner {
+            self.track_owner(order_id, owner);
+        }
+        Ok(LimitOrderResult {
+            order_id,
+            fills: Vec::new(),
+            resting_quantity: quantity,
+            order_index: Some(index),
+            queue_position: Some(queue_position),
+            sequence: 0, // Assigned once the order is accepted
+        })
+    }
+
+    // Appends an order to the back of its price level, visible or hidden, without
+    // matching it. Returns the order's slab index and queue position in that level.
+    pub(crate) fn rest_order(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+        hidden: bool,
+    ) -> Result<(usize, usize), LimitOrderError> {
+        self.check_rest(side, price, quantity, hidden)?;
+        let book = match (side, hidden) {
+            (Side::Bid, false) => &mut self.bids,
+            (Side::Ask, false) => &mut self.asks,
+            (Side::Bid, true) => &mut self.hidden_bids,
+            (Side::Ask, true) => &mut self.hidden_asks,
         };
 
         // Insert into memory
+        let client_order_id = self.incoming_client_order_id.take();
         let index = self.orders.insert(OrderNode {
             quantity,
             order_id,
             previous: None,
             next: None,
+            client_order_id,
         });
+        self.page_versions.touch(index);
 
-        if let Some(level) = book.get_mut(&price) {
+        let queue_position = if let Some(level) = book.get_mut(&price) {
             // Link new order to previous tail
             let old_tail = level.tail;
+            self.page_versions.touch(old_tail);
 
             let Some(next) = self.orders.get_mut(old_tail) else {
-                return Err(LimitOrderError::InternalError);
+                return Err(InternalError::DanglingLevelTail { side, price }.into());
             };
             next.next = Some(index);
 
             let Some(previous) = self.orders.get_mut(index) else {
-                return Err(LimitOrderError::InternalError);
+                return Err(InternalError::MissingNode { index }.into());
             };
             previous.previous = Some(old_tail);
 
             // Update tail & order count
             level.tail = index;
             level.order_count += 1;
+            level.total_quantity += quantity;
+            level.order_count - 1
         } else {
+            if !hidden {
+                self.events.emit(BookEvent::LevelAdded { side, price });
+            }
             book.insert(
                 price,
                 PriceLevel {
                     head: index,
                     tail: index,
                     order_count: 1,
+                    total_quantity: quantity,
                 },
             );
-        }
+            0
+        };
+
+        let totals = self.side_totals_mut(side, hidden);
+        totals.quantity += quantity;
+        totals.order_count += 1;
+        self.queue_ranks.push(side, hidden, price, order_id);
 
         // Update the cancel map
         self.index_map.insert(
@@ -275,9 +1934,43 @@ impl OrderBook {
                 order_index: index,
                 price,
                 side,
+                expires_at: None,
+                time_in_force: TimeInForce::GoodTillCancel,
+                owner: None,
+                timestamp: self.now(),
+                hidden,
             },
         );
 
-        Ok(())
+        if !hidden {
+            self.events.emit(BookEvent::OrderAdded {
+                order_id,
+                side,
+                price,
+                quantity,
+                client_order_id,
+            });
+        }
+
+        Ok((index, queue_position))
     }
 }
+
+// Merges a side's visible and hidden levels, each best price first, taking the
+// hidden level only while `better(hidden, visible)` says its price is ahead
+fn merge_levels<'a>(
+    visible: impl Iterator<Item = (Price, &'a PriceLevel)>,
+    hidden: impl Iterator<Item = (Price, &'a PriceLevel)>,
+    better: fn(Price, Price) -> bool,
+) -> impl Iterator<Item = (Price, &'a PriceLevel)> {
+    let (mut visible, mut hidden) = (visible.peekable(), hidden.peekable());
+    std::iter::from_fn(move || match (visible.peek(), hidden.peek()) {
+        (Some(&(visible_price, _)), Some(&(hidden_price, _)))
+            if !better(hidden_price, visible_price) =>
+        {
+            visible.next()
+        }
+        (Some(_), None) => visible.next(),
+        _ => hidden.next(),
+    })
+}
@@ -0,0 +1,221 @@
+use hashbrown::HashSet;
+
+use crate::{
+    error::{InstrumentError, LimitOrderError},
+    order_ids::{Arrival, OrderIdClaim},
+    orderbook::OrderBook,
+    types::{LimitOrderResult, OrderId, Price, Quantity, Side},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PegType {
+    Primary,  // Same side best price, ie. a bid tracks the best bid
+    Market,   // Opposite side best price, ie. a bid tracks the best ask
+    Midpoint, // Halfway between best bid and best ask, rounded away from crossing
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeggedOrder {
+    pub side: Side,
+    pub peg_type: PegType,
+    pub offset: Price, // Added to the reference price, can be negative
+}
+
+impl PeggedOrder {
+    // Computes the resting price from the best unpegged bid and ask
+    pub fn target_price(&self, best_bid: Option<Price>, best_ask: Option<Price>) -> Option<Price> {
+        let reference = match (self.peg_type, self.side) {
+            (PegType::Primary, Side::Bid) | (PegType::Market, Side::Ask) => best_bid?,
+            (PegType::Primary, Side::Ask) | (PegType::Market, Side::Bid) => best_ask?,
+            (PegType::Midpoint, side) => {
+                let (bid, ask) = (best_bid?, best_ask?);
+                let sum = bid.checked_add(ask)?;
+                match side {
+                    Side::Bid => sum.div_euclid(2),
+                    Side::Ask => sum.div_euclid(2) + sum.rem_euclid(2),
+                }
+            }
+        };
+        reference.checked_add(self.offset)
+    }
+}
+
+impl OrderBook {
+    // Places a limit order whose price tracks a reference price plus an offset.
+    // The order is re-priced (and loses time priority) whenever the reference moves.
+    pub fn execute_pegged_order(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        peg_type: PegType,
+        offset: Price,
+        quantity: Quantity,
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        let peg = PeggedOrder {
+            side,
+            peg_type,
+            offset,
+        };
+        // The offset keeps the target on the tick grid, except for midpoint rounding
+        if let Some(instrument) = &self.instrument
+            && offset.rem_euclid(instrument.tick_size()) != 0
+        {
+            return Err(InstrumentError::PriceNotOnTick.into());
+        }
+
+        let best_bid = self.unpegged_best_price(Side::Bid);
+        let best_ask = self.unpegged_best_price(Side::Ask);
+        let Some(price) = self.peg_target(&peg, best_bid, best_ask) else {
+            return Err(LimitOrderError::PegReferenceUnavailable);
+        };
+
+        let order_id =
+            match self.claim_order_id(order_id, side, price, quantity, Arrival::default())? {
+                OrderIdClaim::New(order_id) => order_id,
+                OrderIdClaim::Amended(result) => return Ok(result),
+            };
+        let result = self.place_limit_order(side, order_id, price, quantity)?;
+        if result.resting_quantity > 0 {
+            self.pegged_orders.insert(order_id, peg);
+        }
+
+        let sequence = self.after_mutation();
+        Ok(LimitOrderResult { sequence, ..result })
+    }
+
+    // Target price, rounded passively onto the instrument's tick grid
+    fn peg_target(
+        &self,
+        peg: &PeggedOrder,
+        best_bid: Option<Price>,
+        best_ask: Option<Price>,
+    ) -> Option<Price> {
+        let target = peg.target_price(best_bid, best_ask)?;
+        Some(match &self.instrument {
+            Some(instrument) => instrument.round_passive(peg.side, target),
+            None => target,
+        })
+    }
+
+    // Best price on a side, ignoring levels made up only of pegged orders,
+    // so pegs never reference themselves or each other.
+    fn unpegged_best_price(&self, side: Side) -> Option<Price> {
+        if self.pegged_orders.is_empty() {
+            return match side {
+                Side::Bid => self.bids.last(),
+                Side::Ask => self.asks.first(),
+            }
+            .map(|(price, _)| price);
+        }
+        self.best_price_without(side, false, |order_id| {
+            self.pegged_orders.contains_key(&order_id)
+        })
+    }
+
+    // Best price on one set of a side's levels holding an order `skip` lets through
+    fn best_price_without(
+        &self,
+        side: Side,
+        hidden: bool,
+        skip: impl Fn(OrderId) -> bool,
+    ) -> Option<Price> {
+        let levels = self.levels(side, hidden);
+        let mut levels: Box<dyn Iterator<Item = _>> = match side {
+            Side::Bid => Box::new(levels.iter().rev()),
+            Side::Ask => Box::new(levels.iter()),
+        };
+        levels
+            .find(|(_, level)| {
+                let mut next = Some(level.head);
+                while let Some(node) = next.and_then(|index| self.orders.get(index)) {
+                    if !skip(node.order_id) {
+                        return true;
+                    }
+                    next = node.next;
+                }
+                false
+            })
+            .map(|(price, _)| price)
+    }
+
+    // Moves every pegged order to its target price when the reference prices have changed.
+    // Pegs never take liquidity when re-priced, they stay put if the target would cross,
+    // and only pegs that actually move lose their place in the queue.
+    pub(crate) fn reprice_pegged_orders(&mut self) {
+        // Held until the book reopens, since re-pricing places orders
+        if self.pegged_orders.is_empty() || !self.matches_orders() {
+            return;
+        }
+
+        let references = (
+            self.unpegged_best_price(Side::Bid),
+            self.unpegged_best_price(Side::Ask),
+        );
+        if references == self.peg_references {
+            return;
+        }
+        self.peg_references = references;
+
+        // Pegs whose target changed, checked as a whole so pegs on opposite sides
+        // don't block each other from reaching their new prices
+        let mut moves = Vec::new();
+        for (order_id, peg) in &self.pegged_orders {
+            let Some(target) = self.peg_target(peg, references.0, references.1) else {
+                continue; // Reference vanished, keep the last price
+            };
+            let Some(entry) = self.index_map.get(order_id) else {
+                continue;
+            };
+            if entry.price == target {
+                continue;
+            }
+            let Some(node) = self.orders.get(entry.order_index) else {
+                continue;
+            };
+            moves.push((*order_id, peg.side, target, node.quantity));
+        }
+
+        // A peg whose target would cross an order staying put, or another peg's target,
+        // holds its price and queue place, which can in turn hold others back
+        loop {
+            let moving: HashSet<OrderId> = moves.iter().map(|&(order_id, ..)| order_id).collect();
+            let best = |side: Side| {
+                let staying = [false, true].into_iter().filter_map(|hidden| {
+                    self.best_price_without(side, hidden, |order_id| moving.contains(&order_id))
+                });
+                let targets = moves
+                    .iter()
+                    .filter(|&&(_, peg_side, ..)| peg_side == side)
+                    .map(|&(_, _, target, _)| target);
+                match side {
+                    Side::Bid => staying.chain(targets).max(),
+                    Side::Ask => staying.chain(targets).min(),
+                }
+            };
+            let (best_bid, best_ask) = (best(Side::Bid), best(Side::Ask));
+            let count = moves.len();
+            moves.retain(|&(_, side, target, _)| match side {
+                Side::Bid => best_ask.is_none_or(|ask| target < ask),
+                Side::Ask => best_bid.is_none_or(|bid| target > bid),
+            });
+            if moves.len() == count {
+                break;
+            }
+        }
+
+        // Pull them all before placing any, none then rests at a stale price in the way
+        for &(order_id, ..) in &moves {
+            let _ = self.remove_resting_order(order_id);
+        }
+        for (order_id, side, target, quantity) in moves {
+            if self
+                .place_limit_order(side, order_id, target, quantity)
+                .is_err()
+            {
+                self.pegged_orders.remove(&order_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+
+use crate::{
+    orderbook::OrderBook,
+    types::{Depth, Price, Quantity, Sequence},
+};
+
+// An immutable, consistent view of the book as of one accepted command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BookView {
+    pub depth: Depth, // Top levels of each side, as configured when publishing began
+    pub best_bid: Option<(Price, Quantity)>,
+    pub best_ask: Option<(Price, Quantity)>,
+    pub last_trade_price: Option<Price>,
+    pub sequence: Sequence, // Command sequence the view was taken after
+}
+
+// The matching side of a published view, swapped for a fresh one after every mutation
+#[derive(Debug)]
+pub struct DepthPublisher {
+    levels: usize,
+    published: Arc<ArcSwap<BookView>>,
+}
+
+// A cloned book publishes to a view of its own, which nothing reads until it calls
+// `publish_depth`
+impl Clone for DepthPublisher {
+    fn clone(&self) -> Self {
+        Self {
+            levels: self.levels,
+            published: Arc::new(ArcSwap::new(self.published.load_full())),
+        }
+    }
+}
+
+impl DepthPublisher {
+    fn publish(&self, book: &OrderBook) {
+        self.published.store(Arc::new(BookView {
+            depth: book.depth(self.levels),
+            best_bid: book.best_bid(),
+            best_ask: book.best_ask(),
+            last_trade_price: book.last_trade_price,
+            sequence: book.command_sequence(),
+        }));
+    }
+}
+
+// A market data reader's handle, cheap to clone and send to other threads. Loading
+// never blocks the matching thread, and a loaded view stays valid however far the
+// book moves on.
+#[derive(Debug, Clone)]
+pub struct DepthReader(Arc<ArcSwap<BookView>>);
+
+impl DepthReader {
+    pub fn load(&self) -> Arc<BookView> {
+        self.0.load_full()
+    }
+}
+
+impl OrderBook {
+    // Publishes a `BookView` with the top `levels` of each side after every accepted
+    // command, returning a reader for it. Building the view costs a depth snapshot
+    // per command. Calling this again replaces the previous publisher, whose readers
+    // keep the last view they saw.
+    pub fn publish_depth(&mut self, levels: usize) -> DepthReader {
+        let publisher = DepthPublisher {
+            levels,
+            published: Arc::default(),
+        };
+        publisher.publish(self);
+
+        let reader = DepthReader(publisher.published.clone());
+        self.depth_publisher = Some(publisher);
+        reader
+    }
+
+    pub(crate) fn publish_view(&self) {
+        if let Some(publisher) = &self.depth_publisher {
+            publisher.publish(self);
+        }
+    }
+}
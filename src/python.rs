@@ -0,0 +1,123 @@
+// Python module `bulk_book`, built as an extension with
+// `maturin build --release --features python,pyo3/extension-module`.
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{
+    orderbook::OrderBook,
+    types::{DepthLevel, Fill, OrderId, Price, Quantity, Side},
+};
+
+// One trade, as a read-only Python object
+#[pyclass(name = "Fill", frozen, get_all)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PyFill {
+    pub price: Price,
+    pub quantity: Quantity,
+    pub maker_order_id: u64,
+    pub trade_id: u64,
+    pub timestamp: u64,
+    pub maker_remaining_quantity: Quantity,
+    pub maker_fully_filled: bool,
+}
+
+impl From<Fill> for PyFill {
+    fn from(fill: Fill) -> Self {
+        Self {
+            price: fill.price,
+            quantity: fill.quantity,
+            maker_order_id: fill.maker_order_id.0,
+            trade_id: fill.trade_id.0,
+            timestamp: fill.timestamp,
+            maker_remaining_quantity: fill.maker_remaining_quantity,
+            maker_fully_filled: fill.maker_fully_filled,
+        }
+    }
+}
+
+// The book as a Python class. Sides are "bid" or "ask", and rejections raise
+// `ValueError` naming the reason. Listeners and hooks needn't be `Sync`, so neither
+// is the book, and an instance stays on the thread that created it.
+#[pyclass(name = "OrderBook", unsendable)]
+#[derive(Debug, Default)]
+pub struct PyOrderBook {
+    book: OrderBook,
+}
+
+#[pymethods]
+impl PyOrderBook {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Returns `(fills, resting_quantity)`
+    pub fn limit(
+        &mut self,
+        side: &str,
+        order_id: u64,
+        price: Price,
+        quantity: Quantity,
+    ) -> PyResult<(Vec<PyFill>, Quantity)> {
+        let result = self
+            .book
+            .execute_limit_order(parse_side(side)?, OrderId(order_id), price, quantity)
+            .map_err(rejected)?;
+        Ok((fills(result.fills), result.resting_quantity))
+    }
+
+    // Returns `(fills, remaining)`, where `remaining` found no liquidity
+    pub fn market(&mut self, side: &str, quantity: Quantity) -> PyResult<(Vec<PyFill>, Quantity)> {
+        let result = self
+            .book
+            .execute_market_order(parse_side(side)?, quantity)
+            .map_err(rejected)?;
+        Ok((fills(result.fills), result.remaining))
+    }
+
+    pub fn cancel(&mut self, order_id: u64) -> PyResult<()> {
+        self.book.cancel_order(OrderId(order_id)).map_err(rejected)
+    }
+
+    pub fn amend(&mut self, order_id: u64, quantity: Quantity) -> PyResult<()> {
+        self.book
+            .amend_quantity(OrderId(order_id), quantity)
+            .map_err(rejected)
+    }
+
+    // Returns `(bids, asks)`, each a best-first list of `(price, quantity, order_count)`
+    pub fn depth(&self, levels: usize) -> (Vec<DepthLevel>, Vec<DepthLevel>) {
+        let depth = self.book.depth(levels);
+        (depth.bids, depth.asks)
+    }
+
+    pub fn best_bid(&self) -> Option<(Price, Quantity)> {
+        self.book.best_bid()
+    }
+
+    pub fn best_ask(&self) -> Option<(Price, Quantity)> {
+        self.book.best_ask()
+    }
+}
+
+fn parse_side(side: &str) -> PyResult<Side> {
+    match side {
+        "bid" => Ok(Side::Bid),
+        "ask" => Ok(Side::Ask),
+        _ => Err(PyValueError::new_err(format!("unknown side {side:?}"))),
+    }
+}
+
+fn rejected(error: impl std::fmt::Debug) -> PyErr {
+    PyValueError::new_err(format!("{error:?}"))
+}
+
+fn fills(fills: Vec<Fill>) -> Vec<PyFill> {
+    fills.into_iter().map(PyFill::from).collect()
+}
+
+#[pymodule]
+fn bulk_book(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyOrderBook>()?;
+    module.add_class::<PyFill>()?;
+    Ok(())
+}
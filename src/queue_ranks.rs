@@ -0,0 +1,144 @@
+use hashbrown::HashMap;
+
+use crate::types::{OrderId, Price, Side};
+
+// Arrival slots of every resting order, so an order's place in its level's queue is a
+// count of the occupied slots ahead of it rather than a walk of the level. Kept in step
+// with the levels by the book, as `OwnerIndex` is with owners.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QueueRanks {
+    bids: HashMap<Price, LevelQueue>,
+    asks: HashMap<Price, LevelQueue>,
+    hidden_bids: HashMap<Price, LevelQueue>,
+    hidden_asks: HashMap<Price, LevelQueue>,
+    slots: HashMap<OrderId, usize>, // Each resting order's slot at its level
+}
+
+// One level's arrival slots, with a Fenwick tree over them counting 1 for each slot still
+// holding an order. Slots left behind by cancels and fills are only reclaimed once they
+// outnumber the occupied ones, so renumbering stays amortized across the removals.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct LevelQueue {
+    orders: Vec<Option<OrderId>>,
+    tree: Vec<u32>,
+    occupied: usize,
+}
+
+impl LevelQueue {
+    fn push(&mut self, order_id: OrderId) -> usize {
+        let slot = self.orders.len();
+        // Each tree entry counts the slots back to just past its position's lowest set bit
+        let position = slot + 1;
+        let covered = self.occupied_before(slot) - self.occupied_before(position & (position - 1));
+        self.orders.push(Some(order_id));
+        self.tree.push(covered as u32 + 1);
+        self.occupied += 1;
+        slot
+    }
+
+    fn vacate(&mut self, slot: usize) {
+        self.orders[slot] = None;
+        self.occupied -= 1;
+        let mut position = slot + 1;
+        while position <= self.tree.len() {
+            self.tree[position - 1] -= 1;
+            position += position & position.wrapping_neg();
+        }
+    }
+
+    // Occupied slots among the first `end`
+    fn occupied_before(&self, mut end: usize) -> usize {
+        let mut count = 0;
+        while end > 0 {
+            count += self.tree[end - 1] as usize;
+            end &= end - 1;
+        }
+        count
+    }
+}
+
+impl QueueRanks {
+    fn levels(&self, side: Side, hidden: bool) -> &HashMap<Price, LevelQueue> {
+        match (side, hidden) {
+            (Side::Bid, false) => &self.bids,
+            (Side::Ask, false) => &self.asks,
+            (Side::Bid, true) => &self.hidden_bids,
+            (Side::Ask, true) => &self.hidden_asks,
+        }
+    }
+
+    fn levels_mut(&mut self, side: Side, hidden: bool) -> &mut HashMap<Price, LevelQueue> {
+        match (side, hidden) {
+            (Side::Bid, false) => &mut self.bids,
+            (Side::Ask, false) => &mut self.asks,
+            (Side::Bid, true) => &mut self.hidden_bids,
+            (Side::Ask, true) => &mut self.hidden_asks,
+        }
+    }
+
+    // Queues an order at the back of its level
+    pub(crate) fn push(&mut self, side: Side, hidden: bool, price: Price, order_id: OrderId) {
+        let slot = self
+            .levels_mut(side, hidden)
+            .entry(price)
+            .or_default()
+            .push(order_id);
+        self.slots.insert(order_id, slot);
+    }
+
+    pub(crate) fn remove(&mut self, side: Side, hidden: bool, price: Price, order_id: OrderId) {
+        let Some(slot) = self.slots.remove(&order_id) else {
+            return;
+        };
+        let levels = match (side, hidden) {
+            (Side::Bid, false) => &mut self.bids,
+            (Side::Ask, false) => &mut self.asks,
+            (Side::Bid, true) => &mut self.hidden_bids,
+            (Side::Ask, true) => &mut self.hidden_asks,
+        };
+        let Some(level) = levels.get_mut(&price) else {
+            return;
+        };
+        level.vacate(slot);
+
+        if level.occupied == 0 {
+            levels.remove(&price);
+        } else if level.orders.len() > 2 * level.occupied {
+            // Renumber the remaining orders from the front, in the same order
+            let remaining = std::mem::take(&mut level.orders);
+            *level = LevelQueue::default();
+            for order_id in remaining.into_iter().flatten() {
+                self.slots.insert(order_id, level.push(order_id));
+            }
+        }
+    }
+
+    // Drops a whole level along with every order queued at it
+    pub(crate) fn remove_level(&mut self, side: Side, hidden: bool, price: Price) {
+        if let Some(level) = self.levels_mut(side, hidden).remove(&price) {
+            for order_id in level.orders.into_iter().flatten() {
+                self.slots.remove(&order_id);
+            }
+        }
+    }
+
+    // Every order holding a slot, in no particular order
+    pub(crate) fn order_ids(&self) -> impl Iterator<Item = OrderId> + '_ {
+        self.slots.keys().copied()
+    }
+
+    // Number of orders ahead of a resting order at its own level
+    pub fn position(
+        &self,
+        side: Side,
+        hidden: bool,
+        price: Price,
+        order_id: OrderId,
+    ) -> Option<usize> {
+        let slot = *self.slots.get(&order_id)?;
+        let level = self.levels(side, hidden).get(&price)?;
+        Some(level.occupied_before(slot))
+    }
+}
@@ -0,0 +1,238 @@
+use std::fmt::Write;
+
+use crate::{
+    command::Command,
+    events::BookEvent,
+    orderbook::OrderBook,
+    types::{DepthLevel, OrderId, Side},
+};
+
+const USAGE: &str = "\
+commands:
+  limit <bid|ask> <id> <price> <qty>
+  market <bid|ask> <qty>
+  cancel <id>
+  depth [levels]
+  snapshot";
+
+// Widest depth bar, drawn for the level with the most quantity
+const BAR_WIDTH: u64 = 40;
+
+// Levels shown by `depth` without an argument
+const DEFAULT_DEPTH: usize = 10;
+
+// Line-oriented front end to a book, behind the `bulk-book-cli` binary. Each input
+// line is one command, and its output is the events it produced or the view asked
+// for, ready to print.
+#[derive(Debug, Default)]
+pub struct Repl {
+    book: OrderBook,
+}
+
+impl Repl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+
+    // Runs one line, returning what to print. Blank lines print nothing.
+    pub fn execute(&mut self, line: &str) -> String {
+        let words: Vec<_> = line.split_whitespace().collect();
+        let Some((&name, args)) = words.split_first() else {
+            return String::new();
+        };
+
+        let command = match name {
+            "limit" => parse_limit(args),
+            "market" => parse_market(args),
+            "cancel" => parse_cancel(args),
+            "depth" => {
+                return match args {
+                    [] => self.depth_view(DEFAULT_DEPTH),
+                    [levels] => levels
+                        .parse()
+                        .map_or_else(|_| USAGE.to_string(), |levels| self.depth_view(levels)),
+                    _ => USAGE.to_string(),
+                };
+            }
+            "snapshot" if args.is_empty() => return self.snapshot_view(),
+            "help" => return USAGE.to_string(),
+            _ => None,
+        };
+        let Some(command) = command else {
+            return USAGE.to_string();
+        };
+
+        match self.book.apply(command) {
+            Ok(events) if events.is_empty() => "ok".to_string(),
+            Ok(events) => events
+                .iter()
+                .map(describe_event)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Err(error) => format!("rejected: {error:?}"),
+        }
+    }
+
+    // Asks above bids, both best price nearest the spread, with a bar per level
+    // scaled to the largest quantity shown
+    fn depth_view(&self, levels: usize) -> String {
+        let depth = self.book.depth(levels);
+        let widest = depth
+            .bids
+            .iter()
+            .chain(&depth.asks)
+            .map(|&(_, quantity, _)| quantity)
+            .max()
+            .unwrap_or(0);
+
+        let mut view = String::new();
+        let row = |view: &mut String, side: &str, (price, quantity, orders): DepthLevel| {
+            let bar = "#".repeat(quantity.saturating_mul(BAR_WIDTH).div_ceil(widest) as usize);
+            let _ = writeln!(
+                view,
+                "{side} {price:>10} {quantity:>10} ({orders:>3}) {bar}"
+            );
+        };
+        for &level in depth.asks.iter().rev() {
+            row(&mut view, "ask", level);
+        }
+        match (depth.bids.first(), depth.asks.first()) {
+            (Some(&(bid, ..)), Some(&(ask, ..))) => {
+                let _ = writeln!(view, "---- spread {} ----", ask - bid);
+            }
+            _ => view.push_str("--------\n"),
+        }
+        for &level in &depth.bids {
+            row(&mut view, "bid", level);
+        }
+        view.pop(); // The caller adds the final newline
+        view
+    }
+
+    // Every resting order as `side price: #id qty, ...`, best levels first
+    fn snapshot_view(&self) -> String {
+        let snapshot = self.book.level3_snapshot();
+        let mut view = String::new();
+        for (side, levels) in [("ask", &snapshot.asks), ("bid", &snapshot.bids)] {
+            for level in levels {
+                let orders: Vec<_> = level
+                    .orders
+                    .iter()
+                    .map(|(order_id, quantity)| format!("#{} {quantity}", order_id.0))
+                    .collect();
+                let _ = writeln!(view, "{side} {}: {}", level.price, orders.join(", "));
+            }
+        }
+        if view.is_empty() {
+            return "empty book".to_string();
+        }
+        view.pop();
+        view
+    }
+}
+
+fn parse_side(side: &str) -> Option<Side> {
+    match side {
+        "bid" => Some(Side::Bid),
+        "ask" => Some(Side::Ask),
+        _ => None,
+    }
+}
+
+fn parse_limit(args: &[&str]) -> Option<Command> {
+    let [side, order_id, price, quantity] = args else {
+        return None;
+    };
+    Some(Command::Limit {
+        side: parse_side(side)?,
+        order_id: OrderId(order_id.parse().ok()?),
+        price: price.parse().ok()?,
+        quantity: quantity.parse().ok()?,
+    })
+}
+
+fn parse_market(args: &[&str]) -> Option<Command> {
+    let [side, quantity] = args else {
+        return None;
+    };
+    Some(Command::Market {
+        side: parse_side(side)?,
+        quantity: quantity.parse().ok()?,
+    })
+}
+
+fn parse_cancel(args: &[&str]) -> Option<Command> {
+    let [order_id] = args else {
+        return None;
+    };
+    Some(Command::Cancel {
+        order_id: OrderId(order_id.parse().ok()?),
+    })
+}
+
+fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::Bid => "bid",
+        Side::Ask => "ask",
+    }
+}
+
+fn describe_event(event: &BookEvent) -> String {
+    match *event {
+        BookEvent::OrderAdded {
+            order_id,
+            side,
+            price,
+            quantity,
+            ..
+        } => format!(
+            "added #{} {} {quantity} @ {price}",
+            order_id.0,
+            side_name(side)
+        ),
+        BookEvent::OrderCancelled {
+            order_id,
+            side,
+            price,
+            quantity,
+            ..
+        } => format!(
+            "cancelled #{} {} {quantity} @ {price}",
+            order_id.0,
+            side_name(side)
+        ),
+        BookEvent::OrderReduced {
+            order_id,
+            side,
+            price,
+            new_quantity,
+            ..
+        } => format!(
+            "reduced #{} {} to {new_quantity} @ {price}",
+            order_id.0,
+            side_name(side)
+        ),
+        BookEvent::Trade {
+            taker_side,
+            ref fill,
+        } => format!(
+            "trade {} {} @ {} against #{}",
+            side_name(taker_side),
+            fill.quantity,
+            fill.price,
+            fill.maker_order_id.0
+        ),
+        BookEvent::AuctionTrade { ref fill } => format!(
+            "auction trade {} @ {} between #{} and #{}",
+            fill.quantity, fill.price, fill.buy_order_id.0, fill.sell_order_id.0
+        ),
+        BookEvent::LevelAdded { side, price } => format!("level added {} {price}", side_name(side)),
+        BookEvent::LevelRemoved { side, price } => {
+            format!("level removed {} {price}", side_name(side))
+        }
+    }
+}
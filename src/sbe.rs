@@ -0,0 +1,254 @@
+use crate::{
+    codec::{ByteReader, ByteWriter},
+    error::{DecodeError, EncodeError},
+    events::BookEvent,
+    types::{AuctionFill, Depth, DepthLevel, Fill, OrderId, TradeId},
+};
+
+// Simple Binary Encoding of book events and depth snapshots, following the schema in
+// sbe/bulk-book.xml. Decoding honours each message's block length, so messages from a
+// newer schema version with extra trailing fields still decode.
+pub const SCHEMA_ID: u16 = 1;
+// Version 2 added the Trade timestamp, 3 AuctionTrade, 4 the Trade maker's remaining quantity
+pub const SCHEMA_VERSION: u16 = 4;
+
+const HEADER_LEN: usize = 8;
+const DEPTH_LEVEL_LEN: u16 = 20;
+const DEPTH_SNAPSHOT_TEMPLATE: u16 = 7;
+
+fn header(writer: &mut ByteWriter, block_length: u16, template_id: u16) {
+    writer.bytes.extend_from_slice(&block_length.to_le_bytes());
+    writer.bytes.extend_from_slice(&template_id.to_le_bytes());
+    writer.bytes.extend_from_slice(&SCHEMA_ID.to_le_bytes());
+    writer
+        .bytes
+        .extend_from_slice(&SCHEMA_VERSION.to_le_bytes());
+}
+
+// Appends one encoded event to `out`
+pub fn encode_event(event: &BookEvent, out: &mut Vec<u8>) {
+    let mut writer = ByteWriter::default();
+    match *event {
+        BookEvent::OrderAdded {
+            order_id,
+            side,
+            price,
+            quantity,
+            ..
+        }
+        | BookEvent::OrderCancelled {
+            order_id,
+            side,
+            price,
+            quantity,
+            ..
+        }
+        | BookEvent::OrderReduced {
+            order_id,
+            side,
+            price,
+            new_quantity: quantity,
+            ..
+        } => {
+            let template_id = match event {
+                BookEvent::OrderAdded { .. } => 1,
+                BookEvent::OrderCancelled { .. } => 2,
+                _ => 3,
+            };
+            header(&mut writer, 25, template_id);
+            writer.u64(order_id.0);
+            writer.side(side);
+            writer.i64(price);
+            writer.u64(quantity);
+        }
+        BookEvent::Trade {
+            taker_side,
+            ref fill,
+        } => {
+            header(&mut writer, 49, 4);
+            writer.side(taker_side);
+            writer.i64(fill.price);
+            writer.u64(fill.quantity);
+            writer.u64(fill.maker_order_id.0);
+            writer.u64(fill.trade_id.0);
+            writer.u64(fill.timestamp);
+            writer.u64(fill.maker_remaining_quantity);
+        }
+        BookEvent::AuctionTrade { ref fill } => {
+            header(&mut writer, 64, 8);
+            writer.i64(fill.price);
+            writer.u64(fill.quantity);
+            writer.u64(fill.buy_order_id.0);
+            writer.i64(fill.buy_price);
+            writer.u64(fill.sell_order_id.0);
+            writer.i64(fill.sell_price);
+            writer.u64(fill.trade_id.0);
+            writer.u64(fill.timestamp);
+        }
+        BookEvent::LevelAdded { side, price } | BookEvent::LevelRemoved { side, price } => {
+            let template_id = match event {
+                BookEvent::LevelAdded { .. } => 5,
+                _ => 6,
+            };
+            header(&mut writer, 9, template_id);
+            writer.side(side);
+            writer.i64(price);
+        }
+    }
+    out.extend_from_slice(&writer.bytes);
+}
+
+// Reads the message header, returning the block length and template id
+fn read_header(reader: &mut ByteReader) -> Result<(usize, u16), DecodeError> {
+    let block_length = u16::from_le_bytes(reader.take()?) as usize;
+    let template_id = u16::from_le_bytes(reader.take()?);
+    let schema_id = u16::from_le_bytes(reader.take()?);
+    let _version = u16::from_le_bytes(reader.take()?);
+    if schema_id != SCHEMA_ID {
+        return Err(DecodeError::BadMagic);
+    }
+    Ok((block_length, template_id))
+}
+
+// Decodes the event at the front of `bytes`, returning it with the bytes it used
+pub fn decode_event(bytes: &[u8]) -> Result<(BookEvent, usize), DecodeError> {
+    let mut reader = ByteReader::new(bytes);
+    let (block_length, template_id) = read_header(&mut reader)?;
+    let Some(block) = reader.bytes.get(..block_length) else {
+        return Err(DecodeError::Truncated);
+    };
+    let mut block = ByteReader::new(block);
+
+    let event = match template_id {
+        1..=3 => {
+            let order_id = OrderId(block.u64()?);
+            let side = block.side()?;
+            let price = block.i64()?;
+            let quantity = block.u64()?;
+            match template_id {
+                1 => BookEvent::OrderAdded {
+                    order_id,
+                    side,
+                    price,
+                    quantity,
+                    client_order_id: None,
+                },
+                2 => BookEvent::OrderCancelled {
+                    order_id,
+                    side,
+                    price,
+                    quantity,
+                    client_order_id: None,
+                },
+                _ => BookEvent::OrderReduced {
+                    order_id,
+                    side,
+                    price,
+                    new_quantity: quantity,
+                    client_order_id: None,
+                },
+            }
+        }
+        4 => {
+            let taker_side = block.side()?;
+            let mut fill = Fill {
+                price: block.i64()?,
+                quantity: block.u64()?,
+                maker_order_id: OrderId(block.u64()?),
+                trade_id: TradeId(block.u64()?),
+                // Absent from version 1 messages
+                timestamp: if block.is_empty() { 0 } else { block.u64()? },
+                fees: None, // Not on the wire, settlement works them out from the schedule
+                maker_client_order_id: None,
+                maker_remaining_quantity: 0,
+                maker_fully_filled: false,
+            };
+            // Absent before version 4, which leaves the maker's state unknown
+            if !block.is_empty() {
+                fill.maker_remaining_quantity = block.u64()?;
+                fill.maker_fully_filled = fill.maker_remaining_quantity == 0;
+            }
+            BookEvent::Trade { taker_side, fill }
+        }
+        5 => BookEvent::LevelAdded {
+            side: block.side()?,
+            price: block.i64()?,
+        },
+        6 => BookEvent::LevelRemoved {
+            side: block.side()?,
+            price: block.i64()?,
+        },
+        8 => BookEvent::AuctionTrade {
+            fill: AuctionFill {
+                price: block.i64()?,
+                quantity: block.u64()?,
+                buy_order_id: OrderId(block.u64()?),
+                buy_price: block.i64()?,
+                sell_order_id: OrderId(block.u64()?),
+                sell_price: block.i64()?,
+                trade_id: TradeId(block.u64()?),
+                timestamp: block.u64()?,
+                fees: None, // Not on the wire, as with continuous fills
+            },
+        },
+        _ => return Err(DecodeError::Corrupt),
+    };
+
+    Ok((event, HEADER_LEN + block_length))
+}
+
+// Appends a depth snapshot as one message with a repeating group per side
+pub fn encode_depth(depth: &Depth, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+    let mut writer = ByteWriter::default();
+    header(&mut writer, 0, DEPTH_SNAPSHOT_TEMPLATE);
+    for levels in [&depth.bids, &depth.asks] {
+        let count = u16::try_from(levels.len()).map_err(|_| EncodeError::ValueOutOfRange)?;
+        writer
+            .bytes
+            .extend_from_slice(&DEPTH_LEVEL_LEN.to_le_bytes());
+        writer.bytes.extend_from_slice(&count.to_le_bytes());
+        for &(price, quantity, order_count) in levels {
+            writer.i64(price);
+            writer.u64(quantity);
+            writer.u32(u32::try_from(order_count).map_err(|_| EncodeError::ValueOutOfRange)?);
+        }
+    }
+    out.extend_from_slice(&writer.bytes);
+    Ok(())
+}
+
+// Decodes the depth snapshot at the front of `bytes`, returning it with the bytes it used
+pub fn decode_depth(bytes: &[u8]) -> Result<(Depth, usize), DecodeError> {
+    let mut reader = ByteReader::new(bytes);
+    let (block_length, template_id) = read_header(&mut reader)?;
+    if template_id != DEPTH_SNAPSHOT_TEMPLATE {
+        return Err(DecodeError::Corrupt);
+    }
+    let Some(rest) = reader.bytes.get(block_length..) else {
+        return Err(DecodeError::Truncated);
+    };
+    reader.bytes = rest;
+
+    let read_group = |reader: &mut ByteReader| -> Result<Vec<DepthLevel>, DecodeError> {
+        let entry_length = u16::from_le_bytes(reader.take()?) as usize;
+        let count = u16::from_le_bytes(reader.take()?);
+        if entry_length < DEPTH_LEVEL_LEN as usize {
+            return Err(DecodeError::Corrupt);
+        }
+
+        let mut levels = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let Some(entry) = reader.bytes.get(..entry_length) else {
+                return Err(DecodeError::Truncated);
+            };
+            reader.bytes = &reader.bytes[entry_length..];
+            let mut entry = ByteReader::new(entry);
+            levels.push((entry.i64()?, entry.u64()?, entry.u32()? as usize));
+        }
+        Ok(levels)
+    };
+    let bids = read_group(&mut reader)?;
+    let asks = read_group(&mut reader)?;
+
+    Ok((Depth { bids, asks }, bytes.len() - reader.bytes.len()))
+}
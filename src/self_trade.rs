@@ -0,0 +1,144 @@
+use hashbrown::{HashMap, HashSet};
+
+use crate::{
+    error::{LimitOrderError, MarketOrderError},
+    order_ids::{Arrival, OrderIdClaim},
+    orderbook::OrderBook,
+    types::{
+        LimitOrderResult, MarketOrderResult, OrderDetails, OrderId, OwnerId, Price, Quantity, Side,
+    },
+};
+
+// What happens instead of a trade when an incoming order meets a resting order from
+// the same owner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelfTradePrevention {
+    CancelNewest,       // Cancel the incoming order's remainder, the resting order stays
+    CancelOldest,       // Cancel the resting order and keep matching
+    CancelBoth,         // Cancel the resting order and the incoming order's remainder
+    DecrementAndCancel, // Reduce both by the smaller quantity, cancelling whichever hits zero
+}
+
+// Owner to resting order Ids, so an owner's orders can be found without scanning the book
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnerIndex(HashMap<OwnerId, HashSet<OrderId>>);
+
+impl OwnerIndex {
+    pub(crate) fn insert(&mut self, owner: OwnerId, order_id: OrderId) {
+        self.0.entry(owner).or_default().insert(order_id);
+    }
+
+    pub(crate) fn remove(&mut self, owner: OwnerId, order_id: OrderId) {
+        if let Some(orders) = self.0.get_mut(&owner) {
+            orders.remove(&order_id);
+            if orders.is_empty() {
+                self.0.remove(&owner);
+            }
+        }
+    }
+
+    // Ids of every resting order belonging to the owner, in no particular order
+    pub fn orders(&self, owner: OwnerId) -> impl Iterator<Item = OrderId> + '_ {
+        self.0.get(&owner).into_iter().flatten().copied()
+    }
+}
+
+impl OrderBook {
+    // Limit order that never trades against its owner's resting orders. Any remainder
+    // rests with the owner, so later owned orders are checked against it too.
+    // A remainder cancelled by the policy is neither filled nor resting in the result.
+    pub fn execute_owned_limit_order(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+        owner: OwnerId,
+        policy: SelfTradePrevention,
+    ) -> Result<LimitOrderResult, LimitOrderError> {
+        let order_id =
+            match self.claim_order_id(order_id, side, price, quantity, Arrival::default())? {
+                OrderIdClaim::New(order_id) => order_id,
+                OrderIdClaim::Amended(result) => return Ok(result),
+            };
+        let mut fills = Vec::new();
+        let result = self.place_limit_order_with(
+            side,
+            order_id,
+            price,
+            quantity,
+            Some((owner, policy)),
+            |fill| fills.push(fill),
+        )?;
+        let sequence = self.after_mutation();
+
+        Ok(LimitOrderResult {
+            fills,
+            sequence,
+            ..result
+        })
+    }
+
+    // Market order that never trades against its owner's resting orders
+    pub fn execute_owned_market_order(
+        &mut self,
+        side: Side,
+        quantity: Quantity,
+        owner: OwnerId,
+        policy: SelfTradePrevention,
+    ) -> Result<MarketOrderResult, MarketOrderError> {
+        self.check_order(None, quantity)?;
+
+        let mut fills = Vec::new();
+        let remaining = self.match_order(side, quantity, None, Some((owner, policy)), |fill| {
+            fills.push(fill)
+        })?;
+        let sequence = self.after_mutation();
+        Ok(MarketOrderResult {
+            remaining,
+            sequence,
+            ..MarketOrderResult::from_fills(fills)
+        })
+    }
+
+    // Tags a resting order with its owner
+    pub(crate) fn track_owner(&mut self, order_id: OrderId, owner: OwnerId) {
+        if let Some(entry) = self.index_map.get_mut(&order_id) {
+            self.page_versions.touch(entry.order_index);
+            entry.owner = Some(owner);
+            self.owner_orders.insert(owner, order_id);
+        }
+    }
+
+    // Every resting order belonging to the owner in ascending Id order, found through the
+    // owner index rather than by walking the book
+    pub fn orders_by_owner(&self, owner: OwnerId) -> Vec<OrderDetails> {
+        let mut orders: Vec<_> = self
+            .owner_orders
+            .orders(owner)
+            .filter_map(|order_id| self.get_order(order_id))
+            .collect();
+        orders.sort_unstable_by_key(|order| order.order_id);
+        orders
+    }
+
+    // Cancels every resting order belonging to the owner, returning their Ids in ascending
+    // order. Cancels nothing while the book is halted.
+    pub fn cancel_all_by_owner(&mut self, owner: OwnerId) -> Vec<OrderId> {
+        if !self.accepts_cancels() {
+            return Vec::new();
+        }
+        let mut cancelled: Vec<_> = self.owner_orders.orders(owner).collect();
+        cancelled.sort_unstable();
+
+        for order_id in &cancelled {
+            let _ = self.remove_resting_order(*order_id);
+            self.pegged_orders.remove(order_id);
+        }
+        self.after_mutation();
+
+        cancelled
+    }
+}
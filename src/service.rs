@@ -0,0 +1,45 @@
+use std::thread;
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{command::Command, engine::Response, error::EngineError, orderbook::OrderBook};
+
+type Request = (Command, oneshot::Sender<Response>);
+
+// An async front end to a book on its own matching thread, so a web service can await
+// each command's result without writing its own actor. Clones share the same book, and
+// the thread exits once every clone is dropped.
+#[derive(Debug, Clone)]
+pub struct BookService {
+    requests: mpsc::Sender<Request>,
+}
+
+impl BookService {
+    pub fn spawn(capacity: usize) -> Self {
+        Self::spawn_with(capacity, OrderBook::new())
+    }
+
+    // Moves an existing book onto the matching thread. At most `capacity` commands
+    // wait for the thread at once.
+    pub fn spawn_with(capacity: usize, mut book: OrderBook) -> Self {
+        let (requests, mut incoming) = mpsc::channel::<Request>(capacity);
+
+        thread::spawn(move || {
+            while let Some((command, reply)) = incoming.blocking_recv() {
+                // The command is applied even if its caller stopped waiting
+                let _ = reply.send(book.respond(command));
+            }
+        });
+
+        Self { requests }
+    }
+
+    // Waits for room in the queue, then for the command to be applied
+    pub async fn submit(&self, command: Command) -> Result<Response, EngineError> {
+        let (reply, response) = oneshot::channel();
+        if let Err(error) = self.requests.send((command.clone(), reply)).await {
+            return Err(EngineError::Stopped(error.0.0));
+        }
+        response.await.map_err(|_| EngineError::Stopped(command))
+    }
+}
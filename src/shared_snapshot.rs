@@ -0,0 +1,289 @@
+use std::{
+    cmp::Reverse,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use crate::{
+    order::TimeInForce,
+    orderbook::{IndexMapEntry, OrderBook, OrderNode, PriceLevel},
+    types::{Level3, Level3Snapshot, OrderId, Price, Quantity, Sequence, Side, TradeId},
+};
+
+// Slab slots per storage page, the unit shared between snapshots
+const PAGE_LEN: usize = 256;
+
+// Stamps are handed out across every book, so two pages with the same stamp hold the same
+// orders even when they belong to a clone or a book restored in the meantime
+static NEXT_PAGE_VERSION: AtomicU64 = AtomicU64::new(1);
+
+fn next_page_version() -> u64 {
+    NEXT_PAGE_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
+// When each page of a book's order storage last changed. Pages never touched share the
+// stamp the book started with. Kept by the book on every change to a node or its Id
+// lookup entry, so a snapshot can reuse an earlier snapshot's copy of any page whose
+// stamp still matches without looking inside it.
+#[derive(Debug, Clone)]
+pub struct PageVersions {
+    initial: u64,
+    pages: Vec<u64>, // Indexed by slab index / PAGE_LEN
+}
+
+impl Default for PageVersions {
+    fn default() -> Self {
+        Self {
+            initial: next_page_version(),
+            pages: Vec::new(),
+        }
+    }
+}
+
+impl PageVersions {
+    // Marks the page holding the slab index as changed
+    pub(crate) fn touch(&mut self, index: usize) {
+        let page = index / PAGE_LEN;
+        if self.pages.len() <= page {
+            self.pages.resize(page + 1, self.initial);
+        }
+        self.pages[page] = next_page_version();
+    }
+
+    // Marks every page as changed, for when orders move between slab indices
+    pub(crate) fn touch_all(&mut self) {
+        *self = Self::default();
+    }
+
+    fn get(&self, page: usize) -> u64 {
+        self.pages.get(page).copied().unwrap_or(self.initial)
+    }
+}
+
+// Occupied slots of one page as (slab index, node, Id lookup entry), by index
+type Page = Arc<[(usize, OrderNode, IndexMapEntry)]>;
+type Levels = Arc<[(Price, PriceLevel)]>;
+
+// An immutable point-in-time copy of the resting book. Orders are held in pages of
+// slab slots behind `Arc`s, so cloning a snapshot is a handful of reference counts,
+// and `OrderBook::snapshot_from` shares every page the book hasn't touched since an
+// earlier snapshot, going by its `PageVersions` rather than comparing or copying it.
+//
+// Only visible resting orders are captured, with their queue priority, owners, and
+// deadlines. Hidden orders, stops, pegs, and the book's configuration aren't, use
+// `to_bytes` for a full copy.
+#[derive(Debug, Clone)]
+pub struct SharedSnapshot {
+    pages: Vec<Page>,   // Indexed by slab index / PAGE_LEN, empty where no order rests
+    versions: Vec<u64>, // The book's stamp for each page when it was copied
+    bids: Levels,       // Lowest price first, as the book keeps them
+    asks: Levels,
+    last_trade_price: Option<Price>,
+    next_trade_id: TradeId,
+    sequence: Sequence,       // Command sequence the snapshot was taken after
+    event_sequence: Sequence, // Last event reflected in the snapshot
+}
+
+impl SharedSnapshot {
+    pub fn sequence(&self) -> Sequence {
+        self.sequence
+    }
+
+    pub fn order_count(&self) -> usize {
+        self.pages.iter().map(|page| page.len()).sum()
+    }
+
+    pub fn best_bid(&self) -> Option<(Price, Quantity)> {
+        self.bids
+            .last()
+            .map(|(price, level)| (*price, level.total_quantity))
+    }
+
+    pub fn best_ask(&self) -> Option<(Price, Quantity)> {
+        self.asks
+            .first()
+            .map(|(price, level)| (*price, level.total_quantity))
+    }
+
+    // Orders resting at a price level in FIFO order, empty if the level doesn't exist
+    pub fn orders_at(&self, side: Side, price: Price) -> impl Iterator<Item = (OrderId, Quantity)> {
+        let levels = match side {
+            Side::Bid => &self.bids,
+            Side::Ask => &self.asks,
+        };
+        let head = levels
+            .binary_search_by_key(&price, |(price, _)| *price)
+            .ok()
+            .map(|position| levels[position].1.head);
+
+        std::iter::successors(head.and_then(|index| self.node(index)), |node| {
+            node.next.and_then(|index| self.node(index))
+        })
+        .map(|node| (node.order_id, node.quantity))
+    }
+
+    // Same as `OrderBook::level3_snapshot` at the time this snapshot was taken
+    pub fn level3_snapshot(&self) -> Level3Snapshot {
+        let level3 = |side: Side, (price, _): &(Price, PriceLevel)| Level3 {
+            price: *price,
+            orders: self.orders_at(side, *price).collect(),
+        };
+
+        Level3Snapshot {
+            bids: self
+                .bids
+                .iter()
+                .rev()
+                .map(|level| level3(Side::Bid, level))
+                .collect(),
+            asks: self
+                .asks
+                .iter()
+                .map(|level| level3(Side::Ask, level))
+                .collect(),
+            sequence: self.event_sequence,
+        }
+    }
+
+    // Number of order pages held in common with `other` rather than copied
+    pub fn shared_pages(&self, other: &SharedSnapshot) -> usize {
+        self.pages
+            .iter()
+            .zip(&other.pages)
+            .filter(|(page, other)| !page.is_empty() && Arc::ptr_eq(page, other))
+            .count()
+    }
+
+    // Builds a live book holding exactly these resting orders, at the same slab
+    // indices and queue positions
+    pub fn to_book(&self) -> OrderBook {
+        let mut book = OrderBook::new();
+        let entries = self.pages.iter().flat_map(|page| page.iter());
+
+        book.orders = entries
+            .clone()
+            .map(|(index, node, _)| (*index, node.clone()))
+            .collect();
+        for (_, node, entry) in entries {
+            book.index_map.insert(node.order_id, entry.clone());
+            if let Some(owner) = entry.owner {
+                book.track_owner(node.order_id, owner);
+            }
+            if let Some(expires_at) = entry.expires_at {
+                book.expiry_queue.push(Reverse((expires_at, node.order_id)));
+            }
+            if entry.time_in_force == TimeInForce::Day {
+                book.day_orders.push(node.order_id);
+            }
+        }
+
+        for (side, levels) in [(Side::Bid, &self.bids), (Side::Ask, &self.asks)] {
+            for (price, level) in levels.iter() {
+                let totals = book.side_totals_mut(side, false);
+                totals.quantity += level.total_quantity;
+                totals.order_count += level.order_count;
+                match side {
+                    Side::Bid => book.bids.insert(*price, level.clone()),
+                    Side::Ask => book.asks.insert(*price, level.clone()),
+                };
+                for (order_id, _) in self.orders_at(side, *price) {
+                    book.queue_ranks.push(side, false, *price, order_id);
+                }
+            }
+        }
+
+        book.last_trade_price = self.last_trade_price;
+        book.next_trade_id = self.next_trade_id;
+        book.command_sequence = self.sequence;
+        book.events.set_sequence(self.event_sequence);
+        book.refresh_top_of_book();
+        book
+    }
+
+    fn node(&self, index: usize) -> Option<&OrderNode> {
+        let page = self.pages.get(index / PAGE_LEN)?;
+        let position = page
+            .binary_search_by_key(&index, |(index, ..)| *index)
+            .ok()?;
+        Some(&page[position].1)
+    }
+}
+
+impl OrderBook {
+    // Copies the resting book into a fresh `SharedSnapshot`
+    pub fn snapshot(&self) -> SharedSnapshot {
+        self.build_snapshot(None)
+    }
+
+    // Same as `snapshot`, sharing every page of orders and each side's levels with
+    // `previous` wherever they haven't changed since. Taking snapshots in a chain
+    // this way keeps both the copying and the memory to the pages that actually moved,
+    // while each side's levels are still compared in full.
+    pub fn snapshot_from(&self, previous: &SharedSnapshot) -> SharedSnapshot {
+        self.build_snapshot(Some(previous))
+    }
+
+    fn build_snapshot(&self, previous: Option<&SharedSnapshot>) -> SharedSnapshot {
+        // Every slab index lies within the slab's capacity
+        let page_count = self.orders.capacity().div_ceil(PAGE_LEN);
+        let versions: Vec<_> = (0..page_count)
+            .map(|page| self.page_versions.get(page))
+            .collect();
+
+        // Only pages stamped since `previous` was taken are copied
+        let pages = versions
+            .iter()
+            .enumerate()
+            .map(|(page, version)| {
+                let shared = previous
+                    .filter(|previous| previous.versions.get(page) == Some(version))
+                    .and_then(|previous| previous.pages.get(page));
+                match shared {
+                    Some(shared) => shared.clone(),
+                    None => self.copy_page(page),
+                }
+            })
+            .collect();
+
+        let levels = |levels: Vec<(Price, PriceLevel)>, shared: Option<&Levels>| match shared {
+            Some(shared) if shared[..] == levels[..] => shared.clone(),
+            _ => Levels::from(levels),
+        };
+        SharedSnapshot {
+            pages,
+            versions,
+            bids: levels(
+                self.bids
+                    .iter()
+                    .map(|(price, level)| (price, level.clone()))
+                    .collect(),
+                previous.map(|previous| &previous.bids),
+            ),
+            asks: levels(
+                self.asks
+                    .iter()
+                    .map(|(price, level)| (price, level.clone()))
+                    .collect(),
+                previous.map(|previous| &previous.asks),
+            ),
+            last_trade_price: self.last_trade_price,
+            next_trade_id: self.next_trade_id,
+            sequence: self.command_sequence,
+            event_sequence: self.event_sequence(),
+        }
+    }
+
+    // Visible orders resting in one page of slab slots, by index
+    fn copy_page(&self, page: usize) -> Page {
+        let start = page * PAGE_LEN;
+        (start..start + PAGE_LEN)
+            .filter_map(|index| {
+                let node = self.orders.get(index)?;
+                let entry = self.index_map.get(&node.order_id)?;
+                (!entry.hidden).then(|| (index, node.clone(), entry.clone()))
+            })
+            .collect()
+    }
+}
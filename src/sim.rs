@@ -0,0 +1,288 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+    command::{Command, CommandResult},
+    orderbook::OrderBook,
+    types::{OrderId, Price, Quantity, Side, Timestamp},
+};
+
+const NANOS_PER_SECOND: f64 = 1e9;
+
+// SplitMix64, small and fast with a full 64 bit period. Good enough to drive order
+// flow, and the same seed gives the same flow on every platform.
+#[derive(Debug, Clone)]
+pub struct SimRng(u64);
+
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Uniform in `range`, which must not be empty
+    pub fn in_range(&mut self, range: &RangeInclusive<i64>) -> i64 {
+        let span = range.end().abs_diff(*range.start()).saturating_add(1);
+        range.start().wrapping_add((self.next_u64() % span) as i64)
+    }
+
+    // Exponentially distributed with the given mean
+    pub fn exponential(&mut self, mean: f64) -> f64 {
+        -(1.0 - self.next_f64()).ln() * mean
+    }
+}
+
+// How far from the mid price, in ticks, an agent places its limit orders. Bids go
+// below the mid and asks above it, so offsets at or below zero cross the spread.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceDistribution {
+    Uniform(RangeInclusive<i64>),
+    Exponential { mean: f64 }, // Mostly near the touch, with a long passive tail
+}
+
+impl PriceDistribution {
+    fn sample(&self, rng: &mut SimRng) -> i64 {
+        match self {
+            Self::Uniform(range) => rng.in_range(range),
+            Self::Exponential { mean } => rng.exponential(*mean) as i64,
+        }
+    }
+}
+
+// One kind of participant. Each of its actions arrives as an independent Poisson
+// process with the given rate, in events per second, so `cancel_rate / limit_rate`
+// is its cancel ratio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Agent {
+    pub limit_rate: f64,
+    pub market_rate: f64,
+    pub cancel_rate: f64, // Only ever cancels its own resting orders
+    pub buy_probability: f64,
+    pub price_offsets: PriceDistribution,
+    pub quantities: RangeInclusive<Quantity>,
+}
+
+impl Agent {
+    // Quotes tightly around the mid and cancels most of what it quotes
+    pub fn market_maker() -> Self {
+        Self {
+            limit_rate: 100.0,
+            market_rate: 0.0,
+            cancel_rate: 90.0,
+            buy_probability: 0.5,
+            price_offsets: PriceDistribution::Uniform(1..=5),
+            quantities: 1..=20,
+        }
+    }
+
+    // Rests orders at random depths, occasionally crossing or trading at market
+    pub fn noise_trader() -> Self {
+        Self {
+            limit_rate: 10.0,
+            market_rate: 2.0,
+            cancel_rate: 3.0,
+            buy_probability: 0.5,
+            price_offsets: PriceDistribution::Exponential { mean: 8.0 },
+            quantities: 1..=50,
+        }
+    }
+
+    // Only takes liquidity
+    pub fn taker() -> Self {
+        Self {
+            limit_rate: 0.0,
+            market_rate: 5.0,
+            cancel_rate: 0.0,
+            buy_probability: 0.5,
+            price_offsets: PriceDistribution::Uniform(0..=0),
+            quantities: 1..=30,
+        }
+    }
+
+    fn total_rate(&self) -> f64 {
+        self.limit_rate + self.market_rate + self.cancel_rate
+    }
+}
+
+// A generated command and when it arrived, in nanoseconds from the start of the run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimOrder {
+    pub timestamp: Timestamp,
+    pub agent: usize, // Index into the simulator's agents
+    pub command: Command,
+}
+
+// Totals from `Simulator::run`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SimStats {
+    pub commands: usize,
+    pub rejected: usize,
+    pub trades: usize,
+    pub traded_quantity: Quantity,
+}
+
+// Generates order flow from a population of agents against a live book, for
+// benchmarking, soak testing, and strategy research. The flow depends only on the
+// seed and the book, so a run can be reproduced exactly.
+#[derive(Debug, Clone)]
+pub struct Simulator {
+    agents: Vec<Agent>,
+    resting: Vec<Vec<OrderId>>, // Per agent, orders that may still rest, pruned lazily
+    rng: SimRng,
+    now: f64, // Nanoseconds since the start of the run
+    next_order_id: u64,
+    mid_price: Price, // Used until the book has prices of its own
+}
+
+impl Simulator {
+    pub fn new(seed: u64, mid_price: Price, agents: Vec<Agent>) -> Self {
+        Self {
+            resting: vec![Vec::new(); agents.len()],
+            agents,
+            rng: SimRng::new(seed),
+            now: 0.0,
+            next_order_id: 1,
+            mid_price,
+        }
+    }
+
+    pub fn agents(&self) -> &[Agent] {
+        &self.agents
+    }
+
+    // The next command by arrival time, priced off the book's current state. Order Ids
+    // count up from 1, so a fresh book never rejects one as a duplicate. None if no
+    // agent acts at all.
+    pub fn next_order(&mut self, book: &OrderBook) -> Option<SimOrder> {
+        let total_rate: f64 = self.agents.iter().map(Agent::total_rate).sum();
+        if total_rate <= 0.0 {
+            return None;
+        }
+        self.now += self.rng.exponential(NANOS_PER_SECOND / total_rate);
+
+        // The merged process picks each action in proportion to its rate
+        let mut pick = self.rng.next_f64() * total_rate;
+        let mut chosen = (self.agents.len() - 1, 0);
+        'agents: for (index, agent) in self.agents.iter().enumerate() {
+            for (action, rate) in [agent.limit_rate, agent.market_rate, agent.cancel_rate]
+                .into_iter()
+                .enumerate()
+            {
+                if pick < rate {
+                    chosen = (index, action);
+                    break 'agents;
+                }
+                pick -= rate;
+            }
+        }
+        let (agent, action) = chosen;
+
+        let command = match action {
+            1 => self.market(agent),
+            // A cancel with nothing left to cancel places an order instead
+            2 => self
+                .cancel(agent, book)
+                .unwrap_or_else(|| self.limit(agent, book)),
+            _ => self.limit(agent, book),
+        };
+        Some(SimOrder {
+            timestamp: self.now as Timestamp,
+            agent,
+            command,
+        })
+    }
+
+    // Generates and applies `count` commands
+    pub fn run(&mut self, book: &mut OrderBook, count: usize) -> SimStats {
+        let mut stats = SimStats::default();
+        for _ in 0..count {
+            let Some(order) = self.next_order(book) else {
+                break;
+            };
+            let result = book.apply_command(&order.command);
+            stats.commands += 1;
+            let fills = match &result {
+                CommandResult::Limit(Ok(result)) => &result.fills[..],
+                CommandResult::Market(Ok(result)) => &result.fills[..],
+                _ => &[],
+            };
+            stats.trades += fills.len();
+            stats.traded_quantity += fills.iter().map(|fill| fill.quantity).sum::<Quantity>();
+            if result.into_result().is_err() {
+                stats.rejected += 1;
+            }
+        }
+        stats
+    }
+
+    fn side(&mut self, agent: usize) -> Side {
+        if self.rng.next_f64() < self.agents[agent].buy_probability {
+            Side::Bid
+        } else {
+            Side::Ask
+        }
+    }
+
+    fn quantity(&mut self, agent: usize) -> Quantity {
+        let quantities = &self.agents[agent].quantities;
+        let range = *quantities.start() as i64..=*quantities.end() as i64;
+        self.rng.in_range(&range) as Quantity
+    }
+
+    fn limit(&mut self, agent: usize, book: &OrderBook) -> Command {
+        let side = self.side(agent);
+        let offset = self.agents[agent].price_offsets.sample(&mut self.rng);
+        let mid = match (book.best_bid(), book.best_ask()) {
+            (Some((bid, _)), Some((ask, _))) => bid + (ask - bid) / 2,
+            (Some((bid, _)), None) => bid,
+            (None, Some((ask, _))) => ask,
+            (None, None) => book.last_trade_price.unwrap_or(self.mid_price),
+        };
+        let price = match side {
+            Side::Bid => mid - offset,
+            Side::Ask => mid + offset,
+        };
+
+        let order_id = OrderId(self.next_order_id);
+        self.next_order_id += 1;
+        self.resting[agent].push(order_id);
+        Command::Limit {
+            side,
+            order_id,
+            price,
+            quantity: self.quantity(agent),
+        }
+    }
+
+    fn market(&mut self, agent: usize) -> Command {
+        Command::Market {
+            side: self.side(agent),
+            quantity: self.quantity(agent),
+        }
+    }
+
+    // Cancels a random one of the agent's orders still on the book, forgetting any
+    // that have traded away since
+    fn cancel(&mut self, agent: usize, book: &OrderBook) -> Option<Command> {
+        let resting = &mut self.resting[agent];
+        while !resting.is_empty() {
+            let index = (self.rng.next_u64() % resting.len() as u64) as usize;
+            let order_id = resting.swap_remove(index);
+            if book.get_order(order_id).is_some() {
+                return Some(Command::Cancel { order_id });
+            }
+        }
+        None
+    }
+}
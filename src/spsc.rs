@@ -0,0 +1,172 @@
+use std::{
+    cell::UnsafeCell,
+    fmt, hint,
+    mem::MaybeUninit,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+// A bounded lock-free ring between exactly one producing and one consuming thread. Each
+// half only ever advances its own index and reads the other's, so neither side takes a
+// lock. Dropping either half closes the ring.
+struct Ring<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    head: AtomicUsize, // Count of values read, only advanced by the consumer
+    tail: AtomicUsize, // Count of values written, only advanced by the producer
+    closed: AtomicBool,
+}
+
+// Slots between head and tail belong to the consumer and the rest to the producer, so
+// no slot is ever reached from both threads at once
+unsafe impl<T: Send> Sync for Ring<T> {}
+
+impl<T> Ring<T> {
+    fn slot(&self, index: usize) -> *mut MaybeUninit<T> {
+        self.slots[index % self.slots.len()].get()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    fn fmt(&self, name: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(name)
+            .field("capacity", &self.slots.len())
+            .field("closed", &self.is_closed())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Drop for Ring<T> {
+    fn drop(&mut self) {
+        let (head, tail) = (*self.head.get_mut(), *self.tail.get_mut());
+        for index in head..tail {
+            // Written by the producer and never read by the consumer
+            unsafe { (*self.slot(index)).assume_init_drop() };
+        }
+    }
+}
+
+// The writing half of a ring, `&mut` on every push keeps it to a single producer
+pub(crate) struct Producer<T>(Arc<Ring<T>>);
+
+// The reading half of a ring, `&mut` on every pop keeps it to a single consumer
+pub(crate) struct Consumer<T>(Arc<Ring<T>>);
+
+// A ring holding up to `capacity` values, at least one
+pub(crate) fn ring<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    let ring = Arc::new(Ring {
+        slots: (0..capacity.max(1))
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect(),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        closed: AtomicBool::new(false),
+    });
+    (Producer(ring.clone()), Consumer(ring))
+}
+
+impl<T> Producer<T> {
+    // Hands the value back if the ring is full or the consumer is gone
+    pub(crate) fn try_push(&mut self, value: T) -> Result<(), T> {
+        let ring = &self.0;
+        let tail = ring.tail.load(Ordering::Relaxed);
+        if ring.is_closed() || tail - ring.head.load(Ordering::Acquire) == ring.slots.len() {
+            return Err(value);
+        }
+        unsafe { (*ring.slot(tail)).write(value) };
+        ring.tail.store(tail + 1, Ordering::Release);
+        Ok(())
+    }
+
+    // Waits for room while the ring is full, handing the value back once the consumer
+    // is gone
+    pub(crate) fn push(&mut self, mut value: T) -> Result<(), T> {
+        let mut backoff = Backoff::default();
+        loop {
+            match self.try_push(value) {
+                Ok(()) => return Ok(()),
+                Err(rejected) if self.is_closed() => return Err(rejected),
+                Err(rejected) => value = rejected,
+            }
+            backoff.wait();
+        }
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+}
+
+impl<T> Consumer<T> {
+    pub(crate) fn try_pop(&mut self) -> Option<T> {
+        let ring = &self.0;
+        let head = ring.head.load(Ordering::Relaxed);
+        if head == ring.tail.load(Ordering::Acquire) {
+            return None;
+        }
+        let value = unsafe { (*ring.slot(head)).assume_init_read() };
+        ring.head.store(head + 1, Ordering::Release);
+        Some(value)
+    }
+
+    // Waits for the next value, None once the producer is gone and the ring is empty
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        let mut backoff = Backoff::default();
+        loop {
+            // Checked before popping, so nothing pushed just before closing is missed
+            let closed = self.0.is_closed();
+            if let Some(value) = self.try_pop() {
+                return Some(value);
+            }
+            if closed {
+                return None;
+            }
+            backoff.wait();
+        }
+    }
+}
+
+impl<T> Drop for Producer<T> {
+    fn drop(&mut self) {
+        self.0.closed.store(true, Ordering::Release);
+    }
+}
+
+impl<T> Drop for Consumer<T> {
+    fn drop(&mut self) {
+        self.0.closed.store(true, Ordering::Release);
+    }
+}
+
+impl<T> fmt::Debug for Producer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt("Producer", f)
+    }
+}
+
+impl<T> fmt::Debug for Consumer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt("Consumer", f)
+    }
+}
+
+// Spins while the other side is likely to answer quickly, then yields, then naps,
+// so an idle ring doesn't hold a core
+#[derive(Default)]
+struct Backoff(u32);
+
+impl Backoff {
+    fn wait(&mut self) {
+        match self.0 {
+            0..64 => hint::spin_loop(),
+            64..128 => thread::yield_now(),
+            _ => thread::sleep(Duration::from_micros(50)),
+        }
+        self.0 = self.0.saturating_add(1);
+    }
+}
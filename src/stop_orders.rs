@@ -0,0 +1,170 @@
+use hashbrown::HashSet;
+
+use crate::{
+    error::{CancelOrderError, InternalError, LimitOrderError, StopOrderError},
+    orderbook::OrderBook,
+    types::{LimitOrderResult, OrderId, Price, Quantity, Side},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StopOrder {
+    pub side: Side,
+    pub stop_price: Price,
+    pub limit_price: Price,
+    pub quantity: Quantity,
+}
+
+impl OrderBook {
+    // Parks a stop-limit order off the book. Once the last trade price reaches the
+    // stop price (at or above for buys, at or below for sells), a limit order is placed
+    // at the limit price, straight away if it already has. Stops elected while the book
+    // isn't open wait for it to reopen.
+    pub fn execute_stop_limit_order(
+        &mut self,
+        side: Side,
+        order_id: OrderId,
+        stop_price: Price,
+        limit_price: Price,
+        quantity: Quantity,
+    ) -> Result<(), StopOrderError> {
+        if self.order_id_exists(order_id) {
+            return Err(StopOrderError::OrderIdAlreadyExists);
+        }
+        if quantity == 0 {
+            return Err(StopOrderError::ZeroQuantity);
+        }
+        if !self.accepts_orders() {
+            return Err(StopOrderError::TradingHalted);
+        }
+        if self.reject_non_positive_prices && (stop_price <= 0 || limit_price <= 0) {
+            return Err(StopOrderError::NonPositivePrice);
+        }
+        self.check_order(Some(stop_price), quantity)?;
+        self.check_order(Some(limit_price), quantity)?;
+
+        let stops = match side {
+            Side::Bid => &mut self.buy_stops,
+            Side::Ask => &mut self.sell_stops,
+        };
+        stops.entry(stop_price).or_default().push(order_id);
+
+        self.stop_orders.insert(
+            order_id,
+            StopOrder {
+                side,
+                stop_price,
+                limit_price,
+                quantity,
+            },
+        );
+
+        self.after_mutation();
+        Ok(())
+    }
+
+    pub(crate) fn cancel_stop_order(&mut self, order_id: OrderId) -> Result<(), CancelOrderError> {
+        let Some(&StopOrder {
+            side, stop_price, ..
+        }) = self.stop_orders.get(&order_id)
+        else {
+            return Err(CancelOrderError::OrderIdNotFound);
+        };
+
+        // Find the stop's queue before removing anything
+        let stops = match side {
+            Side::Bid => &mut self.buy_stops,
+            Side::Ask => &mut self.sell_stops,
+        };
+        let Some(ids) = stops.get_mut(&stop_price) else {
+            return Err(InternalError::StopQueueMismatch { order_id }.into());
+        };
+        ids.retain(|id| *id != order_id);
+        if ids.is_empty() {
+            stops.remove(&stop_price);
+        }
+        self.stop_orders.remove(&order_id);
+
+        Ok(())
+    }
+
+    // The next stop elected by the last trade price, without removing it, passing over
+    // the ones in `skip`. Buy stops with the lowest stop price and sell stops with the
+    // highest go first.
+    fn next_triggered_stop(&self, skip: &HashSet<OrderId>) -> Option<OrderId> {
+        let last = self.last_trade_price?;
+
+        let buys = self.buy_stops.range(..=last);
+        let sells = self.sell_stops.range(last..).rev();
+        buys.chain(sells)
+            .flat_map(|(_, ids)| ids)
+            .find(|&&order_id| !skip.contains(&order_id))
+            .copied()
+    }
+
+    // Places the next elected stop not in `skip` as a limit order under its own Id,
+    // whatever the book's order Id mode. A stop that fails to place is parked at the back
+    // of its queue and added to `skip`, so the stops after it still fire this pass.
+    // Nothing is placed while the book isn't matching.
+    fn trigger_next_stop(
+        &mut self,
+        skip: &mut HashSet<OrderId>,
+    ) -> Option<(OrderId, Result<LimitOrderResult, LimitOrderError>)> {
+        if !self.matches_orders() {
+            return None;
+        }
+        let order_id = self.next_triggered_stop(skip)?;
+        let Some(stop) = self.stop_orders.get(&order_id).cloned() else {
+            skip.insert(order_id);
+            let error = InternalError::StopQueueMismatch { order_id };
+            return Some((order_id, Err(error.into())));
+        };
+        if let Err(error) = self.cancel_stop_order(order_id) {
+            skip.insert(order_id);
+            return Some((order_id, Err(error.into_internal().into())));
+        }
+
+        let result = self.place_limit_order(stop.side, order_id, stop.limit_price, stop.quantity);
+        if result.is_err() {
+            let stops = match stop.side {
+                Side::Bid => &mut self.buy_stops,
+                Side::Ask => &mut self.sell_stops,
+            };
+            stops.entry(stop.stop_price).or_default().push(order_id);
+            self.stop_orders.insert(order_id, stop);
+            skip.insert(order_id);
+        }
+        Some((order_id, result))
+    }
+
+    // Places every elected stop, including stops elected by trades from stops placed
+    // before them. Stops that fail to place are left waiting without holding up the rest.
+    // Runs as part of settling every command.
+    pub(crate) fn trigger_elected_stops(&mut self) {
+        let mut skip = HashSet::new();
+        while self.trigger_next_stop(&mut skip).is_some() {}
+    }
+
+    // Stops are placed as soon as a trade elects them, so this only finds stops left
+    // waiting, after a placement failed. Tries each of them once, in order, and returns
+    // each one's Id alongside its placement result.
+    pub fn trigger_stop_orders(
+        &mut self,
+    ) -> Vec<(OrderId, Result<LimitOrderResult, LimitOrderError>)> {
+        let mut skip = HashSet::new();
+        let mut triggered = Vec::new();
+        while let Some(trigger) = self.trigger_next_stop(&mut skip) {
+            triggered.push(trigger);
+        }
+
+        if triggered.iter().any(|(_, result)| result.is_ok()) {
+            let sequence = self.after_mutation();
+            for (_, result) in &mut triggered {
+                if let Ok(result) = result {
+                    result.sequence = sequence;
+                }
+            }
+        }
+        triggered
+    }
+}
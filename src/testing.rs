@@ -0,0 +1,262 @@
+use std::ops::RangeInclusive;
+
+use proptest::{
+    arbitrary::Arbitrary,
+    collection::{SizeRange, vec},
+    prelude::*,
+    strategy::BoxedStrategy,
+};
+
+use crate::{
+    command::{Command, CommandResult},
+    types::{Level3, Level3Snapshot, OrderId, Price, Quantity, Side},
+};
+
+// Bounds for generated commands. Ids and prices are drawn from small ranges so cancels
+// and amends usually find their order and limit orders often cross.
+#[derive(Debug, Clone)]
+pub struct CommandConfig {
+    pub order_ids: RangeInclusive<u64>,
+    pub prices: RangeInclusive<Price>,
+    pub quantities: RangeInclusive<Quantity>,
+}
+
+impl Default for CommandConfig {
+    fn default() -> Self {
+        Self {
+            order_ids: 1..=64,
+            prices: 90..=110,
+            quantities: 1..=50,
+        }
+    }
+}
+
+pub fn side_strategy() -> impl Strategy<Value = Side> {
+    prop_oneof![Just(Side::Bid), Just(Side::Ask)]
+}
+
+// Limit orders are weighted heaviest so the book builds up depth to cancel and trade against
+pub fn command_strategy(config: CommandConfig) -> BoxedStrategy<Command> {
+    let order_id = config.order_ids.prop_map(OrderId);
+    let quantity = config.quantities;
+
+    prop_oneof![
+        4 => (side_strategy(), order_id.clone(), config.prices, quantity.clone()).prop_map(
+            |(side, order_id, price, quantity)| Command::Limit {
+                side,
+                order_id,
+                price,
+                quantity,
+            }
+        ),
+        1 => (side_strategy(), quantity.clone())
+            .prop_map(|(side, quantity)| Command::Market { side, quantity }),
+        2 => order_id
+            .clone()
+            .prop_map(|order_id| Command::Cancel { order_id }),
+        1 => (order_id, quantity).prop_map(|(order_id, quantity)| Command::Amend { order_id, quantity }),
+    ]
+    .boxed()
+}
+
+pub fn commands_strategy(
+    config: CommandConfig,
+    len: impl Into<SizeRange>,
+) -> impl Strategy<Value = Vec<Command>> {
+    vec(command_strategy(config), len)
+}
+
+impl Arbitrary for Command {
+    type Parameters = CommandConfig;
+    type Strategy = BoxedStrategy<Command>;
+
+    fn arbitrary_with(config: Self::Parameters) -> Self::Strategy {
+        command_strategy(config)
+    }
+}
+
+// One trade as both books can report it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Execution {
+    pub maker_order_id: OrderId,
+    pub price: Price,
+    pub quantity: Quantity,
+}
+
+// The trades an `OrderBook` command result produced, or None if it was rejected
+pub fn executions(result: &CommandResult) -> Option<Vec<Execution>> {
+    let fills = match result {
+        CommandResult::Limit(Ok(result)) => &result.fills,
+        CommandResult::Market(Ok(result)) => &result.fills,
+        CommandResult::Cancel(Ok(())) | CommandResult::Amend(Ok(())) => return Some(Vec::new()),
+        _ => return None,
+    };
+
+    Some(
+        fills
+            .iter()
+            .map(|fill| Execution {
+                maker_order_id: fill.maker_order_id,
+                price: fill.price,
+                quantity: fill.quantity,
+            })
+            .collect(),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RestingOrder {
+    side: Side,
+    order_id: OrderId,
+    price: Price,
+    quantity: Quantity,
+}
+
+// A deliberately naive book: every resting order in one Vec in arrival order, with
+// matching done by scanning for the best price. Slow, but simple enough to trust as
+// the expected behaviour of an open book without an instrument, owners, or fees.
+#[derive(Debug, Default, Clone)]
+pub struct ReferenceBook {
+    orders: Vec<RestingOrder>,
+}
+
+impl ReferenceBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Applies a command, returning its trades or None where `OrderBook` would reject it
+    pub fn apply(&mut self, command: &Command) -> Option<Vec<Execution>> {
+        match *command {
+            Command::Limit {
+                side,
+                order_id,
+                price,
+                quantity,
+            } => {
+                if self.position(order_id).is_some() || quantity == 0 {
+                    return None;
+                }
+                let (executions, remaining) = self.take(side, quantity, Some(price));
+                if remaining > 0 {
+                    self.orders.push(RestingOrder {
+                        side,
+                        order_id,
+                        price,
+                        quantity: remaining,
+                    });
+                }
+                Some(executions)
+            }
+            Command::Market { side, quantity } => Some(self.take(side, quantity, None).0),
+            Command::Cancel { order_id } => {
+                self.orders.remove(self.position(order_id)?);
+                Some(Vec::new())
+            }
+            Command::Amend { order_id, quantity } => {
+                let position = self.position(order_id)?;
+                if quantity == 0 {
+                    return None;
+                }
+                // Increasing loses priority, so the order moves to the back of the queue
+                if quantity > self.orders[position].quantity {
+                    let order = self.orders.remove(position);
+                    self.orders.push(RestingOrder { quantity, ..order });
+                } else {
+                    self.orders[position].quantity = quantity;
+                }
+                Some(Vec::new())
+            }
+        }
+    }
+
+    // Same shape as `OrderBook::level3_snapshot`, with a zero sequence
+    pub fn level3_snapshot(&self) -> Level3Snapshot {
+        Level3Snapshot {
+            bids: self.levels(Side::Bid),
+            asks: self.levels(Side::Ask),
+            sequence: 0,
+        }
+    }
+
+    fn levels(&self, side: Side) -> Vec<Level3> {
+        let mut prices: Vec<Price> = self
+            .orders
+            .iter()
+            .filter(|order| order.side == side)
+            .map(|order| order.price)
+            .collect();
+        prices.sort_unstable();
+        prices.dedup();
+        if side == Side::Bid {
+            prices.reverse();
+        }
+
+        prices
+            .into_iter()
+            .map(|price| Level3 {
+                price,
+                orders: self
+                    .orders
+                    .iter()
+                    .filter(|order| order.side == side && order.price == price)
+                    .map(|order| (order.order_id, order.quantity))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn position(&self, order_id: OrderId) -> Option<usize> {
+        self.orders
+            .iter()
+            .position(|order| order.order_id == order_id)
+    }
+
+    // Takes liquidity from the opposite side up to `limit_price`, returning the trades
+    // and the quantity left over
+    fn take(
+        &mut self,
+        side: Side,
+        mut quantity: Quantity,
+        limit_price: Option<Price>,
+    ) -> (Vec<Execution>, Quantity) {
+        let mut executions = Vec::new();
+
+        while quantity > 0 {
+            // The first order at the best price is the earliest arrival there
+            let best = self
+                .orders
+                .iter()
+                .enumerate()
+                .filter(|(_, order)| order.side != side)
+                .filter(|(_, order)| match (side, limit_price) {
+                    (_, None) => true,
+                    (Side::Bid, Some(limit)) => order.price <= limit,
+                    (Side::Ask, Some(limit)) => order.price >= limit,
+                })
+                .min_by(|(_, a), (_, b)| match side {
+                    Side::Bid => a.price.cmp(&b.price),
+                    Side::Ask => b.price.cmp(&a.price),
+                })
+                .map(|(position, _)| position);
+            let Some(position) = best else {
+                break;
+            };
+
+            let maker = &mut self.orders[position];
+            let traded = quantity.min(maker.quantity);
+            executions.push(Execution {
+                maker_order_id: maker.order_id,
+                price: maker.price,
+                quantity: traded,
+            });
+            maker.quantity -= traded;
+            quantity -= traded;
+            if maker.quantity == 0 {
+                self.orders.remove(position);
+            }
+        }
+
+        (executions, quantity)
+    }
+}
@@ -0,0 +1,165 @@
+#[cfg(test)]
+use crate::{
+    accounts::{Account, Accounts},
+    fees::{FeeRate, FeeSchedule},
+    orderbook::OrderBook,
+    self_trade::SelfTradePrevention,
+    trading_state::TradingState,
+    types::{OrderId, OwnerId, Side},
+};
+
+#[cfg(test)]
+const POLICY: SelfTradePrevention = SelfTradePrevention::CancelNewest;
+
+#[cfg(test)]
+fn tracked_book() -> OrderBook {
+    let mut book = OrderBook::new();
+    book.accounts = Some(Accounts::default());
+    book
+}
+
+#[test]
+fn test_accounts_are_off_by_default() {
+    let mut book = OrderBook::new();
+    book.execute_owned_limit_order(Side::Ask, OrderId(1), 100, 10, OwnerId(1), POLICY)
+        .unwrap();
+    book.execute_owned_market_order(Side::Bid, 5, OwnerId(2), POLICY)
+        .unwrap();
+    assert_eq!(book.account(OwnerId(1)), None);
+    assert!(book.accounts.is_none());
+}
+
+#[test]
+fn test_fills_update_positions_and_balances() {
+    let mut book = tracked_book();
+    let (maker, taker) = (OwnerId(1), OwnerId(2));
+    book.accounts.as_mut().unwrap().deposit(taker, 10_000);
+    book.execute_owned_limit_order(Side::Ask, OrderId(1), 100, 10, maker, POLICY)
+        .unwrap();
+    book.execute_owned_limit_order(Side::Ask, OrderId(2), 101, 10, maker, POLICY)
+        .unwrap();
+
+    book.execute_owned_market_order(Side::Bid, 15, taker, POLICY)
+        .unwrap();
+    assert_eq!(
+        book.account(taker),
+        Some(Account {
+            position: 15,
+            balance: 10_000 - 1_000 - 505,
+            fees: 0,
+            volume: 15,
+        })
+    );
+    assert_eq!(
+        book.account(maker),
+        Some(Account {
+            position: -15,
+            balance: 1_505,
+            fees: 0,
+            volume: 15,
+        })
+    );
+
+    // Selling back reduces the position, orders without an owner aren't tracked
+    book.execute_limit_order(Side::Bid, OrderId(3), 99, 10)
+        .unwrap();
+    book.execute_owned_market_order(Side::Ask, 4, taker, POLICY)
+        .unwrap();
+    let account = book.account(taker).unwrap();
+    assert_eq!((account.position, account.balance), (11, 8_891));
+    assert_eq!(book.accounts.as_ref().unwrap().len(), 2);
+
+    // Owners that never traded read as empty
+    assert_eq!(book.account(OwnerId(9)), Some(Account::default()));
+}
+
+#[test]
+fn test_fees_come_out_of_balances() {
+    let mut book = tracked_book();
+    book.fee_schedule = Some(FeeSchedule {
+        maker: FeeRate::Fixed(-1),
+        taker: FeeRate::Fixed(3),
+        ..Default::default()
+    });
+    let (maker, taker) = (OwnerId(1), OwnerId(2));
+    book.execute_owned_limit_order(Side::Bid, OrderId(1), 100, 10, maker, POLICY)
+        .unwrap();
+    book.execute_owned_limit_order(Side::Ask, OrderId(2), 100, 4, taker, POLICY)
+        .unwrap();
+
+    let maker_account = book.account(maker).unwrap();
+    assert_eq!((maker_account.balance, maker_account.fees), (-399, -1));
+    let taker_account = book.account(taker).unwrap();
+    assert_eq!((taker_account.balance, taker_account.fees), (397, 3));
+
+    // External executions settle the maker too
+    book.execute_resting_order(OrderId(1), 2, Some(95)).unwrap();
+    let maker_account = book.account(maker).unwrap();
+    assert_eq!(maker_account.position, 6);
+    assert_eq!(maker_account.balance, -399 - 190 + 1);
+}
+
+#[test]
+fn test_auction_fills_update_accounts() {
+    let mut book = tracked_book();
+    book.set_trading_state(TradingState::Auction);
+    let (buyer, seller) = (OwnerId(1), OwnerId(2));
+    book.execute_owned_limit_order(Side::Bid, OrderId(1), 102, 5, buyer, POLICY)
+        .unwrap();
+    book.execute_owned_limit_order(Side::Ask, OrderId(2), 98, 8, seller, POLICY)
+        .unwrap();
+
+    let result = book.uncross().unwrap();
+    let notional = result.price as i128 * 5;
+    let buyer_account = book.account(buyer).unwrap();
+    assert_eq!(
+        (buyer_account.position, buyer_account.balance),
+        (5, -notional)
+    );
+    let seller_account = book.account(seller).unwrap();
+    assert_eq!(
+        (seller_account.position, seller_account.balance),
+        (-5, notional)
+    );
+}
+
+#[test]
+fn test_risk_check_helpers() {
+    let long = Account {
+        position: 10,
+        ..Default::default()
+    };
+    assert_eq!(long.position_after(Side::Bid, 5), 15);
+    assert_eq!(long.position_after(Side::Ask, 15), -5);
+
+    assert!(long.reduces(Side::Ask, 4));
+    assert!(long.reduces(Side::Ask, 10));
+    // Flipping short, or adding to the position, isn't reducing
+    assert!(!long.reduces(Side::Ask, 11));
+    assert!(!long.reduces(Side::Bid, 1));
+
+    let short = Account {
+        position: -3,
+        ..Default::default()
+    };
+    assert!(short.reduces(Side::Bid, 3));
+    assert!(!short.reduces(Side::Ask, 1));
+    assert!(!Account::default().reduces(Side::Bid, 1));
+}
+
+#[test]
+fn test_accounts_survive_binary_snapshots() {
+    let mut book = tracked_book();
+    book.accounts.as_mut().unwrap().deposit(OwnerId(3), -50);
+    book.execute_owned_limit_order(Side::Ask, OrderId(1), 100, 10, OwnerId(1), POLICY)
+        .unwrap();
+    book.execute_owned_market_order(Side::Bid, 6, OwnerId(2), POLICY)
+        .unwrap();
+
+    let restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert_eq!(restored.accounts, book.accounts);
+    assert_eq!(restored.to_bytes(), book.to_bytes());
+
+    let untracked = OrderBook::from_bytes(&OrderBook::new().to_bytes()).unwrap();
+    assert!(untracked.accounts.is_none());
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+use crate::{
+    error::AmendOrderError,
+    orderbook::{OrderBook, PriceLevel},
+    types::{Fill, OrderId, Side, TradeId},
+};
+
+#[test]
+fn test_amend_decrease_keeps_priority() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 10)
+        .unwrap();
+
+    let first = book.index_map.get(&OrderId(1)).unwrap().order_index;
+    let second = book.index_map.get(&OrderId(2)).unwrap().order_index;
+
+    book.amend_quantity(OrderId(1), 4).unwrap();
+
+    assert_eq!(book.orders.get(first).unwrap().quantity, 4);
+    assert_eq!(
+        *book.bids.get(&100).unwrap(),
+        PriceLevel {
+            head: first,
+            tail: second,
+            order_count: 2,
+            total_quantity: 14
+        }
+    );
+}
+
+#[test]
+fn test_amend_increase_loses_priority() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 10)
+        .unwrap();
+
+    book.amend_quantity(OrderId(1), 15).unwrap();
+
+    let first = book.index_map.get(&OrderId(1)).unwrap().order_index;
+    let second = book.index_map.get(&OrderId(2)).unwrap().order_index;
+
+    assert_eq!(book.orders.get(first).unwrap().quantity, 15);
+    assert_eq!(
+        *book.asks.get(&100).unwrap(),
+        PriceLevel {
+            head: second,
+            tail: first,
+            order_count: 2,
+            total_quantity: 25
+        }
+    );
+
+    // Order 2 now fills first
+    let fills = book.execute_market_order(Side::Bid, 10).unwrap().fills;
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].maker_order_id, OrderId(2));
+}
+
+#[test]
+fn test_amend_increase_keeps_deadline() {
+    let mut book = OrderBook::new();
+
+    book.execute_gtd_order(Side::Bid, OrderId(1), 100, 10, 50)
+        .unwrap();
+    book.amend_quantity(OrderId(1), 20).unwrap();
+
+    assert_eq!(book.expire_orders(50), vec![OrderId(1)]);
+    assert!(book.bids.is_empty());
+}
+
+#[test]
+fn test_amend_errors() {
+    let mut book = OrderBook::new();
+
+    assert_eq!(
+        book.amend_quantity(OrderId(1), 10),
+        Err(AmendOrderError::OrderIdNotFound)
+    );
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 10)
+        .unwrap();
+    assert_eq!(
+        book.amend_quantity(OrderId(1), 0),
+        Err(AmendOrderError::ZeroQuantity)
+    );
+}
+
+#[test]
+fn test_amend_overflow_keeps_order() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, u64::MAX - 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+
+    assert_eq!(
+        book.amend_quantity(OrderId(2), 6),
+        Err(AmendOrderError::QuantityOverflow)
+    );
+    let order = book.get_order(OrderId(2)).unwrap();
+    assert_eq!((order.quantity, order.queue_position), (5, 1));
+}
+
+#[test]
+fn test_market_buy_exact_fill_with_orders_behind() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 3)
+        .unwrap();
+
+    // Exactly consumes the first order, the second must not see a zero-quantity fill
+    let result = book.execute_market_order(Side::Bid, 2).unwrap().fills;
+    assert_eq!(
+        result,
+        vec![Fill {
+            price: 100,
+            quantity: 2,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
+        }]
+    );
+
+    let second = book.index_map.get(&OrderId(2)).unwrap().order_index;
+    assert_eq!(book.orders.get(second).unwrap().quantity, 3);
+    assert_eq!(book.asks.get(&100).unwrap().order_count, 1);
+}
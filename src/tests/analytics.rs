@@ -0,0 +1,44 @@
+#[cfg(test)]
+use crate::{
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_imbalance_over_top_levels() {
+    let mut book = OrderBook::new();
+    assert_eq!(book.imbalance(5), None);
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 30)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 99, 10)
+        .unwrap();
+    assert_eq!(book.imbalance(1), Some(1.0));
+
+    book.execute_limit_order(Side::Ask, OrderId(3), 103, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(4), 104, 30)
+        .unwrap();
+    assert_eq!(book.imbalance(1), Some(0.5));
+    assert_eq!(book.imbalance(2), Some(0.0));
+    assert_eq!(book.imbalance(0), None);
+}
+
+#[test]
+fn test_weighted_mid_price() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 30)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 99, 10)
+        .unwrap();
+    assert_eq!(book.weighted_mid_price(1), None);
+
+    book.execute_limit_order(Side::Ask, OrderId(3), 103, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(4), 104, 30)
+        .unwrap();
+
+    // Heavier bids push the micro-price toward the ask
+    assert_eq!(book.weighted_mid_price(1), Some(102.25));
+    assert_eq!(book.weighted_mid_price(2), Some(101.75));
+}
@@ -0,0 +1,195 @@
+#[cfg(test)]
+use crate::{
+    auction::IndicativePrice,
+    error::{AuctionError, LimitOrderError, MarketOrderError},
+    events::BookEvent,
+    orderbook::OrderBook,
+    trading_state::TradingState,
+    types::{AuctionFill, OrderId, Side, TradeId},
+};
+
+#[cfg(test)]
+fn auction_book() -> OrderBook {
+    let mut book = OrderBook::new();
+    book.set_trading_state(TradingState::Auction);
+    for (side, order_id, price, quantity) in [
+        (Side::Bid, 1, 102, 5),
+        (Side::Bid, 2, 101, 10),
+        (Side::Bid, 3, 99, 5),
+        (Side::Ask, 4, 98, 4),
+        (Side::Ask, 5, 100, 8),
+        (Side::Ask, 6, 103, 5),
+    ] {
+        book.execute_limit_order(side, OrderId(order_id), price, quantity)
+            .unwrap();
+    }
+    book
+}
+
+#[test]
+fn test_auction_orders_rest_without_matching() {
+    let book = auction_book();
+    assert_eq!(book.best_bid(), Some((102, 5)));
+    assert_eq!(book.best_ask(), Some((98, 4)));
+    assert_eq!(book.last_trade_price, None);
+
+    let mut book = book;
+    assert_eq!(
+        book.execute_market_order(Side::Bid, 1),
+        Err(MarketOrderError::TradingHalted)
+    );
+    assert_eq!(
+        book.execute_fok_order(Side::Bid, OrderId(7), 110, 1),
+        Err(LimitOrderError::TradingHalted)
+    );
+    book.cancel_order(OrderId(3)).unwrap();
+    book.amend_quantity(OrderId(6), 2).unwrap();
+}
+
+#[test]
+fn test_uncross_maximizes_executed_volume() {
+    let mut book = auction_book();
+    book.record_events(true);
+
+    let result = book.uncross().unwrap();
+    // 100 and 101 both execute 12 with a surplus of 3, the lower price wins
+    assert_eq!((result.price, result.quantity), (100, 12));
+    let pairs: Vec<_> = result
+        .fills
+        .iter()
+        .map(|fill| (fill.buy_order_id.0, fill.sell_order_id.0, fill.quantity))
+        .collect();
+    assert_eq!(pairs, vec![(1, 4, 4), (1, 5, 1), (2, 5, 7)]);
+    assert_eq!(
+        result.fills[0],
+        AuctionFill {
+            price: 100,
+            quantity: 4,
+            buy_order_id: OrderId(1),
+            buy_price: 102,
+            sell_order_id: OrderId(4),
+            sell_price: 98,
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+        }
+    );
+
+    // The leftovers no longer cross
+    assert_eq!(book.best_bid(), Some((101, 3)));
+    assert_eq!(book.best_ask(), Some((103, 5)));
+    assert_eq!(book.last_trade_price, Some(100));
+    assert_eq!(book.uncross(), Err(AuctionError::NotCrossed));
+
+    let trades = book
+        .take_events()
+        .into_iter()
+        .filter(|event| matches!(event, BookEvent::AuctionTrade { .. }))
+        .count();
+    assert_eq!(trades, 3);
+
+    // Reopening resumes continuous matching
+    book.set_trading_state(TradingState::Open);
+    let fills = book.execute_market_order(Side::Bid, 2).unwrap().fills;
+    assert_eq!(fills[0].price, 103);
+}
+
+#[test]
+fn test_uncross_price_ties_favour_last_trade_price() {
+    let mut book = auction_book();
+    book.last_trade_price = Some(105);
+
+    let result = book.uncross().unwrap();
+    assert_eq!((result.price, result.quantity), (101, 12));
+}
+
+#[test]
+fn test_uncross_requires_a_crossed_book() {
+    let mut book = OrderBook::new();
+    assert_eq!(book.uncross(), Err(AuctionError::NotCrossed));
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+    assert_eq!(book.uncross(), Err(AuctionError::NotCrossed));
+
+    book.set_trading_state(TradingState::Halted);
+    assert_eq!(book.uncross(), Err(AuctionError::TradingHalted));
+}
+
+#[test]
+fn test_uncross_includes_hidden_orders() {
+    let mut book = OrderBook::new();
+    book.execute_hidden_order(Side::Bid, OrderId(1), 101, 6)
+        .unwrap();
+    book.execute_hidden_order(Side::Ask, OrderId(2), 104, 3)
+        .unwrap();
+    book.set_trading_state(TradingState::Auction);
+    book.execute_limit_order(Side::Bid, OrderId(3), 101, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(4), 100, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(5), 99, 1)
+        .unwrap();
+
+    // The hidden bid rested before the auction counts towards the volume at 101
+    let result = book.uncross().unwrap();
+    assert_eq!((result.price, result.quantity), (100, 8));
+    let pairs: Vec<_> = result
+        .fills
+        .iter()
+        .map(|fill| (fill.buy_order_id.0, fill.sell_order_id.0, fill.quantity))
+        .collect();
+    // The visible bid goes ahead of the hidden one at its price
+    assert_eq!(pairs, vec![(3, 5, 1), (3, 4, 1), (1, 4, 6)]);
+
+    // Nothing is left crossed, visible or hidden
+    assert_eq!(book.best_bid(), None);
+    assert_eq!(book.best_ask(), Some((100, 3)));
+    assert_eq!(book.hidden_volume(Side::Bid), 0);
+    assert_eq!(book.hidden_volume(Side::Ask), 3);
+    assert_eq!(book.uncross(), Err(AuctionError::NotCrossed));
+}
+
+#[test]
+fn test_indicative_price_tracks_arriving_orders() {
+    let mut book = auction_book();
+    assert_eq!(
+        book.indicative_price(),
+        Some(IndicativePrice {
+            price: 100,
+            matched_quantity: 12,
+            imbalance_quantity: 3,
+            imbalance_side: Some(Side::Bid),
+        })
+    );
+
+    // More selling at 101 soaks up the surplus bids there
+    book.execute_limit_order(Side::Ask, OrderId(7), 101, 5)
+        .unwrap();
+    assert_eq!(
+        book.indicative_price(),
+        Some(IndicativePrice {
+            price: 101,
+            matched_quantity: 15,
+            imbalance_quantity: 2,
+            imbalance_side: Some(Side::Ask),
+        })
+    );
+
+    // Nothing crosses once the bids above 100 and the ask at 98 are gone
+    book.cancel_order(OrderId(1)).unwrap();
+    book.cancel_order(OrderId(2)).unwrap();
+    book.cancel_order(OrderId(4)).unwrap();
+    assert_eq!(book.indicative_price(), None);
+
+    // Only published during an auction
+    let mut book = auction_book();
+    book.set_trading_state(TradingState::Halted);
+    assert_eq!(book.indicative_price(), None);
+    book.set_trading_state(TradingState::Auction);
+    assert_eq!(book.indicative_price().map(|price| price.price), Some(100));
+    book.uncross().unwrap();
+    assert_eq!(book.indicative_price(), None);
+}
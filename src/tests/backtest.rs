@@ -0,0 +1,124 @@
+#[cfg(test)]
+use crate::{
+    backtest::{
+        Backtest, BacktestContext, HistoricalEvent, STRATEGY_ORDER_ID_BASE, Strategy, StrategyFill,
+    },
+    command::Command,
+    types::{OrderId, Side},
+};
+
+#[cfg(test)]
+fn limit(timestamp: u64, side: Side, order_id: u64, price: i64, quantity: u64) -> HistoricalEvent {
+    HistoricalEvent {
+        timestamp,
+        command: Command::Limit {
+            side,
+            order_id: OrderId(order_id),
+            price,
+            quantity,
+        },
+    }
+}
+
+#[cfg(test)]
+fn market(timestamp: u64, side: Side, quantity: u64) -> HistoricalEvent {
+    HistoricalEvent {
+        timestamp,
+        command: Command::Market { side, quantity },
+    }
+}
+
+// Joins the best bid once, then flattens any position it's filled into
+#[cfg(test)]
+#[derive(Debug, Default)]
+struct JoinTheBid {
+    joined: bool,
+    fills_seen: usize,
+}
+
+#[cfg(test)]
+impl Strategy for JoinTheBid {
+    fn on_event(&mut self, context: &mut BacktestContext<'_>, _event: &HistoricalEvent) {
+        if !self.joined
+            && let Some((price, _)) = context.book().best_bid()
+        {
+            context.limit(Side::Bid, price, 5).unwrap();
+            self.joined = true;
+        }
+    }
+
+    fn on_fill(&mut self, context: &mut BacktestContext<'_>, fill: &StrategyFill) {
+        self.fills_seen += 1;
+        if !fill.aggressive {
+            context.market(Side::Ask, fill.quantity).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_backtest_strategy_orders_queue_behind_history() {
+    let mut backtest = Backtest::new(JoinTheBid::default());
+    let report = backtest.run([
+        limit(10, Side::Bid, 1, 99, 10),
+        limit(20, Side::Bid, 2, 98, 20),
+        // Fills the historical order ahead of the strategy's first
+        market(30, Side::Ask, 10),
+        // Then reaches the strategy's 5 before the liquidity behind it at 98
+        market(40, Side::Ask, 7),
+    ]);
+
+    assert_eq!(report.events, 4);
+    assert_eq!(report.rejected_events, 0);
+    assert_eq!(
+        report.fills,
+        [
+            StrategyFill {
+                timestamp: 40,
+                order_id: OrderId(STRATEGY_ORDER_ID_BASE),
+                side: Side::Bid,
+                price: 99,
+                quantity: 5,
+                aggressive: false,
+            },
+            StrategyFill {
+                timestamp: 40,
+                order_id: OrderId(0),
+                side: Side::Ask,
+                price: 98,
+                quantity: 5,
+                aggressive: true,
+            },
+        ]
+    );
+    assert_eq!((report.position(), report.cash()), (0, -5));
+    assert_eq!(backtest.strategy().fills_seen, 2);
+    assert_eq!(backtest.book().best_bid(), Some((98, 13)));
+}
+
+#[test]
+fn test_backtest_counts_history_the_strategy_displaced() {
+    // Takes the whole offer as soon as it appears
+    let mut backtest = Backtest::new(|context: &mut BacktestContext<'_>, _: &HistoricalEvent| {
+        if context.book().best_ask().is_some() {
+            context.market(Side::Bid, 100).unwrap();
+        }
+    });
+    let report = backtest.run([
+        limit(1, Side::Ask, 1, 101, 10),
+        HistoricalEvent {
+            timestamp: 2,
+            command: Command::Cancel {
+                order_id: OrderId(1),
+            },
+        },
+    ]);
+
+    assert_eq!(report.rejected_events, 1);
+    assert_eq!(report.position(), 10);
+    assert!(
+        report
+            .fills
+            .iter()
+            .all(|fill| fill.aggressive && fill.timestamp == 1)
+    );
+}
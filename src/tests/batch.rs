@@ -0,0 +1,160 @@
+#[cfg(test)]
+use crate::{
+    command::{Command, CommandResult},
+    error::{CancelOrderError, CommandError, LimitOrderError},
+    events::BookEvent,
+    orderbook::OrderBook,
+    types::{Fill, LimitOrderResult, MarketOrderResult, OrderId, Side, TradeId},
+};
+
+#[test]
+fn test_apply_batch_in_sequence() {
+    let mut book = OrderBook::new();
+
+    let results = book.apply_batch(&[
+        Command::Limit {
+            side: Side::Ask,
+            order_id: OrderId(1),
+            price: 100,
+            quantity: 5,
+        },
+        Command::Limit {
+            side: Side::Ask,
+            order_id: OrderId(2),
+            price: 101,
+            quantity: 5,
+        },
+        Command::Amend {
+            order_id: OrderId(2),
+            quantity: 3,
+        },
+        Command::Cancel {
+            order_id: OrderId(1),
+        },
+        Command::Market {
+            side: Side::Bid,
+            quantity: 10,
+        },
+    ]);
+
+    assert_eq!(results.len(), 5);
+    assert!(matches!(results[0], CommandResult::Limit(Ok(_))));
+    assert!(matches!(results[1], CommandResult::Limit(Ok(_))));
+    assert_eq!(results[2], CommandResult::Amend(Ok(())));
+    assert_eq!(results[3], CommandResult::Cancel(Ok(())));
+    assert_eq!(
+        results[4],
+        CommandResult::Market(Ok(MarketOrderResult {
+            fills: vec![Fill {
+                price: 101,
+                quantity: 3,
+                maker_order_id: OrderId(2),
+                trade_id: TradeId(1),
+                timestamp: 0,
+                fees: None,
+                maker_client_order_id: None,
+                maker_remaining_quantity: 0,
+                maker_fully_filled: true,
+            }],
+            filled_quantity: 3,
+            notional: 303,
+            remaining: 7,
+            resting_quantity: 0,
+            sequence: 5,
+        }))
+    );
+
+    assert!(book.asks.is_empty());
+    assert_eq!(book.orders.len(), 0);
+}
+
+#[test]
+fn test_apply_batch_continues_after_errors() {
+    let mut book = OrderBook::new();
+
+    let results = book.apply_batch(&[
+        Command::Cancel {
+            order_id: OrderId(1),
+        },
+        Command::Limit {
+            side: Side::Bid,
+            order_id: OrderId(1),
+            price: 100,
+            quantity: 5,
+        },
+        Command::Limit {
+            side: Side::Bid,
+            order_id: OrderId(1),
+            price: 100,
+            quantity: 5,
+        },
+    ]);
+
+    assert_eq!(
+        results,
+        vec![
+            CommandResult::Cancel(Err(CancelOrderError::OrderIdNotFound)),
+            CommandResult::Limit(Ok(LimitOrderResult {
+                order_id: OrderId(1),
+                fills: vec![],
+                resting_quantity: 5,
+                order_index: Some(book.index_map.get(&OrderId(1)).unwrap().order_index),
+                queue_position: Some(0),
+                sequence: 1,
+            })),
+            CommandResult::Limit(Err(LimitOrderError::OrderIdAlreadyExists)),
+        ]
+    );
+}
+
+#[test]
+fn test_apply_returns_each_commands_events() {
+    let mut book = OrderBook::new();
+
+    let events = book
+        .apply(Command::Limit {
+            side: Side::Ask,
+            order_id: OrderId(1),
+            price: 100,
+            quantity: 5,
+        })
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            BookEvent::LevelAdded {
+                side: Side::Ask,
+                price: 100,
+            },
+            BookEvent::OrderAdded {
+                order_id: OrderId(1),
+                side: Side::Ask,
+                price: 100,
+                quantity: 5,
+                client_order_id: None,
+            },
+        ]
+    );
+    // Capturing doesn't switch recording on for the caller
+    assert!(!book.events.is_recording());
+
+    assert_eq!(
+        book.apply(Command::Cancel {
+            order_id: OrderId(9)
+        }),
+        Err(CommandError::Cancel(CancelOrderError::OrderIdNotFound))
+    );
+
+    // Events recorded by the caller are left in place, new ones are added after them
+    book.record_events(true);
+    book.execute_limit_order(Side::Bid, OrderId(2), 90, 1)
+        .unwrap();
+    let events = book
+        .apply(Command::Market {
+            side: Side::Bid,
+            quantity: 5,
+        })
+        .unwrap();
+    assert!(matches!(events[0], BookEvent::Trade { .. }));
+    assert_eq!(book.take_events().len(), 2 + events.len());
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+use crate::{
+    book_side::BookSide,
+    error::DecodeError,
+    fees::{FeeRate, FeeSchedule, FeeTier},
+    instrument::Instrument,
+    orderbook::OrderBook,
+    pegged_orders::PegType,
+    types::{OrderId, Side},
+};
+
+#[cfg(test)]
+fn populated_book() -> OrderBook {
+    let mut book = OrderBook::with_instrument(Instrument::default());
+    book.reject_non_positive_prices = true;
+    book.fee_schedule = Some(FeeSchedule {
+        maker: FeeRate::Bps(-1),
+        taker: FeeRate::Fixed(25),
+        tiers: vec![FeeTier {
+            min_volume: 100,
+            maker: FeeRate::Bps(-2),
+            taker: FeeRate::Fixed(20),
+        }],
+        volume_window: 60,
+    });
+    for id in 1..=6 {
+        let side = if id % 2 == 0 { Side::Bid } else { Side::Ask };
+        let price = if side == Side::Bid { 99 } else { 101 };
+        book.execute_limit_order(side, OrderId(id), price, 10)
+            .unwrap();
+    }
+    // Leave holes in the slab, and some trades behind
+    book.cancel_order(OrderId(2)).unwrap();
+    book.execute_market_order(Side::Bid, 15).unwrap();
+    book.execute_gtd_order(Side::Bid, OrderId(7), 98, 5, 500)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Ask, OrderId(8), 95, 94, 3)
+        .unwrap();
+    book.execute_pegged_order(Side::Ask, OrderId(9), PegType::Primary, 1, 4)
+        .unwrap();
+    book
+}
+
+#[test]
+fn test_binary_snapshot_round_trip() {
+    let mut book = populated_book();
+    let mut restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+
+    assert_eq!(restored.pegged_orders, book.pegged_orders);
+    assert_eq!(restored.stop_orders, book.stop_orders);
+    assert_eq!(restored.instrument, book.instrument);
+    assert!(restored.reject_non_positive_prices);
+    assert_eq!(restored.fee_schedule, book.fee_schedule);
+    assert_eq!(restored.best_ask(), book.best_ask());
+    assert_eq!(restored.level3_snapshot(), book.level3_snapshot());
+    for (order_id, entry) in &book.index_map {
+        let restored_entry = &restored.index_map[order_id];
+        assert_eq!(restored_entry.expires_at, entry.expires_at);
+        assert_eq!(restored_entry.timestamp, entry.timestamp);
+    }
+    // Laid out afresh, without the holes
+    assert_eq!(restored.orders.capacity(), restored.orders.len());
+
+    // Re-encoding gives the same bytes, and both books carry on identically
+    assert_eq!(restored.to_bytes(), book.to_bytes());
+    assert_eq!(
+        restored.execute_market_order(Side::Ask, 12),
+        book.execute_market_order(Side::Ask, 12)
+    );
+    assert_eq!(restored.expire_orders(500), book.expire_orders(500));
+    assert_eq!(restored.to_bytes(), book.to_bytes());
+}
+
+#[test]
+fn test_binary_snapshot_empty_book() {
+    let book = OrderBook::new();
+    let restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert!(restored.bids.is_empty() && restored.asks.is_empty());
+    assert_eq!(restored.instrument, None);
+}
+
+#[test]
+fn test_binary_snapshot_rejects_bad_input() {
+    let bytes = populated_book().to_bytes();
+
+    assert_eq!(
+        OrderBook::from_bytes(b"NOPE").unwrap_err(),
+        DecodeError::BadMagic
+    );
+
+    let mut future = bytes.clone();
+    future[4] = 99;
+    assert_eq!(
+        OrderBook::from_bytes(&future).unwrap_err(),
+        DecodeError::UnsupportedVersion(99)
+    );
+
+    for len in 0..bytes.len() {
+        assert!(OrderBook::from_bytes(&bytes[..len]).is_err());
+    }
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        OrderBook::from_bytes(&trailing).unwrap_err(),
+        DecodeError::Corrupt
+    );
+}
+
+#[test]
+fn test_binary_snapshot_keeps_array_sides() {
+    let instrument = Instrument::builder()
+        .tick_size(5)
+        .price_range(50, 1_000)
+        .build()
+        .unwrap();
+    let mut book = OrderBook::with_array_sides(instrument).unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(1), 200, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 300, 3)
+        .unwrap();
+
+    let restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    for (side, restored_side) in [(&book.bids, &restored.bids), (&book.asks, &restored.asks)] {
+        let (BookSide::Array(array), BookSide::Array(restored_array)) = (side, restored_side)
+        else {
+            panic!("expected array backed sides");
+        };
+        assert_eq!(restored_array.bounds(), array.bounds());
+    }
+    assert!(matches!(restored.hidden_bids, BookSide::Tree(_)));
+    assert_eq!(restored.level3_snapshot(), book.level3_snapshot());
+    assert_eq!(restored.to_bytes(), book.to_bytes());
+}
+
+#[test]
+fn test_binary_snapshot_rejects_repeated_stop_prices() {
+    let mut book = OrderBook::new();
+    book.execute_stop_limit_order(Side::Bid, OrderId(1), 7_777, 7_777, 1)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Bid, OrderId(2), 8_888, 8_888, 1)
+        .unwrap();
+    let mut bytes = book.to_bytes();
+    assert!(OrderBook::from_bytes(&bytes).is_ok());
+
+    // The second queue claims the first one's stop price
+    let at = bytes
+        .windows(8)
+        .position(|window| window == 8_888i64.to_le_bytes())
+        .unwrap();
+    bytes[at..at + 8].copy_from_slice(&7_777i64.to_le_bytes());
+    assert_eq!(
+        OrderBook::from_bytes(&bytes).unwrap_err(),
+        DecodeError::Corrupt
+    );
+}
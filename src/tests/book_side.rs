@@ -0,0 +1,133 @@
+#[cfg(test)]
+use crate::{
+    book_side::{BookSide, MAX_ARRAY_LEVELS, PriceArray},
+    error::{InstrumentError, LimitOrderError},
+    instrument::Instrument,
+    orderbook::{OrderBook, PriceLevel},
+    types::{OrderId, Side},
+};
+
+#[cfg(test)]
+fn instrument() -> Instrument {
+    Instrument::builder()
+        .tick_size(5)
+        .price_range(50, 1_000)
+        .build()
+        .unwrap()
+}
+
+#[cfg(test)]
+fn level(total_quantity: u64) -> PriceLevel {
+    PriceLevel {
+        head: 0,
+        tail: 0,
+        order_count: 1,
+        total_quantity,
+    }
+}
+
+#[test]
+fn test_price_array_levels_in_price_order() {
+    let mut side = BookSide::Array(PriceArray::new(0, 1_000, 1).unwrap());
+
+    for price in [640, 3, 999, 64, 0, 128] {
+        assert_eq!(side.insert(price, level(price as u64)), None);
+    }
+    assert_eq!(side.len(), 6);
+    assert_eq!(side.first().map(|(price, _)| price), Some(0));
+    assert_eq!(side.last().map(|(price, _)| price), Some(999));
+
+    let prices: Vec<_> = side.iter().map(|(price, _)| price).collect();
+    assert_eq!(prices, [0, 3, 64, 128, 640, 999]);
+    let prices: Vec<_> = side.range(4..=640).rev().map(|(price, _)| price).collect();
+    assert_eq!(prices, [640, 128, 64]);
+
+    assert_eq!(side.remove(&999), Some(level(999)));
+    assert_eq!(side.remove(&999), None);
+    assert_eq!(
+        side.last_mut()
+            .map(|(price, level)| (price, level.total_quantity)),
+        Some((640, 640))
+    );
+    assert!(!side.contains_key(&999));
+    assert!(!side.accepts(1_001));
+}
+
+#[test]
+fn test_price_array_rejects_wide_range() {
+    assert!(PriceArray::new(0, 100, 0).is_none());
+    assert!(PriceArray::new(100, 0, 1).is_none());
+    assert!(PriceArray::new(i64::MIN, i64::MAX, 1).is_none());
+    // One tick past the memory cap
+    assert!(PriceArray::new(0, MAX_ARRAY_LEVELS as i64, 1).is_none());
+    assert!(PriceArray::new(0, MAX_ARRAY_LEVELS as i64, 2).is_some());
+
+    let wide = Instrument::builder()
+        .price_range(0, i64::MAX)
+        .build()
+        .unwrap();
+    assert_eq!(
+        OrderBook::with_array_sides(wide).err(),
+        Some(InstrumentError::PriceRangeTooWide)
+    );
+    // Slots count ticks up from the minimum price, so it has to be on the tick grid
+    let misaligned = Instrument::builder()
+        .tick_size(5)
+        .price_range(52, 1_000)
+        .build()
+        .unwrap();
+    assert_eq!(
+        OrderBook::with_array_sides(misaligned).err(),
+        Some(InstrumentError::PriceNotOnTick)
+    );
+}
+
+#[test]
+fn test_array_sides_match_tree_sides() {
+    let mut tree = OrderBook::with_instrument(instrument());
+    let mut array = OrderBook::with_array_sides(instrument()).unwrap();
+
+    for book in [&mut tree, &mut array] {
+        book.execute_limit_order(Side::Bid, OrderId(1), 100, 10)
+            .unwrap();
+        book.execute_limit_order(Side::Bid, OrderId(2), 95, 20)
+            .unwrap();
+        book.execute_limit_order(Side::Ask, OrderId(3), 110, 5)
+            .unwrap();
+        book.execute_limit_order(Side::Ask, OrderId(4), 120, 15)
+            .unwrap();
+        book.execute_limit_order(Side::Ask, OrderId(5), 90, 25)
+            .unwrap();
+        book.cancel_order(OrderId(4)).unwrap();
+        book.execute_market_order(Side::Bid, 3).unwrap();
+    }
+
+    assert!(matches!(array.bids, BookSide::Array(_)));
+    assert_eq!(tree.bids, array.bids);
+    assert_eq!(tree.asks, array.asks);
+    assert_eq!(array.best_bid(), tree.best_bid());
+    assert_eq!(array.best_ask(), tree.best_ask());
+    assert_eq!(array.depth(5), tree.depth(5));
+}
+
+#[test]
+fn test_array_sides_reject_out_of_range_price() {
+    let mut book = OrderBook::with_array_sides(instrument()).unwrap();
+
+    assert_eq!(
+        book.execute_limit_order(Side::Bid, OrderId(1), 1_005, 10),
+        Err(LimitOrderError::Instrument(
+            InstrumentError::PriceOutOfRange
+        ))
+    );
+
+    // Without instrument checks the side itself still refuses the price
+    book.instrument = None;
+    assert_eq!(
+        book.execute_limit_order(Side::Bid, OrderId(1), 1_005, 10),
+        Err(LimitOrderError::Instrument(
+            InstrumentError::PriceOutOfRange
+        ))
+    );
+    assert!(book.orders.is_empty());
+}
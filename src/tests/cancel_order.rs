@@ -43,7 +43,8 @@ fn test_cancel_first_bid_of_three() {
             quantity: 2,
             order_id: OrderId(2),
             previous: None,
-            next: Some(third)
+            next: Some(third),
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -53,7 +54,8 @@ fn test_cancel_first_bid_of_three() {
             quantity: 3,
             order_id: OrderId(3),
             previous: Some(second),
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -65,7 +67,8 @@ fn test_cancel_first_bid_of_three() {
         PriceLevel {
             head: second,
             tail: third,
-            order_count: 2
+            order_count: 2,
+            total_quantity: 5
         }
     );
 }
@@ -101,7 +104,8 @@ fn test_cancel_second_bid_of_three() {
             quantity: 1,
             order_id: OrderId(1),
             previous: None,
-            next: Some(third)
+            next: Some(third),
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -112,7 +116,8 @@ fn test_cancel_second_bid_of_three() {
             quantity: 3,
             order_id: OrderId(3),
             previous: Some(first),
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -124,7 +129,8 @@ fn test_cancel_second_bid_of_three() {
         PriceLevel {
             head: first,
             tail: third,
-            order_count: 2
+            order_count: 2,
+            total_quantity: 4
         }
     );
 }
@@ -160,7 +166,8 @@ fn test_cancel_third_bid_of_three() {
             quantity: 1,
             order_id: OrderId(1),
             previous: None,
-            next: Some(second)
+            next: Some(second),
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -170,7 +177,8 @@ fn test_cancel_third_bid_of_three() {
             quantity: 2,
             order_id: OrderId(2),
             previous: Some(first),
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -183,7 +191,8 @@ fn test_cancel_third_bid_of_three() {
         PriceLevel {
             head: first,
             tail: second,
-            order_count: 2
+            order_count: 2,
+            total_quantity: 3
         }
     );
 }
@@ -220,7 +229,8 @@ fn test_cancel_first_ask_of_three() {
             quantity: 2,
             order_id: OrderId(2),
             previous: None,
-            next: Some(third)
+            next: Some(third),
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -230,7 +240,8 @@ fn test_cancel_first_ask_of_three() {
             quantity: 3,
             order_id: OrderId(3),
             previous: Some(second),
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -242,7 +253,8 @@ fn test_cancel_first_ask_of_three() {
         PriceLevel {
             head: second,
             tail: third,
-            order_count: 2
+            order_count: 2,
+            total_quantity: 5
         }
     );
 }
@@ -278,7 +290,8 @@ fn test_cancel_second_ask_of_three() {
             quantity: 1,
             order_id: OrderId(1),
             previous: None,
-            next: Some(third)
+            next: Some(third),
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -289,7 +302,8 @@ fn test_cancel_second_ask_of_three() {
             quantity: 3,
             order_id: OrderId(3),
             previous: Some(first),
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -301,7 +315,8 @@ fn test_cancel_second_ask_of_three() {
         PriceLevel {
             head: first,
             tail: third,
-            order_count: 2
+            order_count: 2,
+            total_quantity: 4
         }
     );
 }
@@ -337,7 +352,8 @@ fn test_cancel_third_ask_of_three() {
             quantity: 1,
             order_id: OrderId(1),
             previous: None,
-            next: Some(second)
+            next: Some(second),
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -347,7 +363,8 @@ fn test_cancel_third_ask_of_three() {
             quantity: 2,
             order_id: OrderId(2),
             previous: Some(first),
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -360,7 +377,80 @@ fn test_cancel_third_ask_of_three() {
         PriceLevel {
             head: first,
             tail: second,
-            order_count: 2
+            order_count: 2,
+            total_quantity: 3
         }
     );
 }
+
+#[test]
+fn test_cancel_quantity_keeps_priority() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 1, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 1, 10)
+        .unwrap();
+
+    let first = book.index_map.get(&OrderId(1)).unwrap().order_index;
+    let second = book.index_map.get(&OrderId(2)).unwrap().order_index;
+
+    let remaining = book.cancel_quantity(OrderId(1), 4).unwrap();
+    assert_eq!(remaining, 6);
+
+    assert_eq!(
+        book.orders.get(first),
+        Some(&OrderNode {
+            quantity: 6,
+            order_id: OrderId(1),
+            previous: None,
+            next: Some(second),
+            client_order_id: None,
+        })
+    );
+    assert_eq!(
+        *book.bids.get(&1).unwrap(),
+        PriceLevel {
+            head: first,
+            tail: second,
+            order_count: 2,
+            total_quantity: 16
+        }
+    );
+}
+
+#[test]
+fn test_cancel_quantity_removes_exhausted_order() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 1, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 1, 10)
+        .unwrap();
+
+    let second = book.index_map.get(&OrderId(2)).unwrap().order_index;
+
+    assert_eq!(book.cancel_quantity(OrderId(1), 10).unwrap(), 0);
+    assert!(book.index_map.get(&OrderId(1)).is_none());
+    assert_eq!(
+        *book.asks.get(&1).unwrap(),
+        PriceLevel {
+            head: second,
+            tail: second,
+            order_count: 1,
+            total_quantity: 10
+        }
+    );
+
+    // Reducing by more than remains also removes the order
+    assert_eq!(book.cancel_quantity(OrderId(2), 50).unwrap(), 0);
+    assert!(book.asks.is_empty());
+    assert_eq!(book.orders.len(), 0);
+}
+
+#[test]
+fn test_cancel_quantity_rejection() {
+    let mut book = OrderBook::new();
+    let result = book.cancel_quantity(OrderId(1), 1);
+    assert_eq!(result, Err(crate::error::CancelOrderError::OrderIdNotFound));
+}
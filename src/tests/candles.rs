@@ -0,0 +1,107 @@
+#[cfg(all(test, feature = "candles"))]
+use std::sync::Arc;
+
+#[cfg(all(test, feature = "candles"))]
+use crate::{
+    candles::{Candle, CandleAggregator},
+    clock::ManualClock,
+    orderbook::OrderBook,
+    types::{Fill, OrderId, Side, TradeId},
+};
+
+#[cfg(all(test, feature = "candles"))]
+fn fill(timestamp: u64, price: i64, quantity: u64) -> Fill {
+    Fill {
+        price,
+        quantity,
+        maker_order_id: OrderId(1),
+        trade_id: TradeId(1),
+        timestamp,
+        fees: None,
+        maker_client_order_id: None,
+        maker_remaining_quantity: 0,
+        maker_fully_filled: true,
+    }
+}
+
+#[cfg(feature = "candles")]
+#[test]
+fn test_candles_roll_over_at_interval_boundaries() {
+    let mut candles = CandleAggregator::new(60, 2);
+
+    assert_eq!(candles.record(&fill(61, 100, 5)), None);
+    assert_eq!(candles.record(&fill(90, 105, 2)), None);
+    assert_eq!(candles.record(&fill(119, 98, 1)), None);
+
+    let first = Candle {
+        start: 60,
+        open: 100,
+        high: 105,
+        low: 98,
+        close: 98,
+        volume: 8,
+        trade_count: 3,
+    };
+    assert_eq!(candles.current(), Some(&first));
+
+    // The empty [120, 180) interval produces no bar
+    assert_eq!(candles.record(&fill(200, 101, 4)), Some(first));
+    assert_eq!(candles.completed().len(), 1);
+
+    // A late fill is folded into the open bar
+    assert_eq!(candles.record(&fill(150, 99, 1)), None);
+    let second = candles.current().copied().unwrap();
+    assert_eq!((second.start, second.low, second.close), (180, 99, 99));
+
+    // Only the most recent `history` completed bars are kept
+    candles.record(&fill(240, 102, 1));
+    candles.record(&fill(300, 103, 1));
+    let starts: Vec<_> = candles.candles().map(|candle| candle.start).collect();
+    assert_eq!(starts, vec![180, 240, 300]);
+}
+
+#[cfg(feature = "candles")]
+#[test]
+fn test_candles_from_book_events() {
+    let clock = Arc::new(ManualClock::new(1_000));
+    let mut book = OrderBook::with_clock(clock.clone());
+    book.record_events(true);
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 10)
+        .unwrap();
+
+    book.execute_market_order(Side::Bid, 12).unwrap();
+    clock.set(2_500);
+    book.execute_market_order(Side::Bid, 3).unwrap();
+
+    let mut candles = CandleAggregator::new(1_000, 10);
+    for event in book.take_events() {
+        candles.record_event(&event);
+    }
+
+    let bars: Vec<_> = candles.candles().copied().collect();
+    assert_eq!(
+        bars,
+        vec![
+            Candle {
+                start: 1_000,
+                open: 100,
+                high: 101,
+                low: 100,
+                close: 101,
+                volume: 12,
+                trade_count: 2,
+            },
+            Candle {
+                start: 2_000,
+                open: 101,
+                high: 101,
+                low: 101,
+                close: 101,
+                volume: 3,
+                trade_count: 1,
+            },
+        ]
+    );
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+use crate::{
+    events::BookEvent,
+    order::{Order, TimeInForce},
+    orderbook::OrderBook,
+    types::{ClientOrderId, OrderId, Side},
+};
+
+#[cfg(test)]
+fn tagged_book() -> OrderBook {
+    let mut book = OrderBook::new();
+    book.record_events(true);
+    let order = Order::builder(Side::Ask, OrderId(1), 100, 10)
+        .client_order_id(ClientOrderId(77))
+        .build();
+    book.submit_order(&order).unwrap();
+    book
+}
+
+#[test]
+fn test_client_order_id_is_echoed_while_resting() {
+    let mut book = tagged_book();
+    assert_eq!(
+        book.take_events()[1],
+        BookEvent::OrderAdded {
+            order_id: OrderId(1),
+            side: Side::Ask,
+            price: 100,
+            quantity: 10,
+            client_order_id: Some(ClientOrderId(77)),
+        }
+    );
+    let details = book.get_order(OrderId(1)).unwrap();
+    assert_eq!(details.client_order_id, Some(ClientOrderId(77)));
+
+    let fills = book.execute_market_order(Side::Bid, 3).unwrap().fills;
+    assert_eq!(fills[0].maker_client_order_id, Some(ClientOrderId(77)));
+
+    book.take_events();
+    book.cancel_quantity(OrderId(1), 2).unwrap();
+    book.cancel_order(OrderId(1)).unwrap();
+    assert_eq!(
+        book.take_events()[..2],
+        [
+            BookEvent::OrderReduced {
+                order_id: OrderId(1),
+                side: Side::Ask,
+                price: 100,
+                new_quantity: 5,
+                client_order_id: Some(ClientOrderId(77)),
+            },
+            BookEvent::OrderCancelled {
+                order_id: OrderId(1),
+                side: Side::Ask,
+                price: 100,
+                quantity: 5,
+                client_order_id: Some(ClientOrderId(77)),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_client_order_id_survives_amends_and_mass_cancels() {
+    let mut book = tagged_book();
+    book.amend_quantity(OrderId(1), 20).unwrap();
+    let events = book.take_events();
+    assert!(events.contains(&BookEvent::OrderAdded {
+        order_id: OrderId(1),
+        side: Side::Ask,
+        price: 100,
+        quantity: 20,
+        client_order_id: Some(ClientOrderId(77)),
+    }));
+
+    let cancelled = book.cancel_range(Side::Ask, ..);
+    assert_eq!(cancelled[0].client_order_id, Some(ClientOrderId(77)));
+}
+
+#[test]
+fn test_client_order_id_only_tags_its_own_order() {
+    let mut book = tagged_book();
+
+    // Fully filled on arrival, so nothing rests to carry the tag
+    let order = Order::builder(Side::Bid, OrderId(2), 100, 4)
+        .client_order_id(ClientOrderId(5))
+        .build();
+    book.submit_order(&order).unwrap();
+    let order = Order::builder(Side::Bid, OrderId(3), 100, 4)
+        .time_in_force(TimeInForce::ImmediateOrCancel)
+        .client_order_id(ClientOrderId(6))
+        .build();
+    book.submit_order(&order).unwrap();
+
+    book.execute_limit_order(Side::Bid, OrderId(4), 99, 1)
+        .unwrap();
+    assert_eq!(book.get_order(OrderId(4)).unwrap().client_order_id, None);
+}
+
+#[test]
+fn test_client_order_id_survives_binary_snapshots() {
+    let book = tagged_book();
+    let restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert_eq!(
+        restored.get_order(OrderId(1)).unwrap().client_order_id,
+        Some(ClientOrderId(77))
+    );
+    assert_eq!(restored.to_bytes(), book.to_bytes());
+}
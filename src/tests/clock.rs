@@ -0,0 +1,96 @@
+#[cfg(test)]
+use std::sync::Arc;
+
+#[cfg(test)]
+use crate::{
+    clock::{Clock, ManualClock, SystemClock},
+    events::BookEvent,
+    orderbook::OrderBook,
+    sbe::{decode_event, encode_event},
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_orders_and_fills_are_timestamped() {
+    let clock = Arc::new(ManualClock::new(1_000));
+    let mut book = OrderBook::with_clock(clock.clone());
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    clock.advance(500);
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+    assert_eq!(book.get_order(OrderId(1)).unwrap().timestamp, 1_000);
+    assert_eq!(book.get_order(OrderId(2)).unwrap().timestamp, 1_500);
+
+    clock.set(2_000);
+    let fills = book.execute_market_order(Side::Bid, 7).unwrap().fills;
+    assert!(fills.iter().all(|fill| fill.timestamp == 2_000));
+
+    // Losing priority on an amend restamps the order
+    clock.set(3_000);
+    book.amend_quantity(OrderId(2), 10).unwrap();
+    assert_eq!(book.get_order(OrderId(2)).unwrap().timestamp, 3_000);
+
+    let restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert_eq!(restored.get_order(OrderId(2)).unwrap().timestamp, 3_000);
+}
+
+#[test]
+fn test_no_clock_stamps_zero() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    let fills = book.execute_market_order(Side::Ask, 5).unwrap().fills;
+    assert_eq!(fills[0].timestamp, 0);
+    assert_eq!(book.now(), 0);
+
+    book.set_clock(SystemClock);
+    assert!(book.now() > 0);
+}
+
+#[test]
+fn test_expire_due_orders() {
+    let clock = Arc::new(ManualClock::new(0));
+    let mut book = OrderBook::with_clock(clock.clone());
+
+    book.execute_gtd_order(Side::Bid, OrderId(1), 100, 5, 10)
+        .unwrap();
+    book.execute_gtd_order(Side::Bid, OrderId(2), 99, 5, 20)
+        .unwrap();
+
+    clock.set(9);
+    assert!(book.expire_due_orders().is_empty());
+    clock.set(15);
+    assert_eq!(book.expire_due_orders(), vec![OrderId(1)]);
+    assert_eq!(clock.now(), 15);
+}
+
+#[test]
+fn test_sbe_trade_timestamp() {
+    let mut book = OrderBook::with_clock(ManualClock::new(42));
+    book.record_events(true);
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_market_order(Side::Ask, 5).unwrap();
+    let trade = book
+        .take_events()
+        .into_iter()
+        .find(|event| matches!(event, BookEvent::Trade { .. }))
+        .unwrap();
+
+    let mut bytes = Vec::new();
+    encode_event(&trade, &mut bytes);
+    assert_eq!(decode_event(&bytes), Ok((trade.clone(), bytes.len())));
+
+    // A version 1 Trade has no timestamp or maker remaining quantity fields
+    let mut legacy = bytes[..bytes.len() - 16].to_vec();
+    legacy[0] = 33;
+    legacy[6] = 1;
+    let (decoded, used) = decode_event(&legacy).unwrap();
+    assert_eq!(used, legacy.len());
+    let BookEvent::Trade { fill, .. } = decoded else {
+        panic!("expected a trade");
+    };
+    assert_eq!(fill.timestamp, 0);
+}
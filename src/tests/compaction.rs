@@ -0,0 +1,48 @@
+#[cfg(test)]
+use crate::{
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_compact_keeps_priority() {
+    let mut book = OrderBook::new();
+
+    for id in 0..100 {
+        let side = if id % 2 == 0 { Side::Bid } else { Side::Ask };
+        let price = if side == Side::Bid { 100 } else { 101 };
+        book.execute_limit_order(side, OrderId(id), price, id + 1)
+            .unwrap();
+    }
+    // Leave a handful of orders scattered across the slab
+    for id in 0..100 {
+        if id % 10 >= 3 {
+            book.cancel_order(OrderId(id)).unwrap();
+        }
+    }
+    let before = book.level3_snapshot();
+
+    book.compact();
+
+    assert_eq!(book.orders.len(), 30);
+    assert!(book.orders.capacity() < 100);
+    assert_eq!(book.level3_snapshot(), before);
+    for (id, entry) in &book.index_map {
+        assert_eq!(book.orders.get(entry.order_index).unwrap().order_id, *id);
+    }
+
+    // Links were rewritten, so matching still walks each level in time priority
+    let fills = book
+        .execute_market_order(Side::Ask, 1 + 3 + 11)
+        .unwrap()
+        .fills;
+    let makers: Vec<_> = fills.iter().map(|fill| fill.maker_order_id).collect();
+    assert_eq!(makers, vec![OrderId(0), OrderId(2), OrderId(10)]);
+
+    book.execute_limit_order(Side::Bid, OrderId(200), 100, 5)
+        .unwrap();
+    assert_eq!(
+        book.orders_at(Side::Bid, 100).last(),
+        Some((OrderId(200), 5))
+    );
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+use crate::{
+    error::CsvError,
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_csv_export() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 99, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(3), 101, 7)
+        .unwrap();
+
+    assert_eq!(
+        book.to_csv(),
+        "side,id,price,qty,priority\nbid,1,99,10,0\nbid,2,99,5,1\nask,3,101,7,0\n"
+    );
+}
+
+#[test]
+fn test_csv_import_preserves_priority() {
+    // Rows out of order, priority decides the queue
+    let csv = "side,id,price,qty,priority\n\
+               ask,3,101,7,0\n\
+               bid,2,99,5,1\n\
+               bid,4,98,1,0\n\
+               bid,1,99,10,0\n";
+    let book = OrderBook::from_csv(csv).unwrap();
+
+    assert_eq!(
+        book.orders_at(Side::Bid, 99).collect::<Vec<_>>(),
+        vec![(OrderId(1), 10), (OrderId(2), 5)]
+    );
+    assert_eq!(book.best_bid(), Some((99, 15)));
+    assert_eq!(book.best_ask(), Some((101, 7)));
+    assert_eq!(
+        OrderBook::from_csv(&book.to_csv()).unwrap().to_csv(),
+        book.to_csv()
+    );
+}
+
+#[test]
+fn test_csv_import_errors() {
+    assert_eq!(
+        OrderBook::from_csv("bid,1,99,10,0\nbuy,2,99,5,1").unwrap_err(),
+        CsvError::InvalidRow(2)
+    );
+    assert_eq!(
+        OrderBook::from_csv("bid,1,99,ten,0").unwrap_err(),
+        CsvError::InvalidRow(1)
+    );
+    assert_eq!(
+        OrderBook::from_csv("bid,1,99,10,0\nbid,2,99,5,0").unwrap_err(),
+        CsvError::DuplicatePriority(2)
+    );
+}
@@ -0,0 +1,151 @@
+#[cfg(test)]
+use crate::{
+    dark_pool::{DarkOrder, DarkPool},
+    error::{CancelOrderError, DarkOrderError},
+    orderbook::OrderBook,
+    trading_state::TradingState,
+    types::{OrderId, Price, Side, TradeId},
+};
+
+#[cfg(test)]
+fn lit_book(bid: Price, ask: Price) -> OrderBook {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), bid, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), ask, 10)
+        .unwrap();
+    book
+}
+
+#[cfg(test)]
+fn dark(side: Side, order_id: u64, quantity: u64, limit_price: Option<Price>) -> DarkOrder {
+    DarkOrder {
+        side,
+        order_id: OrderId(order_id),
+        quantity,
+        limit_price,
+    }
+}
+
+#[test]
+fn test_dark_orders_cross_at_lit_midpoint() {
+    let book = lit_book(100, 103);
+    let mut pool = DarkPool::new();
+
+    assert!(
+        pool.submit(&book, dark(Side::Bid, 1, 5, None))
+            .unwrap()
+            .is_empty()
+    );
+    let fills = pool.submit(&book, dark(Side::Ask, 2, 3, None)).unwrap();
+
+    assert_eq!(fills.len(), 1);
+    let fill = fills[0];
+    assert_eq!(
+        (fill.buy_order_id, fill.sell_order_id),
+        (OrderId(1), OrderId(2))
+    );
+    assert_eq!(fill.quantity, 3);
+    assert_eq!(fill.price(), 101.5);
+    assert_eq!(fill.trade_id, TradeId(1));
+    assert_eq!(pool.get_order(OrderId(1)).unwrap().quantity, 2);
+    assert_eq!(pool.get_order(OrderId(2)), None);
+    assert_eq!(pool.volume(Side::Bid), 2);
+    assert_eq!(pool.order_count(Side::Ask), 0);
+
+    // The lit book is never touched
+    assert_eq!(book.best_bid(), Some((100, 10)));
+    assert_eq!(book.best_ask(), Some((103, 10)));
+}
+
+#[test]
+fn test_dark_pool_time_priority() {
+    let book = lit_book(100, 102);
+    let mut pool = DarkPool::new();
+    pool.submit(&book, dark(Side::Ask, 1, 2, None)).unwrap();
+    pool.submit(&book, dark(Side::Ask, 2, 2, None)).unwrap();
+
+    let fills = pool.submit(&book, dark(Side::Bid, 3, 3, None)).unwrap();
+
+    let sellers: Vec<_> = fills
+        .iter()
+        .map(|fill| (fill.sell_order_id, fill.quantity))
+        .collect();
+    assert_eq!(sellers, [(OrderId(1), 2), (OrderId(2), 1)]);
+    assert_eq!(fills[1].trade_id, TradeId(2));
+}
+
+#[test]
+fn test_dark_pool_limit_skips_order_until_midpoint_moves() {
+    let mut book = lit_book(100, 104);
+    let mut pool = DarkPool::new();
+    // Midpoint 102 is above this buyer's limit, the later one takes the seller instead
+    pool.submit(&book, dark(Side::Bid, 1, 5, Some(101)))
+        .unwrap();
+    pool.submit(&book, dark(Side::Bid, 2, 5, None)).unwrap();
+    let fills = pool.submit(&book, dark(Side::Ask, 3, 5, None)).unwrap();
+    assert_eq!(fills[0].buy_order_id, OrderId(2));
+
+    pool.submit(&book, dark(Side::Ask, 4, 5, None)).unwrap();
+    assert_eq!(pool.volume(Side::Ask), 5);
+
+    // Once the lit ask drops, the midpoint is within the limit
+    book.execute_limit_order(Side::Ask, OrderId(5), 101, 10)
+        .unwrap();
+    let fills = pool.cross(&book);
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].buy_order_id, OrderId(1));
+    assert_eq!(fills[0].price(), 100.5);
+}
+
+#[test]
+fn test_dark_pool_needs_two_sided_open_book() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 10)
+        .unwrap();
+    let mut pool = DarkPool::new();
+    pool.submit(&book, dark(Side::Bid, 1, 5, None)).unwrap();
+
+    // One-sided lit book, no midpoint
+    assert!(
+        pool.submit(&book, dark(Side::Ask, 2, 5, None))
+            .unwrap()
+            .is_empty()
+    );
+
+    book.execute_limit_order(Side::Ask, OrderId(2), 102, 10)
+        .unwrap();
+    book.set_trading_state(TradingState::Halted);
+    assert!(pool.cross(&book).is_empty());
+
+    book.set_trading_state(TradingState::Open);
+    assert_eq!(pool.cross(&book).len(), 1);
+}
+
+#[test]
+fn test_dark_pool_rejects_and_cancels() {
+    let book = lit_book(100, 102);
+    let mut pool = DarkPool::new();
+    pool.submit(&book, dark(Side::Bid, 1, 5, None)).unwrap();
+
+    assert_eq!(
+        pool.submit(&book, dark(Side::Ask, 1, 5, None)),
+        Err(DarkOrderError::OrderIdAlreadyExists)
+    );
+    assert_eq!(
+        pool.submit(&book, dark(Side::Ask, 2, 0, None)),
+        Err(DarkOrderError::ZeroQuantity)
+    );
+
+    assert_eq!(pool.cancel(OrderId(1)).unwrap().quantity, 5);
+    assert_eq!(pool.order_count(Side::Bid), 0);
+    assert_eq!(
+        pool.cancel(OrderId(1)),
+        Err(CancelOrderError::OrderIdNotFound)
+    );
+    assert!(
+        pool.submit(&book, dark(Side::Ask, 2, 5, None))
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+use crate::{
+    order::{Order, TimeInForce},
+    orderbook::OrderBook,
+    trading_state::TradingState,
+    types::{OrderId, Side},
+};
+
+#[cfg(test)]
+fn submit(book: &mut OrderBook, side: Side, order_id: u64, price: i64, tif: TimeInForce) {
+    let order = Order::builder(side, OrderId(order_id), price, 5)
+        .time_in_force(tif)
+        .build();
+    book.submit_order(&order).unwrap();
+}
+
+#[test]
+fn test_cancel_day_orders_in_placement_order() {
+    let mut book = OrderBook::new();
+    submit(&mut book, Side::Bid, 1, 100, TimeInForce::Day);
+    submit(&mut book, Side::Bid, 2, 99, TimeInForce::GoodTillCancel);
+    submit(&mut book, Side::Ask, 3, 105, TimeInForce::Day);
+    submit(&mut book, Side::Ask, 4, 106, TimeInForce::GoodTillDate(50));
+    submit(&mut book, Side::Bid, 5, 98, TimeInForce::Day);
+
+    assert_eq!(book.index_map[&OrderId(1)].time_in_force, TimeInForce::Day);
+    assert_eq!(
+        book.index_map[&OrderId(2)].time_in_force,
+        TimeInForce::GoodTillCancel
+    );
+    assert_eq!(
+        book.index_map[&OrderId(4)].time_in_force,
+        TimeInForce::GoodTillDate(50)
+    );
+
+    assert_eq!(
+        book.cancel_day_orders(),
+        [OrderId(1), OrderId(3), OrderId(5)]
+    );
+    assert_eq!(book.index_map.len(), 2);
+    assert!(book.day_orders.is_empty());
+    assert!(book.cancel_day_orders().is_empty());
+}
+
+#[test]
+fn test_cancel_day_orders_skips_filled_cancelled_and_reused() {
+    let mut book = OrderBook::new();
+    submit(&mut book, Side::Ask, 1, 100, TimeInForce::Day);
+    submit(&mut book, Side::Ask, 2, 101, TimeInForce::Day);
+    submit(&mut book, Side::Ask, 3, 102, TimeInForce::Day);
+
+    book.execute_market_order(Side::Bid, 5).unwrap();
+    book.cancel_order(OrderId(2)).unwrap();
+    submit(&mut book, Side::Ask, 2, 101, TimeInForce::GoodTillCancel);
+
+    assert_eq!(book.cancel_day_orders(), [OrderId(3)]);
+    assert!(book.index_map.contains_key(&OrderId(2)));
+}
+
+#[test]
+fn test_day_order_keeps_time_in_force_through_amend_and_halt() {
+    let mut book = OrderBook::new();
+    submit(&mut book, Side::Bid, 1, 100, TimeInForce::Day);
+    book.amend_quantity(OrderId(1), 8).unwrap();
+    assert_eq!(book.index_map[&OrderId(1)].time_in_force, TimeInForce::Day);
+
+    // The session ends whatever the trading state
+    book.set_trading_state(TradingState::Halted);
+    assert_eq!(book.cancel_day_orders(), [OrderId(1)]);
+}
+
+#[test]
+fn test_day_orders_survive_binary_snapshot() {
+    let mut book = OrderBook::new();
+    submit(&mut book, Side::Bid, 1, 100, TimeInForce::Day);
+    submit(&mut book, Side::Bid, 2, 100, TimeInForce::GoodTillDate(10));
+    submit(&mut book, Side::Bid, 3, 99, TimeInForce::Day);
+    book.cancel_order(OrderId(1)).unwrap();
+
+    let mut restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    for (order_id, entry) in &book.index_map {
+        assert_eq!(
+            restored.index_map[order_id].time_in_force,
+            entry.time_in_force
+        );
+    }
+    assert_eq!(restored.cancel_day_orders(), [OrderId(3)]);
+    assert_eq!(restored.expire_orders(10), [OrderId(2)]);
+}
@@ -0,0 +1,70 @@
+#[cfg(all(test, feature = "decimal"))]
+use rust_decimal::Decimal;
+
+#[cfg(all(test, feature = "decimal"))]
+use crate::{
+    error::{InstrumentError, LimitOrderError},
+    instrument::Instrument,
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[cfg(all(test, feature = "decimal"))]
+fn decimal(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn test_decimal_price_conversion() {
+    let instrument = Instrument::builder().price_decimals(2).build().unwrap();
+
+    assert_eq!(instrument.price_from_decimal(decimal("101.25")), Ok(10_125));
+    assert_eq!(instrument.price_from_decimal(decimal("101.2")), Ok(10_120));
+    assert_eq!(instrument.price_from_decimal(decimal("-0.05")), Ok(-5));
+    assert_eq!(
+        instrument.price_from_decimal(decimal("101.255")),
+        Err(InstrumentError::PriceTooPrecise)
+    );
+    assert_eq!(
+        instrument.price_from_decimal(decimal("1e18")),
+        Err(InstrumentError::PriceOutOfRange)
+    );
+    assert_eq!(instrument.price_to_decimal(10_125), decimal("101.25"));
+
+    // Without an instrument prices are whole units
+    let book = OrderBook::new();
+    assert_eq!(book.price_from_decimal(decimal("42")), Ok(42));
+    assert_eq!(
+        book.price_from_decimal(decimal("42.5")),
+        Err(InstrumentError::PriceTooPrecise)
+    );
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn test_decimal_limit_orders() {
+    let instrument = Instrument::builder()
+        .tick_size(5)
+        .price_decimals(2)
+        .build()
+        .unwrap();
+    let mut book = OrderBook::with_instrument(instrument);
+
+    book.execute_decimal_limit_order(Side::Ask, OrderId(1), decimal("99.95"), 10)
+        .unwrap();
+    assert_eq!(book.best_ask(), Some((9_995, 10)));
+    assert_eq!(book.price_to_decimal(9_995), decimal("99.95"));
+
+    // Converted prices are still checked against the tick size
+    assert_eq!(
+        book.execute_decimal_limit_order(Side::Bid, OrderId(2), decimal("99.91"), 10),
+        Err(LimitOrderError::Instrument(InstrumentError::PriceNotOnTick))
+    );
+    assert_eq!(
+        book.execute_decimal_limit_order(Side::Bid, OrderId(2), decimal("99.951"), 10),
+        Err(LimitOrderError::Instrument(
+            InstrumentError::PriceTooPrecise
+        ))
+    );
+}
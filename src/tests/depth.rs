@@ -0,0 +1,120 @@
+#[cfg(test)]
+use crate::{
+    orderbook::OrderBook,
+    types::{Depth, OrderId, Side},
+};
+
+#[test]
+fn test_depth_empty_book() {
+    let book = OrderBook::new();
+    assert_eq!(book.depth(5), Depth::default());
+}
+
+#[test]
+fn test_depth_priority_order() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 98, 1)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 100, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(4), 99, 4)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(5), 103, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(6), 101, 6)
+        .unwrap();
+
+    assert_eq!(
+        book.depth(10),
+        Depth {
+            bids: vec![(100, 5, 2), (99, 4, 1), (98, 1, 1)],
+            asks: vec![(101, 6, 1), (103, 5, 1)],
+        }
+    );
+}
+
+#[test]
+fn test_depth_truncates_levels() {
+    let mut book = OrderBook::new();
+
+    for i in 0..5 {
+        book.execute_limit_order(Side::Bid, OrderId(i), 100 - i as i64, 1)
+            .unwrap();
+        book.execute_limit_order(Side::Ask, OrderId(10 + i), 101 + i as i64, 1)
+            .unwrap();
+    }
+
+    assert_eq!(
+        book.depth(2),
+        Depth {
+            bids: vec![(100, 1, 1), (99, 1, 1)],
+            asks: vec![(101, 1, 1), (102, 1, 1)],
+        }
+    );
+    assert_eq!(book.depth(0), Depth::default());
+}
+
+#[test]
+fn test_level_iterators_priority_order() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 98, 1)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 99, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(4), 103, 4)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(5), 101, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(6), 101, 6)
+        .unwrap();
+
+    let bids: Vec<_> = book
+        .bids()
+        .map(|(price, level)| (price, level.total_quantity))
+        .collect();
+    assert_eq!(bids, vec![(100, 2), (99, 3), (98, 1)]);
+
+    let asks: Vec<_> = book
+        .asks()
+        .map(|(price, level)| (price, level.order_count))
+        .collect();
+    assert_eq!(asks, vec![(101, 2), (103, 1)]);
+
+    // Worst price first when reversed
+    assert_eq!(book.bids().next_back().map(|(price, _)| price), Some(98));
+}
+
+#[test]
+fn test_depth_json() {
+    let mut book = OrderBook::new();
+    assert_eq!(
+        book.depth_json(5, 0),
+        r#"{"bids":[],"asks":[],"sequence":0}"#
+    );
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 99, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 98, 7)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(4), 101, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(5), 102, 4)
+        .unwrap();
+
+    assert_eq!(
+        book.depth_json(1, 42),
+        r#"{"bids":[[99,15]],"asks":[[101,3]],"sequence":42}"#
+    );
+    assert_eq!(
+        book.depth_json(5, 43),
+        r#"{"bids":[[99,15],[98,7]],"asks":[[101,3],[102,4]],"sequence":43}"#
+    );
+}
@@ -0,0 +1,141 @@
+#[cfg(test)]
+use crate::{
+    depth_delta::{DepthDelta, DepthDeltaTracker, diff_depth},
+    orderbook::OrderBook,
+    types::{Depth, OrderId, Side},
+};
+
+#[test]
+fn test_depth_delta_insert_update_delete() {
+    let mut book = OrderBook::new();
+    book.record_events(true);
+    let mut tracker = DepthDeltaTracker::new(&book);
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 99, 4)
+        .unwrap();
+    let events = book.take_events();
+    assert_eq!(
+        tracker.update(&book, &events),
+        vec![
+            DepthDelta::Inserted {
+                side: Side::Ask,
+                price: 100,
+                quantity: 8
+            },
+            DepthDelta::Inserted {
+                side: Side::Bid,
+                price: 99,
+                quantity: 4
+            },
+        ]
+    );
+
+    book.execute_market_order(Side::Bid, 6).unwrap();
+    book.cancel_order(OrderId(3)).unwrap();
+    let events = book.take_events();
+    assert_eq!(
+        tracker.update(&book, &events),
+        vec![
+            DepthDelta::Updated {
+                side: Side::Ask,
+                price: 100,
+                quantity: 2
+            },
+            DepthDelta::Deleted {
+                side: Side::Bid,
+                price: 99
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_depth_delta_skips_transient_levels() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 4)
+        .unwrap();
+    book.record_events(true);
+    let mut tracker = DepthDeltaTracker::new(&book);
+
+    // A level that appears and disappears within one batch is never published
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+    book.cancel_order(OrderId(2)).unwrap();
+
+    // A level that ends the batch at its published size is unchanged
+    book.amend_quantity(OrderId(1), 2).unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 99, 2)
+        .unwrap();
+
+    let events = book.take_events();
+    assert!(tracker.update(&book, &events).is_empty());
+}
+
+#[test]
+fn test_diff_depth_snapshots() {
+    let before = Depth {
+        bids: vec![(99, 4, 1), (98, 2, 1)],
+        asks: vec![(100, 5, 2), (102, 1, 1)],
+    };
+    let after = Depth {
+        bids: vec![(99, 4, 2), (97, 3, 1)],
+        asks: vec![(100, 3, 1), (101, 2, 1), (102, 1, 1)],
+    };
+
+    // Order counts alone don't make a change
+    assert_eq!(
+        diff_depth(&before, &after),
+        [
+            DepthDelta::Deleted {
+                side: Side::Bid,
+                price: 98
+            },
+            DepthDelta::Inserted {
+                side: Side::Bid,
+                price: 97,
+                quantity: 3
+            },
+            DepthDelta::Updated {
+                side: Side::Ask,
+                price: 100,
+                quantity: 3
+            },
+            DepthDelta::Inserted {
+                side: Side::Ask,
+                price: 101,
+                quantity: 2
+            },
+        ]
+    );
+    assert!(diff_depth(&after, &after).is_empty());
+    assert_eq!(diff_depth(&Depth::default(), &after).len(), 5);
+}
+
+#[test]
+fn test_diff_depth_matches_the_tracker() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 4)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 5)
+        .unwrap();
+    book.record_events(true);
+    let mut tracker = DepthDeltaTracker::new(&book);
+    let before = book.depth(10);
+
+    book.execute_limit_order(Side::Bid, OrderId(3), 101, 7)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(4), 103, 1)
+        .unwrap();
+    let events = book.take_events();
+
+    let mut deltas = diff_depth(&before, &book.depth(10));
+    let mut tracked = tracker.update(&book, &events);
+    let key = |delta: &DepthDelta| format!("{delta:?}");
+    deltas.sort_by_key(key);
+    tracked.sort_by_key(key);
+    assert_eq!(deltas, tracked);
+}
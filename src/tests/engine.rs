@@ -0,0 +1,133 @@
+#[cfg(test)]
+use std::thread;
+
+#[cfg(test)]
+use crate::{
+    clock::ManualClock,
+    command::{Command, CommandResult},
+    engine::MatchingEngine,
+    error::EngineError,
+    events::BookEvent,
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_matching_engine_answers_commands_in_order() {
+    let mut engine = MatchingEngine::spawn(16);
+    let limit = Command::Limit {
+        side: Side::Ask,
+        order_id: OrderId(1),
+        price: 100,
+        quantity: 10,
+    };
+    let market = Command::Market {
+        side: Side::Bid,
+        quantity: 4,
+    };
+    engine.submit(limit.clone()).unwrap();
+    engine.submit(market.clone()).unwrap();
+
+    let response = engine.recv().unwrap();
+    assert_eq!(response.command, limit);
+    assert!(matches!(response.result, CommandResult::Limit(Ok(_))));
+    assert!(
+        response
+            .events
+            .iter()
+            .any(|event| matches!(event, BookEvent::OrderAdded { .. }))
+    );
+
+    let response = engine.recv().unwrap();
+    assert_eq!(response.command, market);
+    let CommandResult::Market(Ok(result)) = response.result else {
+        panic!("market order rejected: {:?}", response.result);
+    };
+    assert_eq!(result.filled_quantity, 4);
+    assert!(matches!(response.events[0], BookEvent::Trade { .. }));
+
+    assert_eq!(engine.shutdown(), Vec::new());
+}
+
+#[test]
+fn test_matching_engine_split_across_threads() {
+    let (mut sender, mut receiver) = MatchingEngine::spawn(4).split();
+
+    let producer = thread::spawn(move || {
+        for n in 0..100 {
+            let command = Command::Limit {
+                side: Side::Bid,
+                order_id: OrderId(n),
+                price: 100 - n as i64,
+                quantity: 1,
+            };
+            sender.submit(command).unwrap();
+        }
+        sender
+    });
+
+    // Reading as they arrive keeps the bounded rings moving
+    for n in 0..100 {
+        let response = receiver.recv().unwrap();
+        let CommandResult::Limit(Ok(result)) = response.result else {
+            panic!("limit order rejected: {:?}", response.result);
+        };
+        assert_eq!(result.order_id, OrderId(n));
+    }
+    let mut sender = producer.join().unwrap();
+
+    // Queued commands are still applied once the sender is gone
+    sender
+        .submit(Command::Cancel {
+            order_id: OrderId(0),
+        })
+        .unwrap();
+    drop(sender);
+    let remaining = receiver.join();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].result, CommandResult::Cancel(Ok(())));
+}
+
+#[test]
+fn test_matching_engine_rings_are_bounded() {
+    let mut engine = MatchingEngine::spawn(1);
+
+    // With nobody reading, at most one command queued, one held by the matching thread,
+    // and one answered fit before the command ring stays full
+    let mut accepted = 0;
+    let full = loop {
+        match engine.try_submit(Command::Cancel {
+            order_id: OrderId(accepted),
+        }) {
+            Ok(()) => accepted += 1,
+            Err(error) => break error,
+        }
+    };
+    assert!(matches!(full, EngineError::Full(Command::Cancel { .. })));
+    assert!(accepted <= 3);
+
+    // Every accepted command is still answered
+    let remaining = engine.shutdown();
+    assert_eq!(remaining.len(), accepted as usize);
+}
+
+#[test]
+fn test_matching_engine_takes_an_existing_book() {
+    let mut book = OrderBook::with_clock(ManualClock::new(7));
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 10)
+        .unwrap();
+
+    let mut engine = MatchingEngine::spawn_with(4, book);
+    engine
+        .submit(Command::Market {
+            side: Side::Bid,
+            quantity: 4,
+        })
+        .unwrap();
+    let CommandResult::Market(Ok(result)) = engine.recv().unwrap().result else {
+        panic!("market order rejected");
+    };
+    assert_eq!(result.fills[0].maker_order_id, OrderId(1));
+    assert_eq!(result.fills[0].timestamp, 7);
+    engine.shutdown();
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+use crate::{
+    events::{BookEvent, SequencedEvent},
+    orderbook::OrderBook,
+    types::{Fill, OrderId, Side, TradeId},
+};
+
+#[test]
+fn test_events_not_recorded_by_default() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    assert!(!book.events.is_recording());
+    assert!(book.take_events().is_empty());
+}
+
+#[test]
+fn test_events_cover_adds_trades_and_cancels() {
+    let mut book = OrderBook::new();
+    book.record_events(true);
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 5)
+        .unwrap();
+    assert_eq!(
+        book.take_events(),
+        vec![
+            BookEvent::LevelAdded {
+                side: Side::Ask,
+                price: 100
+            },
+            BookEvent::OrderAdded {
+                order_id: OrderId(1),
+                side: Side::Ask,
+                price: 100,
+                quantity: 2,
+                client_order_id: None,
+            },
+            BookEvent::LevelAdded {
+                side: Side::Ask,
+                price: 101
+            },
+            BookEvent::OrderAdded {
+                order_id: OrderId(2),
+                side: Side::Ask,
+                price: 101,
+                quantity: 5,
+                client_order_id: None,
+            },
+        ]
+    );
+
+    book.execute_market_order(Side::Bid, 3).unwrap();
+    book.cancel_order(OrderId(2)).unwrap();
+    assert_eq!(
+        book.take_events(),
+        vec![
+            BookEvent::Trade {
+                taker_side: Side::Bid,
+                fill: Fill {
+                    price: 100,
+                    quantity: 2,
+                    maker_order_id: OrderId(1),
+                    trade_id: TradeId(1),
+                    timestamp: 0,
+                    fees: None,
+                    maker_client_order_id: None,
+                    maker_remaining_quantity: 0,
+                    maker_fully_filled: true,
+                }
+            },
+            BookEvent::LevelRemoved {
+                side: Side::Ask,
+                price: 100
+            },
+            BookEvent::Trade {
+                taker_side: Side::Bid,
+                fill: Fill {
+                    price: 101,
+                    quantity: 1,
+                    maker_order_id: OrderId(2),
+                    trade_id: TradeId(2),
+                    timestamp: 0,
+                    fees: None,
+                    maker_client_order_id: None,
+                    maker_remaining_quantity: 4,
+                    maker_fully_filled: false,
+                }
+            },
+            BookEvent::OrderCancelled {
+                order_id: OrderId(2),
+                side: Side::Ask,
+                price: 101,
+                quantity: 4,
+                client_order_id: None,
+            },
+            BookEvent::LevelRemoved {
+                side: Side::Ask,
+                price: 101
+            },
+        ]
+    );
+    assert!(book.take_events().is_empty());
+}
+
+#[test]
+fn test_drain_events_into_appends() {
+    let mut book = OrderBook::new();
+    book.record_events(true);
+
+    let mut buffer = Vec::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.drain_events_into(&mut buffer);
+    book.amend_quantity(OrderId(1), 3).unwrap();
+    book.drain_events_into(&mut buffer);
+
+    assert_eq!(buffer.len(), 3);
+    assert_eq!(
+        buffer[2],
+        BookEvent::OrderReduced {
+            order_id: OrderId(1),
+            side: Side::Bid,
+            price: 100,
+            new_quantity: 3,
+            client_order_id: None,
+        }
+    );
+}
+
+#[test]
+fn test_stop_recording_discards_buffer() {
+    let mut book = OrderBook::new();
+    book.record_events(true);
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+
+    book.record_events(false);
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 5)
+        .unwrap();
+    assert!(book.take_events().is_empty());
+}
+
+#[test]
+fn test_sequence_numbers() {
+    let mut book = OrderBook::new();
+
+    // Events are numbered even while nothing records them
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    assert_eq!(book.event_sequence(), 2);
+    assert_eq!(book.command_sequence(), 1);
+
+    book.record_events(true);
+    let result = book
+        .execute_limit_order(Side::Ask, OrderId(2), 100, 2)
+        .unwrap();
+    assert_eq!(result.sequence, 2);
+
+    let sequences: Vec<_> = book
+        .take_sequenced_events()
+        .into_iter()
+        .map(|SequencedEvent { sequence, .. }| sequence)
+        .collect();
+    assert_eq!(sequences, vec![3]);
+
+    // Rejected commands aren't given a number
+    assert!(book.cancel_order(OrderId(9)).is_err());
+    assert_eq!(book.command_sequence(), 2);
+    book.cancel_order(OrderId(1)).unwrap();
+    assert_eq!(book.command_sequence(), 3);
+
+    let events = book.take_sequenced_events();
+    assert_eq!(events[0].sequence, 4);
+    assert_eq!(events.last().unwrap().sequence, book.event_sequence());
+
+    // Snapshots carry the counters, so a restored book continues the streams
+    let snapshot = book.level3_snapshot();
+    assert_eq!(snapshot.sequence, book.event_sequence());
+    let mut restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert_eq!(restored.event_sequence(), book.event_sequence());
+    assert_eq!(restored.command_sequence(), 3);
+
+    restored.record_events(true);
+    restored
+        .execute_limit_order(Side::Bid, OrderId(3), 99, 1)
+        .unwrap();
+    assert_eq!(restored.take_sequenced_events()[0].sequence, 6);
+}
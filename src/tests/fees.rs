@@ -0,0 +1,162 @@
+#[cfg(test)]
+use std::sync::Arc;
+
+#[cfg(test)]
+use crate::{
+    clock::ManualClock,
+    fees::{AuctionFillFees, FeeRate, FeeSchedule, FeeTier, FillFees},
+    orderbook::OrderBook,
+    self_trade::SelfTradePrevention,
+    trading_state::TradingState,
+    types::{OrderId, OwnerId, Side},
+};
+
+#[test]
+fn test_fee_rates() {
+    assert_eq!(FeeRate::Bps(25).fee(1_000_000), 2_500);
+    assert_eq!(FeeRate::Bps(-2).fee(1_000_000), -200);
+    // Fractions of a unit are dropped
+    assert_eq!(FeeRate::Bps(1).fee(19_999), 1);
+    assert_eq!(FeeRate::Fixed(7).fee(1_000_000), 7);
+}
+
+#[test]
+fn test_fills_carry_fees_from_the_schedule() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(1), 1_000, 10)
+        .unwrap();
+
+    // No schedule, no fees
+    let fills = book.execute_market_order(Side::Bid, 2).unwrap().fills;
+    assert_eq!(fills[0].fees, None);
+
+    book.fee_schedule = Some(FeeSchedule {
+        maker: FeeRate::Bps(-10),
+        taker: FeeRate::Bps(30),
+        ..Default::default()
+    });
+    let fills = book.execute_market_order(Side::Bid, 5).unwrap().fills;
+    assert_eq!(
+        fills[0].fees,
+        Some(FillFees {
+            maker_fee: -5,
+            taker_fee: 15,
+            // The maker sold and earns its rebate, the buying taker pays its fee on top
+            maker_net_notional: 5_005,
+            taker_net_notional: 5_015,
+        })
+    );
+
+    // A selling taker receives the notional less its fee
+    book.fee_schedule = Some(FeeSchedule {
+        maker: FeeRate::Fixed(1),
+        taker: FeeRate::Fixed(2),
+        ..Default::default()
+    });
+    book.execute_limit_order(Side::Bid, OrderId(2), 900, 10)
+        .unwrap();
+    let fills = book.execute_market_order(Side::Ask, 1).unwrap().fills;
+    assert_eq!(
+        fills[0].fees,
+        Some(FillFees {
+            maker_fee: 1,
+            taker_fee: 2,
+            maker_net_notional: 901,
+            taker_net_notional: 898,
+        })
+    );
+
+    let fill = book.execute_resting_order(OrderId(1), 3, None).unwrap();
+    assert_eq!(fill.fees.map(|fees| fees.taker_net_notional), Some(3_002));
+}
+
+#[test]
+fn test_fee_tiers_follow_rolling_owner_volume() {
+    let clock = Arc::new(ManualClock::new(1_000));
+    let mut book = OrderBook::with_clock(clock.clone());
+    book.fee_schedule = Some(FeeSchedule {
+        maker: FeeRate::Bps(0),
+        taker: FeeRate::Bps(10),
+        tiers: vec![FeeTier {
+            min_volume: 10,
+            maker: FeeRate::Bps(-5),
+            taker: FeeRate::Bps(5),
+        }],
+        volume_window: 100,
+    });
+    let (maker, taker) = (OwnerId(1), OwnerId(2));
+    let policy = SelfTradePrevention::CancelNewest;
+    book.execute_owned_limit_order(Side::Ask, OrderId(1), 1_000, 100, maker, policy)
+        .unwrap();
+
+    let buy = |book: &mut OrderBook| {
+        let fills = book
+            .execute_owned_market_order(Side::Bid, 10, taker, policy)
+            .unwrap()
+            .fills;
+        fills[0].fees.map(|fees| (fees.maker_fee, fees.taker_fee))
+    };
+
+    // Both owners start on the base rates, then reach the tier with their first 10
+    assert_eq!(buy(&mut book), Some((0, 10)));
+    clock.set(1_050);
+    assert_eq!(buy(&mut book), Some((-5, 5)));
+    assert_eq!(book.owner_volume(taker), 20);
+
+    // Once those fills leave the window the base rates apply again
+    clock.set(1_150);
+    assert_eq!(buy(&mut book), Some((0, 10)));
+    assert_eq!(book.owner_volume(maker), 10);
+
+    // Orders without an owner never reach a tier
+    let fills = book.execute_market_order(Side::Bid, 10).unwrap().fills;
+    let fees = fills[0].fees.unwrap();
+    assert_eq!((fees.maker_fee, fees.taker_fee), (-5, 10));
+}
+
+#[test]
+fn test_auction_fills_pay_maker_rates_and_count_towards_tiers() {
+    let mut book = OrderBook::new();
+    book.fee_schedule = Some(FeeSchedule {
+        maker: FeeRate::Bps(10),
+        taker: FeeRate::Bps(30),
+        tiers: vec![FeeTier {
+            min_volume: 10,
+            maker: FeeRate::Bps(0),
+            taker: FeeRate::Bps(20),
+        }],
+        volume_window: 100,
+    });
+    let (buyer, seller) = (OwnerId(1), OwnerId(2));
+    let policy = SelfTradePrevention::CancelNewest;
+    book.set_trading_state(TradingState::Auction);
+    book.execute_owned_limit_order(Side::Bid, OrderId(1), 1_000, 10, buyer, policy)
+        .unwrap();
+    book.execute_owned_limit_order(Side::Ask, OrderId(2), 1_000, 10, seller, policy)
+        .unwrap();
+
+    // Neither side took liquidity, so both pay the maker rate
+    let result = book.uncross().unwrap();
+    assert_eq!(
+        result.fills[0].fees,
+        Some(AuctionFillFees {
+            buy_fee: 10,
+            sell_fee: 10,
+            buy_net_notional: 10_010,
+            sell_net_notional: 9_990,
+        })
+    );
+    assert_eq!(book.owner_volume(buyer), 10);
+    assert_eq!(book.owner_volume(seller), 10);
+
+    // The auction volume alone reaches the tier
+    book.set_trading_state(TradingState::Open);
+    book.execute_owned_limit_order(Side::Ask, OrderId(3), 1_000, 5, seller, policy)
+        .unwrap();
+    let fills = book
+        .execute_owned_market_order(Side::Bid, 5, buyer, policy)
+        .unwrap()
+        .fills;
+    let fees = fills[0].fees.unwrap();
+    assert_eq!((fees.maker_fee, fees.taker_fee), (0, 10));
+}
@@ -0,0 +1,63 @@
+#[cfg(all(test, feature = "cdylib"))]
+use std::ptr;
+
+#[cfg(all(test, feature = "cdylib"))]
+use crate::ffi::{
+    BulkBookFill, BulkBookStatus, bulkbook_best, bulkbook_cancel, bulkbook_fill,
+    bulkbook_fill_count, bulkbook_free, bulkbook_limit, bulkbook_market, bulkbook_new,
+};
+
+#[cfg(feature = "cdylib")]
+#[test]
+fn test_c_abi_round_trip() {
+    let book = bulkbook_new();
+    unsafe {
+        let mut resting = 0;
+        assert_eq!(
+            bulkbook_limit(book, 1, 1, 100, 10, &mut resting),
+            BulkBookStatus::Ok
+        );
+        assert_eq!(resting, 10);
+        assert_eq!(
+            bulkbook_limit(book, 1, 1, 101, 10, ptr::null_mut()),
+            BulkBookStatus::OrderIdAlreadyExists
+        );
+        bulkbook_limit(book, 1, 2, 101, 10, ptr::null_mut());
+
+        let mut filled = 0;
+        assert_eq!(
+            bulkbook_market(book, 0, 15, &mut filled),
+            BulkBookStatus::Ok
+        );
+        assert_eq!(filled, 15);
+        assert_eq!(bulkbook_fill_count(book), 2);
+
+        let mut fill = BulkBookFill::default();
+        assert_eq!(bulkbook_fill(book, 1, &mut fill), BulkBookStatus::Ok);
+        assert_eq!(
+            (fill.price, fill.quantity, fill.maker_order_id),
+            (101, 5, 2)
+        );
+        assert_eq!(
+            (fill.maker_remaining_quantity, fill.maker_fully_filled),
+            (5, false)
+        );
+        assert_eq!(
+            bulkbook_fill(book, 2, &mut fill),
+            BulkBookStatus::InvalidArgument
+        );
+
+        let (mut price, mut quantity) = (0, 0);
+        assert!(bulkbook_best(book, 1, &mut price, &mut quantity));
+        assert_eq!((price, quantity), (101, 5));
+        assert!(!bulkbook_best(book, 0, &mut price, &mut quantity));
+
+        assert_eq!(bulkbook_cancel(book, 2), BulkBookStatus::Ok);
+        assert_eq!(bulkbook_cancel(book, 2), BulkBookStatus::OrderIdNotFound);
+        assert_eq!(
+            bulkbook_cancel(ptr::null_mut(), 2),
+            BulkBookStatus::InvalidArgument
+        );
+        bulkbook_free(book);
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+use crate::{
+    error::FixError,
+    fix::{ExecType, FixAdapter, FixMessage, FixOrderType, OrdStatus},
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_fix_parse_messages() {
+    assert_eq!(
+        FixMessage::parse("8=FIX.4.4|35=D|11=a1|54=1|40=2|44=101.25|38=100|59=0|", 2),
+        Ok(FixMessage::NewOrderSingle {
+            cl_ord_id: "a1".to_string(),
+            side: Side::Bid,
+            order_type: FixOrderType::Limit,
+            price: Some(10125),
+            order_qty: 100
+        })
+    );
+    assert_eq!(
+        FixMessage::parse("35=F\x0111=a2\x0141=a1\x01", 2),
+        Ok(FixMessage::OrderCancelRequest {
+            cl_ord_id: "a2".to_string(),
+            orig_cl_ord_id: "a1".to_string()
+        })
+    );
+
+    assert_eq!(
+        FixMessage::parse("35=D|11=a1|54=1|40=2|38=100", 2),
+        Err(FixError::MissingField(44))
+    );
+    assert_eq!(
+        FixMessage::parse("35=D|11=a1|54=1|40=2|44=1.255|38=100", 2),
+        Err(FixError::InvalidValue(44))
+    );
+    assert_eq!(
+        FixMessage::parse("35=D|11=a1|54=1|40=2|44=1|38=100|59=3", 2),
+        Err(FixError::InvalidValue(59))
+    );
+    assert_eq!(
+        FixMessage::parse("35=0|", 2),
+        Err(FixError::UnsupportedMessageType("0".to_string()))
+    );
+}
+
+#[test]
+fn test_fix_new_order_fills_both_sides() {
+    let mut book = OrderBook::new();
+    let mut adapter = FixAdapter::new();
+
+    let sell = FixMessage::parse("35=D|11=s1|54=2|40=2|44=100|38=10", 0).unwrap();
+    let reports = adapter.handle(&mut book, &sell);
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].exec_type, ExecType::New);
+    assert_eq!(reports[0].leaves_qty, 10);
+
+    let buy = FixMessage::parse("35=D|11=b1|54=1|40=1|38=15", 0).unwrap();
+    let reports = adapter.handle(&mut book, &buy);
+    let summary: Vec<_> = reports
+        .iter()
+        .map(|report| {
+            (
+                report.cl_ord_id.as_str(),
+                report.exec_type,
+                report.ord_status,
+                report.last_qty,
+                report.leaves_qty,
+                report.cum_qty,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("b1", ExecType::New, OrdStatus::New, 0, 15, 0),
+            ("b1", ExecType::Trade, OrdStatus::PartiallyFilled, 10, 5, 10),
+            ("s1", ExecType::Trade, OrdStatus::Filled, 10, 0, 10),
+            ("b1", ExecType::Canceled, OrdStatus::Canceled, 0, 0, 10),
+        ]
+    );
+    assert_eq!(reports[1].last_px, Some(100));
+}
+
+#[test]
+fn test_fix_cancel_and_replace() {
+    let mut book = OrderBook::new();
+    let mut adapter = FixAdapter::new();
+    let new = FixMessage::parse("35=D|11=a1|54=1|40=2|44=99|38=10", 0).unwrap();
+    adapter.handle(&mut book, &new);
+
+    // Quantity only, amended in place
+    let replace = FixMessage::parse("35=G|11=a2|41=a1|38=6", 0).unwrap();
+    let reports = adapter.handle(&mut book, &replace);
+    assert_eq!(reports[0].exec_type, ExecType::Replaced);
+    assert_eq!(reports[0].order_id, Some(OrderId(1)));
+    assert_eq!(book.best_bid(), Some((99, 6)));
+
+    // Price change, cancelled and re-entered under a new order id
+    let replace = FixMessage::parse("35=G|11=a3|41=a2|44=98|38=6", 0).unwrap();
+    let reports = adapter.handle(&mut book, &replace);
+    assert_eq!(reports[0].exec_type, ExecType::Replaced);
+    assert_eq!(reports[0].order_id, Some(OrderId(2)));
+    assert_eq!(reports[0].orig_cl_ord_id.as_deref(), Some("a2"));
+    assert_eq!(book.best_bid(), Some((98, 6)));
+
+    // The old ClOrdID no longer refers to a live order
+    let cancel = FixMessage::parse("35=F|11=a4|41=a2", 0).unwrap();
+    let reports = adapter.handle(&mut book, &cancel);
+    assert_eq!(reports[0].exec_type, ExecType::Rejected);
+
+    let cancel = FixMessage::parse("35=F|11=a4|41=a3", 0).unwrap();
+    let reports = adapter.handle(&mut book, &cancel);
+    assert_eq!(reports[0].exec_type, ExecType::Canceled);
+    assert!(book.bids.is_empty());
+}
+
+#[test]
+fn test_fix_rejects_duplicate_cl_ord_id() {
+    let mut book = OrderBook::new();
+    let mut adapter = FixAdapter::new();
+    let new = FixMessage::parse("35=D|11=a1|54=1|40=2|44=99|38=10", 0).unwrap();
+    adapter.handle(&mut book, &new);
+
+    let reports = adapter.handle(&mut book, &new);
+    assert_eq!(reports[0].ord_status, OrdStatus::Rejected);
+    assert_eq!(reports[0].text.as_deref(), Some("Duplicate ClOrdID"));
+    assert_eq!(book.best_bid(), Some((99, 10)));
+}
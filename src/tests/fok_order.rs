@@ -0,0 +1,153 @@
+#[cfg(test)]
+use crate::{
+    error::LimitOrderError,
+    orderbook::OrderBook,
+    types::{Fill, LimitOrderResult, OrderId, Side, TradeId},
+};
+
+#[test]
+fn test_fok_buy_fully_filled() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 3)
+        .unwrap();
+
+    let result = book
+        .execute_fok_order(Side::Bid, OrderId(3), 101, 5)
+        .unwrap();
+
+    assert_eq!(
+        result,
+        LimitOrderResult {
+            order_id: OrderId(3),
+            fills: vec![
+                Fill {
+                    price: 100,
+                    quantity: 2,
+                    maker_order_id: OrderId(1),
+                    trade_id: TradeId(1),
+                    timestamp: 0,
+                    fees: None,
+                    maker_client_order_id: None,
+                    maker_remaining_quantity: 0,
+                    maker_fully_filled: true,
+                },
+                Fill {
+                    price: 101,
+                    quantity: 3,
+                    maker_order_id: OrderId(2),
+                    trade_id: TradeId(2),
+                    timestamp: 0,
+                    fees: None,
+                    maker_client_order_id: None,
+                    maker_remaining_quantity: 0,
+                    maker_fully_filled: true,
+                }
+            ],
+            sequence: 3,
+            ..Default::default()
+        }
+    );
+
+    // Nothing rests, the FOK is never placed on the book
+    assert!(book.asks.is_empty());
+    assert!(book.bids.is_empty());
+    assert_eq!(book.index_map.len(), 0);
+    assert_eq!(book.orders.len(), 0);
+}
+
+#[test]
+fn test_fok_sell_fully_filled() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 101, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 3)
+        .unwrap();
+
+    let result = book
+        .execute_fok_order(Side::Ask, OrderId(3), 100, 4)
+        .unwrap();
+
+    assert_eq!(
+        result.fills,
+        vec![
+            Fill {
+                price: 101,
+                quantity: 2,
+                maker_order_id: OrderId(1),
+                trade_id: TradeId(1),
+                timestamp: 0,
+                fees: None,
+                maker_client_order_id: None,
+                maker_remaining_quantity: 0,
+                maker_fully_filled: true,
+            },
+            Fill {
+                price: 100,
+                quantity: 2,
+                maker_order_id: OrderId(2),
+                trade_id: TradeId(2),
+                timestamp: 0,
+                fees: None,
+                maker_client_order_id: None,
+                maker_remaining_quantity: 1,
+                maker_fully_filled: false,
+            }
+        ]
+    );
+
+    // Partially consumed maker remains
+    assert_eq!(book.bids.len(), 1);
+    let entry = book.index_map.get(&OrderId(2)).unwrap();
+    assert_eq!(book.orders.get(entry.order_index).unwrap().quantity, 1);
+    assert!(book.index_map.get(&OrderId(3)).is_none());
+}
+
+#[test]
+fn test_fok_buy_insufficient_liquidity() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 102, 3)
+        .unwrap();
+
+    // Enough total quantity, but not within the limit price
+    let result = book.execute_fok_order(Side::Bid, OrderId(3), 101, 5);
+    assert_eq!(result, Err(LimitOrderError::InsufficientLiquidity));
+
+    // Book must be untouched
+    assert_eq!(book.asks.len(), 2);
+    assert_eq!(book.index_map.len(), 2);
+    let entry = book.index_map.get(&OrderId(1)).unwrap();
+    assert_eq!(book.orders.get(entry.order_index).unwrap().quantity, 2);
+}
+
+#[test]
+fn test_fok_sell_insufficient_liquidity() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 2)
+        .unwrap();
+
+    let result = book.execute_fok_order(Side::Ask, OrderId(2), 100, 3);
+    assert_eq!(result, Err(LimitOrderError::InsufficientLiquidity));
+
+    assert_eq!(book.bids.len(), 1);
+    assert_eq!(book.index_map.len(), 1);
+    assert_eq!(book.next_trade_id, TradeId(1));
+}
+
+#[test]
+fn test_fok_duplicate_order_id_errors() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 2)
+        .unwrap();
+
+    let result = book.execute_fok_order(Side::Ask, OrderId(1), 100, 1);
+    assert_eq!(result, Err(LimitOrderError::OrderIdAlreadyExists));
+}
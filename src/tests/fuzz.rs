@@ -0,0 +1,72 @@
+#[cfg(test)]
+use crate::{
+    command::{Command, CommandResult},
+    error::CancelOrderError,
+    fuzz::{MAX_FUZZ_COMMANDS, decode_fuzz_input},
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_fuzz_input_decoding() {
+    let bytes = [
+        0b000, 7, 16, 10, // Bid limit
+        0b101, 0, 0, 64, // Ask market, quantity wraps to zero
+        0b010, 7, 0, 0, // Cancel
+        0b111, 9, 0, 5, // Amend
+        0xff, 0xff, // Trailing partial chunk
+    ];
+
+    let commands: Vec<_> = decode_fuzz_input(&bytes).collect();
+    assert_eq!(
+        commands,
+        vec![
+            Command::Limit {
+                side: Side::Bid,
+                order_id: OrderId(7),
+                price: 100,
+                quantity: 10,
+            },
+            Command::Market {
+                side: Side::Ask,
+                quantity: 0,
+            },
+            Command::Cancel {
+                order_id: OrderId(7),
+            },
+            Command::Amend {
+                order_id: OrderId(9),
+                quantity: 5,
+            },
+        ]
+    );
+
+    let mut book = OrderBook::new();
+    let results = book.apply_fuzz_input(&bytes);
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[2], CommandResult::Cancel(Ok(())));
+    assert_eq!(book.best_bid(), None);
+}
+
+#[test]
+fn test_fuzz_input_is_bounded_and_keeps_the_book_valid() {
+    // A fixed pseudo-random stream, long enough to be cut off
+    let mut state: u32 = 0x2545_f491;
+    let bytes: Vec<u8> = (0..8 * MAX_FUZZ_COMMANDS)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect();
+
+    let mut book = OrderBook::new();
+    let results = book.apply_fuzz_input(&bytes);
+    assert_eq!(results.len(), MAX_FUZZ_COMMANDS);
+    assert!(results.iter().any(|result| matches!(
+        result,
+        CommandResult::Cancel(Err(CancelOrderError::OrderIdNotFound))
+    )));
+    assert_eq!(book.validate(), Ok(()));
+}
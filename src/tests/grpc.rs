@@ -0,0 +1,116 @@
+#[cfg(all(test, feature = "grpc"))]
+use tokio::runtime::Builder;
+#[cfg(all(test, feature = "grpc"))]
+use tokio_stream::StreamExt;
+#[cfg(all(test, feature = "grpc"))]
+use tonic::{Code, Request};
+
+#[cfg(all(test, feature = "grpc"))]
+use crate::grpc::{
+    GrpcBookService,
+    proto::{self, command::Kind, event, order_book_service_server::OrderBookService},
+};
+
+#[cfg(all(test, feature = "grpc"))]
+fn limit(side: proto::Side, order_id: u64, price: i64, quantity: u64) -> Request<proto::Command> {
+    Request::new(proto::Command {
+        kind: Some(Kind::Limit(proto::Limit {
+            side: side.into(),
+            order_id,
+            price,
+            quantity,
+        })),
+    })
+}
+
+// The RPC handlers are called directly, without a transport in between
+#[cfg(feature = "grpc")]
+#[test]
+fn test_grpc_service_maps_commands_and_streams_events() {
+    let runtime = Builder::new_current_thread().build().unwrap();
+    let service = GrpcBookService::spawn(16);
+
+    runtime.block_on(async {
+        let mut events = service
+            .subscribe(Request::new(proto::SubscribeRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let reply = service
+            .submit(limit(proto::Side::Ask, 1, 101, 10))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(reply.events.len(), 2); // Level added, then the order
+
+        let reply = service
+            .submit(limit(proto::Side::Bid, 2, 101, 4))
+            .await
+            .unwrap()
+            .into_inner();
+        let trade = reply.events.iter().find_map(|event| match event.kind {
+            Some(event::Kind::Trade(trade)) => Some(trade),
+            _ => None,
+        });
+        assert_eq!(
+            trade.map(|trade| (trade.price, trade.quantity, trade.maker_order_id)),
+            Some((101, 4, 1))
+        );
+
+        let depth = service
+            .get_depth(Request::new(proto::DepthRequest { levels: 5 }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(depth.bids.is_empty());
+        assert_eq!(
+            depth.asks,
+            [proto::Level {
+                price: 101,
+                quantity: 6,
+                order_count: 1
+            }]
+        );
+
+        // Subscribers see every event in book order, numbered without gaps
+        let streamed: Vec<_> = (&mut events)
+            .take(2 + reply.events.len())
+            .map(|event| event.unwrap().sequence)
+            .collect()
+            .await;
+        assert_eq!(streamed, (1..=streamed.len() as u64).collect::<Vec<_>>());
+        assert_eq!(reply.events.last().unwrap().sequence, streamed.len() as u64);
+    });
+}
+
+#[cfg(feature = "grpc")]
+#[test]
+fn test_grpc_service_rejections_are_statuses() {
+    let runtime = Builder::new_current_thread().build().unwrap();
+    let service = GrpcBookService::spawn(16);
+
+    runtime.block_on(async {
+        let cancel = Request::new(proto::Command {
+            kind: Some(Kind::Cancel(proto::Cancel { order_id: 1 })),
+        });
+        let status = service.submit(cancel).await.unwrap_err();
+        assert_eq!(status.code(), Code::FailedPrecondition);
+        assert!(status.message().contains("OrderIdNotFound"));
+
+        let empty = Request::new(proto::Command { kind: None });
+        assert_eq!(
+            service.submit(empty).await.unwrap_err().code(),
+            Code::InvalidArgument
+        );
+
+        let mut bad_side = limit(proto::Side::Bid, 1, 100, 5);
+        if let Some(Kind::Limit(limit)) = &mut bad_side.get_mut().kind {
+            limit.side = 7;
+        }
+        assert_eq!(
+            service.submit(bad_side).await.unwrap_err().code(),
+            Code::InvalidArgument
+        );
+    });
+}
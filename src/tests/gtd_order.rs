@@ -0,0 +1,82 @@
+#[cfg(test)]
+use crate::{
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_gtd_orders_expire_in_deadline_order() {
+    let mut book = OrderBook::new();
+
+    book.execute_gtd_order(Side::Bid, OrderId(1), 100, 5, 30)
+        .unwrap();
+    book.execute_gtd_order(Side::Ask, OrderId(2), 105, 5, 10)
+        .unwrap();
+    book.execute_gtd_order(Side::Bid, OrderId(3), 99, 5, 20)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(4), 99, 5)
+        .unwrap();
+
+    // Nothing has expired yet
+    assert!(book.expire_orders(9).is_empty());
+    assert_eq!(book.index_map.len(), 4);
+
+    assert_eq!(book.expire_orders(20), vec![OrderId(2), OrderId(3)]);
+    assert!(book.asks.is_empty());
+    assert_eq!(book.bids.get(&99).unwrap().order_count, 1);
+
+    assert_eq!(book.expire_orders(100), vec![OrderId(1)]);
+    assert_eq!(book.bids.len(), 1);
+    assert_eq!(book.index_map.len(), 1);
+    assert!(book.index_map.contains_key(&OrderId(4)));
+    assert!(book.expiry_queue.is_empty());
+}
+
+#[test]
+fn test_gtd_expiry_skips_cancelled_and_filled_orders() {
+    let mut book = OrderBook::new();
+
+    book.execute_gtd_order(Side::Ask, OrderId(1), 100, 5, 10)
+        .unwrap();
+    book.execute_gtd_order(Side::Ask, OrderId(2), 101, 5, 10)
+        .unwrap();
+    book.execute_gtd_order(Side::Ask, OrderId(3), 102, 5, 10)
+        .unwrap();
+
+    book.cancel_order(OrderId(1)).unwrap();
+    book.execute_market_order(Side::Bid, 5).unwrap();
+
+    assert_eq!(book.expire_orders(10), vec![OrderId(3)]);
+    assert!(book.asks.is_empty());
+    assert_eq!(book.orders.len(), 0);
+}
+
+#[test]
+fn test_gtd_expiry_ignores_reused_order_id() {
+    let mut book = OrderBook::new();
+
+    book.execute_gtd_order(Side::Bid, OrderId(1), 100, 5, 10)
+        .unwrap();
+    book.cancel_order(OrderId(1)).unwrap();
+
+    // Same Id placed again without a deadline must not be expired
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+
+    assert!(book.expire_orders(10).is_empty());
+    assert!(book.index_map.contains_key(&OrderId(1)));
+}
+
+#[test]
+fn test_gtd_fully_filled_is_not_tracked() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    let result = book
+        .execute_gtd_order(Side::Bid, OrderId(2), 100, 5, 10)
+        .unwrap();
+
+    assert_eq!(result.resting_quantity, 0);
+    assert!(book.expiry_queue.is_empty());
+}
@@ -0,0 +1,185 @@
+#[cfg(test)]
+use crate::{
+    error::LimitOrderError,
+    events::BookEvent,
+    order::Order,
+    orderbook::OrderBook,
+    trading_state::TradingState,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_hidden_order_stays_out_of_market_data() {
+    let mut book = OrderBook::new();
+    book.record_events(true);
+
+    let result = book
+        .execute_hidden_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+
+    assert_eq!(result.resting_quantity, 5);
+    assert!(book.take_events().is_empty());
+    assert_eq!(book.best_bid(), None);
+    assert!(book.depth(10).bids.is_empty());
+    assert!(book.level3_snapshot().bids.is_empty());
+    assert_eq!(book.side_volume(Side::Bid), 0);
+    assert_eq!(book.hidden_volume(Side::Bid), 5);
+    assert_eq!(book.hidden_order_count(Side::Bid), 1);
+    assert_eq!(book.get_order(OrderId(1)).unwrap().quantity, 5);
+    assert_eq!(book.validate(), Ok(()));
+}
+
+#[test]
+fn test_hidden_order_matches_behind_visible_at_same_price() {
+    let mut book = OrderBook::new();
+    book.execute_hidden_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(3), 101, 5)
+        .unwrap();
+
+    // The later visible order goes first, the hidden one before the worse price
+    assert_eq!(book.queue_position(OrderId(1)), Some(1));
+    let result = book.execute_market_order(Side::Bid, 12).unwrap();
+    let makers: Vec<_> = result
+        .fills
+        .iter()
+        .map(|fill| (fill.maker_order_id, fill.price, fill.quantity))
+        .collect();
+    assert_eq!(
+        makers,
+        [
+            (OrderId(2), 100, 5),
+            (OrderId(1), 100, 5),
+            (OrderId(3), 101, 2)
+        ]
+    );
+    assert_eq!(book.hidden_volume(Side::Ask), 0);
+    assert_eq!(book.validate(), Ok(()));
+}
+
+#[test]
+fn test_hidden_order_at_better_price_matches_first() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_hidden_order(Side::Bid, OrderId(2), 101, 5)
+        .unwrap();
+
+    assert_eq!(book.best_bid(), Some((100, 5)));
+    let volume = book.volume_ahead(OrderId(1)).unwrap();
+    assert_eq!((volume.at_price, volume.better_prices), (0, 5));
+
+    book.record_events(true);
+    let result = book
+        .execute_limit_order(Side::Ask, OrderId(3), 100, 7)
+        .unwrap();
+    assert_eq!(result.fills[0].maker_order_id, OrderId(2));
+    assert_eq!(result.fills[0].price, 101);
+    assert_eq!(book.best_bid(), Some((100, 3)));
+
+    // Only the trades reveal the hidden order, never a level of its own
+    let revealing = book.take_events().into_iter().any(|event| {
+        matches!(
+            event,
+            BookEvent::LevelRemoved { price: 101, .. } | BookEvent::OrderCancelled { .. }
+        )
+    });
+    assert!(!revealing);
+}
+
+#[test]
+fn test_hidden_order_crosses_on_arrival() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 3)
+        .unwrap();
+
+    let result = book
+        .execute_hidden_order(Side::Bid, OrderId(2), 101, 5)
+        .unwrap();
+
+    assert_eq!(result.fills.len(), 1);
+    assert_eq!(result.resting_quantity, 2);
+    assert!(book.asks.is_empty());
+    assert_eq!(book.hidden_volume(Side::Bid), 2);
+}
+
+#[test]
+fn test_post_only_rejected_against_hidden_liquidity() {
+    let mut book = OrderBook::new();
+    book.execute_hidden_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+
+    let result = book.execute_post_only_order(Side::Bid, OrderId(2), 100, 5);
+
+    assert_eq!(result, Err(LimitOrderError::PostOnlyWouldCross));
+}
+
+#[test]
+fn test_hidden_order_cancel_and_amend_are_silent() {
+    let mut book = OrderBook::new();
+    book.execute_hidden_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_hidden_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+    book.record_events(true);
+
+    book.amend_quantity(OrderId(1), 8).unwrap();
+    assert_eq!(book.queue_position(OrderId(1)), Some(1));
+    book.cancel_quantity(OrderId(2), 1).unwrap();
+    book.cancel_order(OrderId(2)).unwrap();
+
+    assert!(book.take_events().is_empty());
+    assert_eq!(book.hidden_volume(Side::Ask), 8);
+    assert_eq!(book.hidden_order_count(Side::Ask), 1);
+    assert_eq!(book.validate(), Ok(()));
+}
+
+#[test]
+fn test_submit_hidden_order() {
+    let mut book = OrderBook::new();
+    let order = Order::builder(Side::Bid, OrderId(1), 100, 5)
+        .hidden()
+        .build();
+
+    let result = book.submit_order(&order).unwrap();
+
+    assert_eq!(result.resting_quantity, 5);
+    assert!(book.index_map[&OrderId(1)].hidden);
+    assert!(book.bids.is_empty());
+}
+
+#[test]
+fn test_hidden_order_rejected_in_auction() {
+    let mut book = OrderBook::new();
+    book.set_trading_state(TradingState::Auction);
+
+    let result = book.execute_hidden_order(Side::Bid, OrderId(1), 100, 5);
+
+    assert_eq!(result, Err(LimitOrderError::TradingHalted));
+}
+
+#[test]
+fn test_hidden_orders_survive_snapshot_and_compact() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 5)
+        .unwrap();
+    book.execute_hidden_order(Side::Bid, OrderId(2), 100, 5)
+        .unwrap();
+    book.execute_hidden_order(Side::Ask, OrderId(3), 101, 5)
+        .unwrap();
+    book.cancel_order(OrderId(1)).unwrap();
+    book.compact();
+
+    let restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert_eq!(restored.hidden_bids, book.hidden_bids);
+    assert_eq!(restored.hidden_asks, book.hidden_asks);
+    assert_eq!(restored.index_map, book.index_map);
+    assert_eq!(restored.validate(), Ok(()));
+
+    assert_eq!(book.snapshot().order_count(), 0);
+    assert_eq!(book.clear().len(), 2);
+    assert_eq!(book.hidden_volume(Side::Bid), 0);
+    assert_eq!(book.validate(), Ok(()));
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+use crate::{
+    hooks::{IncomingOrder, MatchHook, MatchedTrade},
+    orderbook::OrderBook,
+    self_trade::SelfTradePrevention,
+    trading_state::TradingState,
+    types::{OrderId, OwnerId, Price, Quantity, Side},
+};
+
+#[cfg(test)]
+#[derive(Debug, PartialEq, Eq)]
+enum Call {
+    // The incoming order, with the best ask it found
+    BeforeMatch(IncomingOrder, Option<(Price, Quantity)>),
+    // The maker, its owners, and the best ask once the operation settled
+    AfterTrade(
+        OrderId,
+        Option<OwnerId>,
+        Option<OwnerId>,
+        Option<(Price, Quantity)>,
+    ),
+}
+
+#[cfg(test)]
+#[derive(Default, Clone)]
+struct Recorder(Arc<Mutex<Vec<Call>>>);
+
+#[cfg(test)]
+impl Recorder {
+    fn take(&self) -> Vec<Call> {
+        self.0.lock().unwrap().drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+impl MatchHook for Recorder {
+    fn before_match(&mut self, book: &OrderBook, order: &IncomingOrder) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(Call::BeforeMatch(*order, book.best_ask()));
+    }
+
+    fn after_trade(&mut self, book: &OrderBook, trade: &MatchedTrade) {
+        self.0.lock().unwrap().push(Call::AfterTrade(
+            trade.fill.maker_order_id,
+            trade.maker_owner,
+            trade.taker_owner,
+            book.best_ask(),
+        ));
+    }
+}
+
+#[test]
+fn test_hooks_see_each_match_and_trade() {
+    let mut book = OrderBook::new();
+    let recorder = Recorder::default();
+    book.set_match_hook(recorder.clone());
+    let policy = SelfTradePrevention::CancelNewest;
+    book.execute_owned_limit_order(Side::Ask, OrderId(1), 100, 5, OwnerId(1), policy)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 5)
+        .unwrap();
+    // Resting orders that don't cross still pass through matching
+    assert_eq!(recorder.take().len(), 2);
+
+    book.execute_owned_limit_order(Side::Bid, OrderId(3), 101, 8, OwnerId(2), policy)
+        .unwrap();
+    let order = IncomingOrder {
+        side: Side::Bid,
+        quantity: 8,
+        limit_price: Some(101),
+        owner: Some(OwnerId(2)),
+    };
+    // Trades are reported once the book has settled, with the level at 101 part filled
+    assert_eq!(
+        recorder.take(),
+        [
+            Call::BeforeMatch(order, Some((100, 5))),
+            Call::AfterTrade(
+                OrderId(1),
+                Some(OwnerId(1)),
+                Some(OwnerId(2)),
+                Some((101, 2))
+            ),
+            Call::AfterTrade(OrderId(2), None, Some(OwnerId(2)), Some((101, 2))),
+        ]
+    );
+
+    book.execute_market_order(Side::Bid, 1).unwrap();
+    let order = IncomingOrder {
+        side: Side::Bid,
+        quantity: 1,
+        limit_price: None,
+        owner: None,
+    };
+    assert_eq!(
+        recorder.take(),
+        [
+            Call::BeforeMatch(order, Some((101, 2))),
+            Call::AfterTrade(OrderId(2), None, None, Some((101, 1))),
+        ]
+    );
+}
+
+#[test]
+fn test_hooks_skip_rejections_and_auctions() {
+    let mut book = OrderBook::new();
+    let recorder = Recorder::default();
+    book.set_match_hook(recorder.clone());
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    recorder.take();
+
+    // Rejected before matching
+    assert!(
+        book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+            .is_err()
+    );
+    assert!(recorder.take().is_empty());
+
+    book.set_trading_state(TradingState::Auction);
+    book.execute_limit_order(Side::Bid, OrderId(2), 101, 5)
+        .unwrap();
+    book.uncross().unwrap();
+    assert!(recorder.take().is_empty());
+}
+
+#[test]
+fn test_clearing_and_cloning_drop_the_hook() {
+    let mut book = OrderBook::new();
+    let recorder = Recorder::default();
+    book.set_match_hook(recorder.clone());
+    assert!(book.match_hooks.is_set());
+
+    let mut copy = book.clone();
+    assert!(!copy.match_hooks.is_set());
+    copy.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    assert!(recorder.take().is_empty());
+
+    book.clear_match_hook();
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_market_order(Side::Bid, 5).unwrap();
+    assert!(recorder.take().is_empty());
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+use crate::{
+    error::{AmendOrderError, InstrumentError, LimitOrderError, MarketOrderError, StopOrderError},
+    instrument::Instrument,
+    orderbook::OrderBook,
+    pegged_orders::PegType,
+    types::{OrderId, Side},
+};
+
+#[cfg(test)]
+fn instrument() -> Instrument {
+    Instrument::builder()
+        .tick_size(5)
+        .lot_size(10)
+        .price_range(50, 200)
+        .order_size_range(0, 1_000)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_instrument_rejects_limit_orders() {
+    let mut book = OrderBook::with_instrument(instrument());
+
+    let rejections = [
+        (102, 10, InstrumentError::PriceNotOnTick),
+        (45, 10, InstrumentError::PriceOutOfRange),
+        (205, 10, InstrumentError::PriceOutOfRange),
+        (100, 15, InstrumentError::QuantityNotOnLot),
+        (100, 1_010, InstrumentError::QuantityTooLarge),
+    ];
+    for (price, quantity, error) in rejections {
+        assert_eq!(
+            book.execute_limit_order(Side::Bid, OrderId(1), price, quantity),
+            Err(LimitOrderError::Instrument(error))
+        );
+        assert_eq!(
+            book.execute_fok_order(Side::Bid, OrderId(1), price, quantity),
+            Err(LimitOrderError::Instrument(error))
+        );
+    }
+    assert!(book.bids.is_empty());
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 1_000)
+        .unwrap();
+    assert_eq!(book.best_bid(), Some((100, 1_000)));
+}
+
+#[test]
+fn test_instrument_rejects_other_order_types() {
+    let mut book = OrderBook::with_instrument(instrument());
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 20)
+        .unwrap();
+
+    assert_eq!(
+        book.execute_market_order(Side::Bid, 5),
+        Err(MarketOrderError::Instrument(
+            InstrumentError::QuantityNotOnLot
+        ))
+    );
+    assert_eq!(
+        book.execute_stop_limit_order(Side::Bid, OrderId(2), 103, 105, 10),
+        Err(StopOrderError::Instrument(InstrumentError::PriceNotOnTick))
+    );
+    assert_eq!(
+        book.amend_quantity(OrderId(1), 15),
+        Err(AmendOrderError::Instrument(
+            InstrumentError::QuantityNotOnLot
+        ))
+    );
+    assert_eq!(
+        book.execute_pegged_order(Side::Bid, OrderId(3), PegType::Primary, 2, 10),
+        Err(LimitOrderError::Instrument(InstrumentError::PriceNotOnTick))
+    );
+    assert_eq!(book.best_ask(), Some((100, 20)));
+}
+
+#[test]
+fn test_instrument_midpoint_peg_rounds_to_tick() {
+    let mut book = OrderBook::with_instrument(instrument());
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 115, 10)
+        .unwrap();
+
+    // Midpoint is 107.5, bids round down and asks round up to the tick
+    book.execute_pegged_order(Side::Bid, OrderId(3), PegType::Midpoint, 0, 10)
+        .unwrap();
+    book.execute_pegged_order(Side::Ask, OrderId(4), PegType::Midpoint, 0, 10)
+        .unwrap();
+    assert_eq!(book.get_order(OrderId(3)).unwrap().price, 105);
+    assert_eq!(book.get_order(OrderId(4)).unwrap().price, 110);
+}
+
+#[test]
+fn test_instrument_minimum_order_size() {
+    let instrument = Instrument::builder()
+        .tick_size(5)
+        .lot_size(10)
+        .price_range(50, 200)
+        .order_size_range(30, 1_000)
+        .build()
+        .unwrap();
+    let mut book = OrderBook::with_instrument(instrument);
+
+    assert_eq!(
+        book.execute_limit_order(Side::Bid, OrderId(1), 100, 20),
+        Err(LimitOrderError::Instrument(
+            InstrumentError::QuantityTooSmall
+        ))
+    );
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 30)
+        .unwrap();
+
+    assert_eq!(
+        book.execute_market_order(Side::Ask, 20),
+        Err(MarketOrderError::Instrument(
+            InstrumentError::QuantityTooSmall
+        ))
+    );
+    assert_eq!(
+        book.amend_quantity(OrderId(1), 20),
+        Err(AmendOrderError::Instrument(
+            InstrumentError::QuantityTooSmall
+        ))
+    );
+    assert_eq!(book.best_bid(), Some((100, 30)));
+}
+
+#[test]
+fn test_instrument_builder_rejects_impossible_rules() {
+    assert_eq!(
+        Instrument::builder().tick_size(0).build(),
+        Err(InstrumentError::NonPositiveTickSize)
+    );
+    assert_eq!(
+        Instrument::builder().tick_size(-5).build(),
+        Err(InstrumentError::NonPositiveTickSize)
+    );
+    assert_eq!(
+        Instrument::builder().lot_size(0).build(),
+        Err(InstrumentError::ZeroLotSize)
+    );
+    assert_eq!(
+        Instrument::builder().price_range(200, 100).build(),
+        Err(InstrumentError::InvertedPriceRange)
+    );
+    assert_eq!(
+        Instrument::builder().order_size_range(10, 5).build(),
+        Err(InstrumentError::InvertedOrderSizeRange)
+    );
+
+    let instrument = instrument();
+    assert_eq!((instrument.tick_size(), instrument.lot_size()), (5, 10));
+    assert_eq!((instrument.min_price(), instrument.max_price()), (50, 200));
+}
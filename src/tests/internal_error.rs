@@ -0,0 +1,82 @@
+#[cfg(test)]
+use crate::{
+    error::{AmendOrderError, CancelOrderError, InternalError, LimitOrderError, MarketOrderError},
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_internal_errors_name_the_broken_invariant() {
+    let mut book = OrderBook::new();
+    let index = book
+        .execute_limit_order(Side::Ask, OrderId(1), 100, 10)
+        .unwrap()
+        .order_index
+        .unwrap();
+
+    // Free the order's node behind the book's back
+    book.orders.remove(index);
+    assert_eq!(
+        book.cancel_quantity(OrderId(1), 5),
+        Err(CancelOrderError::Internal(InternalError::MissingNode {
+            index
+        }))
+    );
+    assert_eq!(
+        book.amend_quantity(OrderId(1), 5),
+        Err(AmendOrderError::Internal(InternalError::MissingNode {
+            index
+        }))
+    );
+    assert_eq!(
+        book.execute_market_order(Side::Bid, 5),
+        Err(MarketOrderError::Internal(
+            InternalError::DanglingLevelHead {
+                side: Side::Ask,
+                price: 100,
+            }
+        ))
+    );
+}
+
+#[test]
+fn test_internal_errors_leave_the_book_untouched() {
+    let mut book = OrderBook::new();
+    book.record_events(true);
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 10)
+        .unwrap();
+    let index = book
+        .execute_limit_order(Side::Ask, OrderId(3), 101, 10)
+        .unwrap()
+        .order_index
+        .unwrap();
+
+    // The sweep reaches the freed node only after trading through the first level
+    book.orders.remove(index);
+    book.take_events();
+    let before = format!("{book:?}");
+
+    let broken = InternalError::DanglingLevelHead {
+        side: Side::Ask,
+        price: 101,
+    };
+    assert_eq!(
+        book.execute_market_order(Side::Bid, 25),
+        Err(MarketOrderError::Internal(broken))
+    );
+    assert_eq!(
+        book.execute_limit_order(Side::Bid, OrderId(4), 101, 25),
+        Err(LimitOrderError::Internal(broken))
+    );
+    assert_eq!(
+        book.cancel_order(OrderId(3)),
+        Err(CancelOrderError::Internal(InternalError::MissingNode {
+            index
+        }))
+    );
+
+    assert_eq!(format!("{book:?}"), before);
+    assert_eq!(book.take_events(), Vec::new());
+}
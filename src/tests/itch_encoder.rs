@@ -0,0 +1,75 @@
+#[cfg(test)]
+use crate::{
+    error::EncodeError,
+    itch_encoder::{
+        ADD_ORDER_LEN, ItchEncoder, ORDER_CANCEL_LEN, ORDER_DELETE_LEN, ORDER_EXECUTED_LEN,
+    },
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_itch_encoder_messages() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 10)
+        .unwrap();
+    book.record_events(true);
+    let mut encoder = ItchEncoder::new(&book, 7, "ABC");
+
+    book.execute_limit_order(Side::Bid, OrderId(2), 99, 5)
+        .unwrap();
+    book.amend_quantity(OrderId(1), 8).unwrap();
+    book.execute_market_order(Side::Bid, 3).unwrap();
+    book.cancel_order(OrderId(2)).unwrap();
+
+    let mut out = Vec::new();
+    encoder
+        .encode(&book.take_events(), 0x0102_0304_0506, &mut out)
+        .unwrap();
+    assert_eq!(
+        out.len(),
+        ADD_ORDER_LEN + ORDER_CANCEL_LEN + ORDER_EXECUTED_LEN + ORDER_DELETE_LEN
+    );
+
+    let (add, rest) = out.split_at(ADD_ORDER_LEN);
+    assert_eq!(add[0], b'A');
+    assert_eq!(add[1..3], 7u16.to_be_bytes());
+    assert_eq!(add[3..5], 0u16.to_be_bytes());
+    assert_eq!(add[5..11], [1, 2, 3, 4, 5, 6]);
+    assert_eq!(add[11..19], 2u64.to_be_bytes());
+    assert_eq!(add[19], b'B');
+    assert_eq!(add[20..24], 5u32.to_be_bytes());
+    assert_eq!(&add[24..32], b"ABC     ");
+    assert_eq!(add[32..36], 99u32.to_be_bytes());
+
+    // Amending 10 down to 8 cancels 2 shares
+    let (cancel, rest) = rest.split_at(ORDER_CANCEL_LEN);
+    assert_eq!(cancel[0], b'X');
+    assert_eq!(cancel[3..5], 1u16.to_be_bytes());
+    assert_eq!(cancel[11..19], 1u64.to_be_bytes());
+    assert_eq!(cancel[19..23], 2u32.to_be_bytes());
+
+    let (executed, delete) = rest.split_at(ORDER_EXECUTED_LEN);
+    assert_eq!(executed[0], b'E');
+    assert_eq!(executed[11..19], 1u64.to_be_bytes());
+    assert_eq!(executed[19..23], 3u32.to_be_bytes());
+    assert_eq!(executed[23..31], 1u64.to_be_bytes());
+
+    assert_eq!(delete[0], b'D');
+    assert_eq!(delete[11..19], 2u64.to_be_bytes());
+}
+
+#[test]
+fn test_itch_encoder_rejects_oversized_fields() {
+    let mut book = OrderBook::new();
+    book.record_events(true);
+    let mut encoder = ItchEncoder::new(&book, 1, "ABC");
+
+    book.execute_limit_order(Side::Bid, OrderId(1), -1, 5)
+        .unwrap();
+    let mut out = Vec::new();
+    assert_eq!(
+        encoder.encode(&book.take_events(), 0, &mut out),
+        Err(EncodeError::ValueOutOfRange)
+    );
+}
@@ -0,0 +1,118 @@
+#[cfg(all(test, feature = "itch"))]
+use crate::{
+    error::{DecodeError, ItchBookError},
+    itch_encoder::{
+        ADD_ORDER_LEN, ItchEncoder, ORDER_CANCEL_LEN, ORDER_DELETE_LEN, ORDER_EXECUTED_LEN,
+    },
+    itch_parser::{ItchBookBuilder, ItchMessage},
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+// Prefixes each fixed-size encoder message with its u16 length
+#[cfg(all(test, feature = "itch"))]
+fn frame(mut messages: &[u8]) -> Vec<u8> {
+    let mut framed = Vec::new();
+    while let Some(&message_type) = messages.first() {
+        let len = match message_type {
+            b'A' => ADD_ORDER_LEN,
+            b'E' => ORDER_EXECUTED_LEN,
+            b'X' => ORDER_CANCEL_LEN,
+            _ => ORDER_DELETE_LEN,
+        };
+        framed.extend_from_slice(&(len as u16).to_be_bytes());
+        framed.extend_from_slice(&messages[..len]);
+        messages = &messages[len..];
+    }
+    framed
+}
+
+#[cfg(feature = "itch")]
+#[test]
+fn test_itch_builder_follows_encoded_feed() {
+    let mut source = OrderBook::new();
+    source.record_events(true);
+    let mut encoder = ItchEncoder::new(&source, 3, "ABC");
+
+    source
+        .execute_limit_order(Side::Ask, OrderId(1), 101, 10)
+        .unwrap();
+    source
+        .execute_limit_order(Side::Ask, OrderId(2), 101, 10)
+        .unwrap();
+    source
+        .execute_limit_order(Side::Bid, OrderId(3), 99, 10)
+        .unwrap();
+    source.execute_market_order(Side::Bid, 14).unwrap();
+    source.cancel_quantity(OrderId(3), 4).unwrap();
+    source
+        .execute_limit_order(Side::Bid, OrderId(4), 98, 10)
+        .unwrap();
+    source.cancel_order(OrderId(4)).unwrap();
+
+    let mut messages = Vec::new();
+    encoder
+        .encode(&source.take_events(), 0, &mut messages)
+        .unwrap();
+
+    let mut builder = ItchBookBuilder::new(3);
+    builder.apply_stream(&frame(&messages)).unwrap();
+    assert_eq!(builder.book.level3_snapshot(), source.level3_snapshot());
+    assert_eq!(builder.book.last_trade_price, Some(101));
+
+    // Messages for another stock locate are ignored
+    let mut other = ItchBookBuilder::new(4);
+    other.apply_stream(&frame(&messages)).unwrap();
+    assert!(other.book.bids.is_empty() && other.book.asks.is_empty());
+}
+
+#[cfg(feature = "itch")]
+#[test]
+fn test_itch_builder_replace_and_errors() {
+    let mut builder = ItchBookBuilder::new(1);
+    builder
+        .apply(&ItchMessage::AddOrder {
+            stock_locate: 1,
+            timestamp: 0,
+            order_ref: 10,
+            side: Side::Bid,
+            shares: 100,
+            stock: *b"ABC     ",
+            price: 500,
+        })
+        .unwrap();
+
+    // 'U' message: type, locate, tracking, timestamp, original ref, new ref, shares, price
+    let mut replace = vec![b'U', 0, 1, 0, 0, 0, 0, 0, 0, 0, 0];
+    replace.extend_from_slice(&10u64.to_be_bytes());
+    replace.extend_from_slice(&11u64.to_be_bytes());
+    replace.extend_from_slice(&60u32.to_be_bytes());
+    replace.extend_from_slice(&505u32.to_be_bytes());
+    builder
+        .apply(&ItchMessage::parse(&replace).unwrap())
+        .unwrap();
+    assert!(builder.book.get_order(OrderId(10)).is_none());
+    let replaced = builder.book.get_order(OrderId(11)).unwrap();
+    assert_eq!(
+        (replaced.side, replaced.price, replaced.quantity),
+        (Side::Bid, 505, 60)
+    );
+
+    assert_eq!(
+        builder.apply(&ItchMessage::OrderDelete {
+            stock_locate: 1,
+            timestamp: 0,
+            order_ref: 10
+        }),
+        Err(ItchBookError::UnknownOrderReference(10))
+    );
+    assert_eq!(
+        ItchMessage::parse(&replace[..20]),
+        Err(DecodeError::Truncated)
+    );
+    assert_eq!(ItchMessage::parse(b"S"), Ok(ItchMessage::Other(b'S')));
+    assert_eq!(
+        builder.apply_stream(&[0, 5, b'A']),
+        Err(ItchBookError::Decode(DecodeError::Truncated))
+    );
+}
@@ -0,0 +1,46 @@
+#[cfg(all(test, feature = "latency"))]
+use crate::{
+    latency::{LatencyReport, Operation},
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[cfg(feature = "latency")]
+#[test]
+fn test_latencies_are_recorded_per_operation() {
+    let mut book = OrderBook::new();
+    for id in 1..=10 {
+        book.execute_limit_order(Side::Ask, OrderId(id), 100 + id as i64, 5)
+            .unwrap();
+    }
+    book.cancel_order(OrderId(1)).unwrap();
+    book.execute_market_order(Side::Bid, 12).unwrap();
+    book.execute_market_order_into(Side::Bid, 3, &mut Vec::new())
+        .unwrap();
+
+    // Rejections aren't counted
+    assert!(book.cancel_order(OrderId(1)).is_err());
+    assert!(
+        book.execute_limit_order(Side::Ask, OrderId(5), 100, 0)
+            .is_err()
+    );
+
+    let latencies = book.latencies();
+    let counts = [
+        Operation::LimitInsert,
+        Operation::Cancel,
+        Operation::MarketMatch,
+    ]
+    .map(|operation| latencies.report(operation).count);
+    assert_eq!(counts, [10, 1, 2]);
+
+    let report = latencies.report(Operation::LimitInsert);
+    assert!(report.min >= 1);
+    assert!(report.min <= report.p50 && report.p50 <= report.p99 && report.p999 <= report.max);
+
+    book.reset_latencies();
+    assert_eq!(
+        book.latencies().report(Operation::LimitInsert),
+        LatencyReport::default()
+    );
+}
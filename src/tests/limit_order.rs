@@ -2,7 +2,7 @@
 use crate::{
     error::LimitOrderError,
     orderbook::{OrderBook, PriceLevel},
-    types::{OrderId, Side},
+    types::{Fill, LimitOrderResult, OrderId, Side, TradeId},
 };
 
 // Testing Order Placement
@@ -21,7 +21,8 @@ fn test_place_limit_bids() {
         PriceLevel {
             head: order_index,
             tail: order_index,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 100
         }
     )
 }
@@ -41,7 +42,8 @@ fn test_place_limit_asks() {
         PriceLevel {
             head: order_index,
             tail: order_index,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 100
         }
     )
 }
@@ -55,7 +57,7 @@ fn test_duplicate_order_id_errors() {
     let duplicate = book.execute_limit_order(Side::Bid, OrderId(123), 222, 333);
     assert_eq!(duplicate, Err(LimitOrderError::OrderIdAlreadyExists));
 
-    book.execute_limit_order(Side::Ask, OrderId(321), 100, 100)
+    book.execute_limit_order(Side::Ask, OrderId(321), 300, 100)
         .unwrap();
     let duplicate = book.execute_limit_order(Side::Ask, OrderId(321), 222, 333);
     assert_eq!(duplicate, Err(LimitOrderError::OrderIdAlreadyExists));
@@ -83,7 +85,8 @@ fn test_place_multiple_limit_bids_same_price() {
         PriceLevel {
             head: first,
             tail: third,
-            order_count: 3
+            order_count: 3,
+            total_quantity: 600
         }
     )
 }
@@ -110,7 +113,8 @@ fn test_place_multiple_limit_asks_same_price() {
         PriceLevel {
             head: first,
             tail: third,
-            order_count: 3
+            order_count: 3,
+            total_quantity: 600
         }
     )
 }
@@ -137,7 +141,8 @@ fn test_place_multiple_limit_bids_different_price() {
         PriceLevel {
             head: first,
             tail: first,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 100
         }
     );
     assert_eq!(
@@ -145,7 +150,8 @@ fn test_place_multiple_limit_bids_different_price() {
         PriceLevel {
             head: second,
             tail: second,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 100
         }
     );
     assert_eq!(
@@ -153,7 +159,8 @@ fn test_place_multiple_limit_bids_different_price() {
         PriceLevel {
             head: third,
             tail: third,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 100
         }
     )
 }
@@ -180,7 +187,8 @@ fn test_place_multiple_limit_asks_different_price() {
         PriceLevel {
             head: first,
             tail: first,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 100
         }
     );
     assert_eq!(
@@ -188,7 +196,8 @@ fn test_place_multiple_limit_asks_different_price() {
         PriceLevel {
             head: second,
             tail: second,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 100
         }
     );
     assert_eq!(
@@ -196,7 +205,408 @@ fn test_place_multiple_limit_asks_different_price() {
         PriceLevel {
             head: third,
             tail: third,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 100
         }
     )
 }
+
+// Testing Crossing Limit Orders
+#[test]
+fn test_crossing_limit_bid_fully_filled() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    let fills = book
+        .execute_limit_order(Side::Bid, OrderId(2), 100, 5)
+        .unwrap()
+        .fills;
+
+    assert_eq!(
+        fills,
+        vec![Fill {
+            price: 100,
+            quantity: 5,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
+        }]
+    );
+
+    // Nothing should rest
+    assert!(book.asks.is_empty());
+    assert!(book.bids.is_empty());
+    assert_eq!(book.index_map.len(), 0);
+    assert_eq!(book.orders.len(), 0);
+}
+
+#[test]
+fn test_crossing_limit_ask_fully_filled() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    let fills = book
+        .execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap()
+        .fills;
+
+    assert_eq!(
+        fills,
+        vec![Fill {
+            price: 100,
+            quantity: 5,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
+        }]
+    );
+
+    // Nothing should rest
+    assert!(book.asks.is_empty());
+    assert!(book.bids.is_empty());
+    assert_eq!(book.index_map.len(), 0);
+    assert_eq!(book.orders.len(), 0);
+}
+
+#[test]
+fn test_crossing_limit_bid_rests_remainder() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(3), 102, 4)
+        .unwrap();
+
+    // Sweeps 100 and 101, but must not touch 102
+    let fills = book
+        .execute_limit_order(Side::Bid, OrderId(4), 101, 10)
+        .unwrap()
+        .fills;
+
+    assert_eq!(
+        fills,
+        vec![
+            Fill {
+                price: 100,
+                quantity: 2,
+                maker_order_id: OrderId(1),
+                trade_id: TradeId(1),
+                timestamp: 0,
+                fees: None,
+                maker_client_order_id: None,
+                maker_remaining_quantity: 0,
+                maker_fully_filled: true,
+            },
+            Fill {
+                price: 101,
+                quantity: 3,
+                maker_order_id: OrderId(2),
+                trade_id: TradeId(2),
+                timestamp: 0,
+                fees: None,
+                maker_client_order_id: None,
+                maker_remaining_quantity: 0,
+                maker_fully_filled: true,
+            }
+        ]
+    );
+
+    assert_eq!(book.asks.len(), 1);
+    assert!(book.asks.contains_key(&102));
+    assert_eq!(book.bids.len(), 1);
+
+    let entry = book.index_map.get(&OrderId(4)).unwrap();
+    assert_eq!(entry.price, 101);
+    assert_eq!(book.orders.get(entry.order_index).unwrap().quantity, 5);
+}
+
+#[test]
+fn test_crossing_limit_ask_rests_remainder() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 102, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 101, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 100, 4)
+        .unwrap();
+
+    // Sweeps 102 and 101, but must not touch 100
+    let fills = book
+        .execute_limit_order(Side::Ask, OrderId(4), 101, 10)
+        .unwrap()
+        .fills;
+
+    assert_eq!(
+        fills,
+        vec![
+            Fill {
+                price: 102,
+                quantity: 2,
+                maker_order_id: OrderId(1),
+                trade_id: TradeId(1),
+                timestamp: 0,
+                fees: None,
+                maker_client_order_id: None,
+                maker_remaining_quantity: 0,
+                maker_fully_filled: true,
+            },
+            Fill {
+                price: 101,
+                quantity: 3,
+                maker_order_id: OrderId(2),
+                trade_id: TradeId(2),
+                timestamp: 0,
+                fees: None,
+                maker_client_order_id: None,
+                maker_remaining_quantity: 0,
+                maker_fully_filled: true,
+            }
+        ]
+    );
+
+    assert_eq!(book.bids.len(), 1);
+    assert!(book.bids.contains_key(&100));
+    assert_eq!(book.asks.len(), 1);
+
+    let entry = book.index_map.get(&OrderId(4)).unwrap();
+    assert_eq!(entry.price, 101);
+    assert_eq!(book.orders.get(entry.order_index).unwrap().quantity, 5);
+}
+
+#[test]
+fn test_non_crossing_limit_orders_rest() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 101, 5)
+        .unwrap();
+    let result = book
+        .execute_limit_order(Side::Bid, OrderId(2), 100, 5)
+        .unwrap();
+
+    assert!(result.fills.is_empty());
+    assert_eq!(book.asks.len(), 1);
+    assert_eq!(book.bids.len(), 1);
+    assert_eq!(book.index_map.len(), 2);
+}
+
+// Testing Limit Order Results
+#[test]
+fn test_limit_result_resting_queue_position() {
+    let mut book = OrderBook::new();
+
+    let first = book
+        .execute_limit_order(Side::Bid, OrderId(1), 100, 10)
+        .unwrap();
+    let second = book
+        .execute_limit_order(Side::Bid, OrderId(2), 100, 20)
+        .unwrap();
+
+    assert_eq!(
+        first,
+        LimitOrderResult {
+            order_id: OrderId(1),
+            fills: vec![],
+            resting_quantity: 10,
+            order_index: Some(book.index_map.get(&OrderId(1)).unwrap().order_index),
+            queue_position: Some(0),
+            sequence: 1,
+        }
+    );
+    assert_eq!(
+        second,
+        LimitOrderResult {
+            order_id: OrderId(2),
+            fills: vec![],
+            resting_quantity: 20,
+            order_index: Some(book.index_map.get(&OrderId(2)).unwrap().order_index),
+            queue_position: Some(1),
+            sequence: 2,
+        }
+    );
+}
+
+#[test]
+fn test_limit_result_partially_filled() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 4)
+        .unwrap();
+    let result = book
+        .execute_limit_order(Side::Ask, OrderId(2), 100, 10)
+        .unwrap();
+
+    assert_eq!(
+        result,
+        LimitOrderResult {
+            order_id: OrderId(2),
+            fills: vec![Fill {
+                price: 100,
+                quantity: 4,
+                maker_order_id: OrderId(1),
+                trade_id: TradeId(1),
+                timestamp: 0,
+                fees: None,
+                maker_client_order_id: None,
+                maker_remaining_quantity: 0,
+                maker_fully_filled: true,
+            }],
+            resting_quantity: 6,
+            order_index: Some(book.index_map.get(&OrderId(2)).unwrap().order_index),
+            queue_position: Some(0),
+            sequence: 2,
+        }
+    );
+}
+
+#[test]
+fn test_limit_result_fully_filled() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 10)
+        .unwrap();
+    let result = book
+        .execute_limit_order(Side::Bid, OrderId(2), 100, 10)
+        .unwrap();
+
+    assert_eq!(
+        result,
+        LimitOrderResult {
+            order_id: OrderId(2),
+            fills: vec![Fill {
+                price: 100,
+                quantity: 10,
+                maker_order_id: OrderId(1),
+                trade_id: TradeId(1),
+                timestamp: 0,
+                fees: None,
+                maker_client_order_id: None,
+                maker_remaining_quantity: 0,
+                maker_fully_filled: true,
+            }],
+            resting_quantity: 0,
+            order_index: None,
+            queue_position: None,
+            sequence: 2,
+        }
+    );
+}
+
+#[test]
+fn test_limit_order_with_callback() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 4)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 4)
+        .unwrap();
+
+    let mut traded = Vec::new();
+    let result = book
+        .execute_limit_order_with(Side::Bid, OrderId(3), 101, 10, |fill| {
+            traded.push((fill.maker_order_id, fill.price, fill.quantity))
+        })
+        .unwrap();
+
+    assert_eq!(traded, vec![(OrderId(1), 100, 4), (OrderId(2), 101, 4)]);
+    assert!(result.fills.is_empty());
+    assert_eq!(result.resting_quantity, 2);
+    assert_eq!(book.best_bid(), Some((101, 2)));
+}
+
+#[test]
+fn test_with_capacity_and_reserve() {
+    let mut book = OrderBook::with_capacity(64);
+    assert!(book.orders.capacity() >= 64);
+    assert!(book.index_map.capacity() >= 64);
+
+    for id in 0..64 {
+        book.execute_limit_order(Side::Bid, OrderId(id), 100 - id as i64, 1)
+            .unwrap();
+    }
+    assert_eq!(book.orders.len(), 64);
+
+    book.reserve(100);
+    assert!(book.orders.capacity() >= 164);
+    assert!(book.index_map.capacity() >= 164);
+}
+
+#[test]
+fn test_resting_quantity_overflow_is_rejected() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, u64::MAX - 1)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 99, 1)
+        .unwrap();
+
+    // Would overflow the side's total even though it starts a new level
+    assert_eq!(
+        book.execute_limit_order(Side::Bid, OrderId(3), 98, 1),
+        Err(LimitOrderError::QuantityOverflow)
+    );
+    assert_eq!(book.side_volume(Side::Bid), u64::MAX);
+    assert_eq!(book.side_order_count(Side::Bid), 2);
+    assert!(book.get_order(OrderId(3)).is_none());
+
+    // Checked before matching, so an order that couldn't rest its remainder doesn't trade
+    book.execute_limit_order(Side::Ask, OrderId(4), 101, 1)
+        .unwrap();
+    assert_eq!(
+        book.execute_limit_order(Side::Bid, OrderId(3), 101, 2),
+        Err(LimitOrderError::QuantityOverflow)
+    );
+    assert_eq!(book.best_ask(), Some((101, 1)));
+    book.cancel_order(OrderId(4)).unwrap();
+
+    // Draining the side makes room again
+    book.execute_market_order(Side::Ask, 1).unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 98, 1)
+        .unwrap();
+    assert_eq!(book.side_volume(Side::Bid), u64::MAX);
+}
+
+#[test]
+fn test_limit_order_input_validation() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+
+    assert_eq!(
+        book.execute_limit_order(Side::Bid, OrderId(2), 0, 0),
+        Err(LimitOrderError::ZeroQuantity)
+    );
+    assert_eq!(
+        book.execute_fok_order(Side::Bid, OrderId(2), 100, 0),
+        Err(LimitOrderError::ZeroQuantity)
+    );
+    assert_eq!(
+        book.execute_post_only_order(Side::Bid, OrderId(2), 99, 0),
+        Err(LimitOrderError::ZeroQuantity)
+    );
+    assert_eq!(book.best_bid(), None);
+    assert_eq!(book.best_ask(), Some((100, 5)));
+
+    // Non-positive prices are allowed unless the book is told otherwise
+    book.execute_limit_order(Side::Bid, OrderId(2), -5, 1)
+        .unwrap();
+    book.reject_non_positive_prices = true;
+    for price in [0, -5] {
+        assert_eq!(
+            book.execute_limit_order(Side::Bid, OrderId(3), price, 1),
+            Err(LimitOrderError::NonPositivePrice)
+        );
+    }
+    assert_eq!(book.side_order_count(Side::Bid), 1);
+}
@@ -0,0 +1,212 @@
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+use crate::{
+    listener::OrderBookListener,
+    orderbook::OrderBook,
+    types::{Fill, OrderId, Price, Quantity, Side, TradeId},
+};
+
+#[cfg(test)]
+#[derive(Debug, PartialEq, Eq)]
+enum Call {
+    Added(OrderId, Side, Price, Quantity),
+    Cancelled(OrderId, Side, Price, Quantity),
+    Reduced(OrderId, Side, Price, Quantity),
+    Trade(Side, OrderId, Price, Quantity, TradeId),
+    LevelAdded(Side, Price),
+    LevelRemoved(Side, Price),
+}
+
+#[cfg(test)]
+#[derive(Default, Clone)]
+struct Recorder(Arc<Mutex<Vec<Call>>>);
+
+#[cfg(test)]
+impl Recorder {
+    fn take(&self) -> Vec<Call> {
+        self.0.lock().unwrap().drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+impl OrderBookListener for Recorder {
+    fn on_order_added(&mut self, order_id: OrderId, side: Side, price: Price, quantity: Quantity) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(Call::Added(order_id, side, price, quantity));
+    }
+
+    fn on_order_cancelled(
+        &mut self,
+        order_id: OrderId,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+    ) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(Call::Cancelled(order_id, side, price, quantity));
+    }
+
+    fn on_order_reduced(
+        &mut self,
+        order_id: OrderId,
+        side: Side,
+        price: Price,
+        new_quantity: Quantity,
+    ) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(Call::Reduced(order_id, side, price, new_quantity));
+    }
+
+    fn on_trade(&mut self, taker_side: Side, fill: &Fill) {
+        self.0.lock().unwrap().push(Call::Trade(
+            taker_side,
+            fill.maker_order_id,
+            fill.price,
+            fill.quantity,
+            fill.trade_id,
+        ));
+    }
+
+    fn on_level_added(&mut self, side: Side, price: Price) {
+        self.0.lock().unwrap().push(Call::LevelAdded(side, price));
+    }
+
+    fn on_level_removed(&mut self, side: Side, price: Price) {
+        self.0.lock().unwrap().push(Call::LevelRemoved(side, price));
+    }
+}
+
+#[test]
+fn test_listener_add_and_cancel() {
+    let recorder = Recorder::default();
+    let mut book = OrderBook::with_listener(recorder.clone());
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 6)
+        .unwrap();
+    assert_eq!(
+        recorder.take(),
+        vec![
+            Call::LevelAdded(Side::Bid, 100),
+            Call::Added(OrderId(1), Side::Bid, 100, 5),
+            Call::Added(OrderId(2), Side::Bid, 100, 6),
+        ]
+    );
+
+    book.cancel_quantity(OrderId(1), 2).unwrap();
+    book.cancel_order(OrderId(1)).unwrap();
+    book.cancel_order(OrderId(2)).unwrap();
+    assert_eq!(
+        recorder.take(),
+        vec![
+            Call::Reduced(OrderId(1), Side::Bid, 100, 3),
+            Call::Cancelled(OrderId(1), Side::Bid, 100, 3),
+            Call::Cancelled(OrderId(2), Side::Bid, 100, 6),
+            Call::LevelRemoved(Side::Bid, 100),
+        ]
+    );
+}
+
+#[test]
+fn test_listener_trades() {
+    let recorder = Recorder::default();
+    let mut book = OrderBook::new();
+    book.set_listener(recorder.clone());
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 5)
+        .unwrap();
+    recorder.take();
+
+    // Sweeps the first level, partially fills the second, then rests the remainder
+    book.execute_limit_order(Side::Bid, OrderId(3), 101, 10)
+        .unwrap();
+    assert_eq!(
+        recorder.take(),
+        vec![
+            Call::Trade(Side::Bid, OrderId(1), 100, 2, TradeId(1)),
+            Call::LevelRemoved(Side::Ask, 100),
+            Call::Trade(Side::Bid, OrderId(2), 101, 5, TradeId(2)),
+            Call::LevelRemoved(Side::Ask, 101),
+            Call::LevelAdded(Side::Bid, 101),
+            Call::Added(OrderId(3), Side::Bid, 101, 3),
+        ]
+    );
+
+    book.execute_market_order(Side::Ask, 1).unwrap();
+    assert_eq!(
+        recorder.take(),
+        vec![Call::Trade(Side::Ask, OrderId(3), 101, 1, TradeId(3))]
+    );
+}
+
+#[test]
+fn test_listener_not_cloned_with_book() {
+    let recorder = Recorder::default();
+    let book = OrderBook::with_listener(recorder.clone());
+    let mut copy = book.clone();
+
+    assert!(book.events.has_listener());
+    assert!(!copy.events.has_listener());
+
+    copy.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    assert!(recorder.take().is_empty());
+}
+
+#[cfg(test)]
+type TopOfBook = (Option<(Price, Quantity)>, Option<(Price, Quantity)>);
+
+#[cfg(test)]
+#[derive(Default, Clone)]
+struct TopOfBookRecorder(Arc<Mutex<Vec<TopOfBook>>>);
+
+#[cfg(test)]
+impl OrderBookListener for TopOfBookRecorder {
+    fn on_top_of_book_changed(
+        &mut self,
+        best_bid: Option<(Price, Quantity)>,
+        best_ask: Option<(Price, Quantity)>,
+    ) {
+        self.0.lock().unwrap().push((best_bid, best_ask));
+    }
+}
+
+#[test]
+fn test_listener_top_of_book_changes_only() {
+    let recorder = TopOfBookRecorder::default();
+    let mut book = OrderBook::with_listener(recorder.clone());
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 102, 5)
+        .unwrap();
+    // Behind the best bid, doesn't change the BBO
+    book.execute_limit_order(Side::Bid, OrderId(3), 99, 5)
+        .unwrap();
+    book.cancel_order(OrderId(3)).unwrap();
+    // Same price, size changes
+    book.execute_limit_order(Side::Bid, OrderId(4), 100, 1)
+        .unwrap();
+    book.execute_market_order(Side::Ask, 6).unwrap();
+
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        vec![
+            (Some((100, 5)), None),
+            (Some((100, 5)), Some((102, 5))),
+            (Some((100, 6)), Some((102, 5))),
+            (None, Some((102, 5))),
+        ]
+    );
+}
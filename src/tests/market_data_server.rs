@@ -0,0 +1,111 @@
+#[cfg(all(test, feature = "websocket"))]
+use std::{net::TcpStream, thread, time::Duration};
+
+#[cfg(all(test, feature = "websocket"))]
+use tungstenite::{WebSocket, stream::MaybeTlsStream};
+
+#[cfg(all(test, feature = "websocket"))]
+use crate::{
+    command::Command,
+    market_data_server::MarketDataServer,
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[cfg(all(test, feature = "websocket"))]
+fn connect(server: &mut MarketDataServer) -> WebSocket<MaybeTlsStream<TcpStream>> {
+    let (client, _) = tungstenite::connect(format!("ws://{}", server.local_addr())).unwrap();
+    // The accept thread hands the client over just after its handshake completes
+    while server.admit_subscribers() == 0 {
+        thread::yield_now();
+    }
+    client
+}
+
+#[cfg(all(test, feature = "websocket"))]
+fn next_message(client: &mut WebSocket<MaybeTlsStream<TcpStream>>) -> String {
+    client.read().unwrap().into_text().unwrap().to_string()
+}
+
+#[cfg(feature = "websocket")]
+#[test]
+fn test_market_data_server_publishes_snapshot_trades_and_deltas() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(1), 101, 10)
+        .unwrap();
+    let mut server = MarketDataServer::bind(book, "127.0.0.1:0").unwrap();
+    let mut client = connect(&mut server);
+    assert_eq!(
+        next_message(&mut client),
+        r#"{"type":"snapshot","bids":[],"asks":[[101,10]],"sequence":0}"#
+    );
+
+    server
+        .apply(Command::Limit {
+            side: Side::Bid,
+            order_id: OrderId(2),
+            price: 101,
+            quantity: 15,
+        })
+        .unwrap();
+    assert_eq!(
+        [(); 3].map(|_| next_message(&mut client)),
+        [
+            r#"{"type":"trade","sequence":1,"taker_side":"bid","price":101,"quantity":10,"trade_id":1}"#,
+            r#"{"type":"delta","sequence":2,"side":"ask","price":101,"quantity":0}"#,
+            r#"{"type":"delta","sequence":3,"side":"bid","price":101,"quantity":5}"#,
+        ]
+    );
+
+    // A late joiner's snapshot picks up where the feed is
+    let mut late = connect(&mut server);
+    assert_eq!(
+        next_message(&mut late),
+        r#"{"type":"snapshot","bids":[[101,5]],"asks":[],"sequence":3}"#
+    );
+
+    server
+        .apply(Command::Cancel {
+            order_id: OrderId(2),
+        })
+        .unwrap();
+    let deleted = r#"{"type":"delta","sequence":4,"side":"bid","price":101,"quantity":0}"#;
+    assert_eq!(next_message(&mut client), deleted);
+    assert_eq!(next_message(&mut late), deleted);
+    assert_eq!(server.subscriber_count(), 2);
+
+    // Rejected commands publish nothing
+    assert!(
+        server
+            .apply(Command::Cancel {
+                order_id: OrderId(2)
+            })
+            .is_err()
+    );
+    assert_eq!(server.sequence(), 4);
+}
+
+#[cfg(feature = "websocket")]
+#[test]
+fn test_market_data_server_drops_disconnected_clients() {
+    let mut server = MarketDataServer::bind(OrderBook::new(), "127.0.0.1:0").unwrap();
+    let client = connect(&mut server);
+    drop(client);
+
+    // Writes to a closed socket can succeed until the peer's reset arrives
+    for order_id in 1..=100 {
+        if server.subscriber_count() == 0 {
+            break;
+        }
+        server
+            .apply(Command::Limit {
+                side: Side::Bid,
+                order_id: OrderId(order_id),
+                price: 100,
+                quantity: 1,
+            })
+            .unwrap();
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(server.subscriber_count(), 0);
+}
@@ -1,7 +1,8 @@
 #[cfg(test)]
 use crate::{
+    error::MarketOrderError,
     orderbook::{OrderBook, OrderNode, PriceLevel},
-    types::{Fill, OrderId, Side},
+    types::{Fill, MarketOrderResult, MarketRemainder, OrderId, Side, TradeId},
 };
 
 #[test]
@@ -11,14 +12,21 @@ fn test_market_buy_greater_than_liquidity() {
     book.execute_limit_order(Side::Ask, OrderId(1), 100, 1)
         .unwrap();
 
-    let result = book.execute_market_order(Side::Bid, 2).unwrap();
+    let result = book.execute_market_order(Side::Bid, 2).unwrap().fills;
 
     assert_eq!(result.len(), 1);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
 
@@ -36,14 +44,21 @@ fn test_market_sell_greater_than_liquidity() {
     book.execute_limit_order(Side::Bid, OrderId(1), 100, 1)
         .unwrap();
 
-    let result = book.execute_market_order(Side::Ask, 2).unwrap();
+    let result = book.execute_market_order(Side::Ask, 2).unwrap().fills;
 
     assert_eq!(result.len(), 1);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
 
@@ -58,7 +73,7 @@ fn test_market_sell_greater_than_liquidity() {
 fn test_market_buy_no_liquidity() {
     let mut book = OrderBook::new();
 
-    let result = book.execute_market_order(Side::Bid, 2).unwrap();
+    let result = book.execute_market_order(Side::Bid, 2).unwrap().fills;
 
     assert_eq!(result.len(), 0);
 
@@ -73,7 +88,7 @@ fn test_market_buy_no_liquidity() {
 fn test_market_sell_no_liquidity() {
     let mut book = OrderBook::new();
 
-    let result = book.execute_market_order(Side::Ask, 2).unwrap();
+    let result = book.execute_market_order(Side::Ask, 2).unwrap().fills;
 
     assert_eq!(result.len(), 0);
 
@@ -91,14 +106,21 @@ fn test_market_buy_less_than_liquidity() {
     book.execute_limit_order(Side::Ask, OrderId(1), 100, 10)
         .unwrap();
 
-    let result = book.execute_market_order(Side::Bid, 3).unwrap();
+    let result = book.execute_market_order(Side::Bid, 3).unwrap().fills;
 
     assert_eq!(result.len(), 1);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 3
+            quantity: 3,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 7,
+            maker_fully_filled: false,
         }
     );
 
@@ -117,7 +139,8 @@ fn test_market_buy_less_than_liquidity() {
             quantity: 10 - 3,
             order_id: OrderId(1),
             previous: None,
-            next: None
+            next: None,
+            client_order_id: None,
         }
     );
 }
@@ -129,14 +152,21 @@ fn test_market_buy_equal_liquidity() {
     book.execute_limit_order(Side::Ask, OrderId(1), 100, 10)
         .unwrap();
 
-    let result = book.execute_market_order(Side::Bid, 10).unwrap();
+    let result = book.execute_market_order(Side::Bid, 10).unwrap().fills;
 
     assert_eq!(result.len(), 1);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 10
+            quantity: 10,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
 
@@ -154,14 +184,21 @@ fn test_market_sell_equal_liquidity() {
     book.execute_limit_order(Side::Bid, OrderId(1), 100, 10)
         .unwrap();
 
-    let result = book.execute_market_order(Side::Ask, 10).unwrap();
+    let result = book.execute_market_order(Side::Ask, 10).unwrap().fills;
 
     assert_eq!(result.len(), 1);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 10
+            quantity: 10,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
 
@@ -179,14 +216,21 @@ fn test_market_sell_less_than_liquidity() {
     book.execute_limit_order(Side::Bid, OrderId(1), 100, 10)
         .unwrap();
 
-    let result = book.execute_market_order(Side::Ask, 3).unwrap();
+    let result = book.execute_market_order(Side::Ask, 3).unwrap().fills;
 
     assert_eq!(result.len(), 1);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 3
+            quantity: 3,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 7,
+            maker_fully_filled: false,
         }
     );
 
@@ -205,7 +249,8 @@ fn test_market_sell_less_than_liquidity() {
             quantity: 10 - 3,
             order_id: OrderId(1),
             previous: None,
-            next: None
+            next: None,
+            client_order_id: None,
         }
     );
 }
@@ -229,27 +274,48 @@ fn test_market_buy_multiple_same_price() {
     let third = book.index_map.get(&OrderId(3)).unwrap().order_index;
 
     // Should have 3 fills
-    let result = book.execute_market_order(Side::Bid, 6).unwrap();
+    let result = book.execute_market_order(Side::Bid, 6).unwrap().fills;
     assert_eq!(result.len(), 3);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[1],
         Fill {
             price: 100,
-            quantity: 2
+            quantity: 2,
+            maker_order_id: OrderId(2),
+            trade_id: TradeId(2),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[2],
         Fill {
             price: 100,
-            quantity: 3
+            quantity: 3,
+            maker_order_id: OrderId(3),
+            trade_id: TradeId(3),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
 
@@ -285,27 +351,48 @@ fn test_market_sell_multiple_same_price() {
     let third = book.index_map.get(&OrderId(3)).unwrap().order_index;
 
     // Should have 3 fills
-    let result = book.execute_market_order(Side::Ask, 6).unwrap();
+    let result = book.execute_market_order(Side::Ask, 6).unwrap().fills;
     assert_eq!(result.len(), 3);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[1],
         Fill {
             price: 100,
-            quantity: 2
+            quantity: 2,
+            maker_order_id: OrderId(2),
+            trade_id: TradeId(2),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[2],
         Fill {
             price: 100,
-            quantity: 3
+            quantity: 3,
+            maker_order_id: OrderId(3),
+            trade_id: TradeId(3),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
 
@@ -341,27 +428,48 @@ fn test_market_buy_sweep_same_price() {
     let third = book.index_map.get(&OrderId(3)).unwrap().order_index;
 
     // Should have two fills
-    let result = book.execute_market_order(Side::Bid, 6).unwrap();
+    let result = book.execute_market_order(Side::Bid, 6).unwrap().fills;
     assert_eq!(result.len(), 3);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[1],
         Fill {
             price: 100,
-            quantity: 2
+            quantity: 2,
+            maker_order_id: OrderId(2),
+            trade_id: TradeId(2),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[2],
         Fill {
             price: 100,
-            quantity: 3
+            quantity: 3,
+            maker_order_id: OrderId(3),
+            trade_id: TradeId(3),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
 
@@ -396,27 +504,48 @@ fn test_market_sell_sweep_same_price() {
     let third = book.index_map.get(&OrderId(3)).unwrap().order_index;
 
     // Should have two fills
-    let result = book.execute_market_order(Side::Ask, 6).unwrap();
+    let result = book.execute_market_order(Side::Ask, 6).unwrap().fills;
     assert_eq!(result.len(), 3);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[1],
         Fill {
             price: 100,
-            quantity: 2
+            quantity: 2,
+            maker_order_id: OrderId(2),
+            trade_id: TradeId(2),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[2],
         Fill {
             price: 100,
-            quantity: 3
+            quantity: 3,
+            maker_order_id: OrderId(3),
+            trade_id: TradeId(3),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
 
@@ -451,20 +580,34 @@ fn test_market_buy_complex_fills_same_price() {
     let third = book.index_map.get(&OrderId(3)).unwrap().order_index;
 
     // Should have two fills
-    let result = book.execute_market_order(Side::Bid, 2).unwrap();
+    let result = book.execute_market_order(Side::Bid, 2).unwrap().fills;
     assert_eq!(result.len(), 2);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[1],
         Fill {
             price: 100,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(2),
+            trade_id: TradeId(2),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 1,
+            maker_fully_filled: false,
         }
     );
 
@@ -479,7 +622,8 @@ fn test_market_buy_complex_fills_same_price() {
         PriceLevel {
             head: second,
             tail: third,
-            order_count: 2
+            order_count: 2,
+            total_quantity: 4
         }
     );
 
@@ -490,7 +634,8 @@ fn test_market_buy_complex_fills_same_price() {
             quantity: 1,
             order_id: OrderId(2),
             previous: None,
-            next: Some(third)
+            next: Some(third),
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -500,7 +645,8 @@ fn test_market_buy_complex_fills_same_price() {
             quantity: 3,
             order_id: OrderId(3),
             previous: Some(second),
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -525,20 +671,34 @@ fn test_market_sell_complex_fills_same_price() {
     let third = book.index_map.get(&OrderId(3)).unwrap().order_index;
 
     // Should have two fills
-    let result = book.execute_market_order(Side::Ask, 2).unwrap();
+    let result = book.execute_market_order(Side::Ask, 2).unwrap().fills;
     assert_eq!(result.len(), 2);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[1],
         Fill {
             price: 100,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(2),
+            trade_id: TradeId(2),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 1,
+            maker_fully_filled: false,
         }
     );
 
@@ -553,7 +713,8 @@ fn test_market_sell_complex_fills_same_price() {
         PriceLevel {
             head: second,
             tail: third,
-            order_count: 2
+            order_count: 2,
+            total_quantity: 4
         }
     );
 
@@ -564,7 +725,8 @@ fn test_market_sell_complex_fills_same_price() {
             quantity: 1,
             order_id: OrderId(2),
             previous: None,
-            next: Some(third)
+            next: Some(third),
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -574,7 +736,8 @@ fn test_market_sell_complex_fills_same_price() {
             quantity: 3,
             order_id: OrderId(3),
             previous: Some(second),
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -599,20 +762,34 @@ fn test_market_buy_complex_fills_different_price() {
     let third = book.index_map.get(&OrderId(3)).unwrap().order_index;
 
     // Should have two fills
-    let result = book.execute_market_order(Side::Bid, 2).unwrap();
+    let result = book.execute_market_order(Side::Bid, 2).unwrap().fills;
     assert_eq!(result.len(), 2);
     assert_eq!(
         result[0],
         Fill {
             price: 100,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(1),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[1],
         Fill {
             price: 200,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(2),
+            trade_id: TradeId(2),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 1,
+            maker_fully_filled: false,
         }
     );
 
@@ -628,7 +805,8 @@ fn test_market_buy_complex_fills_different_price() {
             quantity: 1,
             order_id: OrderId(2),
             previous: None,
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -638,7 +816,8 @@ fn test_market_buy_complex_fills_different_price() {
             quantity: 3,
             order_id: OrderId(3),
             previous: None,
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -654,7 +833,8 @@ fn test_market_buy_complex_fills_different_price() {
         Some(PriceLevel {
             head: second,
             tail: second,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 1
         })
         .as_ref()
     );
@@ -663,7 +843,8 @@ fn test_market_buy_complex_fills_different_price() {
         Some(PriceLevel {
             head: third,
             tail: third,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 3
         })
         .as_ref()
     );
@@ -688,20 +869,34 @@ fn test_market_sell_complex_fills_different_price() {
     let third = book.index_map.get(&OrderId(3)).unwrap().order_index;
 
     // Should have two fills
-    let result = book.execute_market_order(Side::Ask, 4).unwrap();
+    let result = book.execute_market_order(Side::Ask, 4).unwrap().fills;
     assert_eq!(result.len(), 2);
     assert_eq!(
         result[0],
         Fill {
             price: 300,
-            quantity: 3
+            quantity: 3,
+            maker_order_id: OrderId(3),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 0,
+            maker_fully_filled: true,
         }
     );
     assert_eq!(
         result[1],
         Fill {
             price: 200,
-            quantity: 1
+            quantity: 1,
+            maker_order_id: OrderId(2),
+            trade_id: TradeId(2),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 1,
+            maker_fully_filled: false,
         }
     );
 
@@ -716,7 +911,8 @@ fn test_market_sell_complex_fills_different_price() {
             quantity: 2,
             order_id: OrderId(1),
             previous: None,
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -726,7 +922,8 @@ fn test_market_sell_complex_fills_different_price() {
             quantity: 1,
             order_id: OrderId(2),
             previous: None,
-            next: None
+            next: None,
+            client_order_id: None,
         })
         .as_ref()
     );
@@ -742,7 +939,8 @@ fn test_market_sell_complex_fills_different_price() {
         Some(PriceLevel {
             head: first,
             tail: first,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 2
         })
         .as_ref()
     );
@@ -751,9 +949,240 @@ fn test_market_sell_complex_fills_different_price() {
         Some(PriceLevel {
             head: second,
             tail: second,
-            order_count: 1
+            order_count: 1,
+            total_quantity: 1
         })
         .as_ref()
     );
     assert_eq!(third_price, None);
 }
+
+#[test]
+fn test_trade_ids_increase_across_orders() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 1)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+
+    let first = book.execute_market_order(Side::Bid, 2).unwrap().fills;
+    let second = book.execute_market_order(Side::Bid, 2).unwrap().fills;
+
+    let trade_ids: Vec<_> = first.iter().chain(&second).map(|f| f.trade_id).collect();
+    assert_eq!(trade_ids, vec![TradeId(1), TradeId(2), TradeId(3)]);
+    assert_eq!(book.next_trade_id, TradeId(4));
+}
+
+#[test]
+fn test_market_order_into_reuses_buffer() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 3)
+        .unwrap();
+
+    let mut fills = Vec::with_capacity(4);
+    assert_eq!(
+        book.execute_market_order_into(Side::Bid, 1, &mut fills),
+        Ok(0)
+    );
+    assert_eq!(
+        book.execute_market_order_into(Side::Bid, 6, &mut fills),
+        Ok(2)
+    );
+
+    // Fills are appended after whatever the buffer already holds
+    let makers: Vec<_> = fills
+        .iter()
+        .map(|f| (f.maker_order_id, f.quantity))
+        .collect();
+    assert_eq!(
+        makers,
+        vec![(OrderId(1), 1), (OrderId(1), 1), (OrderId(2), 3)]
+    );
+
+    fills.clear();
+    assert_eq!(
+        book.execute_market_order_into(Side::Bid, 1, &mut fills),
+        Ok(1)
+    );
+    assert!(fills.is_empty());
+}
+
+#[test]
+fn test_market_order_with_callback() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 99, 3)
+        .unwrap();
+
+    let mut filled = 0;
+    let mut last_trade = None;
+    let remaining = book
+        .execute_market_order_with(Side::Ask, 10, |fill| {
+            filled += fill.quantity;
+            last_trade = Some(fill.trade_id);
+        })
+        .unwrap();
+
+    assert_eq!(remaining, 4);
+    assert_eq!(filled, 6);
+    assert_eq!(last_trade, Some(TradeId(2)));
+    assert!(book.bids.is_empty());
+}
+
+#[test]
+fn test_market_order_with_price_protection() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(3), 105, 5)
+        .unwrap();
+
+    // Sweeps up to and including 101, then stops short of 105
+    let result = book
+        .execute_market_order_with_limit(Side::Bid, 12, 101)
+        .unwrap();
+    let prices: Vec<_> = result.fills.iter().map(|fill| fill.price).collect();
+    assert_eq!(prices, vec![100, 101]);
+    assert_eq!(result.remaining, 2);
+    assert_eq!(book.best_ask(), Some((105, 5)));
+
+    // Nothing within the limit, nothing traded
+    let result = book
+        .execute_market_order_with_limit(Side::Bid, 3, 104)
+        .unwrap();
+    assert!(result.fills.is_empty());
+    assert_eq!(result.remaining, 3);
+
+    book.execute_limit_order(Side::Bid, OrderId(4), 90, 5)
+        .unwrap();
+    let result = book
+        .execute_market_order_with_limit(Side::Ask, 5, 90)
+        .unwrap();
+    assert_eq!((result.fills.len(), result.remaining), (1, 0));
+    assert_eq!(book.best_bid(), None);
+}
+
+#[test]
+fn test_market_order_remainder_handling() {
+    let mut book = OrderBook::new();
+    let add_asks = |book: &mut OrderBook| {
+        book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+            .unwrap();
+        book.execute_limit_order(Side::Ask, OrderId(2), 101, 5)
+            .unwrap();
+    };
+
+    add_asks(&mut book);
+    let result = book
+        .execute_market_order_with_remainder(Side::Bid, 15, MarketRemainder::Discard)
+        .unwrap();
+    assert_eq!((result.fills.len(), result.remaining), (2, 0));
+    assert_eq!(book.best_bid(), None);
+
+    add_asks(&mut book);
+    let result = book
+        .execute_market_order_with_remainder(Side::Bid, 15, MarketRemainder::Return)
+        .unwrap();
+    assert_eq!((result.remaining, result.resting_quantity), (5, 0));
+
+    add_asks(&mut book);
+    let result = book
+        .execute_market_order_with_remainder(
+            Side::Bid,
+            15,
+            MarketRemainder::RestAtLastTrade(OrderId(3)),
+        )
+        .unwrap();
+    assert_eq!((result.remaining, result.resting_quantity), (0, 5));
+    assert_eq!(book.best_bid(), Some((101, 5)));
+    assert_eq!(book.get_order(OrderId(3)).unwrap().price, 101);
+
+    // The Id for the remainder has to be free up front
+    assert_eq!(
+        book.execute_market_order_with_remainder(
+            Side::Ask,
+            1,
+            MarketRemainder::RestAtLastTrade(OrderId(3)),
+        ),
+        Err(MarketOrderError::OrderIdAlreadyExists)
+    );
+}
+
+#[test]
+fn test_market_order_remainder_without_last_trade() {
+    let mut book = OrderBook::new();
+
+    // Nothing has traded to price the remainder from, so it comes back unfilled
+    let result = book
+        .execute_market_order_with_remainder(
+            Side::Ask,
+            4,
+            MarketRemainder::RestAtLastTrade(OrderId(1)),
+        )
+        .unwrap();
+    assert_eq!(
+        result,
+        MarketOrderResult {
+            fills: Vec::new(),
+            filled_quantity: 0,
+            notional: 0,
+            remaining: 4,
+            resting_quantity: 0,
+            sequence: 1,
+        }
+    );
+    assert!(book.get_order(OrderId(1)).is_none());
+}
+
+#[test]
+fn test_market_order_result_totals() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 103, 1)
+        .unwrap();
+
+    let result = book.execute_market_order(Side::Bid, 4).unwrap();
+    assert_eq!(result.filled_quantity, 3);
+    assert_eq!(result.notional, 303);
+    assert_eq!(result.remaining, 1);
+    assert_eq!(result.average_price(), Some(101.0));
+
+    let result = book.execute_market_order(Side::Bid, 4).unwrap();
+    assert_eq!((result.filled_quantity, result.remaining), (0, 4));
+    assert_eq!(result.average_price(), None);
+}
+
+#[test]
+fn test_fills_report_what_is_left_of_the_maker() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 5)
+        .unwrap();
+
+    let fills = book.execute_market_order(Side::Bid, 7).unwrap().fills;
+    let makers: Vec<_> = fills
+        .iter()
+        .map(|fill| (fill.maker_remaining_quantity, fill.maker_fully_filled))
+        .collect();
+    assert_eq!(makers, [(0, true), (3, false)]);
+
+    // Trading a resting order directly reports it the same way
+    let fill = book.execute_resting_order(OrderId(2), 1, None).unwrap();
+    assert_eq!(
+        (fill.maker_remaining_quantity, fill.maker_fully_filled),
+        (2, false)
+    );
+    let fill = book.execute_resting_order(OrderId(2), 5, None).unwrap();
+    assert_eq!((fill.quantity, fill.maker_remaining_quantity), (2, 0));
+    assert!(fill.maker_fully_filled);
+}
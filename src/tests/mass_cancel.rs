@@ -0,0 +1,141 @@
+#[cfg(test)]
+use crate::{
+    events::BookEvent,
+    orderbook::OrderBook,
+    self_trade::SelfTradePrevention,
+    types::{OrderDetails, OrderId, OwnerId, Side},
+};
+
+#[cfg(test)]
+fn book() -> OrderBook {
+    let mut book = OrderBook::new();
+    for (id, price) in [(1, 98), (2, 99), (3, 99), (4, 100), (5, 97)] {
+        book.execute_limit_order(Side::Bid, OrderId(id), price, id)
+            .unwrap();
+    }
+    book.execute_limit_order(Side::Ask, OrderId(6), 101, 6)
+        .unwrap();
+    book
+}
+
+#[test]
+fn test_cancel_range_bids() {
+    let mut book = book();
+    book.record_events(true);
+
+    let cancelled = book.cancel_range(Side::Bid, 98..=99);
+    let details = |order_id, price, quantity, queue_position| OrderDetails {
+        order_id: OrderId(order_id),
+        side: Side::Bid,
+        price,
+        quantity,
+        queue_position,
+        timestamp: 0,
+        client_order_id: None,
+    };
+    assert_eq!(
+        cancelled,
+        vec![
+            details(2, 99, 2, 0),
+            details(3, 99, 3, 1),
+            details(1, 98, 1, 0)
+        ]
+    );
+
+    let bids: Vec<_> = book.bids().map(|(price, _)| price).collect();
+    assert_eq!(bids, vec![100, 97]);
+    assert_eq!(book.side_order_count(Side::Bid), 2);
+    assert_eq!(book.side_volume(Side::Bid), 9);
+    assert!(book.get_order(OrderId(2)).is_none());
+    assert_eq!(book.best_ask(), Some((101, 6)));
+
+    let events = book.take_events();
+    assert_eq!(
+        events.first(),
+        Some(&BookEvent::OrderCancelled {
+            order_id: OrderId(2),
+            side: Side::Bid,
+            price: 99,
+            quantity: 2,
+            client_order_id: None,
+        })
+    );
+    assert_eq!(
+        events.last(),
+        Some(&BookEvent::LevelRemoved {
+            side: Side::Bid,
+            price: 98
+        })
+    );
+
+    // The freed slots are reused and the book keeps working
+    book.execute_limit_order(Side::Bid, OrderId(2), 99, 4)
+        .unwrap();
+    assert_eq!(book.best_bid(), Some((100, 4)));
+    assert_eq!(
+        book.execute_market_order(Side::Ask, 8).unwrap().fills.len(),
+        2
+    );
+}
+
+#[test]
+fn test_cancel_range_asks_and_empty_band() {
+    let mut book = book();
+    book.execute_owned_limit_order(
+        Side::Ask,
+        OrderId(7),
+        105,
+        1,
+        OwnerId(1),
+        SelfTradePrevention::CancelNewest,
+    )
+    .unwrap();
+
+    assert!(book.cancel_range(Side::Ask, 102..105).is_empty());
+
+    let cancelled: Vec<_> = book
+        .cancel_range(Side::Ask, 101..)
+        .into_iter()
+        .map(|order| order.order_id)
+        .collect();
+    assert_eq!(cancelled, vec![OrderId(6), OrderId(7)]);
+    assert!(book.asks.is_empty());
+    assert_eq!(book.owner_orders.orders(OwnerId(1)).count(), 0);
+}
+
+#[test]
+fn test_clear() {
+    let mut book = book();
+    book.execute_stop_limit_order(Side::Bid, OrderId(8), 110, 111, 1)
+        .unwrap();
+    book.execute_market_order(Side::Bid, 1).unwrap();
+
+    let cancelled: Vec<_> = book
+        .clear()
+        .into_iter()
+        .map(|order| (order.order_id, order.quantity))
+        .collect();
+    assert_eq!(
+        cancelled,
+        vec![
+            (OrderId(4), 4),
+            (OrderId(2), 2),
+            (OrderId(3), 3),
+            (OrderId(1), 1),
+            (OrderId(5), 5),
+            (OrderId(6), 5)
+        ]
+    );
+
+    assert!(book.bids.is_empty() && book.asks.is_empty());
+    assert!(book.orders.is_empty() && book.index_map.is_empty());
+    assert!(book.stop_orders.is_empty());
+    assert_eq!((book.best_bid(), book.best_ask()), (None, None));
+    assert_eq!(book.side_volume(Side::Bid), 0);
+
+    // Ids may be reused, trade ids carry on
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 1)
+        .unwrap();
+    let fills = book.execute_market_order(Side::Bid, 1).unwrap().fills;
+    assert_eq!(fills[0].trade_id.0, 2);
+}
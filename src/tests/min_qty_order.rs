@@ -0,0 +1,70 @@
+#[cfg(test)]
+use crate::{
+    error::LimitOrderError,
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_min_qty_buy_fills_and_rests_remainder() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 2)
+        .unwrap();
+
+    let result = book
+        .execute_min_qty_order(Side::Bid, OrderId(3), 101, 10, 5)
+        .unwrap();
+
+    assert_eq!(result.fills.len(), 2);
+    assert_eq!(result.resting_quantity, 5);
+    assert!(book.asks.is_empty());
+    assert_eq!(book.index_map.get(&OrderId(3)).unwrap().price, 101);
+}
+
+#[test]
+fn test_min_qty_sell_fills_and_rests_remainder() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 3)
+        .unwrap();
+
+    let result = book
+        .execute_min_qty_order(Side::Ask, OrderId(2), 100, 10, 3)
+        .unwrap();
+
+    assert_eq!(result.fills.len(), 1);
+    assert_eq!(result.resting_quantity, 7);
+    assert!(book.bids.is_empty());
+    assert_eq!(book.asks.len(), 1);
+}
+
+#[test]
+fn test_min_qty_rejected_below_minimum() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 102, 5)
+        .unwrap();
+
+    // Only 3 available within the limit price
+    let result = book.execute_min_qty_order(Side::Bid, OrderId(3), 101, 10, 4);
+    assert_eq!(result, Err(LimitOrderError::InsufficientLiquidity));
+
+    // Book must be untouched
+    assert!(book.bids.is_empty());
+    assert_eq!(book.asks.len(), 2);
+    assert_eq!(book.index_map.len(), 2);
+}
+
+#[test]
+fn test_min_qty_rejected_on_empty_book() {
+    let mut book = OrderBook::new();
+
+    let result = book.execute_min_qty_order(Side::Ask, OrderId(1), 100, 10, 1);
+    assert_eq!(result, Err(LimitOrderError::InsufficientLiquidity));
+    assert!(book.asks.is_empty());
+}
@@ -1,3 +1,66 @@
+mod accounts;
+mod amend_order;
+mod analytics;
+mod auction;
+mod backtest;
+mod batch;
+mod binary_snapshot;
+mod book_side;
 mod cancel_order;
+mod candles;
+mod client_order_id;
+mod clock;
+mod compaction;
+mod csv;
+mod dark_pool;
+mod day_order;
+mod decimal;
+mod depth;
+mod depth_delta;
+mod engine;
+mod events;
+mod fees;
+mod ffi;
+mod fix;
+mod fok_order;
+mod fuzz;
+mod grpc;
+mod gtd_order;
+mod hidden_order;
+mod hooks;
+mod instrument;
+mod internal_error;
+mod itch_encoder;
+mod itch_parser;
+mod journal;
+mod latency;
 mod limit_order;
+mod listener;
+mod market_data_server;
 mod market_order;
+mod mass_cancel;
+mod min_qty_order;
+mod order_ids;
+mod order_query;
+mod pegged_order;
+mod post_only_order;
+mod publish;
+mod python;
+mod reference_book;
+mod repl;
+mod resting_execution;
+mod sbe;
+mod self_trade;
+mod serialization;
+mod service;
+mod shared_snapshot;
+mod sim;
+mod snapshot;
+mod spsc;
+mod stop_order;
+mod submit_order;
+mod top_of_book;
+mod trade_stats;
+mod trading_state;
+mod validate;
+mod wasm;
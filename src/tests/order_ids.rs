@@ -0,0 +1,273 @@
+#[cfg(test)]
+use crate::{
+    error::LimitOrderError,
+    events::BookEvent,
+    order::{Order, TimeInForce},
+    order_ids::{DuplicateOrderIdPolicy, OrderIdMode},
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[cfg(test)]
+fn assigning_book() -> OrderBook {
+    let mut book = OrderBook::new();
+    book.order_id_mode = OrderIdMode::Assigned;
+    book
+}
+
+#[test]
+fn test_book_assigns_order_ids() {
+    let mut book = assigning_book();
+
+    // Whatever Id the order comes with is ignored, so it can't be a duplicate
+    let first = book
+        .execute_limit_order(Side::Ask, OrderId(0), 100, 5)
+        .unwrap();
+    let second = book
+        .execute_limit_order(Side::Ask, OrderId(0), 101, 5)
+        .unwrap();
+    assert_eq!((first.order_id, second.order_id), (OrderId(1), OrderId(2)));
+    assert_eq!(book.get_order(OrderId(2)).unwrap().price, 101);
+
+    // Orders that fill on arrival still get an Id of their own
+    let result = book
+        .execute_limit_order(Side::Bid, OrderId(0), 100, 5)
+        .unwrap();
+    assert_eq!(result.order_id, OrderId(3));
+    assert_eq!(result.fills[0].maker_order_id, OrderId(1));
+
+    let result = book
+        .execute_post_only_order(Side::Bid, OrderId(2), 99, 5)
+        .unwrap();
+    assert_eq!(result.order_id, OrderId(4));
+}
+
+#[test]
+fn test_callers_get_their_own_ids_by_default() {
+    let mut book = OrderBook::new();
+    let result = book
+        .execute_limit_order(Side::Ask, OrderId(7), 100, 5)
+        .unwrap();
+    assert_eq!(result.order_id, OrderId(7));
+    let result = book
+        .execute_limit_order(Side::Bid, OrderId(8), 100, 5)
+        .unwrap();
+    assert_eq!(result.order_id, OrderId(8));
+}
+
+#[test]
+fn test_assigned_ids_skip_ids_in_use() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Bid, OrderId(3), 110, 111, 5)
+        .unwrap();
+
+    book.order_id_mode = OrderIdMode::Assigned;
+    let ids: Vec<_> = (0..3)
+        .map(|_| {
+            book.execute_limit_order(Side::Ask, OrderId(0), 101, 1)
+                .unwrap()
+                .order_id
+        })
+        .collect();
+    assert_eq!(ids, [OrderId(1), OrderId(4), OrderId(5)]);
+}
+
+#[test]
+fn test_triggered_stops_keep_their_ids() {
+    let mut book = assigning_book();
+    book.execute_stop_limit_order(Side::Bid, OrderId(50), 100, 101, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(0), 100, 1)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(0), 100, 1)
+        .unwrap();
+
+    assert!(book.stop_orders.is_empty());
+    assert_eq!(book.get_order(OrderId(50)).unwrap().price, 101);
+}
+
+#[test]
+fn test_assigned_ids_keep_order_attributes() {
+    let mut book = assigning_book();
+    let result = book
+        .execute_gtd_order(Side::Bid, OrderId(0), 99, 5, 100)
+        .unwrap();
+    let order = Order::builder(Side::Bid, OrderId(0), 98, 5)
+        .time_in_force(TimeInForce::Day)
+        .build();
+    let day = book.submit_order(&order).unwrap();
+    assert_eq!((result.order_id, day.order_id), (OrderId(1), OrderId(2)));
+
+    assert_eq!(book.expire_orders(100), [OrderId(1)]);
+    assert_eq!(book.cancel_day_orders(), [OrderId(2)]);
+}
+
+#[test]
+fn test_order_id_mode_survives_binary_snapshots() {
+    let mut book = assigning_book();
+    book.execute_limit_order(Side::Ask, OrderId(0), 100, 5)
+        .unwrap();
+
+    let mut restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert_eq!(restored.order_id_mode, OrderIdMode::Assigned);
+    let result = restored
+        .execute_limit_order(Side::Ask, OrderId(0), 100, 5)
+        .unwrap();
+    assert_eq!(result.order_id, OrderId(2));
+}
+
+#[cfg(test)]
+fn book_with_policy(policy: DuplicateOrderIdPolicy) -> OrderBook {
+    let mut book = OrderBook::new();
+    book.duplicate_order_id_policy = policy;
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+    book
+}
+
+#[test]
+fn test_duplicate_order_ids_are_rejected_by_default() {
+    let mut book = book_with_policy(DuplicateOrderIdPolicy::Reject);
+    assert_eq!(
+        book.execute_limit_order(Side::Ask, OrderId(1), 100, 3),
+        Err(LimitOrderError::OrderIdAlreadyExists)
+    );
+    assert_eq!(
+        book.execute_post_only_order(Side::Ask, OrderId(1), 100, 3),
+        Err(LimitOrderError::OrderIdAlreadyExists)
+    );
+    assert_eq!(book.get_order(OrderId(1)).unwrap().quantity, 5);
+}
+
+#[test]
+fn test_duplicate_order_id_cancel_replaces() {
+    let mut book = book_with_policy(DuplicateOrderIdPolicy::CancelReplace);
+    book.record_events(true);
+    let result = book
+        .execute_limit_order(Side::Ask, OrderId(1), 100, 3)
+        .unwrap();
+    // Back of the queue, behind the order that was behind it
+    assert_eq!(result.queue_position, Some(1));
+    assert_eq!(book.best_ask(), Some((100, 8)));
+    let events = book.take_events();
+    assert!(matches!(
+        events[..],
+        [
+            BookEvent::OrderCancelled {
+                order_id: OrderId(1),
+                quantity: 5,
+                ..
+            },
+            BookEvent::OrderAdded {
+                order_id: OrderId(1),
+                quantity: 3,
+                ..
+            },
+            ..
+        ]
+    ));
+
+    // Replacements can move to the other side. The order being replaced is gone by the
+    // time its replacement arrives, so only other orders stop a post-only one.
+    assert_eq!(
+        book.execute_post_only_order(Side::Bid, OrderId(2), 100, 4),
+        Err(LimitOrderError::PostOnlyWouldCross)
+    );
+    book.cancel_order(OrderId(1)).unwrap();
+    let result = book
+        .execute_post_only_order(Side::Bid, OrderId(2), 100, 4)
+        .unwrap();
+    assert_eq!(result.resting_quantity, 4);
+    assert_eq!(book.best_ask(), None);
+
+    // And trade on arrival
+    book.execute_limit_order(Side::Bid, OrderId(3), 99, 2)
+        .unwrap();
+    let result = book
+        .execute_limit_order(Side::Ask, OrderId(2), 99, 2)
+        .unwrap();
+    assert_eq!(result.fills[0].maker_order_id, OrderId(3));
+    assert_eq!((book.best_bid(), book.best_ask()), (None, None));
+}
+
+#[test]
+fn test_cancel_replace_keeps_the_old_order_if_the_new_one_is_invalid() {
+    let mut book = book_with_policy(DuplicateOrderIdPolicy::CancelReplace);
+    assert_eq!(
+        book.execute_limit_order(Side::Ask, OrderId(1), 100, 0),
+        Err(LimitOrderError::ZeroQuantity)
+    );
+    assert_eq!(book.queue_position(OrderId(1)), Some(0));
+
+    // Nor does a replacement the book then turns away
+    book.execute_limit_order(Side::Bid, OrderId(4), 99, 5)
+        .unwrap();
+    assert_eq!(
+        book.execute_fok_order(Side::Bid, OrderId(4), 99, 5),
+        Err(LimitOrderError::InsufficientLiquidity)
+    );
+    // The order being replaced doesn't count as liquidity for its replacement
+    assert_eq!(
+        book.execute_fok_order(Side::Bid, OrderId(1), 100, 10),
+        Err(LimitOrderError::InsufficientLiquidity)
+    );
+    let order = Order::builder(Side::Bid, OrderId(4), 101, 5)
+        .post_only()
+        .build();
+    assert_eq!(
+        book.submit_order(&order),
+        Err(LimitOrderError::PostOnlyWouldCross)
+    );
+    assert_eq!(book.best_bid(), Some((99, 5)));
+    assert_eq!(book.best_ask(), Some((100, 10)));
+    book.cancel_order(OrderId(4)).unwrap();
+
+    // Stops are replaced too
+    book.execute_stop_limit_order(Side::Bid, OrderId(3), 110, 111, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 99, 5)
+        .unwrap();
+    assert!(book.stop_orders.is_empty());
+    assert_eq!(book.best_bid(), Some((99, 5)));
+}
+
+#[test]
+fn test_duplicate_order_id_amends() {
+    let mut book = book_with_policy(DuplicateOrderIdPolicy::Amend);
+    let result = book
+        .execute_limit_order(Side::Ask, OrderId(1), 100, 3)
+        .unwrap();
+    assert_eq!(
+        (result.resting_quantity, result.queue_position),
+        (3, Some(0))
+    );
+    assert_eq!(result.sequence, book.command_sequence());
+
+    // Increasing loses priority, as with `amend_quantity`
+    let order = Order::builder(Side::Ask, OrderId(1), 100, 6).build();
+    let result = book.submit_order(&order).unwrap();
+    assert_eq!(result.queue_position, Some(1));
+    assert_eq!(book.best_ask(), Some((100, 11)));
+
+    // Any other price is a new order
+    let result = book
+        .execute_limit_order(Side::Ask, OrderId(1), 101, 6)
+        .unwrap();
+    assert_eq!(result.queue_position, Some(0));
+    assert_eq!(book.best_ask(), Some((100, 5)));
+    assert_eq!(book.get_order(OrderId(1)).unwrap().price, 101);
+}
+
+#[test]
+fn test_duplicate_order_id_policy_survives_binary_snapshots() {
+    let book = book_with_policy(DuplicateOrderIdPolicy::Amend);
+    let restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert_eq!(
+        restored.duplicate_order_id_policy,
+        DuplicateOrderIdPolicy::Amend
+    );
+}
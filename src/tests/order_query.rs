@@ -0,0 +1,347 @@
+#[cfg(test)]
+use crate::{
+    orderbook::OrderBook,
+    types::{OrderDetails, OrderId, Side, VolumeAhead},
+};
+
+#[test]
+fn test_get_order_details() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 6)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(3), 101, 7)
+        .unwrap();
+
+    assert_eq!(
+        book.get_order(OrderId(1)),
+        Some(OrderDetails {
+            order_id: OrderId(1),
+            side: Side::Bid,
+            price: 100,
+            quantity: 5,
+            queue_position: 0,
+            timestamp: 0,
+            client_order_id: None,
+        })
+    );
+    assert_eq!(
+        book.get_order(OrderId(2)),
+        Some(OrderDetails {
+            order_id: OrderId(2),
+            side: Side::Bid,
+            price: 100,
+            quantity: 6,
+            queue_position: 1,
+            timestamp: 0,
+            client_order_id: None,
+        })
+    );
+    assert_eq!(
+        book.get_order(OrderId(3)),
+        Some(OrderDetails {
+            order_id: OrderId(3),
+            side: Side::Ask,
+            price: 101,
+            quantity: 7,
+            queue_position: 0,
+            timestamp: 0,
+            client_order_id: None,
+        })
+    );
+}
+
+#[test]
+fn test_get_order_after_partial_fill_and_cancel() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(3), 100, 5)
+        .unwrap();
+
+    book.cancel_order(OrderId(1)).unwrap();
+    book.execute_market_order(Side::Bid, 2).unwrap();
+
+    let second = book.get_order(OrderId(2)).unwrap();
+    assert_eq!(second.quantity, 3);
+    assert_eq!(second.queue_position, 0);
+    assert_eq!(book.get_order(OrderId(3)).unwrap().queue_position, 1);
+    assert_eq!(book.get_order(OrderId(1)), None);
+}
+
+#[test]
+fn test_orders_at_fifo_order() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 6)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 99, 1)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(4), 100, 7)
+        .unwrap();
+
+    let orders: Vec<_> = book.orders_at(Side::Bid, 100).collect();
+    assert_eq!(
+        orders,
+        vec![(OrderId(1), 5), (OrderId(2), 6), (OrderId(4), 7)]
+    );
+
+    // Cancel from the middle, amend to the back
+    book.cancel_order(OrderId(2)).unwrap();
+    book.amend_quantity(OrderId(1), 8).unwrap();
+
+    let orders: Vec<_> = book.orders_at(Side::Bid, 100).collect();
+    assert_eq!(orders, vec![(OrderId(4), 7), (OrderId(1), 8)]);
+}
+
+#[test]
+fn test_orders_at_missing_level() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+
+    assert_eq!(book.orders_at(Side::Ask, 101).count(), 0);
+    assert_eq!(book.orders_at(Side::Bid, 100).count(), 0);
+}
+
+#[test]
+fn test_queue_position_every_slot() {
+    let mut book = OrderBook::new();
+
+    for i in 0..7 {
+        book.execute_limit_order(Side::Ask, OrderId(i), 100, 1)
+            .unwrap();
+    }
+
+    for i in 0..7 {
+        assert_eq!(book.queue_position(OrderId(i)), Some(i as usize));
+    }
+
+    // Positions shift forward once orders ahead leave
+    book.cancel_order(OrderId(2)).unwrap();
+    book.execute_market_order(Side::Bid, 1).unwrap();
+
+    assert_eq!(book.queue_position(OrderId(1)), Some(0));
+    assert_eq!(book.queue_position(OrderId(3)), Some(1));
+    assert_eq!(book.queue_position(OrderId(6)), Some(4));
+    assert_eq!(book.queue_position(OrderId(0)), None);
+}
+
+#[cfg(test)]
+fn assert_positions_match_queue(book: &OrderBook, price: i64) {
+    for (position, (order_id, _)) in book.orders_at(Side::Ask, price).enumerate() {
+        assert_eq!(book.queue_position(order_id), Some(position));
+    }
+}
+
+#[test]
+fn test_queue_position_through_churn() {
+    let mut book = OrderBook::new();
+    for i in 0..64 {
+        book.execute_limit_order(Side::Ask, OrderId(i), 100, 2)
+            .unwrap();
+    }
+
+    // Enough cancels from the middle to renumber the level's slots, with fills at the
+    // front, increases moving to the back, and new arrivals in between
+    for i in (1..64).step_by(3) {
+        book.cancel_order(OrderId(i)).unwrap();
+        assert_positions_match_queue(&book, 100);
+    }
+    book.execute_market_order(Side::Bid, 5).unwrap();
+    book.amend_quantity(OrderId(6), 4).unwrap();
+    book.amend_quantity(OrderId(9), 1).unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(100), 100, 2)
+        .unwrap();
+    assert_positions_match_queue(&book, 100);
+    assert_eq!(book.queue_position(OrderId(100)), Some(41));
+
+    // Restored and compacted books carry on counting from the same queue
+    let mut book = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert_positions_match_queue(&book, 100);
+    book.compact();
+    book.cancel_order(OrderId(12)).unwrap();
+    assert_positions_match_queue(&book, 100);
+    assert_eq!(book.queue_position(OrderId(100)), Some(40));
+
+    // A level emptied by a mass cancel starts over
+    book.clear();
+    book.execute_limit_order(Side::Ask, OrderId(3), 100, 2)
+        .unwrap();
+    assert_eq!(book.queue_position(OrderId(3)), Some(0));
+}
+
+#[test]
+fn test_volume_ahead_bid() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 101, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 102, 20)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 100, 1)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(4), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(5), 100, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(6), 100, 4)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(7), 99, 50)
+        .unwrap();
+
+    let expected = [(3, 0), (4, 1), (5, 3), (6, 6)];
+    for (id, at_price) in expected {
+        assert_eq!(
+            book.volume_ahead(OrderId(id)),
+            Some(VolumeAhead {
+                at_price,
+                better_prices: 30
+            })
+        );
+    }
+    assert_eq!(
+        book.volume_ahead(OrderId(2)),
+        Some(VolumeAhead {
+            at_price: 0,
+            better_prices: 0
+        })
+    );
+}
+
+#[test]
+fn test_volume_ahead_ask() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 99, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(3), 100, 6)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(4), 101, 7)
+        .unwrap();
+
+    // Partial fill ahead reduces the volume ahead
+    book.execute_market_order(Side::Bid, 12).unwrap();
+
+    assert_eq!(
+        book.volume_ahead(OrderId(3)),
+        Some(VolumeAhead {
+            at_price: 3,
+            better_prices: 0
+        })
+    );
+    assert_eq!(
+        book.volume_ahead(OrderId(4)),
+        Some(VolumeAhead {
+            at_price: 0,
+            better_prices: 9
+        })
+    );
+    assert_eq!(book.volume_ahead(OrderId(1)), None);
+}
+
+#[cfg(test)]
+fn assert_totals_match_levels(book: &OrderBook) {
+    for side in [Side::Bid, Side::Ask] {
+        let levels = match side {
+            Side::Bid => &book.bids,
+            Side::Ask => &book.asks,
+        };
+        let quantity: u64 = levels.iter().map(|(_, level)| level.total_quantity).sum();
+        let orders: usize = levels.iter().map(|(_, level)| level.order_count).sum();
+        assert_eq!(book.side_volume(side), quantity);
+        assert_eq!(book.side_order_count(side), orders);
+    }
+}
+
+#[test]
+fn test_side_totals() {
+    let mut book = OrderBook::new();
+    assert_eq!(book.side_volume(Side::Bid), 0);
+    assert_eq!(book.side_order_count(Side::Ask), 0);
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 99, 20)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(3), 101, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(4), 102, 7)
+        .unwrap();
+    assert_eq!(book.side_volume(Side::Bid), 30);
+    assert_eq!(book.side_order_count(Side::Bid), 2);
+    assert_eq!(book.side_volume(Side::Ask), 12);
+    assert_eq!(book.side_order_count(Side::Ask), 2);
+
+    // Partial and full fills, cancels, and amends all keep the totals in step
+    book.execute_market_order(Side::Ask, 12).unwrap();
+    book.cancel_quantity(OrderId(2), 3).unwrap();
+    book.amend_quantity(OrderId(4), 4).unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(5), 101, 8)
+        .unwrap();
+    book.cancel_order(OrderId(2)).unwrap();
+    book.execute_resting_order(OrderId(4), 1, None).unwrap();
+
+    assert_eq!(book.side_volume(Side::Bid), 3);
+    assert_eq!(book.side_order_count(Side::Bid), 1);
+    assert_eq!(book.side_volume(Side::Ask), 3);
+    assert_eq!(book.side_order_count(Side::Ask), 1);
+    assert_totals_match_levels(&book);
+
+    let restored = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert_totals_match_levels(&restored);
+    assert_eq!(restored.side_volume(Side::Ask), 3);
+}
+
+#[test]
+fn test_open_orders_cover_the_whole_book() {
+    let mut book = OrderBook::new();
+    assert_eq!(book.open_orders().next(), None);
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 6)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 100, 7)
+        .unwrap();
+    book.execute_hidden_order(Side::Bid, OrderId(4), 100, 8)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(5), 102, 9)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(6), 101, 10)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Bid, OrderId(7), 110, 111, 5)
+        .unwrap();
+    book.cancel_quantity(OrderId(3), 2).unwrap();
+
+    let orders: Vec<_> = book.open_orders().collect();
+    assert_eq!(
+        orders,
+        [
+            (OrderId(2), Side::Bid, 100, 6, 0),
+            (OrderId(3), Side::Bid, 100, 5, 1),
+            (OrderId(1), Side::Bid, 99, 5, 0),
+            (OrderId(4), Side::Bid, 100, 8, 2),
+            (OrderId(6), Side::Ask, 101, 10, 0),
+            (OrderId(5), Side::Ask, 102, 9, 0),
+        ]
+    );
+    for (order_id, side, price, quantity, queue_position) in orders {
+        let details = book.get_order(order_id).unwrap();
+        assert_eq!(
+            (details.side, details.price, details.quantity),
+            (side, price, quantity)
+        );
+        assert_eq!(details.queue_position, queue_position);
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+use crate::{
+    error::LimitOrderError,
+    orderbook::OrderBook,
+    pegged_orders::PegType,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_primary_peg_tracks_best_bid() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 110, 5)
+        .unwrap();
+    book.execute_pegged_order(Side::Bid, OrderId(3), PegType::Primary, -1, 5)
+        .unwrap();
+    assert_eq!(book.index_map.get(&OrderId(3)).unwrap().price, 99);
+
+    // Reference improves, peg follows
+    book.execute_limit_order(Side::Bid, OrderId(4), 102, 5)
+        .unwrap();
+    assert_eq!(book.index_map.get(&OrderId(3)).unwrap().price, 101);
+
+    // Reference is cancelled, peg falls back
+    book.cancel_order(OrderId(4)).unwrap();
+    assert_eq!(book.index_map.get(&OrderId(3)).unwrap().price, 99);
+    assert!(!book.bids.contains_key(&101));
+}
+
+#[test]
+fn test_market_peg_tracks_opposite_side() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 110, 5)
+        .unwrap();
+    book.execute_pegged_order(Side::Ask, OrderId(3), PegType::Market, 2, 5)
+        .unwrap();
+    assert_eq!(book.index_map.get(&OrderId(3)).unwrap().price, 102);
+
+    // Best bid traded through, the ask peg re-prices down
+    book.execute_limit_order(Side::Bid, OrderId(4), 99, 5)
+        .unwrap();
+    book.execute_market_order(Side::Ask, 5).unwrap();
+    assert_eq!(book.index_map.get(&OrderId(3)).unwrap().price, 101);
+}
+
+#[test]
+fn test_midpoint_peg_rounds_passively() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 105, 5)
+        .unwrap();
+    book.execute_pegged_order(Side::Bid, OrderId(3), PegType::Midpoint, 0, 5)
+        .unwrap();
+    book.execute_pegged_order(Side::Ask, OrderId(4), PegType::Midpoint, 0, 5)
+        .unwrap();
+
+    assert_eq!(book.index_map.get(&OrderId(3)).unwrap().price, 102);
+    assert_eq!(book.index_map.get(&OrderId(4)).unwrap().price, 103);
+
+    // Spread widens, both pegs move apart
+    book.cancel_order(OrderId(2)).unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(5), 107, 5)
+        .unwrap();
+    assert_eq!(book.index_map.get(&OrderId(3)).unwrap().price, 103);
+    assert_eq!(book.index_map.get(&OrderId(4)).unwrap().price, 104);
+}
+
+#[test]
+fn test_peg_does_not_reference_other_pegs() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_pegged_order(Side::Bid, OrderId(2), PegType::Primary, 1, 5)
+        .unwrap();
+    book.execute_pegged_order(Side::Bid, OrderId(3), PegType::Primary, 1, 5)
+        .unwrap();
+
+    assert_eq!(book.index_map.get(&OrderId(2)).unwrap().price, 101);
+    assert_eq!(book.index_map.get(&OrderId(3)).unwrap().price, 101);
+    assert_eq!(book.bids.get(&101).unwrap().order_count, 2);
+}
+
+#[test]
+fn test_peg_reprice_never_crosses() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 102, 5)
+        .unwrap();
+    book.execute_pegged_order(Side::Bid, OrderId(3), PegType::Primary, 1, 5)
+        .unwrap();
+    assert_eq!(book.index_map.get(&OrderId(3)).unwrap().price, 101);
+
+    // Target of 102 would take the resting ask, so the peg holds, still ahead of the
+    // order joining it at 101
+    book.execute_limit_order(Side::Bid, OrderId(4), 101, 5)
+        .unwrap();
+    assert_eq!(book.index_map.get(&OrderId(3)).unwrap().price, 101);
+    assert_eq!(book.queue_position(OrderId(3)), Some(0));
+    assert_eq!(book.queue_position(OrderId(4)), Some(1));
+    assert_eq!(book.asks.get(&102).unwrap().order_count, 1);
+}
+
+#[test]
+fn test_filled_or_cancelled_pegs_are_forgotten() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_pegged_order(Side::Ask, OrderId(2), PegType::Market, 5, 5)
+        .unwrap();
+    book.execute_pegged_order(Side::Ask, OrderId(3), PegType::Market, 6, 5)
+        .unwrap();
+
+    book.execute_market_order(Side::Bid, 5).unwrap();
+    assert!(!book.pegged_orders.contains_key(&OrderId(2)));
+
+    book.cancel_order(OrderId(3)).unwrap();
+    assert!(book.pegged_orders.is_empty());
+}
+
+#[test]
+fn test_peg_without_reference_errors() {
+    let mut book = OrderBook::new();
+
+    let result = book.execute_pegged_order(Side::Bid, OrderId(1), PegType::Primary, 0, 5);
+    assert_eq!(result, Err(LimitOrderError::PegReferenceUnavailable));
+
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 5)
+        .unwrap();
+    let result = book.execute_pegged_order(Side::Bid, OrderId(3), PegType::Midpoint, 0, 5);
+    assert_eq!(result, Err(LimitOrderError::PegReferenceUnavailable));
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+use crate::{
+    error::LimitOrderError,
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_post_only_bid_rests() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 101, 5)
+        .unwrap();
+    let result = book
+        .execute_post_only_order(Side::Bid, OrderId(2), 100, 5)
+        .unwrap();
+
+    assert!(result.fills.is_empty());
+    assert_eq!(result.resting_quantity, 5);
+    assert_eq!(book.bids.len(), 1);
+    assert_eq!(book.asks.len(), 1);
+}
+
+#[test]
+fn test_post_only_ask_rests() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    let result = book
+        .execute_post_only_order(Side::Ask, OrderId(2), 101, 5)
+        .unwrap();
+
+    assert!(result.fills.is_empty());
+    assert_eq!(result.resting_quantity, 5);
+    assert_eq!(book.bids.len(), 1);
+    assert_eq!(book.asks.len(), 1);
+}
+
+#[test]
+fn test_post_only_bid_rejected_when_crossing() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    let result = book.execute_post_only_order(Side::Bid, OrderId(2), 100, 5);
+
+    assert_eq!(result, Err(LimitOrderError::PostOnlyWouldCross));
+    assert!(book.bids.is_empty());
+    assert_eq!(book.asks.len(), 1);
+    assert!(book.index_map.get(&OrderId(2)).is_none());
+}
+
+#[test]
+fn test_post_only_ask_rejected_when_crossing() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    let result = book.execute_post_only_order(Side::Ask, OrderId(2), 99, 5);
+
+    assert_eq!(result, Err(LimitOrderError::PostOnlyWouldCross));
+    assert!(book.asks.is_empty());
+    assert_eq!(book.bids.len(), 1);
+    assert!(book.index_map.get(&OrderId(2)).is_none());
+}
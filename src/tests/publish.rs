@@ -0,0 +1,76 @@
+#[cfg(all(test, feature = "publish"))]
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+};
+
+#[cfg(all(test, feature = "publish"))]
+use crate::{
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[cfg(feature = "publish")]
+#[test]
+fn test_published_view_follows_the_book() {
+    let mut book = OrderBook::new();
+    let reader = book.publish_depth(2);
+    assert_eq!(reader.load().sequence, 0);
+    assert_eq!(reader.load().depth.bids, Vec::new());
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 98, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 97, 5)
+        .unwrap();
+    let before = reader.load();
+
+    book.execute_market_order(Side::Ask, 4).unwrap();
+    let after = reader.load();
+
+    // A view taken earlier is left as it was
+    assert_eq!(before.depth.bids, vec![(99, 10, 1), (98, 5, 1)]);
+    assert_eq!(before.sequence, 3);
+    assert_eq!(after.depth.bids, vec![(99, 6, 1), (98, 5, 1)]);
+    assert_eq!(after.best_bid, Some((99, 6)));
+    assert_eq!(after.last_trade_price, Some(99));
+    assert_eq!(after.sequence, 4);
+
+    // A clone publishes to its own view
+    let mut copy = book.clone();
+    copy.execute_market_order(Side::Ask, 6).unwrap();
+    assert_eq!(reader.load().sequence, 4);
+}
+
+#[cfg(feature = "publish")]
+#[test]
+fn test_published_views_are_consistent_across_threads() {
+    let mut book = OrderBook::new();
+    let reader = book.publish_depth(1);
+    let done = Arc::new(AtomicBool::new(false));
+
+    let watcher = {
+        let done = done.clone();
+        thread::spawn(move || {
+            let mut last_sequence = 0;
+            while !done.load(Ordering::Acquire) {
+                let view = reader.load();
+                assert!(view.sequence >= last_sequence);
+                // Each order is matched in full, so a view never shows both sides
+                assert!(view.best_bid.is_none() || view.best_ask.is_none());
+                last_sequence = view.sequence;
+            }
+        })
+    };
+
+    for id in 0..1_000 {
+        let side = if id % 2 == 0 { Side::Bid } else { Side::Ask };
+        book.execute_limit_order(side, OrderId(id), 100, 5).unwrap();
+    }
+    done.store(true, Ordering::Release);
+    watcher.join().unwrap();
+}
@@ -0,0 +1,36 @@
+#[cfg(all(test, feature = "python"))]
+use crate::python::{PyFill, PyOrderBook};
+
+// Driven from Rust, the methods behave as they do when called from Python
+#[cfg(feature = "python")]
+#[test]
+fn test_python_order_book() {
+    let mut book = PyOrderBook::new();
+    assert_eq!(book.limit("ask", 1, 101, 10).unwrap(), (vec![], 10));
+    book.limit("ask", 2, 102, 10).unwrap();
+    book.limit("bid", 3, 99, 5).unwrap();
+
+    let (fills, remaining) = book.market("bid", 12).unwrap();
+    assert_eq!(remaining, 0);
+    assert_eq!(
+        fills
+            .iter()
+            .map(|fill| (fill.price, fill.quantity, fill.maker_order_id))
+            .collect::<Vec<_>>(),
+        vec![(101, 10, 1), (102, 2, 2)]
+    );
+    assert!(matches!(fills[1], PyFill { trade_id: 2, .. }));
+
+    book.amend(2, 4).unwrap();
+    assert_eq!(book.depth(5), (vec![(99, 5, 1)], vec![(102, 4, 1)]));
+    assert_eq!(
+        (book.best_bid(), book.best_ask()),
+        (Some((99, 5)), Some((102, 4)))
+    );
+
+    book.cancel(3).unwrap();
+    assert_eq!(book.best_bid(), None);
+    assert!(book.cancel(3).is_err());
+    assert!(book.limit("buy", 4, 100, 1).is_err());
+    assert!(book.limit("bid", 2, 100, 1).is_err());
+}
@@ -0,0 +1,79 @@
+#[cfg(all(test, feature = "testing"))]
+use proptest::test_runner::TestRunner;
+
+#[cfg(all(test, feature = "testing"))]
+use crate::{
+    command::Command,
+    orderbook::OrderBook,
+    testing::{CommandConfig, Execution, ReferenceBook, commands_strategy, executions},
+    types::{OrderId, Side},
+};
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_reference_book_matches_in_price_time_priority() {
+    let mut reference = ReferenceBook::new();
+    for (order_id, price) in [(1, 101), (2, 100), (3, 100)] {
+        let command = Command::Limit {
+            side: Side::Ask,
+            order_id: OrderId(order_id),
+            price,
+            quantity: 5,
+        };
+        assert_eq!(reference.apply(&command), Some(Vec::new()));
+    }
+
+    let trades = reference.apply(&Command::Limit {
+        side: Side::Bid,
+        order_id: OrderId(4),
+        price: 100,
+        quantity: 7,
+    });
+    assert_eq!(
+        trades,
+        Some(vec![
+            Execution {
+                maker_order_id: OrderId(2),
+                price: 100,
+                quantity: 5,
+            },
+            Execution {
+                maker_order_id: OrderId(3),
+                price: 100,
+                quantity: 2,
+            },
+        ])
+    );
+    assert_eq!(
+        reference.apply(&Command::Cancel {
+            order_id: OrderId(4)
+        }),
+        None
+    );
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_order_book_agrees_with_reference_book() {
+    let mut runner = TestRunner::default();
+    runner
+        .run(
+            &commands_strategy(CommandConfig::default(), 1..200),
+            |commands| {
+                let mut book = OrderBook::new();
+                let mut reference = ReferenceBook::new();
+
+                for command in &commands {
+                    let result = book.apply_command(command);
+                    assert_eq!(executions(&result), reference.apply(command), "{command:?}");
+                }
+
+                let expected = reference.level3_snapshot();
+                let actual = book.level3_snapshot();
+                assert_eq!((actual.bids, actual.asks), (expected.bids, expected.asks));
+                assert_eq!(book.validate(), Ok(()));
+                Ok(())
+            },
+        )
+        .unwrap();
+}
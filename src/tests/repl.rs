@@ -0,0 +1,49 @@
+#[cfg(test)]
+use crate::repl::Repl;
+
+#[test]
+fn test_repl_prints_events_and_views() {
+    let mut repl = Repl::new();
+    assert_eq!(
+        repl.execute("limit ask 1 101 10"),
+        "level added ask 101\nadded #1 ask 10 @ 101"
+    );
+    repl.execute("limit ask 2 103 5");
+    repl.execute("limit bid 3 99 5");
+    assert_eq!(repl.execute("market bid 5"), "trade bid 5 @ 101 against #1");
+
+    assert_eq!(
+        repl.execute("depth 1"),
+        [
+            "ask        101          5 (  1) ########################################",
+            "---- spread 2 ----",
+            "bid         99          5 (  1) ########################################",
+        ]
+        .join("\n")
+    );
+    assert_eq!(
+        repl.execute("snapshot"),
+        "ask 101: #1 5\nask 103: #2 5\nbid 99: #3 5"
+    );
+
+    assert_eq!(
+        repl.execute("cancel 3"),
+        "cancelled #3 bid 5 @ 99\nlevel removed bid 99"
+    );
+    assert_eq!(repl.book().best_bid(), None);
+}
+
+#[test]
+fn test_repl_rejects_bad_input() {
+    let mut repl = Repl::new();
+    assert_eq!(repl.execute("   "), "");
+    assert_eq!(
+        repl.execute("cancel 1"),
+        "rejected: Cancel(OrderIdNotFound)"
+    );
+    assert!(repl.execute("limit up 1 100 5").starts_with("commands:"));
+    assert!(repl.execute("market bid").starts_with("commands:"));
+    assert!(repl.execute("depth many").starts_with("commands:"));
+    assert_eq!(repl.execute("snapshot"), "empty book");
+    assert_eq!(repl.book().best_ask(), None);
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+use crate::{
+    error::CancelOrderError,
+    orderbook::OrderBook,
+    trading_state::TradingState,
+    types::{Fill, OrderId, Side, TradeId},
+};
+
+#[test]
+fn test_execute_resting_order_out_of_priority() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+
+    // Executes the second order in the queue, as reported by an external feed
+    let fill = book.execute_resting_order(OrderId(2), 3, None).unwrap();
+    assert_eq!(
+        fill,
+        Fill {
+            price: 100,
+            quantity: 3,
+            maker_order_id: OrderId(2),
+            trade_id: TradeId(1),
+            timestamp: 0,
+            fees: None,
+            maker_client_order_id: None,
+            maker_remaining_quantity: 2,
+            maker_fully_filled: false,
+        }
+    );
+    assert_eq!(book.best_ask(), Some((100, 7)));
+
+    // Executing more than remains only fills what's left, then removes the order
+    let fill = book
+        .execute_resting_order(OrderId(2), 9, Some(101))
+        .unwrap();
+    assert_eq!((fill.price, fill.quantity), (101, 2));
+    assert!(book.get_order(OrderId(2)).is_none());
+    assert_eq!(book.best_ask(), Some((100, 5)));
+    assert_eq!(book.last_trade_price, Some(101));
+}
+
+#[test]
+fn test_execute_resting_order_rejections() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+
+    assert_eq!(
+        book.execute_resting_order(OrderId(1), 0, None),
+        Err(CancelOrderError::ZeroQuantity)
+    );
+    for state in [TradingState::Auction, TradingState::Halted] {
+        book.set_trading_state(state);
+        assert_eq!(
+            book.execute_resting_order(OrderId(1), 1, None),
+            Err(CancelOrderError::TradingHalted)
+        );
+    }
+
+    // Nothing traded, so the first fill still gets the first trade Id
+    book.set_trading_state(TradingState::Open);
+    let fill = book.execute_resting_order(OrderId(1), 1, None).unwrap();
+    assert_eq!(fill.trade_id, TradeId(1));
+    assert_eq!(book.best_bid(), Some((100, 4)));
+}
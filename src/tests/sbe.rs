@@ -0,0 +1,100 @@
+#[cfg(test)]
+use crate::{
+    error::DecodeError,
+    orderbook::OrderBook,
+    sbe::{decode_depth, decode_event, encode_depth, encode_event},
+    trading_state::TradingState,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_sbe_event_round_trip() {
+    let mut book = OrderBook::new();
+    book.record_events(true);
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 10)
+        .unwrap();
+    book.amend_quantity(OrderId(1), 8).unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 8)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 99, 1)
+        .unwrap();
+    book.cancel_order(OrderId(3)).unwrap();
+    let events = book.take_events();
+
+    let mut bytes = Vec::new();
+    for event in &events {
+        encode_event(event, &mut bytes);
+    }
+
+    let mut decoded = Vec::new();
+    let mut rest = &bytes[..];
+    while !rest.is_empty() {
+        let (event, used) = decode_event(rest).unwrap();
+        decoded.push(event);
+        rest = &rest[used..];
+    }
+    assert_eq!(decoded, events);
+}
+
+#[test]
+fn test_sbe_auction_trade_round_trip() {
+    let mut book = OrderBook::new();
+    book.set_trading_state(TradingState::Auction);
+    book.execute_limit_order(Side::Bid, OrderId(1), 101, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 99, 5)
+        .unwrap();
+    book.record_events(true);
+    book.uncross().unwrap();
+    let events = book.take_events();
+
+    let mut bytes = Vec::new();
+    encode_event(&events[0], &mut bytes);
+    assert_eq!(bytes[..4], [64, 0, 8, 0]);
+    assert_eq!(decode_event(&bytes), Ok((events[0].clone(), 8 + 64)));
+}
+
+#[test]
+fn test_sbe_event_header_and_errors() {
+    let mut book = OrderBook::new();
+    book.record_events(true);
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 10)
+        .unwrap();
+    let events = book.take_events();
+
+    let mut bytes = Vec::new();
+    encode_event(&events[1], &mut bytes);
+    // Block length 25, template 1 (OrderAdded), schema 1, version 4
+    assert_eq!(bytes[..8], [25, 0, 1, 0, 1, 0, 4, 0]);
+    assert_eq!(bytes.len(), 8 + 25);
+    assert_eq!(decode_event(&bytes[..20]), Err(DecodeError::Truncated));
+
+    // A longer block from a newer schema version is still readable
+    let mut extended = bytes.clone();
+    extended[0] = 27;
+    extended.extend_from_slice(&[0, 0]);
+    assert_eq!(decode_event(&extended), Ok((events[1].clone(), 35)));
+}
+
+#[test]
+fn test_sbe_depth_round_trip() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 99, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 98, 7)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(4), 101, 3)
+        .unwrap();
+    let depth = book.depth(10);
+
+    let mut bytes = Vec::new();
+    encode_depth(&depth, &mut bytes).unwrap();
+    assert_eq!(bytes.len(), 8 + 4 + 2 * 20 + 4 + 20);
+    assert_eq!(decode_depth(&bytes), Ok((depth, bytes.len())));
+    assert_eq!(
+        decode_depth(&bytes[..bytes.len() - 1]),
+        Err(DecodeError::Truncated)
+    );
+}
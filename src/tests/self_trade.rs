@@ -0,0 +1,212 @@
+#[cfg(test)]
+use crate::{
+    events::BookEvent,
+    orderbook::OrderBook,
+    self_trade::SelfTradePrevention,
+    types::{OrderId, OwnerId, Side},
+};
+
+// Owner 1 rests 5 @ 100 and 5 @ 101, owner 2 rests 5 @ 100 behind it
+#[cfg(test)]
+fn book() -> OrderBook {
+    let mut book = OrderBook::new();
+    let policy = SelfTradePrevention::CancelNewest;
+    book.execute_owned_limit_order(Side::Ask, OrderId(1), 100, 5, OwnerId(1), policy)
+        .unwrap();
+    book.execute_owned_limit_order(Side::Ask, OrderId(2), 100, 5, OwnerId(2), policy)
+        .unwrap();
+    book.execute_owned_limit_order(Side::Ask, OrderId(3), 101, 5, OwnerId(1), policy)
+        .unwrap();
+    book
+}
+
+#[test]
+fn test_other_owners_trade() {
+    let mut book = book();
+
+    let fills = book
+        .execute_owned_market_order(Side::Bid, 7, OwnerId(3), SelfTradePrevention::CancelBoth)
+        .unwrap()
+        .fills;
+    let makers: Vec<_> = fills
+        .iter()
+        .map(|fill| (fill.maker_order_id, fill.quantity))
+        .collect();
+    assert_eq!(makers, vec![(OrderId(1), 5), (OrderId(2), 2)]);
+
+    // Orders without an owner are never checked
+    book.execute_market_order(Side::Bid, 3).unwrap();
+    assert_eq!(book.best_ask(), Some((101, 5)));
+}
+
+#[test]
+fn test_stp_cancel_newest() {
+    let mut book = book();
+
+    let result = book
+        .execute_owned_limit_order(
+            Side::Bid,
+            OrderId(10),
+            101,
+            8,
+            OwnerId(1),
+            SelfTradePrevention::CancelNewest,
+        )
+        .unwrap();
+
+    // Stops at the first self match, nothing traded and nothing rests
+    assert!(result.fills.is_empty());
+    assert_eq!(result.resting_quantity, 0);
+    assert!(book.get_order(OrderId(10)).is_none());
+    assert_eq!(book.side_order_count(Side::Ask), 3);
+}
+
+#[test]
+fn test_stp_cancel_oldest() {
+    let mut book = book();
+    book.record_events(true);
+
+    let result = book
+        .execute_owned_limit_order(
+            Side::Bid,
+            OrderId(10),
+            101,
+            8,
+            OwnerId(1),
+            SelfTradePrevention::CancelOldest,
+        )
+        .unwrap();
+
+    // Both of owner 1's asks are pulled, only owner 2's trades
+    assert_eq!(result.fills.len(), 1);
+    assert_eq!(result.fills[0].maker_order_id, OrderId(2));
+    assert_eq!(result.resting_quantity, 3);
+    assert!(book.asks.is_empty());
+    assert_eq!(book.best_bid(), Some((101, 3)));
+
+    let cancelled: Vec<_> = book
+        .take_events()
+        .into_iter()
+        .filter_map(|event| match event {
+            BookEvent::OrderCancelled { order_id, .. } => Some(order_id),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(cancelled, vec![OrderId(1), OrderId(3)]);
+}
+
+#[test]
+fn test_stp_cancel_both() {
+    let mut book = book();
+
+    let fills = book
+        .execute_owned_market_order(Side::Bid, 8, OwnerId(1), SelfTradePrevention::CancelBoth)
+        .unwrap()
+        .fills;
+
+    assert!(fills.is_empty());
+    assert!(book.get_order(OrderId(1)).is_none());
+    assert_eq!(book.best_ask(), Some((100, 5)));
+    assert_eq!(book.side_order_count(Side::Ask), 2);
+}
+
+#[test]
+fn test_stp_decrement_and_cancel() {
+    let mut book = book();
+
+    // Smaller incoming order is used up against the resting one, which shrinks
+    let fills = book
+        .execute_owned_market_order(
+            Side::Bid,
+            3,
+            OwnerId(1),
+            SelfTradePrevention::DecrementAndCancel,
+        )
+        .unwrap()
+        .fills;
+    assert!(fills.is_empty());
+    assert_eq!(book.get_order(OrderId(1)).unwrap().quantity, 2);
+
+    // Larger incoming order cancels the resting one and keeps matching
+    let fills = book
+        .execute_owned_market_order(
+            Side::Bid,
+            4,
+            OwnerId(1),
+            SelfTradePrevention::DecrementAndCancel,
+        )
+        .unwrap()
+        .fills;
+    assert!(book.get_order(OrderId(1)).is_none());
+    assert_eq!(fills.len(), 1);
+    assert_eq!(
+        (fills[0].maker_order_id, fills[0].quantity),
+        (OrderId(2), 2)
+    );
+    assert_eq!(book.side_volume(Side::Ask), 8);
+}
+
+#[test]
+fn test_owner_survives_amend_and_snapshot() {
+    let mut book = book();
+    book.amend_quantity(OrderId(1), 10).unwrap();
+
+    let mut book = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert_eq!(book.index_map[&OrderId(1)].owner, Some(OwnerId(1)));
+    assert_eq!(book.owner_orders.orders(OwnerId(1)).count(), 2);
+
+    let fills = book
+        .execute_owned_market_order(Side::Bid, 5, OwnerId(1), SelfTradePrevention::CancelOldest)
+        .unwrap()
+        .fills;
+    assert_eq!(fills[0].maker_order_id, OrderId(2));
+}
+
+#[test]
+fn test_cancel_all_by_owner() {
+    let mut book = book();
+    book.execute_owned_limit_order(
+        Side::Bid,
+        OrderId(4),
+        90,
+        5,
+        OwnerId(1),
+        SelfTradePrevention::CancelNewest,
+    )
+    .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(5), 90, 5)
+        .unwrap();
+
+    assert_eq!(
+        book.cancel_all_by_owner(OwnerId(1)),
+        vec![OrderId(1), OrderId(3), OrderId(4)]
+    );
+    assert_eq!(book.best_ask(), Some((100, 5)));
+    assert_eq!(book.best_bid(), Some((90, 5)));
+    assert_eq!(book.owner_orders.orders(OwnerId(1)).count(), 0);
+
+    // Filled orders leave the index too
+    book.execute_market_order(Side::Bid, 5).unwrap();
+    assert!(book.cancel_all_by_owner(OwnerId(2)).is_empty());
+    assert!(book.cancel_all_by_owner(OwnerId(3)).is_empty());
+}
+
+#[test]
+fn test_orders_by_owner() {
+    let mut book = book();
+    book.execute_market_order(Side::Bid, 2).unwrap();
+
+    let orders = book.orders_by_owner(OwnerId(1));
+    let summary: Vec<_> = orders
+        .iter()
+        .map(|order| (order.order_id, order.price, order.quantity))
+        .collect();
+    assert_eq!(summary, [(OrderId(1), 100, 3), (OrderId(3), 101, 5)]);
+    assert_eq!(orders[0], book.get_order(OrderId(1)).unwrap());
+
+    // Filled and cancelled orders drop out
+    book.execute_market_order(Side::Bid, 8).unwrap();
+    book.cancel_order(OrderId(3)).unwrap();
+    assert!(book.orders_by_owner(OwnerId(1)).is_empty());
+    assert!(book.orders_by_owner(OwnerId(2)).is_empty());
+}
@@ -0,0 +1,53 @@
+#[cfg(all(test, feature = "serde"))]
+use crate::{
+    instrument::Instrument,
+    orderbook::OrderBook,
+    pegged_orders::PegType,
+    types::{OrderId, Side},
+};
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_round_trip() {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 7)
+        .unwrap();
+    book.execute_gtd_order(Side::Ask, OrderId(3), 102, 4, 1_000)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Bid, OrderId(4), 105, 106, 2)
+        .unwrap();
+    book.execute_pegged_order(Side::Bid, OrderId(5), PegType::Primary, 0, 3)
+        .unwrap();
+    book.execute_market_order(Side::Bid, 2).unwrap();
+
+    let json = serde_json::to_string(&book).unwrap();
+    let mut restored: OrderBook = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.level3_snapshot(), book.level3_snapshot());
+    assert_eq!(restored.best_bid(), book.best_bid());
+    assert_eq!(restored.last_trade_price, book.last_trade_price);
+
+    // Both books carry on identically, including trade ids, expiry, and stops
+    assert_eq!(
+        restored.execute_market_order(Side::Bid, 6),
+        book.execute_market_order(Side::Bid, 6)
+    );
+    assert_eq!(restored.expire_orders(1_000), book.expire_orders(1_000));
+    assert_eq!(
+        restored.trigger_stop_orders().len(),
+        book.trigger_stop_orders().len()
+    );
+    assert_eq!(restored.level3_snapshot(), book.level3_snapshot());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_rejects_an_invalid_instrument() {
+    let instrument = Instrument::builder().tick_size(5).build().unwrap();
+    let json = serde_json::to_string(&OrderBook::with_instrument(instrument)).unwrap();
+    assert!(serde_json::from_str::<OrderBook>(&json).is_ok());
+
+    let json = json.replace("\"tick_size\":5", "\"tick_size\":0");
+    assert!(serde_json::from_str::<OrderBook>(&json).is_err());
+}
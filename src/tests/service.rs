@@ -0,0 +1,55 @@
+#[cfg(all(test, feature = "tokio"))]
+use tokio::runtime::Builder;
+
+#[cfg(all(test, feature = "tokio"))]
+use crate::{
+    command::{Command, CommandResult},
+    error::CancelOrderError,
+    service::BookService,
+    types::{OrderId, Side},
+};
+
+#[cfg(feature = "tokio")]
+#[test]
+fn test_book_service_awaits_each_result() {
+    let runtime = Builder::new_current_thread().build().unwrap();
+    let service = BookService::spawn(8);
+
+    runtime.block_on(async {
+        let response = service
+            .submit(Command::Limit {
+                side: Side::Ask,
+                order_id: OrderId(1),
+                price: 100,
+                quantity: 10,
+            })
+            .await
+            .unwrap();
+        assert!(matches!(response.result, CommandResult::Limit(Ok(_))));
+
+        // Clones talk to the same book
+        let response = service
+            .clone()
+            .submit(Command::Market {
+                side: Side::Bid,
+                quantity: 10,
+            })
+            .await
+            .unwrap();
+        let CommandResult::Market(Ok(result)) = response.result else {
+            panic!("market order rejected: {:?}", response.result);
+        };
+        assert_eq!(result.filled_quantity, 10);
+
+        let response = service
+            .submit(Command::Cancel {
+                order_id: OrderId(1),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            response.result,
+            CommandResult::Cancel(Err(CancelOrderError::OrderIdNotFound))
+        );
+    });
+}
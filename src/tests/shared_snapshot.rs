@@ -0,0 +1,127 @@
+#[cfg(test)]
+use crate::{
+    orderbook::OrderBook,
+    self_trade::SelfTradePrevention,
+    types::{OrderId, OwnerId, Side},
+};
+
+#[cfg(test)]
+fn deep_book() -> OrderBook {
+    let mut book = OrderBook::new();
+    for id in 0..600 {
+        let (side, price) = if id % 2 == 0 {
+            (Side::Bid, 99 - (id % 10) as i64)
+        } else {
+            (Side::Ask, 101 + (id % 10) as i64)
+        };
+        book.execute_limit_order(side, OrderId(id), price, 10)
+            .unwrap();
+    }
+    book
+}
+
+#[test]
+fn test_snapshots_share_unchanged_pages() {
+    let mut book = deep_book();
+    let first = book.snapshot();
+    let before = book.level3_snapshot();
+    assert_eq!(first.order_count(), 600);
+    assert_eq!(first.level3_snapshot(), before);
+
+    // Only the page holding the cancelled order is copied again
+    book.cancel_order(OrderId(300)).unwrap();
+    let second = book.snapshot_from(&first);
+    assert_eq!(second.shared_pages(&first), 2);
+    assert_eq!(second.order_count(), 599);
+    assert_eq!(second.level3_snapshot(), book.level3_snapshot());
+
+    // The earlier snapshot is unaffected
+    assert_eq!(first.level3_snapshot(), before);
+
+    // Nothing changed, everything is shared
+    let third = book.snapshot_from(&second);
+    assert_eq!(third.shared_pages(&second), 3);
+    assert_eq!(book.snapshot().shared_pages(&second), 0);
+}
+
+#[test]
+fn test_snapshot_restores_a_live_book() {
+    let mut book = deep_book();
+    book.cancel_order(OrderId(10)).unwrap();
+    book.execute_market_order(Side::Bid, 25).unwrap();
+    let snapshot = book.snapshot();
+
+    let mut restored = snapshot.to_book();
+    assert_eq!(restored.validate(), Ok(()));
+    assert_eq!(restored.best_bid(), book.best_bid());
+    assert_eq!(restored.best_ask(), snapshot.best_ask());
+    assert_eq!(restored.command_sequence(), book.command_sequence());
+    assert_eq!(restored.level3_snapshot(), book.level3_snapshot());
+
+    // Both go on to match identically
+    assert_eq!(
+        restored.execute_market_order(Side::Ask, 35).unwrap().fills,
+        book.execute_market_order(Side::Ask, 35).unwrap().fills
+    );
+}
+
+#[test]
+fn test_restored_book_keeps_queue_positions() {
+    let mut book = deep_book();
+    book.execute_owned_limit_order(
+        Side::Ask,
+        OrderId(700),
+        101,
+        5,
+        OwnerId(7),
+        SelfTradePrevention::CancelNewest,
+    )
+    .unwrap();
+    book.cancel_order(OrderId(21)).unwrap();
+
+    let restored = book.snapshot().to_book();
+    for order_id in [OrderId(1), OrderId(41), OrderId(700)] {
+        assert!(restored.queue_position(order_id).is_some());
+        assert_eq!(
+            restored.queue_position(order_id),
+            book.queue_position(order_id)
+        );
+        assert_eq!(restored.get_order(order_id), book.get_order(order_id));
+    }
+    assert_eq!(
+        restored.orders_by_owner(OwnerId(7)),
+        book.orders_by_owner(OwnerId(7))
+    );
+    assert_eq!(restored.orders_by_owner(OwnerId(7)).len(), 1);
+}
+
+#[test]
+fn test_chained_snapshots_match_fresh_ones() {
+    let mut book = deep_book();
+    let mut chained = book.snapshot();
+    let steps: [fn(&mut OrderBook); 8] = [
+        |book| book.execute_market_order(Side::Bid, 5).map(drop).unwrap(),
+        |book| book.execute_market_order(Side::Bid, 15).map(drop).unwrap(),
+        |book| book.amend_quantity(OrderId(40), 4).unwrap(),
+        |book| book.amend_quantity(OrderId(42), 40).unwrap(),
+        |book| book.cancel_quantity(OrderId(520), 3).map(drop).unwrap(),
+        |book| {
+            book.execute_gtd_order(Side::Bid, OrderId(900), 95, 5, 10)
+                .map(drop)
+                .unwrap()
+        },
+        |book| assert_eq!(book.expire_orders(10), [OrderId(900)]),
+        |book| book.compact(),
+    ];
+
+    // Every step shows up in the pages it touched, and only there
+    for step in steps {
+        step(&mut book);
+        let next = book.snapshot_from(&chained);
+        assert_eq!(
+            next.to_book().to_bytes(),
+            book.snapshot().to_book().to_bytes()
+        );
+        chained = next;
+    }
+}
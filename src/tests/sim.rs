@@ -0,0 +1,91 @@
+#[cfg(test)]
+use crate::{
+    command::Command,
+    orderbook::OrderBook,
+    sim::{Agent, PriceDistribution, SimRng, Simulator},
+};
+
+#[cfg(test)]
+fn population() -> Vec<Agent> {
+    vec![Agent::market_maker(), Agent::noise_trader(), Agent::taker()]
+}
+
+#[test]
+fn test_simulation_is_reproducible_from_its_seed() {
+    let run = |seed| {
+        let mut book = OrderBook::new();
+        let stats = Simulator::new(seed, 1_000, population()).run(&mut book, 2_000);
+        (stats, book.level3_snapshot())
+    };
+
+    assert_eq!(run(7), run(7));
+    assert_ne!(run(7).1, run(8).1);
+}
+
+#[test]
+fn test_simulation_trades_and_cancels_against_a_consistent_book() {
+    let mut book = OrderBook::new();
+    let mut simulator = Simulator::new(42, 1_000, population());
+    let stats = simulator.run(&mut book, 5_000);
+
+    assert_eq!(stats.commands, 5_000);
+    assert!(stats.trades > 0 && stats.traded_quantity > 0);
+    assert_eq!(book.validate(), Ok(()));
+
+    // Every cancel targets an order still resting, so none are rejected
+    let mut cancels = 0;
+    for _ in 0..1_000 {
+        let order = simulator.next_order(&book).unwrap();
+        if let Command::Cancel { .. } = order.command {
+            cancels += 1;
+            assert!(book.apply(order.command).is_ok());
+        }
+    }
+    assert!(cancels > 0);
+}
+
+#[test]
+fn test_simulated_arrivals_follow_the_configured_rates() {
+    let book = OrderBook::new();
+    let quoter = Agent {
+        limit_rate: 1_000.0,
+        market_rate: 0.0,
+        cancel_rate: 0.0,
+        buy_probability: 1.0,
+        price_offsets: PriceDistribution::Uniform(1..=3),
+        quantities: 5..=5,
+    };
+    let mut simulator = Simulator::new(1, 100, vec![quoter]);
+
+    let orders: Vec<_> = (0..10_000)
+        .map(|_| simulator.next_order(&book).unwrap())
+        .collect();
+    // 10k arrivals at 1k a second take about 10 seconds
+    let elapsed = orders.last().unwrap().timestamp as f64 / 1e9;
+    assert!((9.0..11.0).contains(&elapsed), "{elapsed}");
+    for order in &orders {
+        let Command::Limit {
+            price, quantity, ..
+        } = order.command
+        else {
+            panic!("unexpected {:?}", order.command);
+        };
+        assert!((97..=99).contains(&price));
+        assert_eq!(quantity, 5);
+    }
+
+    assert!(
+        Simulator::new(1, 100, Vec::new())
+            .next_order(&book)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_sim_rng_ranges() {
+    let mut rng = SimRng::new(3);
+    for _ in 0..1_000 {
+        assert!((-2..=2).contains(&rng.in_range(&(-2..=2))));
+        assert!((0.0..1.0).contains(&rng.next_f64()));
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+use crate::{
+    orderbook::OrderBook,
+    types::{Level3, Level3Snapshot, OrderId, Side},
+};
+
+#[test]
+fn test_level3_snapshot() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 1)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 2)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 100, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(4), 102, 4)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(5), 101, 5)
+        .unwrap();
+
+    assert_eq!(
+        book.level3_snapshot(),
+        Level3Snapshot {
+            bids: vec![
+                Level3 {
+                    price: 100,
+                    orders: vec![(OrderId(2), 2), (OrderId(3), 3)]
+                },
+                Level3 {
+                    price: 99,
+                    orders: vec![(OrderId(1), 1)]
+                },
+            ],
+            asks: vec![
+                Level3 {
+                    price: 101,
+                    orders: vec![(OrderId(5), 5)]
+                },
+                Level3 {
+                    price: 102,
+                    orders: vec![(OrderId(4), 4)]
+                },
+            ],
+            sequence: 9,
+        }
+    );
+}
+
+#[test]
+fn test_level3_snapshot_round_trip() {
+    let mut book = OrderBook::new();
+
+    for i in 0..20 {
+        book.execute_limit_order(Side::Bid, OrderId(i), 100 - (i % 4) as i64, i + 1)
+            .unwrap();
+        book.execute_limit_order(Side::Ask, OrderId(100 + i), 101 + (i % 3) as i64, i + 1)
+            .unwrap();
+    }
+    book.cancel_order(OrderId(5)).unwrap();
+    book.amend_quantity(OrderId(1), 50).unwrap();
+
+    let snapshot = book.level3_snapshot();
+    let restored = OrderBook::from_level3_snapshot(&snapshot).unwrap();
+
+    assert_eq!(restored.level3_snapshot(), snapshot);
+    assert_eq!(restored.depth(10), book.depth(10));
+    assert_eq!(restored.best_bid(), book.best_bid());
+    assert_eq!(restored.best_ask(), book.best_ask());
+}
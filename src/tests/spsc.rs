@@ -0,0 +1,64 @@
+#[cfg(test)]
+use std::{sync::Arc, thread};
+
+#[cfg(test)]
+use crate::spsc;
+
+#[test]
+fn test_ring_is_first_in_first_out_and_bounded() {
+    let (mut producer, mut consumer) = spsc::ring(2);
+    assert_eq!(consumer.try_pop(), None);
+
+    producer.try_push(1).unwrap();
+    producer.try_push(2).unwrap();
+    assert_eq!(producer.try_push(3), Err(3));
+
+    // Slots are reused once read
+    assert_eq!(consumer.try_pop(), Some(1));
+    producer.try_push(3).unwrap();
+    assert_eq!(consumer.pop(), Some(2));
+    assert_eq!(consumer.pop(), Some(3));
+    assert_eq!(consumer.try_pop(), None);
+}
+
+#[test]
+fn test_ring_closes_when_either_half_drops() {
+    let (mut producer, mut consumer) = spsc::ring(4);
+    producer.push(1).unwrap();
+    drop(producer);
+    // Values pushed before closing are still read
+    assert_eq!(consumer.pop(), Some(1));
+    assert_eq!(consumer.pop(), None);
+
+    let (mut producer, consumer) = spsc::ring(4);
+    drop(consumer);
+    assert!(producer.is_closed());
+    assert_eq!(producer.push(1), Err(1));
+}
+
+#[test]
+fn test_ring_drops_unread_values() {
+    let value = Arc::new(());
+    let (mut producer, consumer) = spsc::ring(4);
+    producer.push(value.clone()).unwrap();
+    producer.push(value.clone()).unwrap();
+    assert_eq!(Arc::strong_count(&value), 3);
+
+    drop(consumer);
+    drop(producer);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_ring_across_threads() {
+    let (mut producer, mut consumer) = spsc::ring(3);
+    let writer = thread::spawn(move || {
+        for n in 0..10_000u64 {
+            producer.push(n).unwrap();
+        }
+    });
+
+    let read: Vec<u64> = std::iter::from_fn(|| consumer.pop()).collect();
+    writer.join().unwrap();
+    assert_eq!(read, (0..10_000).collect::<Vec<_>>());
+}
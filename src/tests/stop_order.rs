@@ -0,0 +1,240 @@
+#[cfg(test)]
+use crate::{
+    error::{CancelOrderError, LimitOrderError, StopOrderError},
+    orderbook::OrderBook,
+    trading_state::TradingState,
+    types::{OrderId, Quantity, Side},
+};
+
+#[test]
+fn test_buy_stop_limit_triggers_on_trade() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 1)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 105, 5)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Bid, OrderId(3), 100, 105, 3)
+        .unwrap();
+
+    // No trades yet, nothing to trigger
+    assert!(book.stop_orders.contains_key(&OrderId(3)));
+
+    // The trade places the stop straight away, which then trades itself
+    let sequence = book.command_sequence();
+    book.execute_market_order(Side::Bid, 1).unwrap();
+    assert_eq!(book.last_trade_price, Some(105));
+    assert_eq!(book.best_ask(), Some((105, 2)));
+    assert_eq!(book.command_sequence(), sequence + 1);
+
+    assert!(book.trigger_stop_orders().is_empty());
+    assert!(book.stop_orders.is_empty());
+    assert!(book.buy_stops.is_empty());
+}
+
+#[test]
+fn test_sell_stop_limit_rests_at_limit_price() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 1)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Ask, OrderId(2), 100, 98, 3)
+        .unwrap();
+
+    book.execute_market_order(Side::Ask, 1).unwrap();
+
+    let entry = book.index_map.get(&OrderId(2)).unwrap();
+    assert_eq!(entry.price, 98);
+    assert_eq!(entry.side, Side::Ask);
+    assert!(book.sell_stops.is_empty());
+}
+
+#[test]
+fn test_stop_limit_not_triggered_before_stop_price() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 1)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Bid, OrderId(2), 101, 105, 3)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Ask, OrderId(3), 99, 95, 3)
+        .unwrap();
+
+    book.execute_market_order(Side::Bid, 1).unwrap();
+
+    assert_eq!(book.stop_orders.len(), 2);
+}
+
+#[test]
+fn test_stop_limit_cascade() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 1)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 102, 1)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(3), 104, 1)
+        .unwrap();
+
+    // First stop trades at 102, which elects the second stop
+    book.execute_stop_limit_order(Side::Bid, OrderId(10), 100, 102, 1)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Bid, OrderId(11), 102, 104, 1)
+        .unwrap();
+
+    book.execute_market_order(Side::Bid, 1).unwrap();
+
+    assert!(book.stop_orders.is_empty());
+    assert_eq!(book.last_trade_price, Some(104));
+    assert!(book.asks.is_empty());
+}
+
+#[test]
+fn test_stop_limit_elected_on_arrival() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 1)
+        .unwrap();
+    book.execute_market_order(Side::Bid, 1).unwrap();
+
+    book.execute_stop_limit_order(Side::Bid, OrderId(2), 99, 98, 3)
+        .unwrap();
+    assert!(book.stop_orders.is_empty());
+    assert_eq!(book.best_bid(), Some((98, 3)));
+}
+
+#[test]
+fn test_stops_wait_for_the_book_to_reopen() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 1)
+        .unwrap();
+    book.execute_market_order(Side::Bid, 1).unwrap();
+
+    book.set_trading_state(TradingState::Auction);
+    book.execute_stop_limit_order(Side::Bid, OrderId(2), 100, 98, 3)
+        .unwrap();
+    assert!(book.trigger_stop_orders().is_empty());
+    assert!(book.stop_orders.contains_key(&OrderId(2)));
+
+    let sequence = book.command_sequence();
+    book.set_trading_state(TradingState::Open);
+    assert!(book.stop_orders.is_empty());
+    assert_eq!(book.best_bid(), Some((98, 3)));
+    assert_eq!(book.command_sequence(), sequence + 1);
+}
+
+#[test]
+fn test_stop_kept_when_placement_fails() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 90, Quantity::MAX - 1)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 1)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Bid, OrderId(3), 100, 95, 5)
+        .unwrap();
+
+    // Resting the stop would overflow the bid side, so it stays a stop
+    book.execute_market_order(Side::Bid, 1).unwrap();
+    assert!(book.stop_orders.contains_key(&OrderId(3)));
+    assert_eq!(book.buy_stops.get(&100).unwrap(), &vec![OrderId(3)]);
+    assert_eq!(
+        book.trigger_stop_orders(),
+        vec![(OrderId(3), Err(LimitOrderError::QuantityOverflow))]
+    );
+
+    // And is placed once it fits
+    book.cancel_order(OrderId(1)).unwrap();
+    assert!(book.stop_orders.is_empty());
+    assert_eq!(book.best_bid(), Some((95, 5)));
+}
+
+#[test]
+fn test_failed_stop_does_not_block_later_stops() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 90, Quantity::MAX - 1)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 1)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Bid, OrderId(3), 100, 95, 5)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Ask, OrderId(4), 100, 105, 2)
+        .unwrap();
+
+    // The buy stop goes first and overflows the bid side, the sell stop still rests
+    book.execute_market_order(Side::Bid, 1).unwrap();
+    assert_eq!(book.best_ask(), Some((105, 2)));
+    assert!(!book.stop_orders.contains_key(&OrderId(4)));
+    assert!(book.stop_orders.contains_key(&OrderId(3)));
+    assert_eq!(
+        book.trigger_stop_orders(),
+        vec![(OrderId(3), Err(LimitOrderError::QuantityOverflow))]
+    );
+}
+
+#[test]
+fn test_cancel_untriggered_stop_limit() {
+    let mut book = OrderBook::new();
+
+    book.execute_stop_limit_order(Side::Bid, OrderId(1), 100, 105, 3)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Bid, OrderId(2), 100, 105, 3)
+        .unwrap();
+
+    let sequence = book.command_sequence();
+    book.cancel_order(OrderId(1)).unwrap();
+    assert_eq!(book.command_sequence(), sequence + 1);
+    assert!(!book.stop_orders.contains_key(&OrderId(1)));
+    assert_eq!(book.buy_stops.get(&100).unwrap(), &vec![OrderId(2)]);
+
+    book.cancel_order(OrderId(2)).unwrap();
+    assert!(book.stop_orders.is_empty());
+    assert!(book.buy_stops.is_empty());
+
+    assert_eq!(
+        book.cancel_order(OrderId(2)),
+        Err(CancelOrderError::OrderIdNotFound)
+    );
+}
+
+#[test]
+fn test_stop_limit_duplicate_order_id_errors() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 1)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Bid, OrderId(2), 100, 105, 3)
+        .unwrap();
+
+    assert_eq!(
+        book.execute_stop_limit_order(Side::Ask, OrderId(1), 100, 95, 3),
+        Err(StopOrderError::OrderIdAlreadyExists)
+    );
+    assert_eq!(
+        book.execute_stop_limit_order(Side::Ask, OrderId(2), 100, 95, 3),
+        Err(StopOrderError::OrderIdAlreadyExists)
+    );
+    assert!(
+        book.execute_limit_order(Side::Bid, OrderId(2), 90, 1)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_stop_limit_input_validation() {
+    let mut book = OrderBook::new();
+
+    assert_eq!(
+        book.execute_stop_limit_order(Side::Bid, OrderId(1), 105, 106, 0),
+        Err(StopOrderError::ZeroQuantity)
+    );
+
+    book.reject_non_positive_prices = true;
+    assert_eq!(
+        book.execute_stop_limit_order(Side::Ask, OrderId(1), 5, 0, 10),
+        Err(StopOrderError::NonPositivePrice)
+    );
+    assert!(book.stop_orders.is_empty());
+}
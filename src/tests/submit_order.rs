@@ -0,0 +1,147 @@
+#[cfg(test)]
+use crate::{
+    error::LimitOrderError,
+    order::{Order, TimeInForce},
+    orderbook::OrderBook,
+    self_trade::SelfTradePrevention,
+    types::{OrderId, OwnerId, Side},
+};
+
+#[cfg(test)]
+fn book() -> OrderBook {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Ask, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 5)
+        .unwrap();
+    book
+}
+
+#[test]
+fn test_submit_order_defaults_to_a_limit_order() {
+    let mut book = book();
+    let order = Order::builder(Side::Bid, OrderId(3), 100, 8).build();
+    assert_eq!(order.time_in_force, TimeInForce::GoodTillCancel);
+
+    let result = book.submit_order(&order).unwrap();
+    assert_eq!(result.fills.len(), 1);
+    assert_eq!(result.resting_quantity, 3);
+    assert_eq!(result.sequence, 3);
+    assert_eq!(book.best_bid(), Some((100, 3)));
+
+    assert_eq!(
+        book.submit_order(&order),
+        Err(LimitOrderError::OrderIdAlreadyExists)
+    );
+}
+
+#[test]
+fn test_submit_order_time_in_force() {
+    let mut book = book();
+
+    // The unfilled part of an immediate-or-cancel order never rests
+    let ioc = Order::builder(Side::Bid, OrderId(3), 100, 8)
+        .time_in_force(TimeInForce::ImmediateOrCancel)
+        .build();
+    let result = book.submit_order(&ioc).unwrap();
+    assert_eq!((result.fills.len(), result.resting_quantity), (1, 0));
+    assert_eq!(book.best_bid(), None);
+
+    let fok = Order::builder(Side::Bid, OrderId(4), 101, 6)
+        .time_in_force(TimeInForce::FillOrKill)
+        .build();
+    assert_eq!(
+        book.submit_order(&fok),
+        Err(LimitOrderError::InsufficientLiquidity)
+    );
+    let fok = Order::builder(Side::Bid, OrderId(4), 101, 5)
+        .time_in_force(TimeInForce::FillOrKill)
+        .build();
+    assert_eq!(book.submit_order(&fok).unwrap().fills.len(), 1);
+
+    let gtd = Order::builder(Side::Bid, OrderId(5), 99, 5)
+        .time_in_force(TimeInForce::GoodTillDate(50))
+        .build();
+    book.submit_order(&gtd).unwrap();
+    assert_eq!(book.expire_orders(50), vec![OrderId(5)]);
+}
+
+#[test]
+fn test_submit_order_flags_and_owner() {
+    let mut book = book();
+
+    let post_only = Order::builder(Side::Bid, OrderId(3), 100, 1)
+        .post_only()
+        .build();
+    assert_eq!(
+        book.submit_order(&post_only),
+        Err(LimitOrderError::PostOnlyWouldCross)
+    );
+
+    let min_quantity = Order::builder(Side::Bid, OrderId(3), 100, 10)
+        .min_quantity(6)
+        .build();
+    assert_eq!(
+        book.submit_order(&min_quantity),
+        Err(LimitOrderError::InsufficientLiquidity)
+    );
+
+    // Owned orders rest with their owner and are checked for self-trades
+    let owner = OwnerId(7);
+    let resting = Order::builder(Side::Bid, OrderId(3), 99, 5)
+        .owner(owner, SelfTradePrevention::CancelNewest)
+        .build();
+    book.submit_order(&resting).unwrap();
+    assert_eq!(
+        book.owner_orders.orders(owner).collect::<Vec<_>>(),
+        [OrderId(3)]
+    );
+
+    let crossing = Order::builder(Side::Ask, OrderId(4), 99, 5)
+        .owner(owner, SelfTradePrevention::CancelNewest)
+        .build();
+    let result = book.submit_order(&crossing).unwrap();
+    assert!(result.fills.is_empty());
+    assert_eq!(book.best_bid(), Some((99, 5)));
+}
+
+#[test]
+fn test_submit_order_fill_requirements_allow_for_self_trades() {
+    let mut book = OrderBook::new();
+    let owner = OwnerId(7);
+    let own_ask = Order::builder(Side::Ask, OrderId(1), 100, 5)
+        .owner(owner, SelfTradePrevention::CancelNewest)
+        .build();
+    book.submit_order(&own_ask).unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 5)
+        .unwrap();
+
+    // The owner's own ask would cancel the order rather than fill it
+    let fok = Order::builder(Side::Bid, OrderId(3), 101, 5)
+        .time_in_force(TimeInForce::FillOrKill)
+        .owner(owner, SelfTradePrevention::CancelNewest)
+        .build();
+    assert_eq!(
+        book.submit_order(&fok),
+        Err(LimitOrderError::InsufficientLiquidity)
+    );
+    let min_quantity = Order::builder(Side::Bid, OrderId(3), 101, 10)
+        .min_quantity(6)
+        .owner(owner, SelfTradePrevention::CancelOldest)
+        .build();
+    assert_eq!(
+        book.submit_order(&min_quantity),
+        Err(LimitOrderError::InsufficientLiquidity)
+    );
+    assert_eq!(book.best_ask(), Some((100, 5)));
+
+    // Cancelling the owner's ask instead leaves enough behind it
+    let fok = Order::builder(Side::Bid, OrderId(3), 101, 5)
+        .time_in_force(TimeInForce::FillOrKill)
+        .owner(owner, SelfTradePrevention::CancelOldest)
+        .build();
+    let result = book.submit_order(&fok).unwrap();
+    assert_eq!(result.fills.len(), 1);
+    assert_eq!(result.fills[0].maker_order_id, OrderId(2));
+    assert_eq!(book.best_ask(), None);
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+use crate::{
+    orderbook::OrderBook,
+    types::{OrderId, Side},
+};
+
+#[test]
+fn test_best_bid_ask_empty_book() {
+    let book = OrderBook::new();
+
+    assert_eq!(book.best_bid(), None);
+    assert_eq!(book.best_ask(), None);
+}
+
+#[test]
+fn test_best_bid_ask_aggregate_quantity() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(3), 100, 4)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(4), 102, 6)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(5), 101, 1)
+        .unwrap();
+
+    assert_eq!(book.best_bid(), Some((100, 7)));
+    assert_eq!(book.best_ask(), Some((101, 1)));
+}
+
+#[test]
+fn test_best_bid_ask_follow_mutations() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Bid, OrderId(2), 100, 3)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(3), 101, 6)
+        .unwrap();
+
+    // Partial fill at the top
+    book.execute_market_order(Side::Bid, 2).unwrap();
+    assert_eq!(book.best_ask(), Some((101, 4)));
+
+    // Level removed by cancel
+    book.cancel_order(OrderId(2)).unwrap();
+    assert_eq!(book.best_bid(), Some((99, 5)));
+
+    // Quantity changes in place
+    book.cancel_quantity(OrderId(1), 1).unwrap();
+    assert_eq!(book.best_bid(), Some((99, 4)));
+    book.amend_quantity(OrderId(3), 10).unwrap();
+    assert_eq!(book.best_ask(), Some((101, 10)));
+
+    // Side emptied by a crossing limit
+    book.execute_limit_order(Side::Bid, OrderId(4), 101, 10)
+        .unwrap();
+    assert_eq!(book.best_ask(), None);
+    assert_eq!(book.best_bid(), Some((99, 4)));
+}
+
+#[test]
+fn test_spread_and_mid_price() {
+    let mut book = OrderBook::new();
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 103, 5)
+        .unwrap();
+
+    assert_eq!(book.spread(), Some(3));
+    assert_eq!(book.mid_price(), Some(101.5));
+
+    book.execute_limit_order(Side::Ask, OrderId(3), 102, 5)
+        .unwrap();
+    assert_eq!(book.spread(), Some(2));
+    assert_eq!(book.mid_price(), Some(101.0));
+}
+
+#[test]
+fn test_spread_and_mid_price_one_sided() {
+    let mut book = OrderBook::new();
+
+    assert_eq!(book.spread(), None);
+    assert_eq!(book.mid_price(), None);
+
+    book.execute_limit_order(Side::Bid, OrderId(1), 100, 5)
+        .unwrap();
+    assert_eq!(book.spread(), None);
+    assert_eq!(book.mid_price(), None);
+
+    book.cancel_order(OrderId(1)).unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 100, 5)
+        .unwrap();
+    assert_eq!(book.spread(), None);
+    assert_eq!(book.mid_price(), None);
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+use crate::{
+    events::BookEvent,
+    trade_stats::RollingTradeStats,
+    types::{Fill, OrderId, Side, TradeId},
+};
+
+#[cfg(test)]
+fn fill(timestamp: u64, price: i64, quantity: u64) -> Fill {
+    Fill {
+        price,
+        quantity,
+        maker_order_id: OrderId(1),
+        trade_id: TradeId(1),
+        timestamp,
+        fees: None,
+        maker_client_order_id: None,
+        maker_remaining_quantity: 0,
+        maker_fully_filled: true,
+    }
+}
+
+#[test]
+fn test_rolling_vwap_and_volume() {
+    let mut stats = RollingTradeStats::new(100);
+    assert_eq!(stats.vwap(), None);
+
+    stats.record(&fill(0, 100, 10));
+    stats.record(&fill(50, 110, 30));
+    assert_eq!(stats.volume(), 40);
+    assert_eq!(stats.notional(), 4_300);
+    assert_eq!(stats.trade_count(), 2);
+    assert_eq!(stats.vwap(), Some(107));
+
+    // The window is (now - 100, now], so the fill at 0 drops out at 100
+    stats.record(&fill(100, 120, 10));
+    assert_eq!(stats.trade_count(), 2);
+    assert_eq!(stats.volume(), 40);
+    assert_eq!(stats.vwap(), Some(112));
+
+    stats.advance_to(160);
+    assert_eq!(stats.trade_count(), 1);
+    assert_eq!(stats.vwap(), Some(120));
+
+    stats.advance_to(200);
+    assert_eq!(stats.trade_count(), 0);
+    assert_eq!(stats.notional(), 0);
+    assert_eq!(stats.vwap(), None);
+}
+
+#[test]
+fn test_rolling_stats_from_events() {
+    let mut stats = RollingTradeStats::new(1_000);
+    stats.record_event(&BookEvent::Trade {
+        taker_side: Side::Bid,
+        fill: fill(5, -20, 4),
+    });
+    stats.record_event(&BookEvent::LevelRemoved {
+        side: Side::Ask,
+        price: -20,
+    });
+    assert_eq!(stats.trade_count(), 1);
+    assert_eq!(stats.vwap(), Some(-20));
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+use crate::{
+    command::{Command, CommandResult},
+    error::{AmendOrderError, CancelOrderError, LimitOrderError, MarketOrderError, StopOrderError},
+    orderbook::OrderBook,
+    pegged_orders::PegType,
+    trading_state::{HaltPolicy, TradingState},
+    types::{OrderId, OwnerId, Side},
+};
+
+#[cfg(test)]
+fn book() -> OrderBook {
+    let mut book = OrderBook::new();
+    book.execute_limit_order(Side::Bid, OrderId(1), 99, 10)
+        .unwrap();
+    book.execute_limit_order(Side::Ask, OrderId(2), 101, 10)
+        .unwrap();
+    book
+}
+
+#[test]
+fn test_halted_book_rejects_everything() {
+    let mut book = book();
+    assert!(book.set_trading_state(TradingState::Halted).is_empty());
+
+    assert_eq!(
+        book.execute_limit_order(Side::Bid, OrderId(3), 101, 5),
+        Err(LimitOrderError::TradingHalted)
+    );
+    assert_eq!(
+        book.execute_fok_order(Side::Bid, OrderId(3), 101, 5),
+        Err(LimitOrderError::TradingHalted)
+    );
+    assert_eq!(
+        book.execute_market_order(Side::Bid, 5),
+        Err(MarketOrderError::TradingHalted)
+    );
+    assert_eq!(
+        book.execute_stop_limit_order(Side::Bid, OrderId(3), 105, 106, 5),
+        Err(StopOrderError::TradingHalted)
+    );
+    assert_eq!(
+        book.amend_quantity(OrderId(1), 5),
+        Err(AmendOrderError::TradingHalted)
+    );
+    assert_eq!(
+        book.cancel_order(OrderId(1)),
+        Err(CancelOrderError::TradingHalted)
+    );
+    assert_eq!(
+        book.cancel_quantity(OrderId(1), 5),
+        Err(CancelOrderError::TradingHalted)
+    );
+    assert!(book.cancel_range(Side::Bid, ..).is_empty());
+    assert!(book.cancel_all_by_owner(OwnerId(1)).is_empty());
+    assert!(book.clear().is_empty());
+
+    assert_eq!(book.best_bid(), Some((99, 10)));
+    assert_eq!(book.best_ask(), Some((101, 10)));
+    assert_eq!(book.command_sequence(), 2);
+
+    // Reopening is sequenced like any other change to the book
+    book.set_trading_state(TradingState::Open);
+    assert_eq!(book.command_sequence(), 3);
+}
+
+#[test]
+fn test_cancel_only_allows_cancels() {
+    let mut book = book();
+    book.set_trading_state(TradingState::CancelOnly);
+
+    assert_eq!(
+        book.execute_limit_order(Side::Ask, OrderId(3), 102, 5),
+        Err(LimitOrderError::TradingHalted)
+    );
+    assert_eq!(
+        book.amend_quantity(OrderId(2), 5),
+        Err(AmendOrderError::TradingHalted)
+    );
+    assert_eq!(book.cancel_quantity(OrderId(2), 4), Ok(6));
+    book.cancel_order(OrderId(1)).unwrap();
+    assert_eq!(book.best_bid(), None);
+
+    book.set_trading_state(TradingState::Open);
+    book.execute_limit_order(Side::Ask, OrderId(3), 102, 5)
+        .unwrap();
+}
+
+#[test]
+fn test_queued_commands_apply_on_reopen() {
+    let mut book = book();
+    book.halt_policy = HaltPolicy::Queue;
+    book.set_trading_state(TradingState::CancelOnly);
+
+    let results = book.apply_batch(&[
+        Command::Market {
+            side: Side::Bid,
+            quantity: 4,
+        },
+        Command::Cancel {
+            order_id: OrderId(1),
+        },
+        Command::Limit {
+            side: Side::Bid,
+            order_id: OrderId(3),
+            price: 98,
+            quantity: 5,
+        },
+    ]);
+    assert_eq!(results[0], CommandResult::Queued);
+    assert_eq!(results[1], CommandResult::Cancel(Ok(())));
+    assert_eq!(results[2], CommandResult::Queued);
+    assert_eq!(book.queued_commands().len(), 2);
+
+    // The queue survives a snapshot
+    let mut book = OrderBook::from_bytes(&book.to_bytes()).unwrap();
+    assert_eq!(book.trading_state(), TradingState::CancelOnly);
+    assert_eq!(book.halt_policy, HaltPolicy::Queue);
+
+    let results = book.set_trading_state(TradingState::Open);
+    assert_eq!(results.len(), 2);
+    assert!(
+        matches!(&results[0], CommandResult::Market(Ok(result)) if result.filled_quantity == 4)
+    );
+    assert!(matches!(results[1], CommandResult::Limit(Ok(_))));
+    assert!(book.queued_commands().is_empty());
+    assert_eq!(book.best_ask(), Some((101, 6)));
+    assert_eq!(book.best_bid(), Some((98, 5)));
+}
+
+#[test]
+fn test_pegs_and_expiry_while_halted() {
+    let mut book = book();
+    book.execute_limit_order(Side::Bid, OrderId(3), 98, 5)
+        .unwrap();
+    book.execute_pegged_order(Side::Bid, OrderId(4), PegType::Primary, 0, 5)
+        .unwrap();
+    book.execute_gtd_order(Side::Ask, OrderId(5), 105, 5, 100)
+        .unwrap();
+    book.set_trading_state(TradingState::CancelOnly);
+
+    // Pegs hold their price until the book reopens
+    book.cancel_order(OrderId(1)).unwrap();
+    assert_eq!(book.get_order(OrderId(4)).unwrap().price, 99);
+
+    // Expiry is housekeeping, so it still happens
+    book.set_trading_state(TradingState::Halted);
+    assert_eq!(book.expire_orders(100), vec![OrderId(5)]);
+
+    book.set_trading_state(TradingState::Open);
+    assert_eq!(book.get_order(OrderId(4)).unwrap().price, 98);
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+use crate::{
+    error::InvariantViolation,
+    orderbook::OrderBook,
+    pegged_orders::PegType,
+    self_trade::SelfTradePrevention,
+    types::{OrderId, OwnerId, Side},
+};
+
+#[cfg(test)]
+fn busy_book() -> OrderBook {
+    let mut book = OrderBook::new();
+    for id in 1..=8 {
+        let side = if id % 2 == 0 { Side::Bid } else { Side::Ask };
+        let price = if side == Side::Bid {
+            99 - id % 3
+        } else {
+            101 + id % 3
+        };
+        book.execute_limit_order(side, OrderId(id), price as i64, 10)
+            .unwrap();
+    }
+    book.cancel_order(OrderId(4)).unwrap();
+    book.amend_quantity(OrderId(6), 20).unwrap();
+    book.execute_market_order(Side::Bid, 15).unwrap();
+    book.execute_owned_limit_order(
+        Side::Bid,
+        OrderId(9),
+        97,
+        5,
+        OwnerId(1),
+        SelfTradePrevention::CancelNewest,
+    )
+    .unwrap();
+    book.execute_pegged_order(Side::Ask, OrderId(10), PegType::Primary, 1, 4)
+        .unwrap();
+    book.execute_stop_limit_order(Side::Ask, OrderId(11), 90, 89, 3)
+        .unwrap();
+    book
+}
+
+#[test]
+fn test_validate_accepts_a_consistent_book() {
+    assert_eq!(OrderBook::new().validate(), Ok(()));
+    assert_eq!(busy_book().validate(), Ok(()));
+}
+
+#[test]
+fn test_validate_reports_violations() {
+    let mut book = busy_book();
+    let level = book.bids.get_mut(&97).unwrap();
+    level.total_quantity += 1;
+    level.order_count += 1;
+    assert_eq!(
+        book.validate(),
+        Err(vec![
+            InvariantViolation::OrderCountMismatch {
+                side: Side::Bid,
+                price: 97,
+                recorded: 4,
+                actual: 3,
+            },
+            InvariantViolation::LevelQuantityMismatch {
+                side: Side::Bid,
+                price: 97,
+                recorded: 26,
+                actual: 25,
+            },
+            InvariantViolation::SideTotalsMismatch { side: Side::Bid },
+        ])
+    );
+
+    // A node freed behind the book's back leaves its level dangling
+    let mut book = busy_book();
+    let index = book.index_map[&OrderId(9)].order_index;
+    book.orders.remove(index);
+    let violations = book.validate().unwrap_err();
+    assert!(violations.contains(&InvariantViolation::MissingNode {
+        side: Side::Bid,
+        price: 97,
+        index,
+    }));
+    assert!(violations.contains(&InvariantViolation::IndexMismatch {
+        order_id: OrderId(9)
+    }));
+
+    let mut book = busy_book();
+    book.stop_orders.clear();
+    assert_eq!(
+        book.validate(),
+        Err(vec![InvariantViolation::StopQueueMismatch {
+            order_id: OrderId(11)
+        }])
+    );
+
+    // Ranks out of step with the level's list, and one for an order no longer resting
+    let mut book = busy_book();
+    book.queue_ranks.remove(Side::Bid, false, 97, OrderId(2));
+    book.queue_ranks.push(Side::Bid, false, 97, OrderId(50));
+    assert_eq!(
+        book.validate(),
+        Err([2, 8, 9, 50]
+            .map(|id| InvariantViolation::QueueRankMismatch {
+                order_id: OrderId(id)
+            })
+            .to_vec())
+    );
+}
+
+#[cfg(feature = "debug-invariants")]
+#[test]
+#[should_panic(expected = "order book invariants broken")]
+fn test_debug_invariants_panic_at_the_next_mutation() {
+    let mut book = busy_book();
+    book.bids.get_mut(&97).unwrap().total_quantity += 1;
+
+    book.execute_limit_order(Side::Ask, OrderId(20), 150, 1)
+        .unwrap();
+}
@@ -0,0 +1,33 @@
+#[cfg(all(test, feature = "wasm-bindgen"))]
+use crate::wasm::WasmOrderBook;
+
+// Only the paths that don't throw run natively, a thrown error needs a JS host
+#[cfg(feature = "wasm-bindgen")]
+#[test]
+fn test_wasm_order_book_results_are_json() {
+    let mut book = WasmOrderBook::new();
+    assert_eq!(
+        book.limit("ask", 1, 101, 10).unwrap(),
+        r#"{"fills":[],"resting_quantity":10}"#
+    );
+    book.limit("bid", 2, 99, 5).unwrap();
+
+    assert_eq!(
+        book.limit("bid", 3, 101, 4).unwrap(),
+        r#"{"fills":[{"price":101,"quantity":4,"maker_order_id":1,"trade_id":1}],"resting_quantity":0}"#
+    );
+    assert_eq!(
+        book.market("ask", 8).unwrap(),
+        r#"{"fills":[{"price":99,"quantity":5,"maker_order_id":2,"trade_id":2}],"filled_quantity":5,"remaining":3}"#
+    );
+
+    book.amend(1, 3).unwrap();
+    assert_eq!((book.best_bid(), book.best_ask()), (None, Some(101)));
+    assert_eq!(
+        book.depth(5),
+        r#"{"bids":[],"asks":[[101,3]],"sequence":5}"#
+    );
+
+    book.cancel(1).unwrap();
+    assert_eq!(book.best_ask(), None);
+}
@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+
+use crate::{
+    events::BookEvent,
+    types::{Fill, Price, Quantity, Timestamp},
+};
+
+// Volume, notional, and VWAP over the fills in the trailing `window` of time,
+// i.e. those with a timestamp in (now - window, now]. `now` is the latest fill
+// timestamp seen, or a later time passed to `advance_to`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RollingTradeStats {
+    window: Timestamp,
+    now: Timestamp,
+    trades: VecDeque<(Timestamp, Price, Quantity)>,
+    volume: Quantity,
+    notional: i128,
+}
+
+impl RollingTradeStats {
+    // Panics if `window` is zero
+    pub fn new(window: Timestamp) -> Self {
+        assert!(window > 0, "rolling window must be non-zero");
+        Self {
+            window,
+            now: 0,
+            trades: VecDeque::new(),
+            volume: 0,
+            notional: 0,
+        }
+    }
+
+    pub fn window(&self) -> Timestamp {
+        self.window
+    }
+
+    pub fn record(&mut self, fill: &Fill) {
+        self.record_trade(fill.timestamp, fill.price, fill.quantity);
+    }
+
+    pub(crate) fn record_trade(&mut self, timestamp: Timestamp, price: Price, quantity: Quantity) {
+        self.trades.push_back((timestamp, price, quantity));
+        self.volume += quantity;
+        self.notional += price as i128 * quantity as i128;
+        self.advance_to(timestamp);
+    }
+
+    // Records the fill carried by a trade or auction trade event, ignoring every other event
+    pub fn record_event(&mut self, event: &BookEvent) {
+        match event {
+            BookEvent::Trade { fill, .. } => self.record(fill),
+            BookEvent::AuctionTrade { fill } => {
+                self.record_trade(fill.timestamp, fill.price, fill.quantity)
+            }
+            _ => {}
+        }
+    }
+
+    // Moves the window forward without a trade, dropping fills that fell out of it
+    pub fn advance_to(&mut self, now: Timestamp) {
+        self.now = self.now.max(now);
+        let Some(cutoff) = self.now.checked_sub(self.window) else {
+            return;
+        };
+        while let Some(&(timestamp, price, quantity)) = self.trades.front()
+            && timestamp <= cutoff
+        {
+            self.trades.pop_front();
+            self.volume -= quantity;
+            self.notional -= price as i128 * quantity as i128;
+        }
+    }
+
+    pub fn volume(&self) -> Quantity {
+        self.volume
+    }
+
+    // Sum of price * quantity over the window
+    pub fn notional(&self) -> i128 {
+        self.notional
+    }
+
+    pub fn trade_count(&self) -> usize {
+        self.trades.len()
+    }
+
+    // Volume weighted average price, rounded toward zero; None with no trades in the window
+    pub fn vwap(&self) -> Option<Price> {
+        (self.volume > 0).then(|| (self.notional / self.volume as i128) as Price)
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    command::{Command, CommandResult},
+    orderbook::OrderBook,
+};
+
+// Whether the book is taking orders, set by an operator to halt trading
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TradingState {
+    #[default]
+    Open,
+    Halted,     // Nothing is accepted, not even cancels or mass cancels
+    CancelOnly, // Cancels and partial cancels are accepted, new orders and amends are not
+    Auction,    // Limit orders rest without matching until `uncross`, market orders are refused
+}
+
+// What `apply_batch` and `replay` do with a command the trading state doesn't allow.
+// Calling the order methods directly always rejects with `TradingHalted`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HaltPolicy {
+    #[default]
+    Reject,
+    Queue, // Held in arrival order and applied once the book reopens
+}
+
+impl OrderBook {
+    pub fn trading_state(&self) -> TradingState {
+        self.trading_state
+    }
+
+    // Moves the book to a new trading state. Reopening settles the book as any other
+    // mutation does, placing elected stops and re-pricing pegged orders, which stay put
+    // while the book isn't open, then applies any queued commands in arrival order and
+    // returns their results. Reopening from an auction doesn't uncross the book, call
+    // `uncross` first.
+    //
+    // Note that a crossed book left open only matches incoming orders, the resting
+    // orders that cross stay where they are.
+    pub fn set_trading_state(&mut self, state: TradingState) -> Vec<CommandResult> {
+        self.trading_state = state;
+        if state != TradingState::Open {
+            self.refresh_indicative_price();
+            return Vec::new();
+        }
+
+        self.after_mutation();
+        let queued = std::mem::take(&mut self.queued_commands);
+        self.apply_batch(&queued)
+    }
+
+    // Commands waiting for the book to reopen, oldest first
+    pub fn queued_commands(&self) -> &[Command] {
+        &self.queued_commands
+    }
+
+    pub(crate) fn accepts_orders(&self) -> bool {
+        matches!(
+            self.trading_state,
+            TradingState::Open | TradingState::Auction
+        )
+    }
+
+    // Whether incoming orders trade on arrival, rather than only resting
+    pub(crate) fn matches_orders(&self) -> bool {
+        self.trading_state == TradingState::Open
+    }
+
+    pub(crate) fn accepts_cancels(&self) -> bool {
+        self.trading_state != TradingState::Halted
+    }
+
+    pub(crate) fn accepts_command(&self, command: &Command) -> bool {
+        match command {
+            Command::Cancel { .. } => self.accepts_cancels(),
+            Command::Market { .. } => self.matches_orders(),
+            _ => self.accepts_orders(),
+        }
+    }
+}
@@ -1,17 +1,190 @@
+use crate::fees::{AuctionFillFees, FillFees};
+
 pub type Price = i64;
 pub type Quantity = u64;
+pub type Timestamp = u64;
+pub type Sequence = u64; // Gapless, starting from 1
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Side {
     Bid,
     Ask,
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+impl Side {
+    pub fn opposite(self) -> Self {
+        match self {
+            Side::Bid => Side::Ask,
+            Side::Ask => Side::Bid,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrderId(pub u64);
 
-#[derive(Debug, PartialEq, Eq)]
+// The account or trader an order belongs to, for self-trade prevention
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnerId(pub u64);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TradeId(pub u64);
+
+// The client's own Id or tag for an order, opaque to the book. Kept with the resting
+// order and echoed back in its events, fills, and cancel results.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClientOrderId(pub u64);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Fill {
     pub price: Price,
     pub quantity: Quantity,
+    pub maker_order_id: OrderId, // The resting order this fill executed against
+    pub maker_client_order_id: Option<ClientOrderId>,
+    pub maker_remaining_quantity: Quantity, // Left resting on the maker order after this fill
+    pub maker_fully_filled: bool,           // The fill consumed the maker order entirely
+    pub trade_id: TradeId,
+    pub timestamp: Timestamp, // From the book's clock when the trade happened
+    pub fees: Option<FillFees>, // Only when the book has a fee schedule
+}
+
+// A match between two resting orders when an auction uncrosses. Both trade at the
+// uncross price, so each order's own level price is kept alongside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuctionFill {
+    pub price: Price,
+    pub quantity: Quantity,
+    pub buy_order_id: OrderId,
+    pub buy_price: Price,
+    pub sell_order_id: OrderId,
+    pub sell_price: Price,
+    pub trade_id: TradeId,
+    pub timestamp: Timestamp,
+    pub fees: Option<AuctionFillFees>, // Only when the book has a fee schedule
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitOrderResult {
+    pub order_id: OrderId, // The Id the order was placed under, see `OrderIdMode`
+    pub fills: Vec<Fill>,
+    pub resting_quantity: Quantity, // Quantity left resting on the book after matching
+    pub order_index: Option<usize>, // Slab index of the resting order, if any
+    pub queue_position: Option<usize>, // Number of orders ahead at the same price level
+    pub sequence: Sequence,         // Sequence number the accepted order was given
+}
+
+// What happens to the part of a market order the book couldn't fill
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarketRemainder {
+    #[default]
+    Discard, // Dropped silently
+    Return,                   // Reported back as `remaining`
+    RestAtLastTrade(OrderId), // Rests as a limit order under this Id at the last traded price
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketOrderResult {
+    pub fills: Vec<Fill>,
+    pub filled_quantity: Quantity,
+    pub notional: i128,             // Sum of price * quantity over the fills
+    pub remaining: Quantity,        // Unfilled quantity that was neither discarded nor rested
+    pub resting_quantity: Quantity, // Remainder rested as a limit order, if asked to
+    pub sequence: Sequence,
+}
+
+impl MarketOrderResult {
+    pub(crate) fn from_fills(fills: Vec<Fill>) -> Self {
+        let filled_quantity = fills.iter().map(|fill| fill.quantity).sum();
+        let notional = fills
+            .iter()
+            .map(|fill| fill.price as i128 * fill.quantity as i128)
+            .sum();
+        Self {
+            fills,
+            filled_quantity,
+            notional,
+            ..Default::default()
+        }
+    }
+
+    // Volume weighted average fill price, None if nothing filled
+    pub fn average_price(&self) -> Option<f64> {
+        (self.filled_quantity > 0).then(|| self.notional as f64 / self.filled_quantity as f64)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UncrossResult {
+    pub price: Price,       // Every fill trades at this single price
+    pub quantity: Quantity, // Total executed volume
+    pub fills: Vec<AuctionFill>,
+    pub sequence: Sequence,
+}
+
+// Running totals across every level on one side of the book
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SideTotals {
+    pub quantity: Quantity,
+    pub order_count: usize,
+}
+
+// Price, aggregate quantity, and order count of a single price level
+pub type DepthLevel = (Price, Quantity, usize);
+
+// Id, side, price, remaining quantity, and queue position of a resting order
+pub type OpenOrder = (OrderId, Side, Price, Quantity, usize);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Depth {
+    pub bids: Vec<DepthLevel>, // Best (highest) price first
+    pub asks: Vec<DepthLevel>, // Best (lowest) price first
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrderDetails {
+    pub order_id: OrderId,
+    pub side: Side,
+    pub price: Price,
+    pub quantity: Quantity,    // Remaining quantity
+    pub queue_position: usize, // Number of orders ahead at the same price level
+    pub timestamp: Timestamp,  // When the order started resting at its current priority
+    pub client_order_id: Option<ClientOrderId>,
+}
+
+// Every order at one price level, in time priority
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Level3 {
+    pub price: Price,
+    pub orders: Vec<(OrderId, Quantity)>,
+}
+
+// Market-by-order view of the whole book
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Level3Snapshot {
+    pub bids: Vec<Level3>,  // Best (highest) price first
+    pub asks: Vec<Level3>,  // Best (lowest) price first
+    pub sequence: Sequence, // Last event reflected in the snapshot
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeAhead {
+    pub at_price: Quantity,      // Queued ahead at the order's own price level
+    pub better_prices: Quantity, // Resting at strictly better prices on the same side
 }
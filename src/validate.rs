@@ -0,0 +1,189 @@
+use hashbrown::HashSet;
+
+use crate::{
+    error::InvariantViolation,
+    orderbook::OrderBook,
+    types::{OrderId, Quantity, Side},
+};
+
+impl OrderBook {
+    // Audits the book's internal structure: every level's linked list, visible or hidden,
+    // its head, tail, order count and quantity, the Id lookup against the slab, side
+    // totals, owners, queue ranks, pegs, and stop queues. Meant for tests and fuzzing, it
+    // walks the whole book. Panics listing every broken invariant. Behind the
+    // `debug-invariants` feature this runs after each mutation, failing at the operation
+    // that corrupted the book.
+    #[cfg(feature = "debug-invariants")]
+    pub(crate) fn assert_invariants(&self) {
+        if let Err(violations) = self.validate() {
+            panic!("order book invariants broken: {violations:?}");
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<InvariantViolation>> {
+        let mut violations = Vec::new();
+        let mut linked = HashSet::new();
+
+        let sides = [
+            (Side::Bid, false),
+            (Side::Ask, false),
+            (Side::Bid, true),
+            (Side::Ask, true),
+        ];
+        for (side, hidden) in sides {
+            let levels = match (side, hidden) {
+                (Side::Bid, false) => &self.bids,
+                (Side::Ask, false) => &self.asks,
+                (Side::Bid, true) => &self.hidden_bids,
+                (Side::Ask, true) => &self.hidden_asks,
+            };
+            let (mut side_quantity, mut side_count): (Quantity, usize) = (0, 0);
+
+            for (price, level) in levels.iter() {
+                if level.order_count == 0 {
+                    violations.push(InvariantViolation::EmptyLevel { side, price });
+                    continue;
+                }
+
+                let (mut quantity, mut count): (Quantity, usize) = (0, 0);
+                let (mut previous, mut next) = (None, Some(level.head));
+                // Bounded by the slab size, so a cycle can't loop forever
+                while let Some(index) = next
+                    && count <= self.orders.len()
+                {
+                    let Some(node) = self.orders.get(index) else {
+                        violations.push(InvariantViolation::MissingNode { side, price, index });
+                        break;
+                    };
+                    if node.previous != previous || !linked.insert(index) {
+                        violations.push(InvariantViolation::BrokenLink { side, price, index });
+                        break;
+                    }
+                    if node.quantity == 0 {
+                        violations.push(InvariantViolation::ZeroQuantityOrder {
+                            order_id: node.order_id,
+                        });
+                    }
+                    match self.index_map.get(&node.order_id) {
+                        Some(entry)
+                            if entry.order_index == index
+                                && entry.side == side
+                                && entry.price == price
+                                && entry.hidden == hidden => {}
+                        Some(_) => violations.push(InvariantViolation::IndexMismatch {
+                            order_id: node.order_id,
+                        }),
+                        None => violations.push(InvariantViolation::UnindexedOrder {
+                            order_id: node.order_id,
+                        }),
+                    }
+
+                    if self
+                        .queue_ranks
+                        .position(side, hidden, price, node.order_id)
+                        != Some(count)
+                    {
+                        violations.push(InvariantViolation::QueueRankMismatch {
+                            order_id: node.order_id,
+                        });
+                    }
+                    quantity = quantity.saturating_add(node.quantity);
+                    count += 1;
+                    (previous, next) = (Some(index), node.next);
+                }
+
+                if previous != Some(level.tail) {
+                    violations.push(InvariantViolation::LevelTailMismatch { side, price });
+                }
+                if count != level.order_count {
+                    violations.push(InvariantViolation::OrderCountMismatch {
+                        side,
+                        price,
+                        recorded: level.order_count,
+                        actual: count,
+                    });
+                }
+                if quantity != level.total_quantity {
+                    violations.push(InvariantViolation::LevelQuantityMismatch {
+                        side,
+                        price,
+                        recorded: level.total_quantity,
+                        actual: quantity,
+                    });
+                }
+                side_quantity = side_quantity.saturating_add(level.total_quantity);
+                side_count += level.order_count;
+            }
+
+            let totals = match (side, hidden) {
+                (Side::Bid, false) => self.bid_totals,
+                (Side::Ask, false) => self.ask_totals,
+                (Side::Bid, true) => self.hidden_bid_totals,
+                (Side::Ask, true) => self.hidden_ask_totals,
+            };
+            if (totals.quantity, totals.order_count) != (side_quantity, side_count) {
+                violations.push(InvariantViolation::SideTotalsMismatch { side });
+            }
+        }
+
+        for (index, _) in self.orders.iter() {
+            if !linked.contains(&index) {
+                violations.push(InvariantViolation::UnlinkedOrder { index });
+            }
+        }
+        for (&order_id, entry) in &self.index_map {
+            if !linked.contains(&entry.order_index) {
+                violations.push(InvariantViolation::IndexMismatch { order_id });
+            }
+            if let Some(owner) = entry.owner
+                && !self.owner_orders.orders(owner).any(|id| id == order_id)
+            {
+                violations.push(InvariantViolation::OwnerIndexMismatch { order_id });
+            }
+        }
+        for order_id in self.queue_ranks.order_ids() {
+            if !self.index_map.contains_key(&order_id) {
+                violations.push(InvariantViolation::QueueRankMismatch { order_id });
+            }
+        }
+        for (&order_id, peg) in &self.pegged_orders {
+            if self
+                .index_map
+                .get(&order_id)
+                .is_none_or(|entry| entry.side != peg.side)
+            {
+                violations.push(InvariantViolation::PegMismatch { order_id });
+            }
+        }
+        self.validate_stops(&mut violations);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    // Every stop order is queued exactly once, under its own side and stop price
+    fn validate_stops(&self, violations: &mut Vec<InvariantViolation>) {
+        let mut queued: HashSet<OrderId> = HashSet::new();
+        for (side, stops) in [(Side::Bid, &self.buy_stops), (Side::Ask, &self.sell_stops)] {
+            for (&stop_price, ids) in stops {
+                for &order_id in ids {
+                    let matches = self
+                        .stop_orders
+                        .get(&order_id)
+                        .is_some_and(|stop| stop.side == side && stop.stop_price == stop_price);
+                    if !matches || !queued.insert(order_id) {
+                        violations.push(InvariantViolation::StopQueueMismatch { order_id });
+                    }
+                }
+            }
+        }
+        for &order_id in self.stop_orders.keys() {
+            if !queued.contains(&order_id) {
+                violations.push(InvariantViolation::StopQueueMismatch { order_id });
+            }
+        }
+    }
+}
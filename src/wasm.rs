@@ -0,0 +1,106 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    orderbook::OrderBook,
+    types::{Fill, OrderId, Price, Side},
+};
+
+// The book as a JavaScript class. Sides are "bid" or "ask", results come back as
+// JSON strings and rejections as thrown errors naming the reason.
+#[wasm_bindgen(js_name = OrderBook)]
+#[derive(Debug, Default)]
+pub struct WasmOrderBook {
+    book: OrderBook,
+}
+
+#[wasm_bindgen(js_class = OrderBook)]
+impl WasmOrderBook {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // `{"fills": [...], "resting_quantity": n}`
+    pub fn limit(
+        &mut self,
+        side: &str,
+        order_id: u64,
+        price: i64,
+        quantity: u64,
+    ) -> Result<String, JsError> {
+        let result = self
+            .book
+            .execute_limit_order(parse_side(side)?, OrderId(order_id), price, quantity)
+            .map_err(rejected)?;
+        Ok(format!(
+            r#"{{"fills":{},"resting_quantity":{}}}"#,
+            fills_json(&result.fills),
+            result.resting_quantity
+        ))
+    }
+
+    // `{"fills": [...], "filled_quantity": n, "remaining": n}`
+    pub fn market(&mut self, side: &str, quantity: u64) -> Result<String, JsError> {
+        let result = self
+            .book
+            .execute_market_order(parse_side(side)?, quantity)
+            .map_err(rejected)?;
+        Ok(format!(
+            r#"{{"fills":{},"filled_quantity":{},"remaining":{}}}"#,
+            fills_json(&result.fills),
+            result.filled_quantity,
+            result.remaining
+        ))
+    }
+
+    pub fn cancel(&mut self, order_id: u64) -> Result<(), JsError> {
+        self.book.cancel_order(OrderId(order_id)).map_err(rejected)
+    }
+
+    pub fn amend(&mut self, order_id: u64, quantity: u64) -> Result<(), JsError> {
+        self.book
+            .amend_quantity(OrderId(order_id), quantity)
+            .map_err(rejected)
+    }
+
+    // Same shape as `OrderBook::depth_json`, sequenced by accepted commands
+    pub fn depth(&self, levels: usize) -> String {
+        self.book.depth_json(levels, self.book.command_sequence())
+    }
+
+    #[wasm_bindgen(js_name = bestBid)]
+    pub fn best_bid(&self) -> Option<Price> {
+        self.book.best_bid().map(|(price, _)| price)
+    }
+
+    #[wasm_bindgen(js_name = bestAsk)]
+    pub fn best_ask(&self) -> Option<Price> {
+        self.book.best_ask().map(|(price, _)| price)
+    }
+}
+
+fn parse_side(side: &str) -> Result<Side, JsError> {
+    match side {
+        "bid" => Ok(Side::Bid),
+        "ask" => Ok(Side::Ask),
+        _ => Err(JsError::new(&format!("unknown side {side:?}"))),
+    }
+}
+
+fn rejected(error: impl std::fmt::Debug) -> JsError {
+    JsError::new(&format!("{error:?}"))
+}
+
+// `[{"price": p, "quantity": q, "maker_order_id": id, "trade_id": id}, ...]`
+fn fills_json(fills: &[Fill]) -> String {
+    let entries: Vec<_> = fills
+        .iter()
+        .map(|fill| {
+            format!(
+                r#"{{"price":{},"quantity":{},"maker_order_id":{},"trade_id":{}}}"#,
+                fill.price, fill.quantity, fill.maker_order_id.0, fill.trade_id.0
+            )
+        })
+        .collect();
+    format!("[{}]", entries.join(","))
+}